@@ -71,6 +71,46 @@ void _vko_assert_is_acceleration_structure_id(AccelerationStructureId id) {}
 #define _vko_acceleration_structure_index(id)                                                      \
     (_vko_assert_is_acceleration_structure_id(id), id._private_index)
 
+struct UniformBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+void _vko_assert_is_uniform_buffer_id(UniformBufferId id) {}
+
+#define _vko_uniform_buffer_index(id)                                                              \
+    (_vko_assert_is_uniform_buffer_id(id), id._private_index)
+
+struct UniformTexelBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+void _vko_assert_is_uniform_texel_buffer_id(UniformTexelBufferId id) {}
+
+#define _vko_uniform_texel_buffer_index(id)                                                        \
+    (_vko_assert_is_uniform_texel_buffer_id(id), id._private_index)
+
+struct StorageTexelBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+void _vko_assert_is_storage_texel_buffer_id(StorageTexelBufferId id) {}
+
+#define _vko_storage_texel_buffer_index(id)                                                        \
+    (_vko_assert_is_storage_texel_buffer_id(id), id._private_index)
+
+struct CombinedImageSamplerId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+void _vko_assert_is_combined_image_sampler_id(CombinedImageSamplerId id) {}
+
+#define _vko_combined_image_sampler_index(id)                                                      \
+    (_vko_assert_is_combined_image_sampler_id(id), id._private_index)
+
 // NOTE(Marc): The following constants must match the definitions in
 // vulkano_taskgraph/src/descriptor_set.rs!
 
@@ -80,6 +120,10 @@ void _vko_assert_is_acceleration_structure_id(AccelerationStructureId id) {}
 #define VKO_STORAGE_IMAGE_BINDING 2
 #define VKO_STORAGE_BUFFER_BINDING 3
 #define VKO_ACCELERATION_STRUCTURE_BINDING 4
+#define VKO_UNIFORM_BUFFER_BINDING 5
+#define VKO_UNIFORM_TEXEL_BUFFER_BINDING 6
+#define VKO_STORAGE_TEXEL_BUFFER_BINDING 7
+#define VKO_COMBINED_IMAGE_SAMPLER_BINDING 8
 
 #define VKO_LOCAL_SET 1
 #define VKO_INPUT_ATTACHMENT_BINDING 0
@@ -1428,6 +1472,190 @@ layout(set = VKO_GLOBAL_SET, binding = VKO_ACCELERATION_STRUCTURE_BINDING)
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
+#if VKO_UNIFORM_BUFFER_ENABLED
+
+#define VKO_DECLARE_UNIFORM_BUFFER(NAME, BLOCK)                                                    \
+    layout(set = VKO_GLOBAL_SET, binding = VKO_UNIFORM_BUFFER_BINDING)                             \
+        uniform BLOCK _vko_##NAME##_uniform_buffers[];
+
+#define VKO_DECLARE_UNIFORM_BUFFER_WITH_LAYOUT(NAME, BLOCK, LAYOUT)                                \
+    layout(set = VKO_GLOBAL_SET, binding = VKO_UNIFORM_BUFFER_BINDING, LAYOUT)                     \
+        uniform BLOCK _vko_##NAME##_uniform_buffers[];
+
+#define vko_uniform_buffer(NAME, id)                                                               \
+    _vko_##NAME##_uniform_buffers[_vko_uniform_buffer_index(id)]
+
+#endif // VKO_UNIFORM_BUFFER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_UNIFORM_TEXEL_BUFFER_ENABLED
+
+#define _VKO_DECLARE_UNIFORM_TEXEL_BUFFER(TYPE)                                                    \
+    layout(set = VKO_GLOBAL_SET, binding = VKO_UNIFORM_TEXEL_BUFFER_BINDING)                       \
+        uniform TYPE _vko_uniform_texel_buffers_##TYPE[];
+
+_VKO_DECLARE_UNIFORM_TEXEL_BUFFER(textureBuffer)
+#define vko_textureBuffer(id)                                                                      \
+    _vko_uniform_texel_buffers_textureBuffer[_vko_uniform_texel_buffer_index(id)]
+
+_VKO_DECLARE_UNIFORM_TEXEL_BUFFER(itextureBuffer)
+#define vko_itextureBuffer(id)                                                                     \
+    _vko_uniform_texel_buffers_itextureBuffer[_vko_uniform_texel_buffer_index(id)]
+
+_VKO_DECLARE_UNIFORM_TEXEL_BUFFER(utextureBuffer)
+#define vko_utextureBuffer(id)                                                                     \
+    _vko_uniform_texel_buffers_utextureBuffer[_vko_uniform_texel_buffer_index(id)]
+
+#undef _VKO_DECLARE_UNIFORM_TEXEL_BUFFER
+
+#endif // VKO_UNIFORM_TEXEL_BUFFER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_STORAGE_TEXEL_BUFFER_ENABLED
+
+#define VKO_DECLARE_STORAGE_TEXEL_BUFFER(NAME, TYPE, FORMAT)                                       \
+    layout(set = VKO_GLOBAL_SET, binding = VKO_STORAGE_TEXEL_BUFFER_BINDING, FORMAT)               \
+        uniform TYPE _vko_##NAME##_storage_texel_buffers[];
+
+#define VKO_DECLARE_STORAGE_TEXEL_BUFFER_WITHOUT_FORMAT(NAME, TYPE)                                \
+    layout(set = VKO_GLOBAL_SET, binding = VKO_STORAGE_TEXEL_BUFFER_BINDING)                       \
+        uniform TYPE _vko_##NAME##_storage_texel_buffers[];
+
+#define vko_texel_buffer(NAME, id)                                                                 \
+    _vko_##NAME##_storage_texel_buffers[_vko_storage_texel_buffer_index(id)]
+
+#endif // VKO_STORAGE_TEXEL_BUFFER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_COMBINED_IMAGE_SAMPLER_ENABLED
+
+#define _VKO_DECLARE_COMBINED_IMAGE_SAMPLER(TYPE)                                                  \
+    layout(set = VKO_GLOBAL_SET, binding = VKO_COMBINED_IMAGE_SAMPLER_BINDING)                     \
+        uniform TYPE _vko_combined_image_samplers_##TYPE[];
+
+#define _VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(DIMENSION)                                   \
+    _VKO_DECLARE_COMBINED_IMAGE_SAMPLER(sampler##DIMENSION)                                        \
+    _VKO_DECLARE_COMBINED_IMAGE_SAMPLER(isampler##DIMENSION)                                       \
+    _VKO_DECLARE_COMBINED_IMAGE_SAMPLER(usampler##DIMENSION)
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(1D)
+#define vko_combined_sampler1D(id)                                                                 \
+    _vko_combined_image_samplers_sampler1D[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler1D(id)                                                                \
+    _vko_combined_image_samplers_isampler1D[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler1D(id)                                                                \
+    _vko_combined_image_samplers_usampler1D[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER(sampler1DShadow)
+#define vko_combined_sampler1DShadow(id)                                                           \
+    _vko_combined_image_samplers_sampler1DShadow[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(1DArray)
+#define vko_combined_sampler1DArray(id)                                                            \
+    _vko_combined_image_samplers_sampler1DArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler1DArray(id)                                                           \
+    _vko_combined_image_samplers_isampler1DArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler1DArray(id)                                                           \
+    _vko_combined_image_samplers_usampler1DArray[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER(sampler1DArrayShadow)
+#define vko_combined_sampler1DArrayShadow(id)                                                      \
+    _vko_combined_image_samplers_sampler1DArrayShadow[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(2D)
+#define vko_combined_sampler2D(id)                                                                 \
+    _vko_combined_image_samplers_sampler2D[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler2D(id)                                                                \
+    _vko_combined_image_samplers_isampler2D[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler2D(id)                                                                \
+    _vko_combined_image_samplers_usampler2D[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER(sampler2DShadow)
+#define vko_combined_sampler2DShadow(id)                                                           \
+    _vko_combined_image_samplers_sampler2DShadow[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(2DArray)
+#define vko_combined_sampler2DArray(id)                                                            \
+    _vko_combined_image_samplers_sampler2DArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler2DArray(id)                                                           \
+    _vko_combined_image_samplers_isampler2DArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler2DArray(id)                                                           \
+    _vko_combined_image_samplers_usampler2DArray[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER(sampler2DArrayShadow)
+#define vko_combined_sampler2DArrayShadow(id)                                                      \
+    _vko_combined_image_samplers_sampler2DArrayShadow[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(2DMS)
+#define vko_combined_sampler2DMS(id)                                                               \
+    _vko_combined_image_samplers_sampler2DMS[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler2DMS(id)                                                              \
+    _vko_combined_image_samplers_isampler2DMS[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler2DMS(id)                                                              \
+    _vko_combined_image_samplers_usampler2DMS[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(2DMSArray)
+#define vko_combined_sampler2DMSArray(id)                                                          \
+    _vko_combined_image_samplers_sampler2DMSArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler2DMSArray(id)                                                         \
+    _vko_combined_image_samplers_isampler2DMSArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler2DMSArray(id)                                                         \
+    _vko_combined_image_samplers_usampler2DMSArray[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(3D)
+#define vko_combined_sampler3D(id)                                                                 \
+    _vko_combined_image_samplers_sampler3D[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isampler3D(id)                                                                \
+    _vko_combined_image_samplers_isampler3D[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usampler3D(id)                                                                \
+    _vko_combined_image_samplers_usampler3D[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(Cube)
+#define vko_combined_samplerCube(id)                                                               \
+    _vko_combined_image_samplers_samplerCube[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isamplerCube(id)                                                              \
+    _vko_combined_image_samplers_isamplerCube[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usamplerCube(id)                                                              \
+    _vko_combined_image_samplers_usamplerCube[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER(samplerCubeShadow)
+#define vko_combined_samplerCubeShadow(id)                                                         \
+    _vko_combined_image_samplers_samplerCubeShadow[_vko_combined_image_sampler_index(id)]
+
+#if VKO_IMAGE_CUBE_ARRAY_ENABLED
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION(CubeArray)
+#define vko_combined_samplerCubeArray(id)                                                          \
+    _vko_combined_image_samplers_samplerCubeArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_isamplerCubeArray(id)                                                         \
+    _vko_combined_image_samplers_isamplerCubeArray[_vko_combined_image_sampler_index(id)]
+#define vko_combined_usamplerCubeArray(id)                                                         \
+    _vko_combined_image_samplers_usamplerCubeArray[_vko_combined_image_sampler_index(id)]
+
+_VKO_DECLARE_COMBINED_IMAGE_SAMPLER(samplerCubeArrayShadow)
+#define vko_combined_samplerCubeArrayShadow(id)                                                    \
+    _vko_combined_image_samplers_samplerCubeArrayShadow[_vko_combined_image_sampler_index(id)]
+
+#endif // VKO_IMAGE_CUBE_ARRAY_ENABLED
+
+#undef _VKO_DECLARE_COMBINED_IMAGE_SAMPLER
+#undef _VKO_DECLARE_COMBINED_IMAGE_SAMPLER_DIMENSION
+
+#endif // VKO_COMBINED_IMAGE_SAMPLER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
 #if VKO_INPUT_ATTACHMENT_ENABLED
 
 #define _VKO_DECLARE_INPUT_ATTACHMENT(TYPE)                                                        \
@@ -0,0 +1,485 @@
+// IMPORTANT NOTE: Names starting with an underscore are private and NOT PART OF THE PUBLIC API.
+// They can be changed at any moment without warning.
+
+#ifndef _VULKANO_HEADER
+#define _VULKANO_HEADER
+
+// NOTE: This is the HLSL counterpart of vulkano.glsl. Unlike in GLSL, the ID types are made type
+// safe through overloads of the `vko_index` function. As HLSL doesn't have qualifiers that could
+// be propagated, every accessor has a `_nonuniform` variant which wraps the index in
+// `NonUniformResourceIndex`.
+
+struct SamplerId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(SamplerId id) {
+    return id._private_index;
+}
+
+struct SampledImageId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(SampledImageId id) {
+    return id._private_index;
+}
+
+struct StorageImageId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(StorageImageId id) {
+    return id._private_index;
+}
+
+struct StorageBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(StorageBufferId id) {
+    return id._private_index;
+}
+
+struct AccelerationStructureId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(AccelerationStructureId id) {
+    return id._private_index;
+}
+
+struct UniformBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(UniformBufferId id) {
+    return id._private_index;
+}
+
+struct UniformTexelBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(UniformTexelBufferId id) {
+    return id._private_index;
+}
+
+struct StorageTexelBufferId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(StorageTexelBufferId id) {
+    return id._private_index;
+}
+
+struct CombinedImageSamplerId {
+    uint _private_index;
+    uint _private_generation;
+};
+
+uint vko_index(CombinedImageSamplerId id) {
+    return id._private_index;
+}
+
+// NOTE(Marc): The following constants must match the definitions in
+// vulkano_taskgraph/src/descriptor_set.rs!
+
+#define VKO_GLOBAL_SET 0
+#define VKO_SAMPLER_BINDING 0
+#define VKO_SAMPLED_IMAGE_BINDING 1
+#define VKO_STORAGE_IMAGE_BINDING 2
+#define VKO_STORAGE_BUFFER_BINDING 3
+#define VKO_ACCELERATION_STRUCTURE_BINDING 4
+#define VKO_UNIFORM_BUFFER_BINDING 5
+#define VKO_UNIFORM_TEXEL_BUFFER_BINDING 6
+#define VKO_STORAGE_TEXEL_BUFFER_BINDING 7
+#define VKO_COMBINED_IMAGE_SAMPLER_BINDING 8
+
+#define VKO_LOCAL_SET 1
+#define VKO_INPUT_ATTACHMENT_BINDING 0
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#define _VKO_DECLARE_SAMPLER(TYPE)                                                                 \
+    [[vk::binding(VKO_SAMPLER_BINDING, VKO_GLOBAL_SET)]]                                           \
+        TYPE _vko_samplers_##TYPE[];
+
+_VKO_DECLARE_SAMPLER(SamplerState)
+#define vko_SamplerState(id)                                                                       \
+    _vko_samplers_SamplerState[vko_index(id)]
+#define vko_SamplerState_nonuniform(id)                                                            \
+    _vko_samplers_SamplerState[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLER(SamplerComparisonState)
+#define vko_SamplerComparisonState(id)                                                             \
+    _vko_samplers_SamplerComparisonState[vko_index(id)]
+#define vko_SamplerComparisonState_nonuniform(id)                                                  \
+    _vko_samplers_SamplerComparisonState[NonUniformResourceIndex(vko_index(id))]
+
+#undef _VKO_DECLARE_SAMPLER
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#define _VKO_DECLARE_SAMPLED_IMAGE(NAME, TYPE)                                                     \
+    [[vk::binding(VKO_SAMPLED_IMAGE_BINDING, VKO_GLOBAL_SET)]]                                     \
+        TYPE _vko_sampled_images_##NAME[];
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture1D, Texture1D<float4>)
+#define vko_Texture1D(id)                                                                          \
+    _vko_sampled_images_Texture1D[vko_index(id)]
+#define vko_Texture1D_nonuniform(id)                                                               \
+    _vko_sampled_images_Texture1D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture1D, Texture1D<int4>)
+#define vko_iTexture1D(id)                                                                         \
+    _vko_sampled_images_iTexture1D[vko_index(id)]
+#define vko_iTexture1D_nonuniform(id)                                                              \
+    _vko_sampled_images_iTexture1D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture1D, Texture1D<uint4>)
+#define vko_uTexture1D(id)                                                                         \
+    _vko_sampled_images_uTexture1D[vko_index(id)]
+#define vko_uTexture1D_nonuniform(id)                                                              \
+    _vko_sampled_images_uTexture1D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture1DArray, Texture1DArray<float4>)
+#define vko_Texture1DArray(id)                                                                     \
+    _vko_sampled_images_Texture1DArray[vko_index(id)]
+#define vko_Texture1DArray_nonuniform(id)                                                          \
+    _vko_sampled_images_Texture1DArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture1DArray, Texture1DArray<int4>)
+#define vko_iTexture1DArray(id)                                                                    \
+    _vko_sampled_images_iTexture1DArray[vko_index(id)]
+#define vko_iTexture1DArray_nonuniform(id)                                                         \
+    _vko_sampled_images_iTexture1DArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture1DArray, Texture1DArray<uint4>)
+#define vko_uTexture1DArray(id)                                                                    \
+    _vko_sampled_images_uTexture1DArray[vko_index(id)]
+#define vko_uTexture1DArray_nonuniform(id)                                                         \
+    _vko_sampled_images_uTexture1DArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture2D, Texture2D<float4>)
+#define vko_Texture2D(id)                                                                          \
+    _vko_sampled_images_Texture2D[vko_index(id)]
+#define vko_Texture2D_nonuniform(id)                                                               \
+    _vko_sampled_images_Texture2D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture2D, Texture2D<int4>)
+#define vko_iTexture2D(id)                                                                         \
+    _vko_sampled_images_iTexture2D[vko_index(id)]
+#define vko_iTexture2D_nonuniform(id)                                                              \
+    _vko_sampled_images_iTexture2D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture2D, Texture2D<uint4>)
+#define vko_uTexture2D(id)                                                                         \
+    _vko_sampled_images_uTexture2D[vko_index(id)]
+#define vko_uTexture2D_nonuniform(id)                                                              \
+    _vko_sampled_images_uTexture2D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture2DArray, Texture2DArray<float4>)
+#define vko_Texture2DArray(id)                                                                     \
+    _vko_sampled_images_Texture2DArray[vko_index(id)]
+#define vko_Texture2DArray_nonuniform(id)                                                          \
+    _vko_sampled_images_Texture2DArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture2DArray, Texture2DArray<int4>)
+#define vko_iTexture2DArray(id)                                                                    \
+    _vko_sampled_images_iTexture2DArray[vko_index(id)]
+#define vko_iTexture2DArray_nonuniform(id)                                                         \
+    _vko_sampled_images_iTexture2DArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture2DArray, Texture2DArray<uint4>)
+#define vko_uTexture2DArray(id)                                                                    \
+    _vko_sampled_images_uTexture2DArray[vko_index(id)]
+#define vko_uTexture2DArray_nonuniform(id)                                                         \
+    _vko_sampled_images_uTexture2DArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture2DMS, Texture2DMS<float4>)
+#define vko_Texture2DMS(id)                                                                        \
+    _vko_sampled_images_Texture2DMS[vko_index(id)]
+#define vko_Texture2DMS_nonuniform(id)                                                             \
+    _vko_sampled_images_Texture2DMS[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture2DMS, Texture2DMS<int4>)
+#define vko_iTexture2DMS(id)                                                                       \
+    _vko_sampled_images_iTexture2DMS[vko_index(id)]
+#define vko_iTexture2DMS_nonuniform(id)                                                            \
+    _vko_sampled_images_iTexture2DMS[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture2DMS, Texture2DMS<uint4>)
+#define vko_uTexture2DMS(id)                                                                       \
+    _vko_sampled_images_uTexture2DMS[vko_index(id)]
+#define vko_uTexture2DMS_nonuniform(id)                                                            \
+    _vko_sampled_images_uTexture2DMS[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture2DMSArray, Texture2DMSArray<float4>)
+#define vko_Texture2DMSArray(id)                                                                   \
+    _vko_sampled_images_Texture2DMSArray[vko_index(id)]
+#define vko_Texture2DMSArray_nonuniform(id)                                                        \
+    _vko_sampled_images_Texture2DMSArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture2DMSArray, Texture2DMSArray<int4>)
+#define vko_iTexture2DMSArray(id)                                                                  \
+    _vko_sampled_images_iTexture2DMSArray[vko_index(id)]
+#define vko_iTexture2DMSArray_nonuniform(id)                                                       \
+    _vko_sampled_images_iTexture2DMSArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture2DMSArray, Texture2DMSArray<uint4>)
+#define vko_uTexture2DMSArray(id)                                                                  \
+    _vko_sampled_images_uTexture2DMSArray[vko_index(id)]
+#define vko_uTexture2DMSArray_nonuniform(id)                                                       \
+    _vko_sampled_images_uTexture2DMSArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(Texture3D, Texture3D<float4>)
+#define vko_Texture3D(id)                                                                          \
+    _vko_sampled_images_Texture3D[vko_index(id)]
+#define vko_Texture3D_nonuniform(id)                                                               \
+    _vko_sampled_images_Texture3D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTexture3D, Texture3D<int4>)
+#define vko_iTexture3D(id)                                                                         \
+    _vko_sampled_images_iTexture3D[vko_index(id)]
+#define vko_iTexture3D_nonuniform(id)                                                              \
+    _vko_sampled_images_iTexture3D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTexture3D, Texture3D<uint4>)
+#define vko_uTexture3D(id)                                                                         \
+    _vko_sampled_images_uTexture3D[vko_index(id)]
+#define vko_uTexture3D_nonuniform(id)                                                              \
+    _vko_sampled_images_uTexture3D[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(TextureCube, TextureCube<float4>)
+#define vko_TextureCube(id)                                                                        \
+    _vko_sampled_images_TextureCube[vko_index(id)]
+#define vko_TextureCube_nonuniform(id)                                                             \
+    _vko_sampled_images_TextureCube[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTextureCube, TextureCube<int4>)
+#define vko_iTextureCube(id)                                                                       \
+    _vko_sampled_images_iTextureCube[vko_index(id)]
+#define vko_iTextureCube_nonuniform(id)                                                            \
+    _vko_sampled_images_iTextureCube[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTextureCube, TextureCube<uint4>)
+#define vko_uTextureCube(id)                                                                       \
+    _vko_sampled_images_uTextureCube[vko_index(id)]
+#define vko_uTextureCube_nonuniform(id)                                                            \
+    _vko_sampled_images_uTextureCube[NonUniformResourceIndex(vko_index(id))]
+
+#if VKO_IMAGE_CUBE_ARRAY_ENABLED
+
+_VKO_DECLARE_SAMPLED_IMAGE(TextureCubeArray, TextureCubeArray<float4>)
+#define vko_TextureCubeArray(id)                                                                   \
+    _vko_sampled_images_TextureCubeArray[vko_index(id)]
+#define vko_TextureCubeArray_nonuniform(id)                                                        \
+    _vko_sampled_images_TextureCubeArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(iTextureCubeArray, TextureCubeArray<int4>)
+#define vko_iTextureCubeArray(id)                                                                  \
+    _vko_sampled_images_iTextureCubeArray[vko_index(id)]
+#define vko_iTextureCubeArray_nonuniform(id)                                                       \
+    _vko_sampled_images_iTextureCubeArray[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_SAMPLED_IMAGE(uTextureCubeArray, TextureCubeArray<uint4>)
+#define vko_uTextureCubeArray(id)                                                                  \
+    _vko_sampled_images_uTextureCubeArray[vko_index(id)]
+#define vko_uTextureCubeArray_nonuniform(id)                                                       \
+    _vko_sampled_images_uTextureCubeArray[NonUniformResourceIndex(vko_index(id))]
+
+#endif // VKO_IMAGE_CUBE_ARRAY_ENABLED
+
+#undef _VKO_DECLARE_SAMPLED_IMAGE
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#define VKO_DECLARE_STORAGE_IMAGE(NAME, TYPE, FORMAT)                                              \
+    [[vk::binding(VKO_STORAGE_IMAGE_BINDING, VKO_GLOBAL_SET)]] [[vk::image_format(FORMAT)]]        \
+        TYPE _vko_##NAME##_storage_images[];
+
+#define VKO_DECLARE_STORAGE_IMAGE_WITHOUT_FORMAT(NAME, TYPE)                                       \
+    [[vk::binding(VKO_STORAGE_IMAGE_BINDING, VKO_GLOBAL_SET)]]                                     \
+        TYPE _vko_##NAME##_storage_images[];
+
+#define vko_image(NAME, id)                                                                        \
+    _vko_##NAME##_storage_images[vko_index(id)]
+#define vko_image_nonuniform(NAME, id)                                                             \
+    _vko_##NAME##_storage_images[NonUniformResourceIndex(vko_index(id))]
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#define VKO_DECLARE_STORAGE_BUFFER(NAME, TYPE)                                                     \
+    [[vk::binding(VKO_STORAGE_BUFFER_BINDING, VKO_GLOBAL_SET)]]                                    \
+        TYPE _vko_##NAME##_storage_buffers[];
+
+#define vko_buffer(NAME, id)                                                                       \
+    _vko_##NAME##_storage_buffers[vko_index(id)]
+#define vko_buffer_nonuniform(NAME, id)                                                            \
+    _vko_##NAME##_storage_buffers[NonUniformResourceIndex(vko_index(id))]
+
+VKO_DECLARE_STORAGE_BUFFER(_ByteAddressBuffer, ByteAddressBuffer)
+#define vko_ByteAddressBuffer(id)                                                                  \
+    _vko__ByteAddressBuffer_storage_buffers[vko_index(id)]
+#define vko_ByteAddressBuffer_nonuniform(id)                                                       \
+    _vko__ByteAddressBuffer_storage_buffers[NonUniformResourceIndex(vko_index(id))]
+
+VKO_DECLARE_STORAGE_BUFFER(_RWByteAddressBuffer, RWByteAddressBuffer)
+#define vko_RWByteAddressBuffer(id)                                                                \
+    _vko__RWByteAddressBuffer_storage_buffers[vko_index(id)]
+#define vko_RWByteAddressBuffer_nonuniform(id)                                                     \
+    _vko__RWByteAddressBuffer_storage_buffers[NonUniformResourceIndex(vko_index(id))]
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_ACCELERATION_STRUCTURE_ENABLED
+
+[[vk::binding(VKO_ACCELERATION_STRUCTURE_BINDING, VKO_GLOBAL_SET)]]
+    RaytracingAccelerationStructure _vko_acceleration_structures_RaytracingAccelerationStructure[];
+
+#define vko_RaytracingAccelerationStructure(id)                                                    \
+    _vko_acceleration_structures_RaytracingAccelerationStructure[vko_index(id)]
+#define vko_RaytracingAccelerationStructure_nonuniform(id)                                         \
+    _vko_acceleration_structures_RaytracingAccelerationStructure[NonUniformResourceIndex(vko_index(id))]
+
+#endif // VKO_ACCELERATION_STRUCTURE_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_UNIFORM_BUFFER_ENABLED
+
+#define VKO_DECLARE_UNIFORM_BUFFER(NAME, TYPE)                                                     \
+    [[vk::binding(VKO_UNIFORM_BUFFER_BINDING, VKO_GLOBAL_SET)]]                                    \
+        ConstantBuffer<TYPE> _vko_##NAME##_uniform_buffers[];
+
+#define vko_uniform_buffer(NAME, id)                                                               \
+    _vko_##NAME##_uniform_buffers[vko_index(id)]
+#define vko_uniform_buffer_nonuniform(NAME, id)                                                    \
+    _vko_##NAME##_uniform_buffers[NonUniformResourceIndex(vko_index(id))]
+
+#endif // VKO_UNIFORM_BUFFER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_UNIFORM_TEXEL_BUFFER_ENABLED
+
+#define _VKO_DECLARE_UNIFORM_TEXEL_BUFFER(NAME, TYPE)                                              \
+    [[vk::binding(VKO_UNIFORM_TEXEL_BUFFER_BINDING, VKO_GLOBAL_SET)]]                              \
+        TYPE _vko_uniform_texel_buffers_##NAME[];
+
+_VKO_DECLARE_UNIFORM_TEXEL_BUFFER(Buffer, Buffer<float4>)
+#define vko_Buffer(id)                                                                             \
+    _vko_uniform_texel_buffers_Buffer[vko_index(id)]
+#define vko_Buffer_nonuniform(id)                                                                  \
+    _vko_uniform_texel_buffers_Buffer[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_UNIFORM_TEXEL_BUFFER(iBuffer, Buffer<int4>)
+#define vko_iBuffer(id)                                                                            \
+    _vko_uniform_texel_buffers_iBuffer[vko_index(id)]
+#define vko_iBuffer_nonuniform(id)                                                                 \
+    _vko_uniform_texel_buffers_iBuffer[NonUniformResourceIndex(vko_index(id))]
+
+_VKO_DECLARE_UNIFORM_TEXEL_BUFFER(uBuffer, Buffer<uint4>)
+#define vko_uBuffer(id)                                                                            \
+    _vko_uniform_texel_buffers_uBuffer[vko_index(id)]
+#define vko_uBuffer_nonuniform(id)                                                                 \
+    _vko_uniform_texel_buffers_uBuffer[NonUniformResourceIndex(vko_index(id))]
+
+#undef _VKO_DECLARE_UNIFORM_TEXEL_BUFFER
+
+#endif // VKO_UNIFORM_TEXEL_BUFFER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_STORAGE_TEXEL_BUFFER_ENABLED
+
+#define VKO_DECLARE_STORAGE_TEXEL_BUFFER(NAME, TYPE, FORMAT)                                       \
+    [[vk::binding(VKO_STORAGE_TEXEL_BUFFER_BINDING, VKO_GLOBAL_SET)]] [[vk::image_format(FORMAT)]] \
+        TYPE _vko_##NAME##_storage_texel_buffers[];
+
+#define VKO_DECLARE_STORAGE_TEXEL_BUFFER_WITHOUT_FORMAT(NAME, TYPE)                                \
+    [[vk::binding(VKO_STORAGE_TEXEL_BUFFER_BINDING, VKO_GLOBAL_SET)]]                              \
+        TYPE _vko_##NAME##_storage_texel_buffers[];
+
+#define vko_texel_buffer(NAME, id)                                                                 \
+    _vko_##NAME##_storage_texel_buffers[vko_index(id)]
+#define vko_texel_buffer_nonuniform(NAME, id)                                                      \
+    _vko_##NAME##_storage_texel_buffers[NonUniformResourceIndex(vko_index(id))]
+
+#endif // VKO_STORAGE_TEXEL_BUFFER_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+// NOTE: HLSL has no combined image sampler types, so there are no accessors for the
+// `VKO_COMBINED_IMAGE_SAMPLER_BINDING`. Use a sampled image together with a sampler instead.
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#if VKO_INPUT_ATTACHMENT_ENABLED
+
+#define _VKO_DECLARE_INPUT_ATTACHMENT(NAME, TYPE)                                                  \
+    [[vk::binding(VKO_INPUT_ATTACHMENT_BINDING, VKO_LOCAL_SET)]] [[vk::input_attachment_index(0)]] \
+        TYPE _vko_input_attachments_##NAME[];
+
+_VKO_DECLARE_INPUT_ATTACHMENT(SubpassInput, SubpassInput<float4>)
+#define vko_SubpassInput(INDEX)                                                                    \
+    _vko_input_attachments_SubpassInput[INDEX]
+
+_VKO_DECLARE_INPUT_ATTACHMENT(iSubpassInput, SubpassInput<int4>)
+#define vko_iSubpassInput(INDEX)                                                                   \
+    _vko_input_attachments_iSubpassInput[INDEX]
+
+_VKO_DECLARE_INPUT_ATTACHMENT(uSubpassInput, SubpassInput<uint4>)
+#define vko_uSubpassInput(INDEX)                                                                   \
+    _vko_input_attachments_uSubpassInput[INDEX]
+
+_VKO_DECLARE_INPUT_ATTACHMENT(SubpassInputMS, SubpassInputMS<float4>)
+#define vko_SubpassInputMS(INDEX)                                                                  \
+    _vko_input_attachments_SubpassInputMS[INDEX]
+
+_VKO_DECLARE_INPUT_ATTACHMENT(iSubpassInputMS, SubpassInputMS<int4>)
+#define vko_iSubpassInputMS(INDEX)                                                                 \
+    _vko_input_attachments_iSubpassInputMS[INDEX]
+
+_VKO_DECLARE_INPUT_ATTACHMENT(uSubpassInputMS, SubpassInputMS<uint4>)
+#define vko_uSubpassInputMS(INDEX)                                                                 \
+    _vko_input_attachments_uSubpassInputMS[INDEX]
+
+#undef _VKO_DECLARE_INPUT_ATTACHMENT
+
+#endif // VKO_INPUT_ATTACHMENT_ENABLED
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+#endif // _VULKANO_HEADER
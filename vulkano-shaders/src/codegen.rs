@@ -45,7 +45,7 @@ fn compile_into_spirv_glslc(
         command.arg(format!("--target-spv={}", spirv));
     }
 
-    // vulkano.glsl dir first, then user include directories.
+    // vulkano header dir first, then user include directories.
     command.arg(format!("-I{}", vulkano_dir.display()));
     set_common_options(&mut command, options);
 
@@ -129,7 +129,7 @@ fn compile_into_spirv_slangc(
         .arg("-profile")
         .arg(spirv_version.as_slangc_profile());
 
-    // vulkano.glsl dir first, working dir for module imports, then user include directories.
+    // vulkano header dir first, working dir for module imports, then user include directories.
     command.arg(format!("-I{}", vulkano_dir.display()));
     command.arg(format!("-I{}", working_dir.display()));
     set_common_options(&mut command, options);
@@ -248,6 +248,11 @@ fn create_vulkano_dir() -> Result<TempDir, String> {
         include_str!("../include/vulkano.glsl"),
     )
     .map_err(|e| format!("failed to write vulkano.glsl: {e}"))?;
+    fs::write(
+        vulkano_dir.0.join("vulkano.hlsl"),
+        include_str!("../include/vulkano.hlsl"),
+    )
+    .map_err(|e| format!("failed to write vulkano.hlsl: {e}"))?;
 
     Ok(vulkano_dir)
 }
@@ -267,6 +267,37 @@ pub fn include_vulkano_glsl(input: proc_macro::TokenStream) -> proc_macro::Token
     expanded.into()
 }
 
+/// Expands to the contents of `vulkano.hlsl` as a string literal.
+///
+/// This is the HLSL counterpart of `vulkano.glsl`, declaring the descriptor arrays of the global
+/// descriptor set of vulkano-taskgraph's `BindlessContext` together with typed accessors for the
+/// IDs. When compiling HLSL with the [`shader!`] macro, it can be included using
+/// `#include <vulkano.hlsl>`.
+#[proc_macro]
+pub fn include_vulkano_hlsl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as syn::parse::Nothing);
+    let include_string = include_str!("../include/vulkano.hlsl");
+    let expanded = quote! { #include_string };
+    expanded.into()
+}
+
+/// Expands to items for use in Rust-GPU shaders matching the global descriptor set of
+/// vulkano-taskgraph's `BindlessContext`.
+///
+/// This generates the set and binding number constants (`GLOBAL_SET`, `SAMPLED_IMAGE_BINDING`,
+/// etc.) and the ID types (`SampledImageId`, `StorageBufferId`, etc.), which have the same layout
+/// as their host-side counterparts. Each ID type has a `get` method which returns the descriptor
+/// corresponding to the ID in a `RuntimeArray` bound at the matching binding. The expansion
+/// requires the `spirv_std` crate to be in scope.
+///
+/// The items are generated from `vulkano.glsl`, so that the shading languages share the same
+/// definitions.
+#[proc_macro]
+pub fn include_vulkano_rust_gpu(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as syn::parse::Nothing);
+    rust_gpu::bindless_module().into()
+}
+
 #[proc_macro]
 pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as MacroInput);
@@ -1,5 +1,82 @@
+use heck::ToShoutySnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+const VULKANO_GLSL: &str = include_str!("../include/vulkano.glsl");
+
+/// Generates the Rust-GPU counterpart of `vulkano.glsl`: the set and binding number constants as
+/// well as the ID types with accessors into the descriptor arrays of the global set.
+pub(super) fn bindless_module() -> TokenStream {
+    let constants = binding_constants(VULKANO_GLSL).map(|(name, value)| {
+        let name = format_ident!("{name}");
+
+        quote! { pub const #name: u32 = #value; }
+    });
+
+    let id_types = id_types(VULKANO_GLSL).map(|name| {
+        let binding = format!(
+            "{}_BINDING",
+            name.strip_suffix("Id").unwrap().to_shouty_snake_case(),
+        );
+        let get_doc = format!(
+            " Returns the descriptor corresponding to `self` in `descriptors`.\n\n # Safety\n\n \
+            - `descriptors` must be the descriptor array at binding [`{binding}`] of the global \
+            set.\n - `self` must be a valid ID of a descriptor that's still alive.",
+        );
+        let name = format_ident!("{name}");
+
+        quote! {
+            #[derive(Clone, Copy, PartialEq, Eq)]
+            #[repr(C)]
+            pub struct #name {
+                index: u32,
+                generation: u32,
+            }
+
+            impl #name {
+                /// Returns the index of the descriptor in its descriptor array.
+                #[inline]
+                pub const fn index(self) -> u32 {
+                    self.index
+                }
+
+                #[doc = #get_doc]
+                #[inline]
+                pub unsafe fn get<T>(self, descriptors: &::spirv_std::RuntimeArray<T>) -> &T {
+                    unsafe { descriptors.index(self.index as usize) }
+                }
+            }
+        }
+    });
+
+    quote! {
+        #( #constants )*
+        #( #id_types )*
+    }
+}
+
+/// Returns the set and binding number constants defined in the given header, without the `VKO_`
+/// prefix.
+fn binding_constants(header: &str) -> impl Iterator<Item = (&str, u32)> {
+    header.lines().filter_map(|line| {
+        let (name, value) = line.strip_prefix("#define VKO_")?.split_once(' ')?;
+
+        Some((name, value.trim().parse().ok()?))
+    })
+}
+
+/// Returns the names of the ID types defined in the given header.
+fn id_types(header: &str) -> impl Iterator<Item = &str> {
+    header.lines().filter_map(|line| {
+        let name = line.strip_prefix("struct ")?.strip_suffix(" {")?;
+
+        name.ends_with("Id").then_some(name)
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{codegen::reflect, structs::TypeRegistry, MacroInput};
     use proc_macro2::Span;
     use syn::LitStr;
@@ -41,4 +118,38 @@ mod tests {
         )
         .expect("reflecting spv failed");
     }
+
+    #[test]
+    fn headers_in_sync() {
+        let glsl_constants = binding_constants(VULKANO_GLSL).collect::<Vec<_>>();
+        let hlsl_constants =
+            binding_constants(include_str!("../include/vulkano.hlsl")).collect::<Vec<_>>();
+
+        assert!(!glsl_constants.is_empty());
+        assert_eq!(glsl_constants, hlsl_constants);
+
+        let glsl_id_types = id_types(VULKANO_GLSL).collect::<Vec<_>>();
+        let hlsl_id_types = id_types(include_str!("../include/vulkano.hlsl")).collect::<Vec<_>>();
+
+        assert!(!glsl_id_types.is_empty());
+        assert_eq!(glsl_id_types, hlsl_id_types);
+    }
+
+    #[test]
+    fn bindless_module_id_types() {
+        let constants = binding_constants(VULKANO_GLSL)
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+
+        for name in id_types(VULKANO_GLSL) {
+            let binding = format!(
+                "{}_BINDING",
+                name.strip_suffix("Id").unwrap().to_shouty_snake_case(),
+            );
+
+            assert!(constants.contains(&binding.as_str()), "{binding}");
+        }
+
+        syn::parse2::<syn::File>(bindless_module()).expect("generated invalid Rust code");
+    }
 }
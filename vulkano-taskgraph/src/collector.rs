@@ -3,7 +3,9 @@
 use crate::{
     assert_unsafe_precondition,
     descriptor_set::{
-        AccelerationStructureId, SampledImageId, SamplerId, StorageBufferId, StorageImageId,
+        AccelerationStructureId, CombinedImageSamplerId, SampledImageId, SamplerId,
+        StorageBufferId, StorageImageId, StorageTexelBufferId, UniformBufferId,
+        UniformTexelBufferId,
     },
     resource::{Flight, Resources},
     Id,
@@ -239,6 +241,122 @@ impl<'a> DeferredBatch<'a> {
         })
     }
 
+    /// Defers the destruction of the uniform buffer descriptor corresponding to `id`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `Resources` collection wasn't created with a bindless context.
+    /// - Panics if `id` is invalid.
+    #[inline]
+    #[track_caller]
+    pub fn destroy_uniform_buffer(&mut self, id: UniformBufferId) -> &mut Self {
+        let bcx = self
+            .resources
+            .bindless_context()
+            .expect("no bindless context");
+
+        bcx.global_set()
+            .invalidate_uniform_buffer(id, &self.guard)
+            .expect("invalid uniform buffer ID");
+
+        self.defer(move |resources| {
+            let bcx = resources.bindless_context().unwrap();
+
+            // SAFETY: Same as in the `destroy_buffer` method above.
+            unsafe {
+                bcx.global_set()
+                    .remove_invalidated_uniform_buffer_unchecked(id)
+            };
+        })
+    }
+
+    /// Defers the destruction of the uniform texel buffer descriptor corresponding to `id`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `Resources` collection wasn't created with a bindless context.
+    /// - Panics if `id` is invalid.
+    #[inline]
+    #[track_caller]
+    pub fn destroy_uniform_texel_buffer(&mut self, id: UniformTexelBufferId) -> &mut Self {
+        let bcx = self
+            .resources
+            .bindless_context()
+            .expect("no bindless context");
+
+        bcx.global_set()
+            .invalidate_uniform_texel_buffer(id, &self.guard)
+            .expect("invalid uniform texel buffer ID");
+
+        self.defer(move |resources| {
+            let bcx = resources.bindless_context().unwrap();
+
+            // SAFETY: Same as in the `destroy_buffer` method above.
+            unsafe {
+                bcx.global_set()
+                    .remove_invalidated_uniform_texel_buffer_unchecked(id)
+            };
+        })
+    }
+
+    /// Defers the destruction of the storage texel buffer descriptor corresponding to `id`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `Resources` collection wasn't created with a bindless context.
+    /// - Panics if `id` is invalid.
+    #[inline]
+    #[track_caller]
+    pub fn destroy_storage_texel_buffer(&mut self, id: StorageTexelBufferId) -> &mut Self {
+        let bcx = self
+            .resources
+            .bindless_context()
+            .expect("no bindless context");
+
+        bcx.global_set()
+            .invalidate_storage_texel_buffer(id, &self.guard)
+            .expect("invalid storage texel buffer ID");
+
+        self.defer(move |resources| {
+            let bcx = resources.bindless_context().unwrap();
+
+            // SAFETY: Same as in the `destroy_buffer` method above.
+            unsafe {
+                bcx.global_set()
+                    .remove_invalidated_storage_texel_buffer_unchecked(id)
+            };
+        })
+    }
+
+    /// Defers the destruction of the combined image sampler descriptor corresponding to `id`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `Resources` collection wasn't created with a bindless context.
+    /// - Panics if `id` is invalid.
+    #[inline]
+    #[track_caller]
+    pub fn destroy_combined_image_sampler(&mut self, id: CombinedImageSamplerId) -> &mut Self {
+        let bcx = self
+            .resources
+            .bindless_context()
+            .expect("no bindless context");
+
+        bcx.global_set()
+            .invalidate_combined_image_sampler(id, &self.guard)
+            .expect("invalid combined image sampler ID");
+
+        self.defer(move |resources| {
+            let bcx = resources.bindless_context().unwrap();
+
+            // SAFETY: Same as in the `destroy_buffer` method above.
+            unsafe {
+                bcx.global_set()
+                    .remove_invalidated_combined_image_sampler_unchecked(id)
+            };
+        })
+    }
+
     /// Defers the destruction of the given `object`.
     #[inline]
     pub fn destroy_object(&mut self, object: impl Send + 'static) -> &mut Self {
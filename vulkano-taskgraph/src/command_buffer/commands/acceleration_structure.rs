@@ -0,0 +1,557 @@
+use crate::{
+    command_buffer::{RecordingCommandBuffer, Result},
+    resource::AccessTypes,
+};
+use std::sync::Arc;
+use vulkano::{
+    acceleration_structure::{
+        AccelerationStructure, AccelerationStructureBuildGeometryInfo,
+        AccelerationStructureBuildRangeInfo, AccelerationStructureGeometryData,
+        BuildAccelerationStructureMode, CopyAccelerationStructureInfo,
+        CopyAccelerationStructureToMemoryInfo, CopyMemoryToAccelerationStructureInfo,
+    },
+    query::QueryPool,
+    DeviceAddress,
+};
+
+/// # Commands to build and copy acceleration structures
+///
+/// Acceleration structures are not resources of the task graph themselves. Instead, the buffers
+/// backing them as well as any buffers referenced by device address (such as the scratch buffer
+/// and the geometry data) must be accounted for in the task's access set. This way, the task graph
+/// can insert the appropriate barriers between for example a build and a subsequent ray tracing
+/// operation that reads the built acceleration structure. The `try_` variants of the commands
+/// return an error if any of these buffers isn't accounted for.
+///
+/// These commands require the [`acceleration_structure`] feature to be enabled on the device.
+///
+/// [`acceleration_structure`]: vulkano::device::DeviceFeatures::acceleration_structure
+impl RecordingCommandBuffer<'_> {
+    /// Builds or updates an acceleration structure, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_build_acceleration_structure().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_build_acceleration_structure`] returns a [`ValidationError`].
+    ///
+    /// [`try_build_acceleration_structure`]: Self::try_build_acceleration_structure
+    #[track_caller]
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) -> &mut Self {
+        unsafe { self.try_build_acceleration_structure(info, build_range_infos) }.unwrap()
+    }
+
+    /// Builds or updates an acceleration structure.
+    ///
+    /// The following must be accounted for in the task's access set:
+    ///
+    /// - The buffer backing `info.dst_acceleration_structure`, with
+    ///   [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE`].
+    /// - If `info.mode` is [`BuildAccelerationStructureMode::Update`], the buffer backing the
+    ///   source acceleration structure, with
+    ///   [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ`].
+    /// - The buffer containing `info.scratch_data`, with both
+    ///   [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ`] and
+    ///   [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE`].
+    /// - The buffers containing the vertex, index, transform, AABB and instance data of the
+    ///   geometries, with [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_SHADER_READ`].
+    ///
+    /// Only the start of each device address is checked against the task's access set. If
+    /// `info` builds a top-level acceleration structure whose instances are given as an array of
+    /// pointers, the instances that the pointers point to aren't checked.
+    ///
+    /// # Safety
+    ///
+    /// - All device addresses in `info` must refer to memory that is valid for the duration of
+    ///   the build.
+    /// - If building a top-level acceleration structure, the bottom-level acceleration structures
+    ///   referenced by the instances must have been built before the command is executed.
+    ///
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE
+    /// [`BuildAccelerationStructureMode::Update`]: vulkano::acceleration_structure::BuildAccelerationStructureMode::Update
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_SHADER_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_BUILD_SHADER_READ
+    pub unsafe fn try_build_acceleration_structure(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) -> Result<&mut Self> {
+        self.validate_build_acceleration_structure(info)?;
+
+        unsafe {
+            self.inner
+                .try_build_acceleration_structure(info, build_range_infos)
+        }?;
+
+        Ok(self)
+    }
+
+    fn validate_build_acceleration_structure(
+        &self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+    ) -> Result {
+        if let Some(dst_acceleration_structure) = info.dst_acceleration_structure {
+            self.accesses.validate_buffer_access(
+                dst_acceleration_structure.buffer(),
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE,
+                "info.dst_acceleration_structure",
+                "ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE",
+            )?;
+        }
+
+        if info.mode == BuildAccelerationStructureMode::Update {
+            if let Some(src_acceleration_structure) = info.src_acceleration_structure {
+                self.accesses.validate_buffer_access(
+                    src_acceleration_structure.buffer(),
+                    AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ,
+                    "info.src_acceleration_structure",
+                    "ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ",
+                )?;
+            }
+        }
+
+        self.accesses.validate_device_address_access(
+            info.scratch_data,
+            AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ
+                | AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE,
+            "info.scratch_data",
+            "ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ | \
+            AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE",
+        )?;
+
+        for (index, geometry) in info.geometries.iter().enumerate() {
+            let device_addresses: &[(DeviceAddress, &str)] = match &geometry.geometry {
+                AccelerationStructureGeometryData::Triangles(triangles) => &[
+                    (triangles.vertex_data, "vertex_data"),
+                    (
+                        if triangles.index_type.is_some() {
+                            triangles.index_data
+                        } else {
+                            0
+                        },
+                        "index_data",
+                    ),
+                    (triangles.transform_data, "transform_data"),
+                ],
+                AccelerationStructureGeometryData::Aabbs(aabbs) => &[(aabbs.data, "data")],
+                AccelerationStructureGeometryData::Instances(instances) => {
+                    &[(instances.data, "data")]
+                }
+                _ => &[],
+            };
+
+            for &(device_address, field) in device_addresses {
+                // A device address of zero means that the data is not provided.
+                if device_address == 0 {
+                    continue;
+                }
+
+                self.accesses.validate_device_address_access(
+                    device_address,
+                    AccessTypes::ACCELERATION_STRUCTURE_BUILD_SHADER_READ,
+                    format!("info.geometries[{}].geometry.{}", index, field),
+                    "ACCELERATION_STRUCTURE_BUILD_SHADER_READ",
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub unsafe fn build_acceleration_structure_unchecked(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) -> &mut Self {
+        unsafe {
+            self.inner
+                .build_acceleration_structure_unchecked(info, build_range_infos)
+        };
+
+        self
+    }
+
+    /// Builds or updates an acceleration structure, using
+    /// [`AccelerationStructureBuildRangeInfo`] structs that are read from a buffer, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_build_acceleration_structure_indirect().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_build_acceleration_structure_indirect`] returns a [`ValidationError`].
+    ///
+    /// [`try_build_acceleration_structure_indirect`]: Self::try_build_acceleration_structure_indirect
+    #[track_caller]
+    pub unsafe fn build_acceleration_structure_indirect(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+        indirect_device_address: DeviceAddress,
+        indirect_stride: u32,
+        max_primitive_counts: &[u32],
+    ) -> &mut Self {
+        unsafe {
+            self.try_build_acceleration_structure_indirect(
+                info,
+                indirect_device_address,
+                indirect_stride,
+                max_primitive_counts,
+            )
+        }
+        .unwrap()
+    }
+
+    /// Builds or updates an acceleration structure, using
+    /// [`AccelerationStructureBuildRangeInfo`] structs that are read from a buffer.
+    ///
+    /// The same resources as for [`try_build_acceleration_structure`] must be accounted for in
+    /// the task's access set. In addition, the buffer containing `indirect_device_address` must
+    /// be accounted for with [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_INDIRECT_COMMAND_READ`].
+    ///
+    /// # Safety
+    ///
+    /// - The safety requirements of [`try_build_acceleration_structure`] apply.
+    /// - The `primitive_count` of each [`AccelerationStructureBuildRangeInfo`] read from
+    ///   `indirect_device_address` must not be greater than the corresponding element of
+    ///   `max_primitive_counts`.
+    ///
+    /// [`try_build_acceleration_structure`]: Self::try_build_acceleration_structure
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_INDIRECT_COMMAND_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_BUILD_INDIRECT_COMMAND_READ
+    pub unsafe fn try_build_acceleration_structure_indirect(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+        indirect_device_address: DeviceAddress,
+        indirect_stride: u32,
+        max_primitive_counts: &[u32],
+    ) -> Result<&mut Self> {
+        self.validate_build_acceleration_structure(info)?;
+        self.accesses.validate_device_address_access(
+            indirect_device_address,
+            AccessTypes::ACCELERATION_STRUCTURE_BUILD_INDIRECT_COMMAND_READ,
+            "indirect_device_address",
+            "ACCELERATION_STRUCTURE_BUILD_INDIRECT_COMMAND_READ",
+        )?;
+
+        unsafe {
+            self.inner.try_build_acceleration_structure_indirect(
+                info,
+                indirect_device_address,
+                indirect_stride,
+                max_primitive_counts,
+            )
+        }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn build_acceleration_structure_indirect_unchecked(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo<'_>,
+        indirect_device_address: DeviceAddress,
+        indirect_stride: u32,
+        max_primitive_counts: &[u32],
+    ) -> &mut Self {
+        unsafe {
+            self.inner.build_acceleration_structure_indirect_unchecked(
+                info,
+                indirect_device_address,
+                indirect_stride,
+                max_primitive_counts,
+            )
+        };
+
+        self
+    }
+
+    /// Copies the data of one acceleration structure to another, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_copy_acceleration_structure().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_copy_acceleration_structure`] returns a [`ValidationError`].
+    ///
+    /// [`try_copy_acceleration_structure`]: Self::try_copy_acceleration_structure
+    #[track_caller]
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        info: &CopyAccelerationStructureInfo<'_>,
+    ) -> &mut Self {
+        unsafe { self.try_copy_acceleration_structure(info) }.unwrap()
+    }
+
+    /// Copies the data of one acceleration structure to another.
+    ///
+    /// The buffer backing `info.src` must be accounted for in the task's access set with
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ`], and the buffer
+    /// backing `info.dst` with
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE`].
+    ///
+    /// # Safety
+    ///
+    /// - `info.src` must have been built before the command is executed.
+    ///
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE
+    pub unsafe fn try_copy_acceleration_structure(
+        &mut self,
+        info: &CopyAccelerationStructureInfo<'_>,
+    ) -> Result<&mut Self> {
+        self.accesses.validate_buffer_access(
+            info.src.buffer(),
+            AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ,
+            "info.src",
+            "ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ",
+        )?;
+        self.accesses.validate_buffer_access(
+            info.dst.buffer(),
+            AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE,
+            "info.dst",
+            "ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE",
+        )?;
+
+        unsafe { self.inner.try_copy_acceleration_structure(info) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn copy_acceleration_structure_unchecked(
+        &mut self,
+        info: &CopyAccelerationStructureInfo<'_>,
+    ) -> &mut Self {
+        unsafe { self.inner.copy_acceleration_structure_unchecked(info) };
+
+        self
+    }
+
+    /// Serializes the data of an acceleration structure and writes it to a buffer, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_copy_acceleration_structure_to_memory().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_copy_acceleration_structure_to_memory`] returns a [`ValidationError`].
+    ///
+    /// [`try_copy_acceleration_structure_to_memory`]: Self::try_copy_acceleration_structure_to_memory
+    #[track_caller]
+    pub unsafe fn copy_acceleration_structure_to_memory(
+        &mut self,
+        info: &CopyAccelerationStructureToMemoryInfo<'_>,
+    ) -> &mut Self {
+        unsafe { self.try_copy_acceleration_structure_to_memory(info) }.unwrap()
+    }
+
+    /// Serializes the data of an acceleration structure and writes it to a buffer.
+    ///
+    /// The buffer backing `info.src` must be accounted for in the task's access set with
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ`], and the buffer
+    /// containing `info.dst` with [`AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_WRITE`].
+    ///
+    /// # Safety
+    ///
+    /// - `info.src` must have been built before the command is executed.
+    /// - `info.dst` must refer to memory that is large enough to hold the serialized data.
+    ///
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_WRITE`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_WRITE
+    pub unsafe fn try_copy_acceleration_structure_to_memory(
+        &mut self,
+        info: &CopyAccelerationStructureToMemoryInfo<'_>,
+    ) -> Result<&mut Self> {
+        self.accesses.validate_buffer_access(
+            info.src.buffer(),
+            AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ,
+            "info.src",
+            "ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_READ",
+        )?;
+        self.accesses.validate_device_address_access(
+            info.dst,
+            AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_WRITE,
+            "info.dst",
+            "ACCELERATION_STRUCTURE_COPY_TRANSFER_WRITE",
+        )?;
+
+        unsafe { self.inner.try_copy_acceleration_structure_to_memory(info) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn copy_acceleration_structure_to_memory_unchecked(
+        &mut self,
+        info: &CopyAccelerationStructureToMemoryInfo<'_>,
+    ) -> &mut Self {
+        unsafe {
+            self.inner
+                .copy_acceleration_structure_to_memory_unchecked(info)
+        };
+
+        self
+    }
+
+    /// Reads data from a buffer that was previously written by
+    /// [`copy_acceleration_structure_to_memory`] and deserializes it into an acceleration
+    /// structure, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_copy_memory_to_acceleration_structure().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_copy_memory_to_acceleration_structure`] returns a [`ValidationError`].
+    ///
+    /// [`copy_acceleration_structure_to_memory`]: Self::copy_acceleration_structure_to_memory
+    /// [`try_copy_memory_to_acceleration_structure`]: Self::try_copy_memory_to_acceleration_structure
+    #[track_caller]
+    pub unsafe fn copy_memory_to_acceleration_structure(
+        &mut self,
+        info: &CopyMemoryToAccelerationStructureInfo<'_>,
+    ) -> &mut Self {
+        unsafe { self.try_copy_memory_to_acceleration_structure(info) }.unwrap()
+    }
+
+    /// Reads data from a buffer that was previously written by
+    /// [`copy_acceleration_structure_to_memory`] and deserializes it into an acceleration
+    /// structure.
+    ///
+    /// The buffer containing `info.src` must be accounted for in the task's access set with
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_READ`], and the buffer backing
+    /// `info.dst` with [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE`].
+    ///
+    /// # Safety
+    ///
+    /// - `info.src` must refer to serialized data that is compatible with the device.
+    ///
+    /// [`copy_acceleration_structure_to_memory`]: Self::copy_acceleration_structure_to_memory
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_READ
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE
+    pub unsafe fn try_copy_memory_to_acceleration_structure(
+        &mut self,
+        info: &CopyMemoryToAccelerationStructureInfo<'_>,
+    ) -> Result<&mut Self> {
+        self.accesses.validate_device_address_access(
+            info.src,
+            AccessTypes::ACCELERATION_STRUCTURE_COPY_TRANSFER_READ,
+            "info.src",
+            "ACCELERATION_STRUCTURE_COPY_TRANSFER_READ",
+        )?;
+        self.accesses.validate_buffer_access(
+            info.dst.buffer(),
+            AccessTypes::ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE,
+            "info.dst",
+            "ACCELERATION_STRUCTURE_COPY_ACCELERATION_STRUCTURE_WRITE",
+        )?;
+
+        unsafe { self.inner.try_copy_memory_to_acceleration_structure(info) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn copy_memory_to_acceleration_structure_unchecked(
+        &mut self,
+        info: &CopyMemoryToAccelerationStructureInfo<'_>,
+    ) -> &mut Self {
+        unsafe {
+            self.inner
+                .copy_memory_to_acceleration_structure_unchecked(info)
+        };
+
+        self
+    }
+
+    /// Writes the properties of one or more acceleration structures to a query, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_write_acceleration_structures_properties().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_write_acceleration_structures_properties`] returns a
+    ///   [`ValidationError`].
+    ///
+    /// [`try_write_acceleration_structures_properties`]: Self::try_write_acceleration_structures_properties
+    #[track_caller]
+    pub unsafe fn write_acceleration_structures_properties(
+        &mut self,
+        acceleration_structures: &[Arc<AccelerationStructure>],
+        query_pool: &QueryPool,
+        first_query: u32,
+    ) -> &mut Self {
+        unsafe {
+            self.try_write_acceleration_structures_properties(
+                acceleration_structures,
+                query_pool,
+                first_query,
+            )
+        }
+        .unwrap()
+    }
+
+    /// Writes the properties of one or more acceleration structures to a query.
+    ///
+    /// For each element in `acceleration_structures`, one query is written, in numeric order
+    /// starting at `first_query`.
+    ///
+    /// The buffers backing `acceleration_structures` must be accounted for in the task's access
+    /// set with [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ`].
+    ///
+    /// # Safety
+    ///
+    /// - All elements of `acceleration_structures` must have been built before the command is
+    ///   executed.
+    /// - If `query_pool` is a compacted size query pool, all elements of
+    ///   `acceleration_structures` must have been built with
+    ///   [`BuildAccelerationStructureFlags::ALLOW_COMPACTION`].
+    /// - The queries must be unavailable, ensured by calling [`reset_query_pool`].
+    ///
+    /// [`AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ`]: crate::resource::AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ
+    /// [`BuildAccelerationStructureFlags::ALLOW_COMPACTION`]: vulkano::acceleration_structure::BuildAccelerationStructureFlags::ALLOW_COMPACTION
+    /// [`reset_query_pool`]: Self::reset_query_pool
+    pub unsafe fn try_write_acceleration_structures_properties(
+        &mut self,
+        acceleration_structures: &[Arc<AccelerationStructure>],
+        query_pool: &QueryPool,
+        first_query: u32,
+    ) -> Result<&mut Self> {
+        for (index, acceleration_structure) in acceleration_structures.iter().enumerate() {
+            self.accesses.validate_buffer_access(
+                acceleration_structure.buffer(),
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ,
+                format!("acceleration_structures[{}]", index),
+                "ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ",
+            )?;
+        }
+
+        unsafe {
+            self.inner.try_write_acceleration_structures_properties(
+                acceleration_structures,
+                query_pool,
+                first_query,
+            )
+        }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn write_acceleration_structures_properties_unchecked(
+        &mut self,
+        acceleration_structures: &[Arc<AccelerationStructure>],
+        query_pool: &QueryPool,
+        first_query: u32,
+    ) -> &mut Self {
+        unsafe {
+            self.inner
+                .write_acceleration_structures_properties_unchecked(
+                    acceleration_structures,
+                    query_pool,
+                    first_query,
+                )
+        };
+
+        self
+    }
+}
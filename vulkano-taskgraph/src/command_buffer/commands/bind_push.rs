@@ -15,6 +15,7 @@ use vulkano::{
         ray_tracing::RayTracingPipeline, ComputePipeline, GraphicsPipeline, Pipeline,
         PipelineBindPoint, PipelineLayout,
     },
+    shader::{object::ShaderObject, ShaderStage},
     DeviceSize, Version, VulkanObject,
 };
 
@@ -22,6 +23,65 @@ use vulkano::{
 ///
 /// These commands require a queue with a pipeline type that uses the given state.
 impl RecordingCommandBuffer<'_> {
+    /// Binds descriptor buffers for future dispatch or draw calls, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_bind_descriptor_buffers().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_bind_descriptor_buffers`] returns a [`ValidationError`].
+    ///
+    /// [`try_bind_descriptor_buffers`]: Self::try_bind_descriptor_buffers
+    #[track_caller]
+    pub unsafe fn bind_descriptor_buffers(&mut self, buffers: &[Id<Buffer>]) -> &mut Self {
+        unsafe { self.try_bind_descriptor_buffers(buffers) }.unwrap()
+    }
+
+    /// Binds descriptor buffers for future dispatch or draw calls.
+    ///
+    /// The descriptor sets are then taken from regions of the bound buffers, which are set with
+    /// [`set_descriptor_buffer_offsets`].
+    ///
+    /// [`set_descriptor_buffer_offsets`]: Self::set_descriptor_buffer_offsets
+    pub unsafe fn try_bind_descriptor_buffers(
+        &mut self,
+        buffers: &[Id<Buffer>],
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.bind_descriptor_buffers_unchecked(buffers) })
+    }
+
+    pub unsafe fn bind_descriptor_buffers_unchecked(
+        &mut self,
+        buffers: &[Id<Buffer>],
+    ) -> &mut Self {
+        if buffers.is_empty() {
+            return self;
+        }
+
+        let binding_infos_vk: SmallVec<[_; 4]> = buffers
+            .iter()
+            .map(|&buffer| {
+                let buffer = unsafe { self.accesses.buffer_unchecked(buffer) };
+
+                vk::DescriptorBufferBindingInfoEXT::default()
+                    .address(unsafe { buffer.device_address_unchecked() }.get())
+                    .usage(buffer.usage().into())
+            })
+            .collect();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_descriptor_buffer.cmd_bind_descriptor_buffers_ext)(
+                self.handle(),
+                binding_infos_vk.len() as u32,
+                binding_infos_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
     /// Binds an index buffer for future indexed draw calls, panicking on a validation error.
     ///
     /// This is a shortcut for `try_bind_index_buffer().unwrap()`.
@@ -245,6 +305,81 @@ impl RecordingCommandBuffer<'_> {
         self
     }
 
+    /// Binds shader objects to the given shader stages for future draw or dispatch calls,
+    /// panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_bind_shaders().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_bind_shaders`] returns a [`ValidationError`].
+    ///
+    /// [`try_bind_shaders`]: Self::try_bind_shaders
+    #[track_caller]
+    pub unsafe fn bind_shaders(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&Arc<ShaderObject>>],
+    ) -> &mut Self {
+        unsafe { self.try_bind_shaders(stages, shaders) }.unwrap()
+    }
+
+    /// Binds shader objects to the given shader stages for future draw or dispatch calls. A
+    /// `None` element unbinds the shader object from the corresponding stage.
+    pub unsafe fn try_bind_shaders(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&Arc<ShaderObject>>],
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.bind_shaders_unchecked(stages, shaders) })
+    }
+
+    pub unsafe fn bind_shaders_unchecked(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&Arc<ShaderObject>>],
+    ) -> &mut Self {
+        if stages.is_empty() {
+            return self;
+        }
+
+        let stages_vk = stages
+            .iter()
+            .map(|&stage| stage.into())
+            .collect::<SmallVec<[vk::ShaderStageFlags; 5]>>();
+        let shaders_vk = shaders
+            .iter()
+            .map(|shader| shader.map_or(vk::ShaderEXT::null(), |shader| shader.handle()))
+            .collect::<SmallVec<[_; 5]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_shader_object.cmd_bind_shaders_ext)(
+                self.handle(),
+                stages_vk.len() as u32,
+                stages_vk.as_ptr(),
+                shaders_vk.as_ptr(),
+            )
+        };
+
+        for &shader in shaders.iter().flatten() {
+            let bind_point = shader.bind_point();
+            let invalidate_from = self
+                .state
+                .invalidate_descriptor_sets(bind_point, shader.layout());
+
+            if let Some(first_set) = invalidate_from {
+                if first_set <= LOCAL_SET {
+                    self.bind_bindless_sets(bind_point, shader.layout(), first_set);
+                }
+            }
+
+            self.deferreds.push(Deferred::destroy(shader.clone()));
+        }
+
+        self
+    }
+
     /// Binds vertex buffers for future draw calls, panicking on a validation error.
     ///
     /// This is a shortcut for `try_bind_vertex_buffers().unwrap()`.
@@ -446,4 +581,89 @@ impl RecordingCommandBuffer<'_> {
 
         self
     }
+
+    /// Sets the offsets into the bound descriptor buffers for future dispatch or draw calls,
+    /// panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_set_descriptor_buffer_offsets().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_descriptor_buffer_offsets`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_descriptor_buffer_offsets`]: Self::try_set_descriptor_buffer_offsets
+    #[track_caller]
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &Arc<PipelineLayout>,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> &mut Self {
+        unsafe {
+            self.try_set_descriptor_buffer_offsets(
+                pipeline_bind_point,
+                layout,
+                first_set,
+                buffer_indices,
+                offsets,
+            )
+        }
+        .unwrap()
+    }
+
+    /// Sets the offsets into the bound descriptor buffers for future dispatch or draw calls.
+    ///
+    /// Set number `first_set + i` is taken from the buffer at index `buffer_indices[i]` of those
+    /// bound with [`bind_descriptor_buffers`], starting at byte offset `offsets[i]`.
+    ///
+    /// [`bind_descriptor_buffers`]: Self::bind_descriptor_buffers
+    pub unsafe fn try_set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &Arc<PipelineLayout>,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> Result<&mut Self> {
+        Ok(unsafe {
+            self.set_descriptor_buffer_offsets_unchecked(
+                pipeline_bind_point,
+                layout,
+                first_set,
+                buffer_indices,
+                offsets,
+            )
+        })
+    }
+
+    pub unsafe fn set_descriptor_buffer_offsets_unchecked(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &Arc<PipelineLayout>,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> &mut Self {
+        if offsets.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_descriptor_buffer
+                .cmd_set_descriptor_buffer_offsets_ext)(
+                self.handle(),
+                pipeline_bind_point.into(),
+                layout.handle(),
+                first_set,
+                offsets.len() as u32,
+                buffer_indices.as_ptr(),
+                offsets.as_ptr(),
+            )
+        };
+
+        self
+    }
 }
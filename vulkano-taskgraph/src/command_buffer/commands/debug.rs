@@ -0,0 +1,106 @@
+use crate::command_buffer::{RecordingCommandBuffer, Result};
+use vulkano::instance::debug::DebugUtilsLabel;
+
+/// # Commands for debugging
+///
+/// These commands require the [`ext_debug_utils`] instance extension to be enabled.
+///
+/// [`ext_debug_utils`]: vulkano::instance::InstanceExtensions::ext_debug_utils
+impl RecordingCommandBuffer<'_> {
+    /// Opens a command buffer debug label region, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_begin_debug_utils_label().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_begin_debug_utils_label`] returns a [`ValidationError`].
+    ///
+    /// [`try_begin_debug_utils_label`]: Self::try_begin_debug_utils_label
+    #[track_caller]
+    pub unsafe fn begin_debug_utils_label(&mut self, label_info: &DebugUtilsLabel) -> &mut Self {
+        unsafe { self.try_begin_debug_utils_label(label_info) }.unwrap()
+    }
+
+    /// Opens a command buffer debug label region.
+    ///
+    /// The region must be closed with [`end_debug_utils_label`] within the same task.
+    ///
+    /// [`end_debug_utils_label`]: Self::end_debug_utils_label
+    pub unsafe fn try_begin_debug_utils_label(
+        &mut self,
+        label_info: &DebugUtilsLabel,
+    ) -> Result<&mut Self> {
+        unsafe { self.inner.try_begin_debug_utils_label(label_info) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn begin_debug_utils_label_unchecked(
+        &mut self,
+        label_info: &DebugUtilsLabel,
+    ) -> &mut Self {
+        unsafe { self.inner.begin_debug_utils_label_unchecked(label_info) };
+
+        self
+    }
+
+    /// Closes a command buffer debug label region, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_end_debug_utils_label().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_end_debug_utils_label`] returns a [`ValidationError`].
+    ///
+    /// [`try_end_debug_utils_label`]: Self::try_end_debug_utils_label
+    #[track_caller]
+    pub unsafe fn end_debug_utils_label(&mut self) -> &mut Self {
+        unsafe { self.try_end_debug_utils_label() }.unwrap()
+    }
+
+    /// Closes a command buffer debug label region.
+    pub unsafe fn try_end_debug_utils_label(&mut self) -> Result<&mut Self> {
+        unsafe { self.inner.try_end_debug_utils_label() }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn end_debug_utils_label_unchecked(&mut self) -> &mut Self {
+        unsafe { self.inner.end_debug_utils_label_unchecked() };
+
+        self
+    }
+
+    /// Inserts a command buffer debug label, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_insert_debug_utils_label().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_insert_debug_utils_label`] returns a [`ValidationError`].
+    ///
+    /// [`try_insert_debug_utils_label`]: Self::try_insert_debug_utils_label
+    #[track_caller]
+    pub unsafe fn insert_debug_utils_label(&mut self, label_info: &DebugUtilsLabel) -> &mut Self {
+        unsafe { self.try_insert_debug_utils_label(label_info) }.unwrap()
+    }
+
+    /// Inserts a command buffer debug label.
+    pub unsafe fn try_insert_debug_utils_label(
+        &mut self,
+        label_info: &DebugUtilsLabel,
+    ) -> Result<&mut Self> {
+        unsafe { self.inner.try_insert_debug_utils_label(label_info) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn insert_debug_utils_label_unchecked(
+        &mut self,
+        label_info: &DebugUtilsLabel,
+    ) -> &mut Self {
+        unsafe { self.inner.insert_debug_utils_label_unchecked(label_info) };
+
+        self
+    }
+}
@@ -3,12 +3,17 @@ use ash::vk;
 use smallvec::SmallVec;
 use vulkano::{
     device::DeviceOwned,
+    image::SampleCount,
     pipeline::graphics::{
-        color_blend::LogicOp,
+        color_blend::{AttachmentBlend, ColorComponents, LogicOp},
         depth_stencil::{CompareOp, StencilFaces, StencilOp},
         fragment_shading_rate::FragmentShadingRateCombinerOp,
         input_assembly::PrimitiveTopology,
-        rasterization::{ConservativeRasterizationMode, CullMode, FrontFace},
+        rasterization::{
+            ConservativeRasterizationMode, CullMode, FrontFace, LineRasterizationMode, PolygonMode,
+            ProvokingVertexMode,
+        },
+        tessellation::TessellationDomainOrigin,
         vertex_input::{
             VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputState,
         },
@@ -1287,6 +1292,569 @@ impl RecordingCommandBuffer<'_> {
         self
     }
 
+    /// Sets the dynamic tessellation domain origin for future draw calls, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_set_tessellation_domain_origin().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_tessellation_domain_origin`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_tessellation_domain_origin`]: Self::try_set_tessellation_domain_origin
+    #[track_caller]
+    pub unsafe fn set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        unsafe { self.try_set_tessellation_domain_origin(domain_origin) }.unwrap()
+    }
+
+    /// Sets the dynamic tessellation domain origin for future draw calls.
+    pub unsafe fn try_set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_tessellation_domain_origin_unchecked(domain_origin) })
+    }
+
+    pub unsafe fn set_tessellation_domain_origin_unchecked(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_tessellation_domain_origin_ext)(
+                self.handle(), domain_origin.into()
+            )
+        };
+
+        self
+    }
+
+    /// Sets whether dynamic depth clamping is enabled for future draw calls, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_set_depth_clamp_enable().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_depth_clamp_enable`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_depth_clamp_enable`]: Self::try_set_depth_clamp_enable
+    #[track_caller]
+    pub unsafe fn set_depth_clamp_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_depth_clamp_enable(enable) }.unwrap()
+    }
+
+    /// Sets whether dynamic depth clamping is enabled for future draw calls.
+    pub unsafe fn try_set_depth_clamp_enable(&mut self, enable: bool) -> Result<&mut Self> {
+        Ok(unsafe { self.set_depth_clamp_enable_unchecked(enable) })
+    }
+
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_depth_clamp_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
+    /// Sets the dynamic polygon mode for future draw calls, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_set_polygon_mode().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_polygon_mode`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_polygon_mode`]: Self::try_set_polygon_mode
+    #[track_caller]
+    pub unsafe fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        unsafe { self.try_set_polygon_mode(polygon_mode) }.unwrap()
+    }
+
+    /// Sets the dynamic polygon mode for future draw calls.
+    pub unsafe fn try_set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> Result<&mut Self> {
+        Ok(unsafe { self.set_polygon_mode_unchecked(polygon_mode) })
+    }
+
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+                self.handle(),
+                polygon_mode.into(),
+            )
+        };
+
+        self
+    }
+
+    /// Sets the dynamic number of rasterization samples for future draw calls, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_set_rasterization_samples().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_rasterization_samples`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_rasterization_samples`]: Self::try_set_rasterization_samples
+    #[track_caller]
+    pub unsafe fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        unsafe { self.try_set_rasterization_samples(rasterization_samples) }.unwrap()
+    }
+
+    /// Sets the dynamic number of rasterization samples for future draw calls.
+    pub unsafe fn try_set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_rasterization_samples_unchecked(rasterization_samples) })
+    }
+
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_rasterization_samples_ext)(
+                self.handle(), rasterization_samples.into()
+            )
+        };
+
+        self
+    }
+
+    /// Sets the dynamic sample mask for future draw calls, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_set_sample_mask().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_sample_mask`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_sample_mask`]: Self::try_set_sample_mask
+    #[track_caller]
+    pub unsafe fn set_sample_mask(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        unsafe { self.try_set_sample_mask(samples, sample_mask) }.unwrap()
+    }
+
+    /// Sets the dynamic sample mask for future draw calls.
+    pub unsafe fn try_set_sample_mask(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_sample_mask_unchecked(samples, sample_mask) })
+    }
+
+    pub unsafe fn set_sample_mask_unchecked(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_sample_mask_ext)(
+                self.handle(),
+                samples.into(),
+                sample_mask.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    /// Sets whether dynamic alpha-to-coverage is enabled for future draw calls, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_set_alpha_to_coverage_enable().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_alpha_to_coverage_enable`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_alpha_to_coverage_enable`]: Self::try_set_alpha_to_coverage_enable
+    #[track_caller]
+    pub unsafe fn set_alpha_to_coverage_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_alpha_to_coverage_enable(enable) }.unwrap()
+    }
+
+    /// Sets whether dynamic alpha-to-coverage is enabled for future draw calls.
+    pub unsafe fn try_set_alpha_to_coverage_enable(&mut self, enable: bool) -> Result<&mut Self> {
+        Ok(unsafe { self.set_alpha_to_coverage_enable_unchecked(enable) })
+    }
+
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_alpha_to_coverage_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
+    /// Sets whether dynamic alpha-to-one is enabled for future draw calls, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_set_alpha_to_one_enable().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_alpha_to_one_enable`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_alpha_to_one_enable`]: Self::try_set_alpha_to_one_enable
+    #[track_caller]
+    pub unsafe fn set_alpha_to_one_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_alpha_to_one_enable(enable) }.unwrap()
+    }
+
+    /// Sets whether dynamic alpha-to-one is enabled for future draw calls.
+    pub unsafe fn try_set_alpha_to_one_enable(&mut self, enable: bool) -> Result<&mut Self> {
+        Ok(unsafe { self.set_alpha_to_one_enable_unchecked(enable) })
+    }
+
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_alpha_to_one_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
+    /// Sets whether dynamic logic ops are enabled for future draw calls, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_set_logic_op_enable().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_logic_op_enable`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_logic_op_enable`]: Self::try_set_logic_op_enable
+    #[track_caller]
+    pub unsafe fn set_logic_op_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_logic_op_enable(enable) }.unwrap()
+    }
+
+    /// Sets whether dynamic logic ops are enabled for future draw calls.
+    pub unsafe fn try_set_logic_op_enable(&mut self, enable: bool) -> Result<&mut Self> {
+        Ok(unsafe { self.set_logic_op_enable_unchecked(enable) })
+    }
+
+    pub unsafe fn set_logic_op_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_logic_op_enable_ext)(
+                self.handle(),
+                enable.into(),
+            )
+        };
+
+        self
+    }
+
+    /// Sets whether dynamic color blending should be enabled for each attachment in the
+    /// framebuffer, starting at `first_attachment` for future draw calls, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_set_color_blend_enable().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_color_blend_enable`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_color_blend_enable`]: Self::try_set_color_blend_enable
+    #[track_caller]
+    pub unsafe fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> &mut Self {
+        unsafe { self.try_set_color_blend_enable(first_attachment, enables) }.unwrap()
+    }
+
+    /// Sets whether dynamic color blending should be enabled for each attachment in the
+    /// framebuffer, starting at `first_attachment` for future draw calls.
+    pub unsafe fn try_set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_color_blend_enable_unchecked(first_attachment, enables) })
+    }
+
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> &mut Self {
+        if enables.is_empty() {
+            return self;
+        }
+
+        let enables_vk = enables
+            .iter()
+            .copied()
+            .map(|v| v as vk::Bool32)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_color_blend_enable_ext)(
+                self.handle(),
+                first_attachment,
+                enables_vk.len() as u32,
+                enables_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    /// Sets the dynamic color blend equation for each attachment in the framebuffer, starting at
+    /// `first_attachment` for future draw calls, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_set_color_blend_equation().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_color_blend_equation`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_color_blend_equation`]: Self::try_set_color_blend_equation
+    #[track_caller]
+    pub unsafe fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> &mut Self {
+        unsafe { self.try_set_color_blend_equation(first_attachment, equations) }.unwrap()
+    }
+
+    /// Sets the dynamic color blend equation for each attachment in the framebuffer, starting at
+    /// `first_attachment` for future draw calls.
+    pub unsafe fn try_set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_color_blend_equation_unchecked(first_attachment, equations) })
+    }
+
+    pub unsafe fn set_color_blend_equation_unchecked(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> &mut Self {
+        if equations.is_empty() {
+            return self;
+        }
+
+        let equations_vk = equations
+            .iter()
+            .map(AttachmentBlend::to_vk_color_blend_equation)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_color_blend_equation_ext)(
+                self.handle(),
+                first_attachment,
+                equations_vk.len() as u32,
+                equations_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    /// Sets the dynamic color write mask for each attachment in the framebuffer, starting at
+    /// `first_attachment` for future draw calls, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_set_color_write_mask().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_color_write_mask`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_color_write_mask`]: Self::try_set_color_write_mask
+    #[track_caller]
+    pub unsafe fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> &mut Self {
+        unsafe { self.try_set_color_write_mask(first_attachment, masks) }.unwrap()
+    }
+
+    /// Sets the dynamic color write mask for each attachment in the framebuffer, starting at
+    /// `first_attachment` for future draw calls.
+    pub unsafe fn try_set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_color_write_mask_unchecked(first_attachment, masks) })
+    }
+
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> &mut Self {
+        if masks.is_empty() {
+            return self;
+        }
+
+        let masks_vk = masks
+            .iter()
+            .map(|&mask| vk::ColorComponentFlags::from(mask))
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_color_write_mask_ext)(
+                self.handle(),
+                first_attachment,
+                masks_vk.len() as u32,
+                masks_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    /// Sets the dynamic provoking vertex mode for future draw calls, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_set_provoking_vertex_mode().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_provoking_vertex_mode`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_provoking_vertex_mode`]: Self::try_set_provoking_vertex_mode
+    #[track_caller]
+    pub unsafe fn set_provoking_vertex_mode(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        unsafe { self.try_set_provoking_vertex_mode(provoking_vertex_mode) }.unwrap()
+    }
+
+    /// Sets the dynamic provoking vertex mode for future draw calls.
+    pub unsafe fn try_set_provoking_vertex_mode(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_provoking_vertex_mode_unchecked(provoking_vertex_mode) })
+    }
+
+    pub unsafe fn set_provoking_vertex_mode_unchecked(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_provoking_vertex_mode_ext)(
+                self.handle(), provoking_vertex_mode.into()
+            )
+        };
+
+        self
+    }
+
+    /// Sets the dynamic line rasterization mode for future draw calls, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_set_line_rasterization_mode().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_line_rasterization_mode`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_line_rasterization_mode`]: Self::try_set_line_rasterization_mode
+    #[track_caller]
+    pub unsafe fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        unsafe { self.try_set_line_rasterization_mode(line_rasterization_mode) }.unwrap()
+    }
+
+    /// Sets the dynamic line rasterization mode for future draw calls.
+    pub unsafe fn try_set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<&mut Self> {
+        Ok(unsafe { self.set_line_rasterization_mode_unchecked(line_rasterization_mode) })
+    }
+
+    pub unsafe fn set_line_rasterization_mode_unchecked(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_line_rasterization_mode_ext)(
+                self.handle(), line_rasterization_mode.into()
+            )
+        };
+
+        self
+    }
+
+    /// Sets whether dynamic line stippling is enabled for future draw calls, panicking on a
+    /// validation error.
+    ///
+    /// This is a shortcut for `try_set_line_stipple_enable().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_set_line_stipple_enable`] returns a [`ValidationError`].
+    ///
+    /// [`try_set_line_stipple_enable`]: Self::try_set_line_stipple_enable
+    #[track_caller]
+    pub unsafe fn set_line_stipple_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_line_stipple_enable(enable) }.unwrap()
+    }
+
+    /// Sets whether dynamic line stippling is enabled for future draw calls.
+    pub unsafe fn try_set_line_stipple_enable(&mut self, enable: bool) -> Result<&mut Self> {
+        Ok(unsafe { self.set_line_stipple_enable_unchecked(enable) })
+    }
+
+    pub unsafe fn set_line_stipple_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_line_stipple_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
     /// Sets the dynamic fragment shading rate for future draw calls, panicking on a validation
     /// error.
     ///
@@ -1,7 +1,10 @@
+pub(super) mod acceleration_structure;
 pub(super) mod bind_push;
 pub(super) mod clear;
 pub(super) mod copy;
+pub(super) mod debug;
 pub(super) mod dynamic_state;
 pub(super) mod pipeline;
+pub(super) mod query;
 pub(super) mod render_pass;
 pub(super) mod sync;
@@ -0,0 +1,356 @@
+use crate::{
+    command_buffer::{RecordingCommandBuffer, Result},
+    Id,
+};
+use vulkano::{
+    buffer::Buffer,
+    query::{QueryControlFlags, QueryPool, QueryResultElement, QueryResultFlags},
+    sync::PipelineStage,
+    DeviceSize,
+};
+
+/// # Commands related to queries
+impl RecordingCommandBuffer<'_> {
+    /// Begins a query, panicking on a validation error.
+    ///
+    /// The query will be active until [`end_query`] is called for the same query.
+    ///
+    /// This is a shortcut for `try_begin_query().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_begin_query`] returns a [`ValidationError`].
+    ///
+    /// [`end_query`]: Self::end_query
+    /// [`try_begin_query`]: Self::try_begin_query
+    #[track_caller]
+    pub unsafe fn begin_query(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+        flags: QueryControlFlags,
+    ) -> &mut Self {
+        unsafe { self.try_begin_query(query_pool, query, flags) }.unwrap()
+    }
+
+    /// Begins a query.
+    ///
+    /// The query will be active until [`end_query`] is called for the same query.
+    ///
+    /// [`end_query`]: Self::end_query
+    pub unsafe fn try_begin_query(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+        flags: QueryControlFlags,
+    ) -> Result<&mut Self> {
+        unsafe { self.inner.try_begin_query(query_pool, query, flags) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn begin_query_unchecked(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+        flags: QueryControlFlags,
+    ) -> &mut Self {
+        unsafe { self.inner.begin_query_unchecked(query_pool, query, flags) };
+
+        self
+    }
+
+    /// Ends an active query, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_end_query().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_end_query`] returns a [`ValidationError`].
+    ///
+    /// [`try_end_query`]: Self::try_end_query
+    #[track_caller]
+    pub unsafe fn end_query(&mut self, query_pool: &QueryPool, query: u32) -> &mut Self {
+        unsafe { self.try_end_query(query_pool, query) }.unwrap()
+    }
+
+    /// Ends an active query.
+    pub unsafe fn try_end_query(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+    ) -> Result<&mut Self> {
+        unsafe { self.inner.try_end_query(query_pool, query) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn end_query_unchecked(&mut self, query_pool: &QueryPool, query: u32) -> &mut Self {
+        unsafe { self.inner.end_query_unchecked(query_pool, query) };
+
+        self
+    }
+
+    /// Writes a timestamp to a timestamp query, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_write_timestamp().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_write_timestamp`] returns a [`ValidationError`].
+    ///
+    /// [`try_write_timestamp`]: Self::try_write_timestamp
+    #[track_caller]
+    pub unsafe fn write_timestamp(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+        stage: PipelineStage,
+    ) -> &mut Self {
+        unsafe { self.try_write_timestamp(query_pool, query, stage) }.unwrap()
+    }
+
+    /// Writes a timestamp to a timestamp query.
+    pub unsafe fn try_write_timestamp(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+        stage: PipelineStage,
+    ) -> Result<&mut Self> {
+        unsafe { self.inner.try_write_timestamp(query_pool, query, stage) }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn write_timestamp_unchecked(
+        &mut self,
+        query_pool: &QueryPool,
+        query: u32,
+        stage: PipelineStage,
+    ) -> &mut Self {
+        unsafe {
+            self.inner
+                .write_timestamp_unchecked(query_pool, query, stage)
+        };
+
+        self
+    }
+
+    /// Resets a range of queries on a query pool, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_reset_query_pool().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_reset_query_pool`] returns a [`ValidationError`].
+    ///
+    /// [`try_reset_query_pool`]: Self::try_reset_query_pool
+    #[track_caller]
+    pub unsafe fn reset_query_pool(
+        &mut self,
+        query_pool: &QueryPool,
+        first_query: u32,
+        query_count: u32,
+    ) -> &mut Self {
+        unsafe { self.try_reset_query_pool(query_pool, first_query, query_count) }.unwrap()
+    }
+
+    /// Resets a range of queries on a query pool.
+    ///
+    /// The affected queries will be marked as "unavailable" after this command runs, and will no
+    /// longer return any results. They will be ready to have new results recorded for them.
+    pub unsafe fn try_reset_query_pool(
+        &mut self,
+        query_pool: &QueryPool,
+        first_query: u32,
+        query_count: u32,
+    ) -> Result<&mut Self> {
+        unsafe {
+            self.inner
+                .try_reset_query_pool(query_pool, first_query, query_count)
+        }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn reset_query_pool_unchecked(
+        &mut self,
+        query_pool: &QueryPool,
+        first_query: u32,
+        query_count: u32,
+    ) -> &mut Self {
+        unsafe {
+            self.inner
+                .reset_query_pool_unchecked(query_pool, first_query, query_count)
+        };
+
+        self
+    }
+
+    /// Copies the results of a range of queries to a buffer on the GPU, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_copy_query_pool_results().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_copy_query_pool_results`] returns a [`ValidationError`].
+    ///
+    /// [`try_copy_query_pool_results`]: Self::try_copy_query_pool_results
+    #[track_caller]
+    pub unsafe fn copy_query_pool_results<T: QueryResultElement>(
+        &mut self,
+        copy_query_pool_results_info: &CopyQueryPoolResultsInfo<'_>,
+    ) -> &mut Self {
+        unsafe { self.try_copy_query_pool_results::<T>(copy_query_pool_results_info) }.unwrap()
+    }
+
+    /// Copies the results of a range of queries to a buffer on the GPU.
+    ///
+    /// [`query_pool.result_len(flags)`] elements of type `T` will be written for each query in
+    /// the range, plus 1 extra element per query if [`WITH_AVAILABILITY`] is enabled.
+    ///
+    /// The destination buffer must be accounted for in the task's access set with
+    /// [`AccessTypes::COPY_TRANSFER_WRITE`].
+    ///
+    /// [`query_pool.result_len(flags)`]: QueryPool::result_len
+    /// [`WITH_AVAILABILITY`]: QueryResultFlags::WITH_AVAILABILITY
+    /// [`AccessTypes::COPY_TRANSFER_WRITE`]: crate::resource::AccessTypes::COPY_TRANSFER_WRITE
+    pub unsafe fn try_copy_query_pool_results<T: QueryResultElement>(
+        &mut self,
+        copy_query_pool_results_info: &CopyQueryPoolResultsInfo<'_>,
+    ) -> Result<&mut Self> {
+        let &CopyQueryPoolResultsInfo {
+            query_pool,
+            first_query,
+            query_count,
+            dst_buffer,
+            dst_offset,
+            stride,
+            flags,
+            _ne: _,
+        } = copy_query_pool_results_info;
+
+        let dst_buffer = unsafe { self.accesses.buffer_unchecked(dst_buffer) };
+        let stride = stride_or_default::<T>(query_pool, stride, flags);
+
+        unsafe {
+            self.inner.try_copy_query_pool_results::<T>(
+                query_pool,
+                first_query,
+                query_count,
+                dst_buffer,
+                dst_offset,
+                stride,
+                flags,
+            )
+        }?;
+
+        Ok(self)
+    }
+
+    pub unsafe fn copy_query_pool_results_unchecked<T: QueryResultElement>(
+        &mut self,
+        copy_query_pool_results_info: &CopyQueryPoolResultsInfo<'_>,
+    ) -> &mut Self {
+        let &CopyQueryPoolResultsInfo {
+            query_pool,
+            first_query,
+            query_count,
+            dst_buffer,
+            dst_offset,
+            stride,
+            flags,
+            _ne: _,
+        } = copy_query_pool_results_info;
+
+        let dst_buffer = unsafe { self.accesses.buffer_unchecked(dst_buffer) };
+        let stride = stride_or_default::<T>(query_pool, stride, flags);
+
+        unsafe {
+            self.inner.copy_query_pool_results_unchecked::<T>(
+                query_pool,
+                first_query,
+                query_count,
+                dst_buffer,
+                dst_offset,
+                stride,
+                flags,
+            )
+        };
+
+        self
+    }
+}
+
+fn stride_or_default<T>(
+    query_pool: &QueryPool,
+    stride: DeviceSize,
+    flags: QueryResultFlags,
+) -> DeviceSize {
+    if stride == 0 {
+        query_pool.result_len(flags) * size_of::<T>() as DeviceSize
+    } else {
+        stride
+    }
+}
+
+/// Parameters to copy the results of queries to a buffer.
+#[derive(Clone, Debug)]
+pub struct CopyQueryPoolResultsInfo<'a> {
+    /// The query pool to copy the results from.
+    ///
+    /// There is no default value.
+    pub query_pool: &'a QueryPool,
+
+    /// The index of the first query to copy the results of.
+    ///
+    /// The default value is `0`.
+    pub first_query: u32,
+
+    /// The number of queries to copy the results of.
+    ///
+    /// The default value is the number of queries in `query_pool`.
+    pub query_count: u32,
+
+    /// The buffer to copy the results to.
+    ///
+    /// The default value is [`Id::INVALID`], which must be overridden.
+    pub dst_buffer: Id<Buffer>,
+
+    /// The offset in bytes from the start of `dst_buffer` that copying will start at.
+    ///
+    /// The default value is `0`.
+    pub dst_offset: DeviceSize,
+
+    /// The distance in bytes between the results of consecutive queries in `dst_buffer`.
+    ///
+    /// If set to `0`, the results are tightly packed.
+    ///
+    /// The default value is `0`.
+    pub stride: DeviceSize,
+
+    /// Additional flags to control how the results are copied.
+    ///
+    /// The default value is empty.
+    pub flags: QueryResultFlags,
+
+    pub _ne: crate::NonExhaustive<'a>,
+}
+
+impl<'a> CopyQueryPoolResultsInfo<'a> {
+    /// Returns a default `CopyQueryPoolResultsInfo` with the provided `query_pool`.
+    #[inline]
+    pub fn new(query_pool: &'a QueryPool) -> Self {
+        Self {
+            query_pool,
+            first_query: 0,
+            query_count: query_pool.query_count(),
+            dst_buffer: Id::INVALID,
+            dst_offset: 0,
+            stride: 0,
+            flags: QueryResultFlags::empty(),
+            _ne: crate::NE,
+        }
+    }
+}
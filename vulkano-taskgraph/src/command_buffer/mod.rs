@@ -1,24 +1,28 @@
 //! Recording commands to execute on the device.
 
 #[allow(unused_imports)] // everything is exported for future-proofing
-pub use self::commands::{clear::*, copy::*, dynamic_state::*, pipeline::*, sync::*};
+pub use self::commands::{
+    acceleration_structure::*, clear::*, copy::*, debug::*, dynamic_state::*, pipeline::*,
+    query::*, sync::*,
+};
 use crate::{
     collector::Deferred,
     descriptor_set::{LocalDescriptorSet, GLOBAL_SET, LOCAL_SET},
-    graph::ResourceMap,
+    graph::{self, ResourceMap},
+    resource::AccessTypes,
     Id,
 };
 use ash::vk;
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 use vulkano::{
-    buffer::Buffer,
+    buffer::{Buffer, BufferUsage},
     command_buffer as raw,
     device::{Device, DeviceOwned},
     image::Image,
     pipeline::{PipelineBindPoint, PipelineLayout},
     render_pass::Framebuffer,
-    VulkanError, VulkanObject,
+    DeviceAddress, ValidationError, VulkanError, VulkanObject,
 };
 
 mod commands;
@@ -38,6 +42,7 @@ pub struct RecordingCommandBuffer<'a> {
 
 struct ResourceAccesses<'a> {
     resource_map: &'a ResourceMap<'a>,
+    node_accesses: &'a graph::ResourceAccesses,
 }
 
 #[derive(Default)]
@@ -57,12 +62,16 @@ impl<'a> RecordingCommandBuffer<'a> {
         inner: &'a mut raw::RecordingCommandBuffer,
         state: &'a mut CommandBufferState,
         resource_map: &'a ResourceMap<'a>,
+        node_accesses: &'a graph::ResourceAccesses,
         deferreds: &'a mut Vec<Deferred>,
     ) -> Self {
         RecordingCommandBuffer {
             inner,
             state,
-            accesses: ResourceAccesses { resource_map },
+            accesses: ResourceAccesses {
+                resource_map,
+                node_accesses,
+            },
             deferreds,
         }
     }
@@ -201,6 +210,87 @@ impl<'a> ResourceAccesses<'a> {
             }
         }
     }
+
+    /// Returns an error if `buffer` isn't accounted for in the task's access set with
+    /// `access_types`.
+    fn validate_buffer_access(
+        &self,
+        buffer: &Buffer,
+        access_types: AccessTypes,
+        context: impl Into<Cow<'static, str>>,
+        access_types_name: &'static str,
+    ) -> Result {
+        let is_accessed = self
+            .node_accesses
+            .buffers_with_access(access_types)
+            // SAFETY: The IDs in the task's access set are valid and the caller of `Task::execute`
+            // must ensure that `self.resource_map` maps the virtual IDs of the graph exhaustively.
+            .any(|id| unsafe { self.buffer_unchecked(id) }.handle() == buffer.handle());
+
+        if !is_accessed {
+            return Err(Box::new(ValidationError {
+                context: context.into(),
+                problem: format!(
+                    "is not backed by a buffer that is accounted for in the task's access set with \
+                    `AccessTypes::{}`",
+                    access_types_name,
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if `device_address` isn't contained in a buffer that is accounted for in
+    /// the task's access set with `access_types`.
+    fn validate_device_address_access(
+        &self,
+        device_address: DeviceAddress,
+        access_types: AccessTypes,
+        context: impl Into<Cow<'static, str>>,
+        access_types_name: &'static str,
+    ) -> Result {
+        let is_accessed = self
+            .node_accesses
+            .buffers_with_access(access_types)
+            .any(|id| {
+                // SAFETY: The IDs in the task's access set are valid and the caller of
+                // `Task::execute` must ensure that `self.resource_map` maps the virtual IDs of the
+                // graph exhaustively.
+                let buffer = unsafe { self.buffer_unchecked(id) };
+
+                if !buffer
+                    .usage()
+                    .intersects(BufferUsage::SHADER_DEVICE_ADDRESS)
+                {
+                    return false;
+                }
+
+                // SAFETY: We checked that the buffer was created with the
+                // `SHADER_DEVICE_ADDRESS` usage, which requires the `buffer_device_address`
+                // feature to be enabled.
+                let start = unsafe { buffer.device_address_unchecked() }.get();
+
+                (start..start + buffer.size()).contains(&device_address)
+            });
+
+        if !is_accessed {
+            return Err(Box::new(ValidationError {
+                context: context.into(),
+                problem: format!(
+                    "is not contained in a buffer that is accounted for in the task's access set \
+                    with `AccessTypes::{}`",
+                    access_types_name,
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
 }
 
 impl CommandBufferState {
@@ -9,7 +9,10 @@ use smallvec::{smallvec, SmallVec};
 use std::{mem, slice, sync::Arc};
 use vulkano::{
     acceleration_structure::AccelerationStructure,
-    buffer::Buffer,
+    buffer::{
+        view::{BufferView, BufferViewCreateInfo},
+        Buffer, BufferUsage,
+    },
     descriptor_set::{
         allocator::{AllocationHandle, DescriptorSetAlloc, DescriptorSetAllocator},
         layout::{
@@ -36,10 +39,11 @@ use vulkano::{
     },
     render_pass::Framebuffer,
     shader::ShaderStages,
-    DeviceSize, Validated, Version, VulkanError, VulkanObject,
+    DeviceSize, Validated, ValidationError, Version, VulkanError, VulkanObject,
 };
 
-// NOTE(Marc): The following constants must match the definitions in include/vulkano.glsl!
+// NOTE(Marc): The following constants must match the definitions in include/vulkano.glsl and
+// include/vulkano.hlsl!
 
 /// The set number of the [`GlobalDescriptorSet`].
 pub const GLOBAL_SET: u32 = 0;
@@ -59,6 +63,18 @@ pub const STORAGE_BUFFER_BINDING: u32 = 3;
 /// The binding number of acceleration structures in the [`GlobalDescriptorSet`].
 pub const ACCELERATION_STRUCTURE_BINDING: u32 = 4;
 
+/// The binding number of uniform buffers in the [`GlobalDescriptorSet`].
+pub const UNIFORM_BUFFER_BINDING: u32 = 5;
+
+/// The binding number of uniform texel buffers in the [`GlobalDescriptorSet`].
+pub const UNIFORM_TEXEL_BUFFER_BINDING: u32 = 6;
+
+/// The binding number of storage texel buffers in the [`GlobalDescriptorSet`].
+pub const STORAGE_TEXEL_BUFFER_BINDING: u32 = 7;
+
+/// The binding number of combined image samplers in the [`GlobalDescriptorSet`].
+pub const COMBINED_IMAGE_SAMPLER_BINDING: u32 = 8;
+
 /// The set number of the local descriptor set.
 pub const LOCAL_SET: u32 = 1;
 
@@ -259,6 +275,10 @@ pub struct GlobalDescriptorSet {
     storage_buffers: SlotMap<StorageBufferId, Option<StorageBufferDescriptor>>,
     acceleration_structures:
         SlotMap<AccelerationStructureId, Option<AccelerationStructureDescriptor>>,
+    uniform_buffers: SlotMap<UniformBufferId, Option<UniformBufferDescriptor>>,
+    uniform_texel_buffers: SlotMap<UniformTexelBufferId, Option<UniformTexelBufferDescriptor>>,
+    storage_texel_buffers: SlotMap<StorageTexelBufferId, Option<StorageTexelBufferDescriptor>>,
+    combined_image_samplers: SlotMap<CombinedImageSamplerId, CombinedImageSamplerDescriptor>,
 }
 
 #[derive(Debug)]
@@ -290,6 +310,30 @@ pub struct AccelerationStructureDescriptor {
     acceleration_structure: Arc<AccelerationStructure>,
 }
 
+#[derive(Debug)]
+pub struct UniformBufferDescriptor {
+    buffer: Arc<Buffer>,
+    offset: DeviceSize,
+    size: DeviceSize,
+}
+
+#[derive(Debug)]
+pub struct UniformTexelBufferDescriptor {
+    buffer_view: Arc<BufferView>,
+}
+
+#[derive(Debug)]
+pub struct StorageTexelBufferDescriptor {
+    buffer_view: Arc<BufferView>,
+}
+
+#[derive(Debug)]
+pub struct CombinedImageSamplerDescriptor {
+    image_view: Arc<ImageView>,
+    image_layout: ImageLayout,
+    sampler: Arc<Sampler>,
+}
+
 impl GlobalDescriptorSet {
     fn new(
         resources: &Arc<ResourceStorage>,
@@ -308,6 +352,10 @@ impl GlobalDescriptorSet {
         let max_storage_images = descriptor_count(STORAGE_IMAGE_BINDING);
         let max_storage_buffers = descriptor_count(STORAGE_BUFFER_BINDING);
         let max_acceleration_structures = descriptor_count(ACCELERATION_STRUCTURE_BINDING);
+        let max_uniform_buffers = descriptor_count(UNIFORM_BUFFER_BINDING);
+        let max_uniform_texel_buffers = descriptor_count(UNIFORM_TEXEL_BUFFER_BINDING);
+        let max_storage_texel_buffers = descriptor_count(STORAGE_TEXEL_BUFFER_BINDING);
+        let max_combined_image_samplers = descriptor_count(COMBINED_IMAGE_SAMPLER_BINDING);
 
         // SAFETY: We make sure that any guard we acquire through `ResourceStorage::pin` doesn't
         // outlive the `Resources` collection.
@@ -329,6 +377,22 @@ impl GlobalDescriptorSet {
         let acceleration_structures = unsafe {
             SlotMap::with_collector_and_key(max_acceleration_structures, hyaline_collector.clone())
         };
+        // SAFETY: Same as the previous.
+        let uniform_buffers = unsafe {
+            SlotMap::with_collector_and_key(max_uniform_buffers, hyaline_collector.clone())
+        };
+        // SAFETY: Same as the previous.
+        let uniform_texel_buffers = unsafe {
+            SlotMap::with_collector_and_key(max_uniform_texel_buffers, hyaline_collector.clone())
+        };
+        // SAFETY: Same as the previous.
+        let storage_texel_buffers = unsafe {
+            SlotMap::with_collector_and_key(max_storage_texel_buffers, hyaline_collector.clone())
+        };
+        // SAFETY: Same as the previous.
+        let combined_image_samplers = unsafe {
+            SlotMap::with_collector_and_key(max_combined_image_samplers, hyaline_collector.clone())
+        };
 
         if device.enabled_features().null_descriptor {
             let guard = resources.pin();
@@ -339,6 +403,26 @@ impl GlobalDescriptorSet {
                 acceleration_structures.insert(None, &guard),
                 AccelerationStructureId::NULL,
             );
+
+            // The bindings of the following descriptor types only exist if they were requested and
+            // the device supports updating them after binding.
+            if max_uniform_buffers != 0 {
+                assert_eq!(uniform_buffers.insert(None, &guard), UniformBufferId::NULL);
+            }
+
+            if max_uniform_texel_buffers != 0 {
+                assert_eq!(
+                    uniform_texel_buffers.insert(None, &guard),
+                    UniformTexelBufferId::NULL,
+                );
+            }
+
+            if max_storage_texel_buffers != 0 {
+                assert_eq!(
+                    storage_texel_buffers.insert(None, &guard),
+                    StorageTexelBufferId::NULL,
+                );
+            }
         }
 
         Ok(GlobalDescriptorSet {
@@ -350,6 +434,10 @@ impl GlobalDescriptorSet {
             storage_images,
             storage_buffers,
             acceleration_structures,
+            uniform_buffers,
+            uniform_texel_buffers,
+            storage_texel_buffers,
+            combined_image_samplers,
         })
     }
 
@@ -365,7 +453,7 @@ impl GlobalDescriptorSet {
 
         let stages = get_all_supported_shader_stages(device);
 
-        let mut bindings: SmallVec<[_; 5]> = smallvec![
+        let mut bindings: SmallVec<[_; 9]> = smallvec![
             DescriptorSetLayoutBinding {
                 binding: SAMPLER_BINDING,
                 binding_flags,
@@ -406,6 +494,58 @@ impl GlobalDescriptorSet {
             });
         }
 
+        if create_info.max_uniform_buffers != 0
+            && device
+                .enabled_features()
+                .descriptor_binding_uniform_buffer_update_after_bind
+        {
+            bindings.push(DescriptorSetLayoutBinding {
+                binding: UNIFORM_BUFFER_BINDING,
+                binding_flags,
+                descriptor_count: create_info.max_uniform_buffers,
+                stages,
+                ..DescriptorSetLayoutBinding::new(DescriptorType::UniformBuffer)
+            });
+        }
+
+        if create_info.max_uniform_texel_buffers != 0
+            && device
+                .enabled_features()
+                .descriptor_binding_uniform_texel_buffer_update_after_bind
+        {
+            bindings.push(DescriptorSetLayoutBinding {
+                binding: UNIFORM_TEXEL_BUFFER_BINDING,
+                binding_flags,
+                descriptor_count: create_info.max_uniform_texel_buffers,
+                stages,
+                ..DescriptorSetLayoutBinding::new(DescriptorType::UniformTexelBuffer)
+            });
+        }
+
+        if create_info.max_storage_texel_buffers != 0
+            && device
+                .enabled_features()
+                .descriptor_binding_storage_texel_buffer_update_after_bind
+        {
+            bindings.push(DescriptorSetLayoutBinding {
+                binding: STORAGE_TEXEL_BUFFER_BINDING,
+                binding_flags,
+                descriptor_count: create_info.max_storage_texel_buffers,
+                stages,
+                ..DescriptorSetLayoutBinding::new(DescriptorType::StorageTexelBuffer)
+            });
+        }
+
+        if create_info.max_combined_image_samplers != 0 {
+            bindings.push(DescriptorSetLayoutBinding {
+                binding: COMBINED_IMAGE_SAMPLER_BINDING,
+                binding_flags,
+                descriptor_count: create_info.max_combined_image_samplers,
+                stages,
+                ..DescriptorSetLayoutBinding::new(DescriptorType::CombinedImageSampler)
+            });
+        }
+
         let layout = DescriptorSetLayout::new(
             device,
             &DescriptorSetLayoutCreateInfo {
@@ -519,6 +659,104 @@ impl GlobalDescriptorSet {
         Ok(self.add_storage_buffer(buffer, offset, size))
     }
 
+    #[track_caller]
+    pub fn create_uniform_buffer(
+        &self,
+        buffer_id: Id<Buffer>,
+        offset: DeviceSize,
+        size: Option<DeviceSize>,
+    ) -> Result<UniformBufferId, VulkanError> {
+        match self.try_create_uniform_buffer(buffer_id, offset, size) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    pub fn try_create_uniform_buffer(
+        &self,
+        buffer_id: Id<Buffer>,
+        offset: DeviceSize,
+        size: Option<DeviceSize>,
+    ) -> Result<UniformBufferId, Validated<VulkanError>> {
+        let buffer_state = self.resources.buffer(buffer_id).unwrap();
+        let buffer = buffer_state.buffer().clone();
+
+        Ok(self.add_uniform_buffer(buffer, offset, size)?)
+    }
+
+    #[track_caller]
+    pub fn create_uniform_texel_buffer(
+        &self,
+        buffer_id: Id<Buffer>,
+        create_info: &BufferViewCreateInfo<'_>,
+    ) -> Result<UniformTexelBufferId, VulkanError> {
+        match self.try_create_uniform_texel_buffer(buffer_id, create_info) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    pub fn try_create_uniform_texel_buffer(
+        &self,
+        buffer_id: Id<Buffer>,
+        create_info: &BufferViewCreateInfo<'_>,
+    ) -> Result<UniformTexelBufferId, Validated<VulkanError>> {
+        let buffer_state = self.resources.buffer(buffer_id).unwrap();
+        let buffer_view = BufferView::try_new(buffer_state.buffer(), create_info)?;
+
+        Ok(self.add_uniform_texel_buffer(buffer_view))
+    }
+
+    #[track_caller]
+    pub fn create_storage_texel_buffer(
+        &self,
+        buffer_id: Id<Buffer>,
+        create_info: &BufferViewCreateInfo<'_>,
+    ) -> Result<StorageTexelBufferId, VulkanError> {
+        match self.try_create_storage_texel_buffer(buffer_id, create_info) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    pub fn try_create_storage_texel_buffer(
+        &self,
+        buffer_id: Id<Buffer>,
+        create_info: &BufferViewCreateInfo<'_>,
+    ) -> Result<StorageTexelBufferId, Validated<VulkanError>> {
+        let buffer_state = self.resources.buffer(buffer_id).unwrap();
+        let buffer_view = BufferView::try_new(buffer_state.buffer(), create_info)?;
+
+        Ok(self.add_storage_texel_buffer(buffer_view))
+    }
+
+    #[track_caller]
+    pub fn create_combined_image_sampler(
+        &self,
+        image_id: Id<Image>,
+        create_info: &ImageViewCreateInfo<'_>,
+        image_layout: ImageLayout,
+        sampler: Arc<Sampler>,
+    ) -> Result<CombinedImageSamplerId, VulkanError> {
+        match self.try_create_combined_image_sampler(image_id, create_info, image_layout, sampler) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    pub fn try_create_combined_image_sampler(
+        &self,
+        image_id: Id<Image>,
+        create_info: &ImageViewCreateInfo<'_>,
+        image_layout: ImageLayout,
+        sampler: Arc<Sampler>,
+    ) -> Result<CombinedImageSamplerId, Validated<VulkanError>> {
+        let image_state = self.resources.image(image_id).unwrap();
+        let image_view = ImageView::new(image_state.image(), create_info)?;
+
+        Ok(self.add_combined_image_sampler(image_view, image_layout, sampler))
+    }
+
     pub fn add_sampler(&self, sampler: Arc<Sampler>) -> SamplerId {
         self.samplers.insert_with(&self.resources.pin(), |id| {
             let image_info = DescriptorImageInfo {
@@ -650,6 +888,200 @@ impl GlobalDescriptorSet {
             })
     }
 
+    /// Adds a uniform buffer descriptor for the range of `buffer` starting at `offset` and
+    /// spanning `size` bytes, or the rest of the buffer if `size` is `None`.
+    pub fn add_uniform_buffer(
+        &self,
+        buffer: Arc<Buffer>,
+        offset: DeviceSize,
+        size: Option<DeviceSize>,
+    ) -> Result<UniformBufferId, Box<ValidationError>> {
+        self.validate_add_uniform_buffer(&buffer, offset, size)?;
+
+        let size = size.unwrap_or(buffer.size() - offset);
+
+        let id = self
+            .uniform_buffers
+            .insert_with(&self.resources.pin(), |id| {
+                let buffer_info = DescriptorBufferInfo {
+                    buffer: Some(&buffer),
+                    offset,
+                    range: Some(size),
+                };
+                let write = WriteDescriptorSet::buffer_array(
+                    UNIFORM_BUFFER_BINDING,
+                    id.index,
+                    slice::from_ref(&buffer_info),
+                );
+                unsafe { self.inner.update_unchecked(slice::from_ref(&write), &[]) };
+
+                Some(UniformBufferDescriptor {
+                    buffer,
+                    offset,
+                    size,
+                })
+            });
+
+        Ok(id)
+    }
+
+    fn validate_add_uniform_buffer(
+        &self,
+        buffer: &Buffer,
+        offset: DeviceSize,
+        size: Option<DeviceSize>,
+    ) -> Result<(), Box<ValidationError>> {
+        if self
+            .inner
+            .layout()
+            .binding(UNIFORM_BUFFER_BINDING)
+            .is_none()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the global descriptor set has no uniform buffer binding, because \
+                    `GlobalDescriptorSetCreateInfo::max_uniform_buffers` was zero or the \
+                    `descriptor_binding_uniform_buffer_update_after_bind` feature is not enabled"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if !buffer.usage().intersects(BufferUsage::UNIFORM_BUFFER) {
+            return Err(Box::new(ValidationError {
+                context: "buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::UNIFORM_BUFFER`".into(),
+                vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00330"],
+                ..Default::default()
+            }));
+        }
+
+        if offset >= buffer.size() {
+            return Err(Box::new(ValidationError {
+                problem: "`offset` is not less than the size of the buffer".into(),
+                vuids: &["VUID-VkDescriptorBufferInfo-offset-00340"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if !offset.is_multiple_of(
+            properties
+                .min_uniform_buffer_offset_alignment
+                .as_devicesize(),
+        ) {
+            return Err(Box::new(ValidationError {
+                problem: "`offset` is not a multiple of the `min_uniform_buffer_offset_alignment` \
+                    device property"
+                    .into(),
+                vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00327"],
+                ..Default::default()
+            }));
+        }
+
+        let size = match size {
+            Some(0) => {
+                return Err(Box::new(ValidationError {
+                    context: "size".into(),
+                    problem: "is zero".into(),
+                    vuids: &["VUID-VkDescriptorBufferInfo-range-00341"],
+                    ..Default::default()
+                }));
+            }
+            Some(size) => {
+                if size > buffer.size() - offset {
+                    return Err(Box::new(ValidationError {
+                        problem: "`offset + size` is greater than the size of the buffer".into(),
+                        vuids: &["VUID-VkDescriptorBufferInfo-range-00342"],
+                        ..Default::default()
+                    }));
+                }
+
+                size
+            }
+            None => buffer.size() - offset,
+        };
+
+        if size > DeviceSize::from(properties.max_uniform_buffer_range) {
+            return Err(Box::new(ValidationError {
+                problem: "the size of the descriptor is greater than the \
+                    `max_uniform_buffer_range` device limit"
+                    .into(),
+                vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00332"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    pub fn add_uniform_texel_buffer(&self, buffer_view: Arc<BufferView>) -> UniformTexelBufferId {
+        self.uniform_texel_buffers
+            .insert_with(&self.resources.pin(), |id| {
+                let buffer_view_ref = Some(&buffer_view);
+                let write = WriteDescriptorSet::buffer_view_array(
+                    UNIFORM_TEXEL_BUFFER_BINDING,
+                    id.index,
+                    slice::from_ref(&buffer_view_ref),
+                );
+                unsafe { self.inner.update_unchecked(slice::from_ref(&write), &[]) };
+
+                Some(UniformTexelBufferDescriptor { buffer_view })
+            })
+    }
+
+    pub fn add_storage_texel_buffer(&self, buffer_view: Arc<BufferView>) -> StorageTexelBufferId {
+        self.storage_texel_buffers
+            .insert_with(&self.resources.pin(), |id| {
+                let buffer_view_ref = Some(&buffer_view);
+                let write = WriteDescriptorSet::buffer_view_array(
+                    STORAGE_TEXEL_BUFFER_BINDING,
+                    id.index,
+                    slice::from_ref(&buffer_view_ref),
+                );
+                unsafe { self.inner.update_unchecked(slice::from_ref(&write), &[]) };
+
+                Some(StorageTexelBufferDescriptor { buffer_view })
+            })
+    }
+
+    pub fn add_combined_image_sampler(
+        &self,
+        image_view: Arc<ImageView>,
+        image_layout: ImageLayout,
+        sampler: Arc<Sampler>,
+    ) -> CombinedImageSamplerId {
+        assert!(matches!(
+            image_layout,
+            ImageLayout::General
+                | ImageLayout::DepthStencilReadOnlyOptimal
+                | ImageLayout::ShaderReadOnlyOptimal
+                | ImageLayout::DepthReadOnlyStencilAttachmentOptimal
+                | ImageLayout::DepthAttachmentStencilReadOnlyOptimal,
+        ));
+
+        self.combined_image_samplers
+            .insert_with(&self.resources.pin(), |id| {
+                let image_info = DescriptorImageInfo {
+                    sampler: Some(&sampler),
+                    image_view: Some(&image_view),
+                    image_layout,
+                };
+                let write = WriteDescriptorSet::image_array(
+                    COMBINED_IMAGE_SAMPLER_BINDING,
+                    id.index,
+                    slice::from_ref(&image_info),
+                );
+                unsafe { self.inner.update_unchecked(slice::from_ref(&write), &[]) };
+
+                CombinedImageSamplerDescriptor {
+                    image_view,
+                    image_layout,
+                    sampler,
+                }
+            })
+    }
+
     pub(crate) fn invalidate_sampler<'a>(
         &'a self,
         id: SamplerId,
@@ -710,6 +1142,53 @@ impl GlobalDescriptorSet {
             .map(|x| x.as_ref().unwrap())
     }
 
+    pub(crate) fn invalidate_uniform_buffer<'a>(
+        &'a self,
+        id: UniformBufferId,
+        guard: &'a hyaline::Guard<'a>,
+    ) -> Option<&'a UniformBufferDescriptor> {
+        // Prevent invalidation of NULL
+        self.uniform_buffers.get(id, guard)?.as_ref()?;
+
+        self.uniform_buffers
+            .invalidate(id, guard)
+            .map(|x| x.as_ref().unwrap())
+    }
+
+    pub(crate) fn invalidate_uniform_texel_buffer<'a>(
+        &'a self,
+        id: UniformTexelBufferId,
+        guard: &'a hyaline::Guard<'a>,
+    ) -> Option<&'a UniformTexelBufferDescriptor> {
+        // Prevent invalidation of NULL
+        self.uniform_texel_buffers.get(id, guard)?.as_ref()?;
+
+        self.uniform_texel_buffers
+            .invalidate(id, guard)
+            .map(|x| x.as_ref().unwrap())
+    }
+
+    pub(crate) fn invalidate_storage_texel_buffer<'a>(
+        &'a self,
+        id: StorageTexelBufferId,
+        guard: &'a hyaline::Guard<'a>,
+    ) -> Option<&'a StorageTexelBufferDescriptor> {
+        // Prevent invalidation of NULL
+        self.storage_texel_buffers.get(id, guard)?.as_ref()?;
+
+        self.storage_texel_buffers
+            .invalidate(id, guard)
+            .map(|x| x.as_ref().unwrap())
+    }
+
+    pub(crate) fn invalidate_combined_image_sampler<'a>(
+        &'a self,
+        id: CombinedImageSamplerId,
+        guard: &'a hyaline::Guard<'a>,
+    ) -> Option<&'a CombinedImageSamplerDescriptor> {
+        self.combined_image_samplers.invalidate(id, guard)
+    }
+
     pub(crate) unsafe fn remove_invalidated_sampler_unchecked(&self, id: SamplerId) {
         // SAFETY: Enforced by the caller.
         unsafe { self.samplers.remove_invalidated_unchecked(id) };
@@ -741,6 +1220,38 @@ impl GlobalDescriptorSet {
         };
     }
 
+    pub(crate) unsafe fn remove_invalidated_uniform_buffer_unchecked(&self, id: UniformBufferId) {
+        // SAFETY: Enforced by the caller.
+        unsafe { self.uniform_buffers.remove_invalidated_unchecked(id) };
+    }
+
+    pub(crate) unsafe fn remove_invalidated_uniform_texel_buffer_unchecked(
+        &self,
+        id: UniformTexelBufferId,
+    ) {
+        // SAFETY: Enforced by the caller.
+        unsafe { self.uniform_texel_buffers.remove_invalidated_unchecked(id) };
+    }
+
+    pub(crate) unsafe fn remove_invalidated_storage_texel_buffer_unchecked(
+        &self,
+        id: StorageTexelBufferId,
+    ) {
+        // SAFETY: Enforced by the caller.
+        unsafe { self.storage_texel_buffers.remove_invalidated_unchecked(id) };
+    }
+
+    pub(crate) unsafe fn remove_invalidated_combined_image_sampler_unchecked(
+        &self,
+        id: CombinedImageSamplerId,
+    ) {
+        // SAFETY: Enforced by the caller.
+        unsafe {
+            self.combined_image_samplers
+                .remove_invalidated_unchecked(id)
+        };
+    }
+
     #[inline]
     pub fn sampler(&self, id: SamplerId) -> Option<Ref<'_, SamplerDescriptor>> {
         let guard = self.resources.pin();
@@ -819,6 +1330,72 @@ impl GlobalDescriptorSet {
 
         Some(Ref::new(descriptor, guard))
     }
+
+    #[inline]
+    pub fn uniform_buffer(&self, id: UniformBufferId) -> Option<Ref<'_, UniformBufferDescriptor>> {
+        let guard = self.resources.pin();
+
+        // SAFETY: Same as in the `sampler` method above.
+        let descriptor = self
+            .uniform_buffers
+            .get(id, unsafe {
+                mem::transmute::<&hyaline::Guard<'_>, &hyaline::Guard<'_>>(&guard)
+            })?
+            .as_ref()?;
+
+        Some(Ref::new(descriptor, guard))
+    }
+
+    #[inline]
+    pub fn uniform_texel_buffer(
+        &self,
+        id: UniformTexelBufferId,
+    ) -> Option<Ref<'_, UniformTexelBufferDescriptor>> {
+        let guard = self.resources.pin();
+
+        // SAFETY: Same as in the `sampler` method above.
+        let descriptor = self
+            .uniform_texel_buffers
+            .get(id, unsafe {
+                mem::transmute::<&hyaline::Guard<'_>, &hyaline::Guard<'_>>(&guard)
+            })?
+            .as_ref()?;
+
+        Some(Ref::new(descriptor, guard))
+    }
+
+    #[inline]
+    pub fn storage_texel_buffer(
+        &self,
+        id: StorageTexelBufferId,
+    ) -> Option<Ref<'_, StorageTexelBufferDescriptor>> {
+        let guard = self.resources.pin();
+
+        // SAFETY: Same as in the `sampler` method above.
+        let descriptor = self
+            .storage_texel_buffers
+            .get(id, unsafe {
+                mem::transmute::<&hyaline::Guard<'_>, &hyaline::Guard<'_>>(&guard)
+            })?
+            .as_ref()?;
+
+        Some(Ref::new(descriptor, guard))
+    }
+
+    #[inline]
+    pub fn combined_image_sampler(
+        &self,
+        id: CombinedImageSamplerId,
+    ) -> Option<Ref<'_, CombinedImageSamplerDescriptor>> {
+        let guard = self.resources.pin();
+
+        // SAFETY: Same as in the `sampler` method above.
+        let descriptor = self.combined_image_samplers.get(id, unsafe {
+            mem::transmute::<&hyaline::Guard<'_>, &hyaline::Guard<'_>>(&guard)
+        })?;
+
+        Some(Ref::new(descriptor, guard))
+    }
 }
 
 unsafe impl VulkanObject for GlobalDescriptorSet {
@@ -892,6 +1469,54 @@ impl AccelerationStructureDescriptor {
     }
 }
 
+impl UniformBufferDescriptor {
+    #[inline]
+    pub fn buffer(&self) -> &Arc<Buffer> {
+        &self.buffer
+    }
+
+    #[inline]
+    pub fn offset(&self) -> DeviceSize {
+        self.offset
+    }
+
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.size
+    }
+}
+
+impl UniformTexelBufferDescriptor {
+    #[inline]
+    pub fn buffer_view(&self) -> &Arc<BufferView> {
+        &self.buffer_view
+    }
+}
+
+impl StorageTexelBufferDescriptor {
+    #[inline]
+    pub fn buffer_view(&self) -> &Arc<BufferView> {
+        &self.buffer_view
+    }
+}
+
+impl CombinedImageSamplerDescriptor {
+    #[inline]
+    pub fn image_view(&self) -> &Arc<ImageView> {
+        &self.image_view
+    }
+
+    #[inline]
+    pub fn image_layout(&self) -> ImageLayout {
+        self.image_layout
+    }
+
+    #[inline]
+    pub fn sampler(&self) -> &Arc<Sampler> {
+        &self.sampler
+    }
+}
+
 /// Parameters to create a new [`GlobalDescriptorSet`].
 #[derive(Clone, Debug)]
 pub struct GlobalDescriptorSetCreateInfo<'a> {
@@ -921,6 +1546,73 @@ pub struct GlobalDescriptorSetCreateInfo<'a> {
     /// The default value is `1_000_000`.
     pub max_acceleration_structures: u32,
 
+    /// The maximum number of uniform [`Buffer`] descriptors that the collection can hold at once.
+    ///
+    /// If this is zero, or if the [`descriptor_binding_uniform_buffer_update_after_bind`] feature
+    /// is not enabled on the device, the binding doesn't exist. Otherwise, this must not exceed
+    /// the [`max_per_stage_descriptor_update_after_bind_uniform_buffers`] and
+    /// [`max_descriptor_set_update_after_bind_uniform_buffers`] device limits. Shaders that use
+    /// the binding through `vulkano.glsl` or `vulkano.hlsl` must define
+    /// `VKO_UNIFORM_BUFFER_ENABLED` before including the header.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`descriptor_binding_uniform_buffer_update_after_bind`]: DeviceFeatures::descriptor_binding_uniform_buffer_update_after_bind
+    /// [`max_per_stage_descriptor_update_after_bind_uniform_buffers`]: vulkano::device::DeviceProperties::max_per_stage_descriptor_update_after_bind_uniform_buffers
+    /// [`max_descriptor_set_update_after_bind_uniform_buffers`]: vulkano::device::DeviceProperties::max_descriptor_set_update_after_bind_uniform_buffers
+    pub max_uniform_buffers: u32,
+
+    /// The maximum number of uniform texel buffer descriptors that the collection can hold at
+    /// once.
+    ///
+    /// If this is zero, or if the [`descriptor_binding_uniform_texel_buffer_update_after_bind`]
+    /// feature is not enabled on the device, the binding doesn't exist. Otherwise, this must not
+    /// exceed the [`max_per_stage_descriptor_update_after_bind_sampled_images`] and
+    /// [`max_descriptor_set_update_after_bind_sampled_images`] device limits, which it shares with
+    /// the sampled images and combined image samplers. Shaders that use the binding through
+    /// `vulkano.glsl` or `vulkano.hlsl` must define `VKO_UNIFORM_TEXEL_BUFFER_ENABLED` before
+    /// including the header.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`descriptor_binding_uniform_texel_buffer_update_after_bind`]: DeviceFeatures::descriptor_binding_uniform_texel_buffer_update_after_bind
+    /// [`max_per_stage_descriptor_update_after_bind_sampled_images`]: vulkano::device::DeviceProperties::max_per_stage_descriptor_update_after_bind_sampled_images
+    /// [`max_descriptor_set_update_after_bind_sampled_images`]: vulkano::device::DeviceProperties::max_descriptor_set_update_after_bind_sampled_images
+    pub max_uniform_texel_buffers: u32,
+
+    /// The maximum number of storage texel buffer descriptors that the collection can hold at
+    /// once.
+    ///
+    /// If this is zero, or if the [`descriptor_binding_storage_texel_buffer_update_after_bind`]
+    /// feature is not enabled on the device, the binding doesn't exist. Otherwise, this must not
+    /// exceed the [`max_per_stage_descriptor_update_after_bind_storage_images`] and
+    /// [`max_descriptor_set_update_after_bind_storage_images`] device limits, which it shares with
+    /// the storage images. Shaders that use the binding through `vulkano.glsl` or `vulkano.hlsl`
+    /// must define `VKO_STORAGE_TEXEL_BUFFER_ENABLED` before including the header.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`descriptor_binding_storage_texel_buffer_update_after_bind`]: DeviceFeatures::descriptor_binding_storage_texel_buffer_update_after_bind
+    /// [`max_per_stage_descriptor_update_after_bind_storage_images`]: vulkano::device::DeviceProperties::max_per_stage_descriptor_update_after_bind_storage_images
+    /// [`max_descriptor_set_update_after_bind_storage_images`]: vulkano::device::DeviceProperties::max_descriptor_set_update_after_bind_storage_images
+    pub max_storage_texel_buffers: u32,
+
+    /// The maximum number of combined image sampler descriptors that the collection can hold at
+    /// once.
+    ///
+    /// If this is zero, the binding doesn't exist. Otherwise, this must not exceed the
+    /// [`max_per_stage_descriptor_update_after_bind_samplers`] and
+    /// [`max_per_stage_descriptor_update_after_bind_sampled_images`] device limits, which it
+    /// shares with the samplers and sampled images, nor the corresponding per-set limits. Shaders
+    /// that use the binding through `vulkano.glsl` must define
+    /// `VKO_COMBINED_IMAGE_SAMPLER_ENABLED` before including the header.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`max_per_stage_descriptor_update_after_bind_samplers`]: vulkano::device::DeviceProperties::max_per_stage_descriptor_update_after_bind_samplers
+    /// [`max_per_stage_descriptor_update_after_bind_sampled_images`]: vulkano::device::DeviceProperties::max_per_stage_descriptor_update_after_bind_sampled_images
+    pub max_combined_image_samplers: u32,
+
     pub _ne: crate::NonExhaustive<'a>,
 }
 
@@ -941,6 +1633,10 @@ impl GlobalDescriptorSetCreateInfo<'_> {
             max_storage_images: 1_000_000,
             max_storage_buffers: 1_000_000,
             max_acceleration_structures: 1_000_000,
+            max_uniform_buffers: 0,
+            max_uniform_texel_buffers: 0,
+            max_storage_texel_buffers: 0,
+            max_combined_image_samplers: 0,
             _ne: crate::NE,
         }
     }
@@ -1045,6 +1741,22 @@ declare_key_with_null! {
     pub struct AccelerationStructureId;
 }
 
+declare_key_with_null! {
+    pub struct UniformBufferId;
+}
+
+declare_key_with_null! {
+    pub struct UniformTexelBufferId;
+}
+
+declare_key_with_null! {
+    pub struct StorageTexelBufferId;
+}
+
+declare_key! {
+    pub struct CombinedImageSamplerId;
+}
+
 struct GlobalDescriptorSetAllocator {
     device: Arc<Device>,
 }
@@ -1234,3 +1946,163 @@ fn get_all_supported_shader_stages(device: &Arc<Device>) -> ShaderStages {
 
     stages
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::ResourcesCreateInfo;
+    use vulkano::{
+        buffer::BufferCreateInfo,
+        device::{DeviceCreateInfo, QueueCreateInfo},
+        memory::allocator::{AllocationCreateInfo, DeviceLayout},
+        VulkanLibrary,
+    };
+
+    macro_rules! bindless_resources {
+        ($global_set:expr) => {{
+            let Ok(library) = (unsafe { VulkanLibrary::new() }) else {
+                return;
+            };
+            let Ok(instance) = Instance::new(&library, &Default::default()) else {
+                return;
+            };
+            let device_extensions = BindlessContext::required_extensions(&instance);
+            let device_features = DeviceFeatures {
+                descriptor_binding_uniform_buffer_update_after_bind: true,
+                ..BindlessContext::required_features(&instance)
+            };
+            let Ok(mut physical_devices) = instance.enumerate_physical_devices() else {
+                return;
+            };
+            let Some(physical_device) = physical_devices.find(|p| {
+                p.supported_extensions().contains(&device_extensions)
+                    && p.supported_features().contains(&device_features)
+            }) else {
+                return;
+            };
+            let Ok((device, _)) = Device::new(
+                &physical_device,
+                &DeviceCreateInfo {
+                    enabled_extensions: &device_extensions,
+                    enabled_features: &device_features,
+                    queue_create_infos: &[QueueCreateInfo::default()],
+                    ..Default::default()
+                },
+            ) else {
+                return;
+            };
+
+            Resources::new(
+                &device,
+                &ResourcesCreateInfo {
+                    bindless_context: Some(&BindlessContextCreateInfo {
+                        global_set: $global_set,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        }};
+    }
+
+    fn create_buffer(resources: &Resources, usage: BufferUsage, size: DeviceSize) -> Arc<Buffer> {
+        let id = resources
+            .create_buffer(
+                &BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                &AllocationCreateInfo::default(),
+                DeviceLayout::new_unsized::<[u8]>(size).unwrap(),
+            )
+            .unwrap();
+
+        resources.buffer(id).buffer().clone()
+    }
+
+    #[test]
+    fn optional_bindings() {
+        let resources = bindless_resources!(&GlobalDescriptorSetCreateInfo::new());
+        let layout = resources.bindless_context().unwrap().global_set_layout();
+
+        assert!(layout.binding(SAMPLED_IMAGE_BINDING).is_some());
+        assert!(layout.binding(UNIFORM_BUFFER_BINDING).is_none());
+        assert!(layout.binding(UNIFORM_TEXEL_BUFFER_BINDING).is_none());
+        assert!(layout.binding(STORAGE_TEXEL_BUFFER_BINDING).is_none());
+        assert!(layout.binding(COMBINED_IMAGE_SAMPLER_BINDING).is_none());
+
+        let global_set = resources.bindless_context().unwrap().global_set();
+        let buffer = create_buffer(&resources, BufferUsage::UNIFORM_BUFFER, 256);
+        assert!(global_set.add_uniform_buffer(buffer, 0, None).is_err());
+    }
+
+    #[test]
+    fn uniform_buffer_add_remove() {
+        let resources = bindless_resources!(&GlobalDescriptorSetCreateInfo {
+            max_uniform_buffers: 16,
+            ..GlobalDescriptorSetCreateInfo::new()
+        });
+        let global_set = resources.bindless_context().unwrap().global_set();
+        let buffer = create_buffer(&resources, BufferUsage::UNIFORM_BUFFER, 256);
+
+        let id1 = global_set
+            .add_uniform_buffer(buffer.clone(), 0, None)
+            .unwrap();
+        let id2 = global_set
+            .add_uniform_buffer(buffer.clone(), 0, Some(64))
+            .unwrap();
+        assert_ne!(id1, id2);
+
+        let descriptor = global_set.uniform_buffer(id1).unwrap();
+        assert_eq!(descriptor.offset(), 0);
+        assert_eq!(descriptor.size(), 256);
+        drop(descriptor);
+        assert_eq!(global_set.uniform_buffer(id2).unwrap().size(), 64);
+
+        let mut batch = resources.create_deferred_batch();
+        batch.destroy_uniform_buffer(id1);
+        batch.enqueue();
+
+        assert!(global_set.uniform_buffer(id1).is_none());
+        assert!(global_set.uniform_buffer(id2).is_some());
+    }
+
+    #[test]
+    fn uniform_buffer_validation() {
+        let resources = bindless_resources!(&GlobalDescriptorSetCreateInfo {
+            max_uniform_buffers: 16,
+            ..GlobalDescriptorSetCreateInfo::new()
+        });
+        let global_set = resources.bindless_context().unwrap().global_set();
+        let properties = resources.device().physical_device().properties();
+        let alignment = properties
+            .min_uniform_buffer_offset_alignment
+            .as_devicesize();
+        let buffer = create_buffer(&resources, BufferUsage::UNIFORM_BUFFER, alignment * 2);
+
+        // The offset must not be past the end of the buffer, which used to underflow.
+        assert!(global_set
+            .add_uniform_buffer(buffer.clone(), alignment * 3, None)
+            .is_err());
+        assert!(global_set
+            .add_uniform_buffer(buffer.clone(), 0, Some(alignment * 2 + 1))
+            .is_err());
+        assert!(global_set
+            .add_uniform_buffer(buffer.clone(), 0, Some(0))
+            .is_err());
+
+        if alignment > 1 {
+            assert!(global_set
+                .add_uniform_buffer(buffer.clone(), 1, None)
+                .is_err());
+        }
+
+        assert!(global_set
+            .add_uniform_buffer(buffer, alignment, None)
+            .is_ok());
+
+        let buffer = create_buffer(&resources, BufferUsage::STORAGE_BUFFER, 256);
+        assert!(global_set.add_uniform_buffer(buffer, 0, None).is_err());
+    }
+}
@@ -1,32 +1,45 @@
-// FIXME: host read barriers
-
 use super::{
-    Attachments, BarrierIndex, ExecutableTaskGraph, Instruction, NodeIndex, NodeInner,
-    RenderPassIndex, ResourceAccess, ResourceAccesses, SemaphoreIndex, Submission, TaskGraph,
+    schedule::ScheduledBarriers, Attachments, BarrierIndex, ExecutableTaskGraph, HistoryImages,
+    HistoryInfo, Instruction, NodeId, NodeIndex, NodeInner, RenderPassIndex, ResourceAccess,
+    ResourceAccesses, Schedule, SemaphoreIndex, Submission, TaskGraph, TaskTimestampQueries,
+    TransientInfo,
+};
+use crate::{
+    linear_map::LinearMap,
+    resource::{self, Flight},
+    Id, QueueFamilyType,
 };
-use crate::{linear_map::LinearMap, resource::Flight, Id, QueueFamilyType};
 use ash::vk;
+use foldhash::HashMap;
 use smallvec::{smallvec, SmallVec};
 use std::{
     cell::{Cell, RefCell},
     cmp,
     error::Error,
     fmt,
-    mem::ManuallyDrop,
+    mem::{self, ManuallyDrop},
     ops::Range,
     sync::Arc,
 };
 use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage, RawBuffer},
     device::{Device, DeviceOwned, Queue, QueueFlags},
     format::Format,
-    image::{sampler::ComponentMapping, Image, ImageLayout, ImageUsage},
+    image::{
+        sampler::ComponentMapping, sys::RawImage, Image, ImageCreateInfo, ImageLayout, ImageUsage,
+    },
+    memory::{
+        DedicatedAllocation, DeviceMemory, MemoryAllocateFlags, MemoryAllocateInfo,
+        MemoryPropertyFlags, MemoryRequirements, ResourceMemory,
+    },
+    query::{QueryPool, QueryPoolCreateInfo, QueryType},
     render_pass::{
         AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
         Framebuffer, RenderPass, RenderPassCreateInfo, SubpassDependency, SubpassDescription,
     },
     swapchain::Swapchain,
-    sync::{semaphore::Semaphore, AccessFlags, DependencyFlags, PipelineStages},
-    Validated, VulkanError,
+    sync::{semaphore::Semaphore, AccessFlags, DependencyFlags, PipelineStages, Sharing},
+    DeviceSize, Validated, VulkanError,
 };
 
 impl<W: ?Sized> TaskGraph<W> {
@@ -34,10 +47,14 @@ impl<W: ?Sized> TaskGraph<W> {
     ///
     /// # Safety
     ///
-    /// - There must be no conflicting device accesses in task nodes with no path between them.
+    /// - There must be no conflicting device accesses in task nodes with no path between them,
+    ///   unless `compile_info.detect_hazards` is `true`.
     /// - There must be no accesses that are incompatible with the queue family type of the task
     ///   node.
     /// - There must be no accesses that are unsupported by the device.
+    /// - If `compile_info.schedule` is `Some`, it must have been obtained from a task graph that
+    ///   was compiled for the same physical device, and must not have been modified since, other
+    ///   than by a (de)serialization roundtrip.
     ///
     /// # Panics
     ///
@@ -46,6 +63,12 @@ impl<W: ?Sized> TaskGraph<W> {
     ///   `compile_info.present_queue` is not the same as that of `self`.
     /// - Panics if `compile_info.queues` contains duplicate queue families.
     /// - Panics if `compile_info.present_queue` is `None` and the task graph uses any swapchains.
+    /// - Panics if the create info of any [transient resource] is not valid.
+    /// - Panics if `compile_info.task_timestamps` is `true` and any queue in
+    ///   `compile_info.queues` is from a queue family that doesn't support timestamps.
+    /// - Panics if `compile_info.task_timestamps` is `true` and `compile_info.flight_id` is
+    ///   invalid.
+    /// - Panics if a task node that was [set to be recorded in parallel] has a framebuffer.
     ///
     /// # Errors
     ///
@@ -54,7 +77,11 @@ impl<W: ?Sized> TaskGraph<W> {
     ///   disregarding the direction of the edges.
     /// - It must have no [directed cycles]: if you were to walk starting from any node following
     ///   the direction of the edges, there must be no way to end up at the node you started at.
+    /// - If `compile_info.detect_hazards` is `true`, there must be no conflicting device accesses
+    ///   in task nodes with no path between them.
     ///
+    /// [transient resource]: Self::add_transient_image
+    /// [set to be recorded in parallel]: super::TaskNodeBuilder::record_in_parallel
     /// [weakly connected]: https://en.wikipedia.org/wiki/Connectivity_(graph_theory)#Connected_vertices_and_graphs
     /// [directed cycles]: https://en.wikipedia.org/wiki/Cycle_(graph_theory)#Directed_circuit_and_directed_cycle
     pub unsafe fn compile(
@@ -65,6 +92,11 @@ impl<W: ?Sized> TaskGraph<W> {
             queues,
             present_queue,
             flight_id,
+            detect_hazards,
+            infer_edges,
+            task_timestamps,
+            async_compute_policy,
+            schedule,
             _ne: _,
         } = compile_info;
 
@@ -88,22 +120,89 @@ impl<W: ?Sized> TaskGraph<W> {
             assert_eq!(present_queue.device(), device);
         }
 
-        if !self.is_weakly_connected() {
-            return Err(CompileError::new(self, CompileErrorKind::Unconnected));
+        if task_timestamps {
+            let queue_family_properties = device.physical_device().queue_family_properties();
+
+            for queue in queues {
+                assert!(
+                    queue_family_properties[queue.queue_family_index() as usize]
+                        .timestamp_valid_bits
+                        .is_some(),
+                    "expected each queue in `compile_info.queues` to support timestamps when \
+                    `compile_info.task_timestamps` is `true`",
+                );
+            }
         }
 
-        let topological_order = match self.topological_sort() {
-            Ok(topological_order) => topological_order,
-            Err(kind) => return Err(CompileError::new(self, kind)),
-        };
-        unsafe { self.dependency_levels(&topological_order) };
-        let queue_family_indices =
-            match unsafe { self.queue_family_indices(device, queues, &topological_order) } {
+        for (_, node) in self.nodes.nodes() {
+            if let NodeInner::Task(task_node) = &node.inner {
+                assert!(
+                    !(task_node.record_in_parallel && task_node.attachments.is_some()),
+                    "the task node `{}` can't be recorded in parallel, because it is part of a \
+                    render pass",
+                    node.name,
+                );
+            }
+        }
+
+        let fingerprint = self.fingerprint(compile_info);
+
+        if infer_edges {
+            unsafe { self.infer_edges() };
+        }
+
+        let scheduled_barriers =
+            schedule.and_then(|schedule| schedule.validate(&self, fingerprint, compile_info));
+        let schedule = schedule.filter(|_| scheduled_barriers.is_some());
+
+        let (topological_order, max_queue_family_index) = if let Some(schedule) = schedule {
+            // The schedule was obtained from a successful compilation of a task graph with the
+            // same structure, so the graph must be weakly connected, acyclic and free of hazards
+            // that would have been detected.
+            for (&node_index, &queue_family_index) in schedule
+                .topological_order()
+                .iter()
+                .zip(schedule.queue_family_indices())
+            {
+                unsafe { self.nodes.task_node_unchecked_mut(node_index) }.queue_family_index =
+                    queue_family_index;
+            }
+
+            let max_queue_family_index = *schedule.queue_family_indices().iter().max().unwrap();
+
+            (
+                schedule.topological_order().to_vec(),
+                max_queue_family_index,
+            )
+        } else {
+            if !self.is_weakly_connected() {
+                return Err(CompileError::new(self, CompileErrorKind::Unconnected));
+            }
+
+            let topological_order = match self.topological_sort() {
+                Ok(topological_order) => topological_order,
+                Err(kind) => return Err(CompileError::new(self, kind)),
+            };
+
+            if detect_hazards {
+                if let Err(kind) = unsafe { self.detect_hazards(&topological_order) } {
+                    return Err(CompileError::new(self, kind));
+                }
+            }
+
+            unsafe { self.dependency_levels(&topological_order) };
+            let queue_family_indices = match unsafe {
+                self.queue_family_indices(device, queues, async_compute_policy, &topological_order)
+            } {
                 Ok(queue_family_indices) => queue_family_indices,
                 Err(kind) => return Err(CompileError::new(self, kind)),
             };
+            let max_queue_family_index = *queue_family_indices.iter().max().unwrap();
+
+            (topological_order, max_queue_family_index)
+        };
         let mut queues_by_queue_family_index: SmallVec<[_; 8]> =
-            smallvec![None; *queue_family_indices.iter().max().unwrap() as usize + 1];
+            smallvec![None; max_queue_family_index as usize + 1];
 
         for &queue in queues {
             if let Some(x) =
@@ -113,22 +212,70 @@ impl<W: ?Sized> TaskGraph<W> {
             }
         }
 
-        let (builder, last_swapchain_accesses) =
-            match unsafe { self.lower(present_queue, &topological_order) } {
-                Ok(x) => x,
+        let mut transient_resources =
+            match unsafe { self.transient_resources(device, &topological_order) } {
+                Ok(transient_resources) => transient_resources,
                 Err(kind) => return Err(CompileError::new(self, kind)),
             };
+        let aliases = mem::take(&mut transient_resources.aliases);
+        let (builder, last_swapchain_accesses) = match unsafe {
+            self.lower(
+                present_queue,
+                &topological_order,
+                aliases,
+                schedule.map(Schedule::subpasses),
+                scheduled_barriers,
+            )
+        } {
+            Ok(x) => x,
+            Err(kind) => return Err(CompileError::new(self, kind)),
+        };
         let IntermediateRepresentationBuilder {
             submissions,
             nodes,
-            prev_accesses: last_accesses,
+            prev_accesses: mut last_accesses,
             prev_node_indices: _,
+            aliases,
             semaphore_count,
             render_passes,
             is_render_pass_instance_active: _,
             pre_present_queue_family_ownership_transfers,
+            is_scheduled: _,
         } = builder;
 
+        transient_resources.discard_last_accesses(&mut last_accesses);
+
+        let schedule = Schedule::new(
+            fingerprint,
+            topological_order.clone(),
+            topological_order
+                .iter()
+                .map(|&node_index| {
+                    unsafe { self.nodes.task_node_unchecked(node_index) }.queue_family_index
+                })
+                .collect(),
+            topological_order
+                .iter()
+                .map(|&node_index| {
+                    nodes[node_index as usize].subpass.map(|subpass| {
+                        (
+                            subpass.render_pass_index as u32,
+                            subpass.subpass_index as u32,
+                        )
+                    })
+                })
+                .collect(),
+            submissions
+                .iter()
+                .map(|submission_state| submission_state.initial_barriers.as_slice()),
+            topological_order
+                .iter()
+                .map(|&node_index| nodes[node_index as usize].start_barriers.as_slice()),
+            topological_order
+                .iter()
+                .map(|&node_index| nodes[node_index as usize].end_barriers.as_slice()),
+        );
+
         let mut builder = FinalRepresentationBuilder::new(present_queue);
         let mut prev_submission_end = 0;
         let mut submission_index = 0;
@@ -155,7 +302,20 @@ impl<W: ?Sized> TaskGraph<W> {
                     builder.wait_semaphore(semaphore_index);
                 }
 
-                builder.pipeline_barrier(&node_state.start_barriers);
+                if task_node.conditional && node_state.subpass.is_none() {
+                    let (skippable_barriers, barriers): (Vec<_>, Vec<_>) = node_state
+                        .start_barriers
+                        .iter()
+                        .cloned()
+                        .partition(|barrier| {
+                            is_skippable_barrier(barrier, &task_node.accesses, &aliases)
+                        });
+                    builder.pipeline_barrier(&barriers);
+                    task_node.skippable_barrier_range =
+                        builder.pipeline_barrier(&skippable_barriers);
+                } else {
+                    builder.pipeline_barrier(&node_state.start_barriers);
+                }
 
                 if let Some(subpass) = node_state.subpass {
                     let render_pass_state = &render_passes[subpass.render_pass_index];
@@ -272,11 +432,62 @@ impl<W: ?Sized> TaskGraph<W> {
             Err(err) => return Err(CompileError::new(self, CompileErrorKind::VulkanError(err))),
         };
 
+        let task_timestamps = if task_timestamps {
+            match self.task_timestamp_queries(device, flight_id, &builder.instructions) {
+                Ok(task_timestamps) => task_timestamps,
+                Err(err) => {
+                    return Err(CompileError::new(self, CompileErrorKind::VulkanError(err)));
+                }
+            }
+        } else {
+            None
+        };
+
+        let history_resource_count =
+            match transient_resources.add_history_images(device, &self.resources.histories) {
+                Ok(history_resource_count) => history_resource_count,
+                Err(err) => {
+                    return Err(CompileError::new(self, CompileErrorKind::VulkanError(err)));
+                }
+            };
+
+        let mut transient_resources = match transient_resources
+            .allocate(&self.resources.physical_resources)
+        {
+            Ok(transient_resources) => transient_resources,
+            Err(err) => return Err(CompileError::new(self, CompileErrorKind::VulkanError(err))),
+        };
+        let mut history_resources = transient_resources
+            .split_off(transient_resources.len() - history_resource_count)
+            .into_iter()
+            .map(|(_, physical_id)| unsafe { physical_id.parametrize() });
+        let history_images = self
+            .resources
+            .histories
+            .iter()
+            .map(|(_, history_info)| HistoryImages {
+                ids: history_info.ids.clone(),
+                physical_ids: history_resources
+                    .by_ref()
+                    .take(history_info.ids.len())
+                    .collect(),
+            })
+            .collect();
+        let transient_resources = transient_resources
+            .into_iter()
+            .map(|(virtual_id, physical_id)| {
+                self.resources.physical_map.insert(physical_id, virtual_id);
+
+                physical_id
+            })
+            .collect();
+
         let swapchains = last_swapchain_accesses.keys().copied().collect();
 
         Ok(ExecutableTaskGraph {
             graph: ManuallyDrop::new(self),
             flight_id,
+            schedule,
             instructions: builder.instructions,
             submissions: builder.submissions,
             barriers: builder.barriers,
@@ -286,11 +497,142 @@ impl<W: ?Sized> TaskGraph<W> {
             swapchains,
             present_queue: present_queue.cloned(),
             last_accesses,
+            transient_resources,
+            history_images,
+            execution_index: Cell::new(0),
+            task_timestamps,
             last_frame: Cell::new(None),
             drop_graph: true,
         })
     }
 
+    /// Creates the query pool for the timestamps around each task node, with two queries per task
+    /// node per frame in flight.
+    fn task_timestamp_queries(
+        &self,
+        device: &Arc<Device>,
+        flight_id: Id<Flight>,
+        instructions: &[Instruction],
+    ) -> Result<Option<TaskTimestampQueries>, VulkanError> {
+        let frame_count = self
+            .resources
+            .physical_resources
+            .flight(flight_id)
+            .frame_count();
+
+        let mut node_ids = vec![NodeId::INVALID; self.nodes.reserved_len() as usize];
+
+        for (node_id, _) in self.nodes.nodes() {
+            node_ids[node_id.index() as usize] = node_id;
+        }
+
+        let mut task_node_ids = Vec::new();
+        let mut pair_indices = Vec::new();
+        let mut pair_count = 0;
+        let mut is_in_render_pass = false;
+
+        for instruction in instructions {
+            match *instruction {
+                Instruction::ExecuteTask { node_index } => {
+                    task_node_ids.push(node_ids[node_index as usize]);
+
+                    if is_in_render_pass {
+                        // All task nodes in a render pass instance share the pair that was
+                        // allocated when the render pass instance began.
+                        pair_indices.push(pair_count - 1);
+                    } else {
+                        pair_indices.push(pair_count);
+                        pair_count += 1;
+                    }
+                }
+                Instruction::BeginRenderPass { .. } => {
+                    is_in_render_pass = true;
+                    pair_count += 1;
+                }
+                Instruction::EndRenderPass => {
+                    is_in_render_pass = false;
+                }
+                _ => {}
+            }
+        }
+
+        if task_node_ids.is_empty() {
+            return Ok(None);
+        }
+
+        let query_pool = QueryPool::new(
+            device,
+            &QueryPoolCreateInfo {
+                query_count: 2 * pair_count * frame_count,
+                ..QueryPoolCreateInfo::new(QueryType::Timestamp)
+            },
+        )?;
+
+        Ok(Some(TaskTimestampQueries {
+            query_pool,
+            node_ids: task_node_ids,
+            pair_indices,
+            pair_count,
+            executed: RefCell::new(vec![Vec::new(); frame_count as usize]),
+        }))
+    }
+
+    /// Adds edges between task nodes based on their resource accesses, treating the order in which
+    /// the nodes were created as the order in which their accesses happen. A node that writes to
+    /// a resource, or that needs an image in a different layout than the previous accesses, is
+    /// ordered after all previous accesses of the resource since the last such node. A node that
+    /// only reads from a resource is ordered after the last such node.
+    unsafe fn infer_edges(&mut self) {
+        #[derive(Default)]
+        struct ResourceState {
+            last_exclusive: Option<NodeIndex>,
+            reads: Vec<NodeIndex>,
+            image_layout: ImageLayout,
+        }
+
+        let mut resource_states: HashMap<Id, ResourceState> = HashMap::default();
+        let mut edges = Vec::new();
+
+        for &node_index in &self.nodes.creation_order {
+            let task_node = unsafe { self.nodes.task_node_unchecked(node_index) };
+
+            for (id, access) in task_node.accesses.iter() {
+                let state = resource_states.entry(id).or_default();
+
+                let is_exclusive = access.access_mask.contains_writes()
+                    || (state.last_exclusive.is_some() || !state.reads.is_empty())
+                        && state.image_layout != access.image_layout;
+
+                // The reads since the last exclusive access are already ordered after it, so
+                // there's no need for an edge from the last exclusive access in that case.
+                if is_exclusive && !state.reads.is_empty() {
+                    edges.extend(state.reads.drain(..).map(|i| (i, node_index)));
+                } else {
+                    edges.extend(state.last_exclusive.map(|i| (i, node_index)));
+                }
+
+                if is_exclusive {
+                    state.last_exclusive = Some(node_index);
+                } else {
+                    state.reads.push(node_index);
+                }
+
+                state.image_layout = access.image_layout;
+            }
+        }
+
+        for (from, to) in edges {
+            let out_edges = &mut unsafe { self.nodes.node_unchecked_mut(from) }.out_edges;
+
+            if !out_edges.contains(&to) {
+                out_edges.push(to);
+                unsafe { self.nodes.node_unchecked_mut(to) }
+                    .in_edges
+                    .push(from);
+            }
+        }
+    }
+
     /// Performs [depth-first search] on the equivalent undirected graph to determine if every node
     /// is visited, meaning the undirected graph is [connected]. If it is, then the directed graph
     /// is [weakly connected]. This property is required because otherwise it could happen that we
@@ -424,12 +766,86 @@ impl<W: ?Sized> TaskGraph<W> {
         levels
     }
 
+    /// Checks that there are no conflicting accesses to the same resource in task nodes with no
+    /// path between them. Two accesses conflict if at least one of them writes to the resource or
+    /// if they require the image to be in different layouts. Nodes are visited in topological
+    /// order, so only paths from earlier to later nodes need to be checked.
+    unsafe fn detect_hazards(
+        &self,
+        topological_order: &[NodeIndex],
+    ) -> Result<(), CompileErrorKind> {
+        const WORD_BITS: usize = u64::BITS as usize;
+
+        let node_count = self.nodes.reserved_len() as usize;
+        let word_count = node_count.div_ceil(WORD_BITS);
+
+        // For every node, the set of nodes that are reachable from it, stored as a bitset.
+        let mut reachable = vec![0u64; node_count * word_count];
+
+        for &node_index in topological_order.iter().rev() {
+            let node = unsafe { self.nodes.node_unchecked(node_index) };
+            let offset = node_index as usize * word_count;
+
+            for &out_node_index in &node.out_edges {
+                let out_offset = out_node_index as usize * word_count;
+
+                for word_index in 0..word_count {
+                    reachable[offset + word_index] |= reachable[out_offset + word_index];
+                }
+
+                reachable[offset + out_node_index as usize / WORD_BITS] |=
+                    1 << (out_node_index as usize % WORD_BITS);
+            }
+        }
+
+        let is_reachable = |from: NodeIndex, to: NodeIndex| {
+            let word = reachable[from as usize * word_count + to as usize / WORD_BITS];
+
+            word & (1 << (to as usize % WORD_BITS)) != 0
+        };
+
+        let mut node_ids = vec![NodeId::INVALID; node_count];
+
+        for (node_id, _) in self.nodes.nodes() {
+            node_ids[node_id.index() as usize] = node_id;
+        }
+
+        let mut prev_accesses: HashMap<Id, Vec<(NodeIndex, &ResourceAccess)>> = HashMap::default();
+
+        for &node_index in topological_order {
+            let task_node = unsafe { self.nodes.task_node_unchecked(node_index) };
+
+            for (id, access) in task_node.accesses.iter() {
+                let prev_accesses = prev_accesses.entry(id).or_default();
+
+                for &(prev_node_index, prev_access) in prev_accesses.iter() {
+                    let is_conflicting = prev_access.access_mask.contains_writes()
+                        || access.access_mask.contains_writes()
+                        || prev_access.image_layout != access.image_layout;
+
+                    if is_conflicting && !is_reachable(prev_node_index, node_index) {
+                        return Err(CompileErrorKind::Hazard {
+                            node1: node_ids[prev_node_index as usize],
+                            node2: node_ids[node_index as usize],
+                            resource: id,
+                        });
+                    }
+                }
+
+                prev_accesses.push((node_index, access));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Assigns a queue family index to each task node. Returns a vector of the used queue family
     /// indices in topological order.
     unsafe fn queue_family_indices(
         &mut self,
         device: &Device,
         queues: &[&Arc<Queue>],
+        async_compute_policy: AsyncComputePolicy,
         topological_order: &[NodeIndex],
     ) -> Result<SmallVec<[u32; 3]>, CompileErrorKind> {
         let queue_family_properties = device.physical_device().queue_family_properties();
@@ -470,6 +886,25 @@ impl<W: ?Sized> TaskGraph<W> {
             .or(compute_queue_family_index)
             .or(graphics_queue_family_index);
 
+        // The queue family that compute task nodes are executed on when they're not executed
+        // asynchronously, if it differs from the one they're executed on asynchronously.
+        let sync_compute_queue_family_index = graphics_queue_family_index
+            .filter(|&index| {
+                queue_family_properties[index as usize]
+                    .queue_flags
+                    .contains(QueueFlags::COMPUTE)
+            })
+            .filter(|&index| Some(index) != compute_queue_family_index);
+        let is_critical = match (async_compute_policy, sync_compute_queue_family_index) {
+            (AsyncComputePolicy::Always, _) | (_, None) => None,
+            (AsyncComputePolicy::Never, Some(_)) => {
+                Some(vec![true; self.nodes.reserved_len() as usize])
+            }
+            (AsyncComputePolicy::OffCriticalPath, Some(_)) => {
+                Some(unsafe { self.critical_path(topological_order) })
+            }
+        };
+
         let mut queue_family_indices = SmallVec::new();
 
         for &node_index in topological_order {
@@ -480,7 +915,12 @@ impl<W: ?Sized> TaskGraph<W> {
 
             let queue_family_index = match task_node.queue_family_type() {
                 QueueFamilyType::Graphics => graphics_queue_family_index,
-                QueueFamilyType::Compute => compute_queue_family_index,
+                QueueFamilyType::Compute => match &is_critical {
+                    Some(is_critical) if is_critical[node_index as usize] => {
+                        sync_compute_queue_family_index
+                    }
+                    _ => compute_queue_family_index,
+                },
                 QueueFamilyType::Transfer => transfer_queue_family_index,
                 QueueFamilyType::Specific { index } => queues
                     .iter()
@@ -499,12 +939,219 @@ impl<W: ?Sized> TaskGraph<W> {
         Ok(queue_family_indices)
     }
 
-    /// Lowers the task graph to the intermediate representation.
+    /// Determines which task nodes lie on a critical path of the graph, which is a longest path in
+    /// terms of the number of task nodes, such that delaying any of them delays the whole graph.
+    /// The dependency levels must have been computed beforehand.
+    unsafe fn critical_path(&self, topological_order: &[NodeIndex]) -> Vec<bool> {
+        // The length of the longest path starting at each node.
+        let mut heights = vec![0; self.nodes.reserved_len() as usize];
+        let mut max_height = 0;
+
+        for &node_index in topological_order.iter().rev() {
+            let node = unsafe { self.nodes.node_unchecked(node_index) };
+            let height = node
+                .out_edges
+                .iter()
+                .map(|&out_node_index| heights[out_node_index as usize] + 1)
+                .max()
+                .unwrap_or(0);
+            heights[node_index as usize] = height;
+            max_height = cmp::max(max_height, height);
+        }
+
+        let mut is_critical = vec![false; self.nodes.reserved_len() as usize];
+
+        for &node_index in topological_order {
+            let task_node = unsafe { self.nodes.task_node_unchecked(node_index) };
+
+            // The dependency level of a node is the length of the longest path ending at it.
+            is_critical[node_index as usize] =
+                task_node.dependency_level_index + heights[node_index as usize] == max_height;
+        }
+
+        is_critical
+    }
+
+    /// Creates the transient resources and performs lifetime analysis on them. The lifetime of a
+    /// resource spans from the first to the last task node in topological order that accesses
+    /// it. Transient resources whose lifetimes don't overlap are assigned to the same alias
+    /// group, such that they can share the same memory.
+    unsafe fn transient_resources(
+        &self,
+        device: &Arc<Device>,
+        topological_order: &[NodeIndex],
+    ) -> Result<TransientResources, CompileErrorKind> {
+        let mut resources = Vec::with_capacity(self.resources.transients.len());
+
+        for (&id, transient_info) in self.resources.transients.iter() {
+            let raw_resource = create_raw_resource(device, transient_info)
+                .map_err(CompileErrorKind::VulkanError)?;
+            let is_host_accessed = id.is::<Buffer>() && {
+                let id = unsafe { id.parametrize() };
+
+                self.resources.host_reads.contains(&id) || self.resources.host_writes.contains(&id)
+            };
+
+            resources.push(TransientResourceState {
+                id,
+                raw_resource,
+                lifetime: None,
+                queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                is_aliasable: !is_host_accessed,
+            });
+        }
+
+        let mut aliases = vec![Alias::None; self.resources.reserved_len() as usize];
+
+        if resources.is_empty() {
+            return Ok(TransientResources {
+                resources,
+                alias_groups: Vec::new(),
+                aliases,
+            });
+        }
+
+        // Consecutive task nodes with attachments may get merged into the same render pass
+        // instance, in which case any aliasing barriers would be moved before the render pass
+        // instance. To account for that, a resource accessed by any such task node is considered
+        // to be alive for the whole span of task nodes.
+        let mut spans = Vec::with_capacity(topological_order.len());
+
+        while spans.len() < topological_order.len() {
+            let start = spans.len();
+            let mut end = start + 1;
+            let task_node = unsafe { self.nodes.task_node_unchecked(topological_order[start]) };
+
+            if task_node.attachments.is_some() {
+                while let Some(&node_index) = topological_order.get(end) {
+                    let next_task_node = unsafe { self.nodes.task_node_unchecked(node_index) };
+
+                    if next_task_node.attachments.is_none()
+                        || next_task_node.queue_family_index != task_node.queue_family_index
+                    {
+                        break;
+                    }
+
+                    end += 1;
+                }
+            }
+
+            spans.resize(end, start as u32..end as u32);
+        }
+
+        for (&node_index, span) in topological_order.iter().zip(&spans) {
+            let task_node = unsafe { self.nodes.task_node_unchecked(node_index) };
+
+            for (id, _) in task_node.accesses.iter() {
+                let Some(index) = self.resources.transients.index_of(&id) else {
+                    continue;
+                };
+                let resource = &mut resources[index];
+
+                resource.lifetime = Some(match resource.lifetime.clone() {
+                    Some(lifetime) => {
+                        cmp::min(lifetime.start, span.start)..cmp::max(lifetime.end, span.end)
+                    }
+                    None => span.clone(),
+                });
+
+                if resource.queue_family_index == vk::QUEUE_FAMILY_IGNORED {
+                    resource.queue_family_index = task_node.queue_family_index;
+                } else if resource.queue_family_index != task_node.queue_family_index {
+                    // Resources used on multiple queues can't be aliased, as there is no
+                    // guarantee about the execution order of the queues.
+                    resource.is_aliasable = false;
+                }
+            }
+        }
+
+        let mut resource_indices = (0..resources.len()).collect::<Vec<_>>();
+        resource_indices.sort_by_key(|&index| {
+            resources[index]
+                .lifetime
+                .as_ref()
+                .map_or(u32::MAX, |lifetime| lifetime.start)
+        });
+
+        let mut alias_groups: Vec<AliasGroup> = Vec::new();
+
+        for index in resource_indices {
+            let resource = &resources[index];
+            let memory_requirements = resource.raw_resource.memory_requirements();
+            let size = memory_requirements.layout.size();
+            let memory_type_bits = memory_requirements.memory_type_bits;
+
+            let alias_group = if let (true, false, Some(lifetime)) = (
+                resource.is_aliasable,
+                memory_requirements.requires_dedicated_allocation,
+                &resource.lifetime,
+            ) {
+                // Best-fit search for a group whose lifetime ended before the resource's begins.
+                alias_groups
+                    .iter_mut()
+                    .filter(|alias_group| {
+                        alias_group.is_aliasable
+                            && alias_group.end <= lifetime.start
+                            && alias_group.queue_family_index == resource.queue_family_index
+                            && alias_group.memory_type_bits & memory_type_bits != 0
+                            && resources[alias_group.members[0]].id.object_type()
+                                == resource.id.object_type()
+                    })
+                    .min_by_key(|alias_group| alias_group.size.abs_diff(size))
+            } else {
+                None
+            };
+
+            if let Some(alias_group) = alias_group {
+                let &predecessor_index = alias_group.members.last().unwrap();
+
+                if alias_group.members.len() == 1 {
+                    aliases[resources[predecessor_index].id.index() as usize] = Alias::First;
+                }
+
+                aliases[resource.id.index() as usize] = Alias::Successor {
+                    predecessor_id: resources[predecessor_index].id,
+                };
+
+                alias_group.members.push(index);
+                alias_group.size = cmp::max(alias_group.size, size);
+                alias_group.memory_type_bits &= memory_type_bits;
+                alias_group.end = resource.lifetime.as_ref().unwrap().end;
+            } else {
+                alias_groups.push(AliasGroup {
+                    members: smallvec![index],
+                    size,
+                    memory_type_bits,
+                    queue_family_index: resource.queue_family_index,
+                    end: resource
+                        .lifetime
+                        .as_ref()
+                        .map_or(u32::MAX, |lifetime| lifetime.end),
+                    is_aliasable: resource.is_aliasable
+                        && resource.lifetime.is_some()
+                        && !memory_requirements.requires_dedicated_allocation,
+                });
+            }
+        }
+
+        Ok(TransientResources {
+            resources,
+            alias_groups,
+            aliases,
+        })
+    }
+
+    /// Lowers the task graph to the intermediate representation. If `subpasses` and
+    /// `scheduled_barriers` are given, the render pass merging and barrier placement are taken
+    /// from them rather than being computed.
     // TODO: Cull redundant semaphores.
     unsafe fn lower(
         &mut self,
         present_queue: Option<&Arc<Queue>>,
         topological_order: &[NodeIndex],
+        aliases: Vec<Alias>,
+        subpasses: Option<&[Option<(u32, u32)>]>,
+        scheduled_barriers: Option<ScheduledBarriers>,
     ) -> Result<
         (
             IntermediateRepresentationBuilder,
@@ -515,11 +1162,13 @@ impl<W: ?Sized> TaskGraph<W> {
         let mut builder = IntermediateRepresentationBuilder::new(
             self.nodes.reserved_len(),
             self.resources.reserved_len(),
+            aliases,
         );
+        builder.is_scheduled = scheduled_barriers.is_some();
         let mut prev_queue_family_index = vk::QUEUE_FAMILY_IGNORED;
         let mut last_swapchain_accesses = LinearMap::new();
 
-        for &node_index in topological_order {
+        for (position, &node_index) in topological_order.iter().enumerate() {
             let node = unsafe { self.nodes.node_unchecked(node_index) };
             let NodeInner::Task(task_node) = &node.inner else {
                 unreachable!();
@@ -553,7 +1202,18 @@ impl<W: ?Sized> TaskGraph<W> {
             builder.nodes[node_index as usize].submission_index = submission_index;
 
             if let Some(attachments) = &task_node.attachments {
-                builder.subpass(node_index, &task_node.accesses, attachments);
+                let scheduled_subpass = subpasses.and_then(|subpasses| subpasses[position]).map(
+                    |(render_pass_index, subpass_index)| Subpass {
+                        render_pass_index: render_pass_index as RenderPassIndex,
+                        subpass_index: subpass_index as usize,
+                    },
+                );
+                builder.subpass(
+                    node_index,
+                    &task_node.accesses,
+                    attachments,
+                    scheduled_subpass,
+                );
                 builder.is_render_pass_instance_active = true;
             } else {
                 builder.is_render_pass_instance_active = false;
@@ -585,44 +1245,382 @@ impl<W: ?Sized> TaskGraph<W> {
             }
         }
 
+        for &buffer_id in &self.resources.host_reads {
+            builder.host_read_barrier(buffer_id.erase());
+        }
+
+        if let Some(scheduled_barriers) = scheduled_barriers {
+            for (submission_state, initial_barriers) in builder
+                .submissions
+                .iter_mut()
+                .zip(scheduled_barriers.initial_barriers)
+            {
+                submission_state.initial_barriers = initial_barriers;
+            }
+
+            for ((&node_index, start_barriers), end_barriers) in topological_order
+                .iter()
+                .zip(scheduled_barriers.start_barriers)
+                .zip(scheduled_barriers.end_barriers)
+            {
+                let node_state = &mut builder.nodes[node_index as usize];
+                node_state.start_barriers = start_barriers;
+                node_state.end_barriers = end_barriers;
+            }
+        }
+
         Ok((builder, last_swapchain_accesses))
     }
 }
 
-struct IntermediateRepresentationBuilder {
-    submissions: Vec<SubmissionState>,
-    nodes: Vec<NodeState>,
-    prev_accesses: Vec<ResourceAccess>,
-    prev_node_indices: Vec<NodeIndex>,
-    semaphore_count: usize,
-    render_passes: Vec<RenderPassState>,
-    is_render_pass_instance_active: bool,
-    pre_present_queue_family_ownership_transfers: Vec<Id<Swapchain>>,
+struct TransientResources {
+    resources: Vec<TransientResourceState>,
+    alias_groups: Vec<AliasGroup>,
+    aliases: Vec<Alias>,
 }
 
-struct SubmissionState {
-    initial_barriers: Vec<super::MemoryBarrier>,
-    first_node_index: NodeIndex,
-    last_node_index: NodeIndex,
+struct TransientResourceState {
+    id: Id,
+    raw_resource: RawResource,
+    lifetime: Option<Range<u32>>,
+    queue_family_index: u32,
+    is_aliasable: bool,
 }
 
-#[derive(Clone, Default)]
-struct NodeState {
-    submission_index: usize,
-    wait_acquire: Vec<(Id<Swapchain>, PipelineStages)>,
-    wait_semaphores: Vec<SemaphoreIndex>,
-    start_barriers: Vec<super::MemoryBarrier>,
-    subpass: Option<Subpass>,
-    clear_attachments: Vec<Id>,
-    end_barriers: Vec<super::MemoryBarrier>,
-    signal_semaphores: Vec<SemaphoreIndex>,
-    signal_pre_present: Vec<(Id<Swapchain>, PipelineStages)>,
-    signal_present: Vec<(Id<Swapchain>, PipelineStages)>,
+enum RawResource {
+    Buffer(RawBuffer),
+    Image(Box<RawImage>),
 }
 
-#[derive(Clone, Copy)]
-struct Subpass {
-    render_pass_index: RenderPassIndex,
+struct AliasGroup {
+    members: SmallVec<[usize; 4]>,
+    size: DeviceSize,
+    memory_type_bits: u32,
+    queue_family_index: u32,
+    end: u32,
+    is_aliasable: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Alias {
+    /// The resource doesn't share memory with any other resource.
+    #[default]
+    None,
+    /// The resource is the first in its alias group to be accessed.
+    First,
+    /// The resource shares memory with `predecessor_id`, whose lifetime ends before that of the
+    /// resource begins.
+    Successor { predecessor_id: Id },
+}
+
+impl TransientResources {
+    /// Makes the next execution's initial barriers of every alias group member wait for all the
+    /// last accesses of the group, discarding the previous contents.
+    fn discard_last_accesses(&self, last_accesses: &mut [ResourceAccess]) {
+        for alias_group in &self.alias_groups {
+            let mut stage_mask = PipelineStages::empty();
+            let mut access_mask = AccessFlags::empty();
+
+            for &index in &alias_group.members {
+                let last_access = last_accesses[self.resources[index].id.index() as usize];
+                stage_mask |= last_access.stage_mask;
+                access_mask |= last_access.access_mask;
+            }
+
+            if stage_mask.is_empty() {
+                continue;
+            }
+
+            for &index in &alias_group.members {
+                let last_access = &mut last_accesses[self.resources[index].id.index() as usize];
+                *last_access = ResourceAccess {
+                    stage_mask,
+                    access_mask,
+                    image_layout: ImageLayout::Undefined,
+                    queue_family_index: alias_group.queue_family_index,
+                };
+            }
+        }
+    }
+
+    /// Creates the resources backing the history images, each in an alias group of its own, as
+    /// their contents must persist across executions. Returns the number of resources created.
+    ///
+    /// This must be called after the last accesses have been discarded, as the contents of the
+    /// history images must not be discarded.
+    fn add_history_images(
+        &mut self,
+        device: &Arc<Device>,
+        histories: &LinearMap<Id, HistoryInfo>,
+    ) -> Result<usize, VulkanError> {
+        let start = self.resources.len();
+
+        for history_info in histories.values() {
+            for &id in &history_info.ids {
+                let raw_resource = create_raw_resource(device, &history_info.transient_info)?;
+                let memory_requirements = raw_resource.memory_requirements();
+
+                self.alias_groups.push(AliasGroup {
+                    members: smallvec![self.resources.len()],
+                    size: memory_requirements.layout.size(),
+                    memory_type_bits: memory_requirements.memory_type_bits,
+                    queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    end: u32::MAX,
+                    is_aliasable: false,
+                });
+                self.resources.push(TransientResourceState {
+                    id: id.erase(),
+                    raw_resource,
+                    lifetime: None,
+                    queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    is_aliasable: false,
+                });
+            }
+        }
+
+        Ok(self.resources.len() - start)
+    }
+
+    /// Allocates the memory of every alias group, binds it to the group members, and adds the
+    /// resulting resources to the physical resources. Returns the mappings from virtual to
+    /// physical resources.
+    fn allocate(
+        self,
+        physical_resources: &resource::Resources,
+    ) -> Result<Vec<(Id, Id)>, VulkanError> {
+        let device = physical_resources.device();
+        let memory_types = &device.physical_device().memory_properties().memory_types;
+        let mut resources: Vec<_> = self.resources.into_iter().map(Some).collect();
+        let mut bound_resources = Vec::with_capacity(resources.len());
+
+        for alias_group in &self.alias_groups {
+            let memory_type_index = memory_types
+                .iter()
+                .enumerate()
+                .filter(|&(index, memory_type)| {
+                    alias_group.memory_type_bits & (1 << index) != 0
+                        && !memory_type.property_flags.intersects(
+                            MemoryPropertyFlags::LAZILY_ALLOCATED | MemoryPropertyFlags::PROTECTED,
+                        )
+                })
+                .min_by_key(|(_, memory_type)| {
+                    !memory_type
+                        .property_flags
+                        .intersects(MemoryPropertyFlags::DEVICE_LOCAL)
+                })
+                .map(|(index, _)| index as u32)
+                .ok_or(VulkanError::OutOfDeviceMemory)?;
+
+            let mut flags = MemoryAllocateFlags::empty();
+
+            for &index in &alias_group.members {
+                if let RawResource::Buffer(raw_buffer) =
+                    &resources[index].as_ref().unwrap().raw_resource
+                {
+                    if raw_buffer
+                        .usage()
+                        .intersects(BufferUsage::SHADER_DEVICE_ADDRESS)
+                    {
+                        flags |= MemoryAllocateFlags::DEVICE_ADDRESS;
+                    }
+                }
+            }
+
+            let dedicated_allocation = if let &[index] = alias_group.members.as_slice() {
+                let raw_resource = &resources[index].as_ref().unwrap().raw_resource;
+
+                if raw_resource
+                    .memory_requirements()
+                    .prefers_dedicated_allocation
+                {
+                    Some(match raw_resource {
+                        RawResource::Buffer(raw_buffer) => DedicatedAllocation::Buffer(raw_buffer),
+                        RawResource::Image(raw_image) => DedicatedAllocation::Image(raw_image),
+                    })
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            let device_memory = Arc::new(DeviceMemory::allocate(
+                device,
+                &MemoryAllocateInfo {
+                    allocation_size: alias_group.size,
+                    memory_type_index,
+                    dedicated_allocation,
+                    flags,
+                    ..Default::default()
+                },
+            )?);
+
+            for &index in &alias_group.members {
+                let TransientResourceState {
+                    id, raw_resource, ..
+                } = resources[index].take().unwrap();
+                let size = raw_resource.memory_requirements().layout.size();
+
+                // SAFETY: The members of the alias group have disjoint lifetimes, and the task
+                // graph inserts aliasing barriers between them.
+                let memory = unsafe {
+                    ResourceMemory::from_device_memory_unchecked(&device_memory, 0, size)
+                };
+
+                let resource = match raw_resource {
+                    RawResource::Buffer(raw_buffer) => {
+                        let buffer = raw_buffer.bind_memory(memory).map_err(|(err, _, _)| err)?;
+
+                        TransientResource::Buffer(Arc::new(buffer))
+                    }
+                    RawResource::Image(raw_image) => {
+                        let image = raw_image.bind_memory([memory]).map_err(|(err, _, _)| err)?;
+
+                        TransientResource::Image(Arc::new(image))
+                    }
+                };
+
+                bound_resources.push((id, resource));
+            }
+        }
+
+        Ok(bound_resources
+            .into_iter()
+            .map(|(virtual_id, resource)| {
+                let physical_id = match resource {
+                    TransientResource::Buffer(buffer) => {
+                        physical_resources.add_buffer(buffer).erase()
+                    }
+                    TransientResource::Image(image) => physical_resources.add_image(image).erase(),
+                };
+
+                (virtual_id, physical_id)
+            })
+            .collect())
+    }
+}
+
+enum TransientResource {
+    Buffer(Arc<Buffer>),
+    Image(Arc<Image>),
+}
+
+impl RawResource {
+    fn memory_requirements(&self) -> &MemoryRequirements {
+        match self {
+            RawResource::Buffer(raw_buffer) => raw_buffer.memory_requirements(),
+            RawResource::Image(raw_image) => &raw_image.memory_requirements()[0],
+        }
+    }
+}
+
+fn create_raw_resource(
+    device: &Arc<Device>,
+    transient_info: &TransientInfo,
+) -> Result<RawResource, VulkanError> {
+    fn sharing(queue_family_indices: &[u32]) -> Sharing<'_> {
+        if queue_family_indices.is_empty() {
+            Sharing::Exclusive
+        } else {
+            Sharing::Concurrent(queue_family_indices)
+        }
+    }
+
+    match transient_info {
+        TransientInfo::Buffer {
+            flags,
+            size,
+            usage,
+            queue_family_indices,
+        } => {
+            let raw_buffer = RawBuffer::new(
+                device,
+                &BufferCreateInfo {
+                    flags: *flags,
+                    sharing: sharing(queue_family_indices),
+                    size: *size,
+                    usage: *usage,
+                    ..Default::default()
+                },
+            )?;
+
+            Ok(RawResource::Buffer(raw_buffer))
+        }
+        TransientInfo::Image {
+            flags,
+            image_type,
+            format,
+            view_formats,
+            extent,
+            array_layers,
+            mip_levels,
+            samples,
+            tiling,
+            usage,
+            stencil_usage,
+            queue_family_indices,
+        } => {
+            let raw_image = RawImage::new(
+                device,
+                &ImageCreateInfo {
+                    flags: *flags,
+                    image_type: *image_type,
+                    format: *format,
+                    view_formats,
+                    extent: *extent,
+                    array_layers: *array_layers,
+                    mip_levels: *mip_levels,
+                    samples: *samples,
+                    tiling: *tiling,
+                    usage: *usage,
+                    stencil_usage: *stencil_usage,
+                    sharing: sharing(queue_family_indices),
+                    ..Default::default()
+                },
+            )?;
+
+            Ok(RawResource::Image(Box::new(raw_image)))
+        }
+    }
+}
+
+struct IntermediateRepresentationBuilder {
+    submissions: Vec<SubmissionState>,
+    nodes: Vec<NodeState>,
+    prev_accesses: Vec<ResourceAccess>,
+    prev_node_indices: Vec<NodeIndex>,
+    aliases: Vec<Alias>,
+    semaphore_count: usize,
+    render_passes: Vec<RenderPassState>,
+    is_render_pass_instance_active: bool,
+    pre_present_queue_family_ownership_transfers: Vec<Id<Swapchain>>,
+    // Whether the barriers are taken from a schedule, in which case they aren't recorded.
+    is_scheduled: bool,
+}
+
+struct SubmissionState {
+    initial_barriers: Vec<super::MemoryBarrier>,
+    first_node_index: NodeIndex,
+    last_node_index: NodeIndex,
+}
+
+#[derive(Clone, Default)]
+struct NodeState {
+    submission_index: usize,
+    wait_acquire: Vec<(Id<Swapchain>, PipelineStages)>,
+    wait_semaphores: Vec<SemaphoreIndex>,
+    start_barriers: Vec<super::MemoryBarrier>,
+    subpass: Option<Subpass>,
+    clear_attachments: Vec<Id>,
+    end_barriers: Vec<super::MemoryBarrier>,
+    signal_semaphores: Vec<SemaphoreIndex>,
+    signal_pre_present: Vec<(Id<Swapchain>, PipelineStages)>,
+    signal_present: Vec<(Id<Swapchain>, PipelineStages)>,
+}
+
+#[derive(Clone, Copy)]
+struct Subpass {
+    render_pass_index: RenderPassIndex,
     subpass_index: usize,
 }
 
@@ -656,16 +1654,20 @@ struct SubpassState {
 }
 
 impl IntermediateRepresentationBuilder {
-    fn new(node_capacity: u32, resource_capacity: u32) -> Self {
+    fn new(node_capacity: u32, resource_capacity: u32, aliases: Vec<Alias>) -> Self {
+        debug_assert_eq!(aliases.len(), resource_capacity as usize);
+
         IntermediateRepresentationBuilder {
             submissions: Vec::new(),
             nodes: vec![NodeState::default(); node_capacity as usize],
             prev_accesses: vec![ResourceAccess::default(); resource_capacity as usize],
             prev_node_indices: vec![0; resource_capacity as usize],
+            aliases,
             semaphore_count: 0,
             render_passes: Vec::new(),
             is_render_pass_instance_active: false,
             pre_present_queue_family_ownership_transfers: Vec::new(),
+            is_scheduled: false,
         }
     }
 
@@ -674,9 +1676,18 @@ impl IntermediateRepresentationBuilder {
         node_index: NodeIndex,
         accesses: &ResourceAccesses,
         attachments: &Attachments,
+        scheduled_subpass: Option<Subpass>,
     ) {
-        let is_same_render_pass = self.is_render_pass_instance_active
-            && is_render_pass_mergeable(self.render_passes.last().unwrap(), accesses, attachments);
+        let is_same_render_pass = if let Some(subpass) = scheduled_subpass {
+            subpass.render_pass_index < self.render_passes.len()
+        } else {
+            self.is_render_pass_instance_active
+                && is_render_pass_mergeable(
+                    self.render_passes.last().unwrap(),
+                    accesses,
+                    attachments,
+                )
+        };
 
         if !is_same_render_pass {
             self.render_passes
@@ -685,12 +1696,16 @@ impl IntermediateRepresentationBuilder {
 
         let render_pass_state = self.render_passes.last_mut().unwrap();
 
-        let is_same_subpass = render_pass_state
-            .subpasses
-            .last()
-            .is_some_and(|subpass_state| {
-                is_subpass_mergeable(subpass_state, accesses, attachments)
-            });
+        let is_same_subpass = if let Some(subpass) = scheduled_subpass {
+            subpass.subpass_index < render_pass_state.subpasses.len()
+        } else {
+            render_pass_state
+                .subpasses
+                .last()
+                .is_some_and(|subpass_state| {
+                    is_subpass_mergeable(subpass_state, accesses, attachments)
+                })
+        };
 
         if !is_same_subpass {
             render_pass_state
@@ -760,11 +1775,15 @@ impl IntermediateRepresentationBuilder {
         let mut barriered = true;
 
         if prev_access.stage_mask.is_empty() {
-            if id.is::<Swapchain>() {
+            let alias = self.aliases[id.index() as usize];
+
+            if let Alias::Successor { predecessor_id } = alias {
+                self.aliasing_barrier(node_index, predecessor_id, id, access);
+            } else if id.is::<Swapchain>() {
                 self.swapchain_acquire(node_index, unsafe { id.parametrize() }, access);
             } else if id.is::<Image>() {
                 self.initial_image_layout_transition(id, access);
-            } else if access.access_mask.contains_reads() {
+            } else if access.access_mask.contains_reads() || alias == Alias::First {
                 self.initial_memory_barrier(id, access);
             }
         } else if prev_access.queue_family_index != access.queue_family_index {
@@ -806,6 +1825,10 @@ impl IntermediateRepresentationBuilder {
             prev_access.stage_mask |= access.stage_mask;
             prev_access.access_mask |= access.access_mask;
 
+            if self.is_scheduled {
+                return;
+            }
+
             let prev_barrier = self.nodes[*prev_node_index as usize]
                 .start_barriers
                 .iter_mut()
@@ -852,11 +1875,38 @@ impl IntermediateRepresentationBuilder {
             .push((swapchain_id, access.stage_mask));
     }
 
+    fn aliasing_barrier(
+        &mut self,
+        node_index: NodeIndex,
+        predecessor_id: Id,
+        id: Id,
+        access: ResourceAccess,
+    ) {
+        // The resource shares its memory with the predecessor, so the first access must wait for
+        // all accesses to the predecessor to finish. The previous contents are discarded.
+        let predecessor_access = self.prev_accesses[predecessor_id.index() as usize];
+        let src = ResourceAccess {
+            image_layout: ImageLayout::Undefined,
+            queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            ..predecessor_access
+        };
+        let dst = ResourceAccess {
+            queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            ..access
+        };
+
+        self.memory_barrier_inner(node_index, id, src, dst, false);
+    }
+
     fn initial_image_layout_transition(&mut self, id: Id, access: ResourceAccess) {
         self.initial_memory_barrier(id, access);
     }
 
     fn initial_memory_barrier(&mut self, id: Id, access: ResourceAccess) {
+        if self.is_scheduled {
+            return;
+        }
+
         let submission_state = self.submissions.last_mut().unwrap();
 
         submission_state
@@ -969,6 +2019,10 @@ impl IntermediateRepresentationBuilder {
         dst: ResourceAccess,
         is_end_barrier: bool,
     ) {
+        if self.is_scheduled {
+            return;
+        }
+
         let mut node_state = &mut self.nodes[node_index as usize];
 
         // Regular pipeline barriers are not permitted during a render pass instance, so we need to
@@ -1005,6 +2059,36 @@ impl IntermediateRepresentationBuilder {
         });
     }
 
+    fn host_read_barrier(&mut self, id: Id) {
+        let prev_access = self.prev_accesses[id.index() as usize];
+
+        if prev_access.stage_mask.is_empty() {
+            return;
+        }
+
+        let prev_node_index = self.prev_node_indices[id.index() as usize];
+        let src = ResourceAccess {
+            // If the last access was a read, then any prior writes have already been made
+            // available by a previous barrier and we only need the memory domain operation, which
+            // is performed as long as the destination access mask includes host reads.
+            access_mask: if prev_access.access_mask.contains_writes() {
+                prev_access.access_mask
+            } else {
+                AccessFlags::empty()
+            },
+            queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            ..prev_access
+        };
+        let dst = ResourceAccess {
+            stage_mask: PipelineStages::HOST,
+            access_mask: AccessFlags::HOST_READ,
+            image_layout: ImageLayout::Undefined,
+            queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+        };
+
+        self.memory_barrier_inner(prev_node_index, id, src, dst, true);
+    }
+
     fn swapchain_present(
         &mut self,
         node_index: NodeIndex,
@@ -1122,6 +2206,29 @@ impl SubpassState {
     }
 }
 
+/// Returns `true` if `barrier`, which is one of the start barriers of a conditional task node,
+/// exists solely for the task node's own access and can therefore be merged with the next barrier
+/// on the same resource when the task node is disabled.
+fn is_skippable_barrier(
+    barrier: &super::MemoryBarrier,
+    accesses: &ResourceAccesses,
+    aliases: &[Alias],
+) -> bool {
+    let Some(access) = accesses.get(barrier.resource) else {
+        return false;
+    };
+
+    // Queue family ownership transfers must be matched by the other queue, swapchain images must
+    // be transitioned for presentation, and the barriers of resources that share memory must
+    // stay ordered with respect to the barriers of the other resources in the alias group.
+    // Lastly, if later reads were merged into the barrier, those rely on it as well.
+    barrier.src_queue_family_index == barrier.dst_queue_family_index
+        && !barrier.resource.is::<Swapchain>()
+        && aliases[barrier.resource.index() as usize] == Alias::None
+        && barrier.dst_stage_mask == access.stage_mask
+        && barrier.dst_access_mask == access.access_mask
+}
+
 fn is_render_pass_mergeable(
     render_pass_state: &RenderPassState,
     accesses: &ResourceAccesses,
@@ -1508,8 +2615,11 @@ impl FinalRepresentationBuilder {
         self.prev_barrier_index = self.barriers.len();
     }
 
-    fn pipeline_barrier(&mut self, barriers: &[super::MemoryBarrier]) {
+    fn pipeline_barrier(&mut self, barriers: &[super::MemoryBarrier]) -> Range<BarrierIndex> {
+        let start = self.barriers.len() as BarrierIndex;
         self.barriers.extend_from_slice(barriers);
+
+        start..self.barriers.len() as BarrierIndex
     }
 
     fn wait_acquire(&mut self, swapchain_id: Id<Swapchain>, stage_mask: PipelineStages) {
@@ -1684,6 +2794,12 @@ impl<W: ?Sized> ExecutableTaskGraph<W> {
     /// Decompiles the graph back into a modifiable form.
     #[inline]
     pub fn decompile(mut self) -> TaskGraph<W> {
+        // The physical resources backing transient resources are destroyed together with the
+        // executable, so the decompiled graph must not keep referring to them.
+        for id in &self.transient_resources {
+            self.graph.resources.physical_map.remove(id);
+        }
+
         self.drop_graph = false;
 
         // SAFETY: We unset the `drop_graph` flag which ensures that the graph isn't dropped by the
@@ -1719,6 +2835,63 @@ pub struct CompileInfo<'a> {
     /// The default value is `Id::INVALID`, which must be overridden.
     pub flight_id: Id<Flight>,
 
+    /// Whether to check the task graph for conflicting accesses in task nodes with no path between
+    /// them. If a conflict is found, compilation fails with [`CompileErrorKind::Hazard`].
+    ///
+    /// This lifts the safety requirement on [`TaskGraph::compile`] that there be no such
+    /// conflicts, at the cost of some compilation time.
+    ///
+    /// The default value is `false`.
+    pub detect_hazards: bool,
+
+    /// Whether to add edges between task nodes based on their resource accesses, in addition to
+    /// the edges added with [`TaskGraph::add_edge`].
+    ///
+    /// The order in which the task nodes were created is taken to be the order in which their
+    /// accesses happen: a node that reads from a resource is ordered after the last node created
+    /// before it that writes to the resource, and a node that writes to a resource is ordered
+    /// after all nodes created before it that access the resource. Explicit edges are then only
+    /// needed for dependencies that aren't declared as resource accesses. The inferred edges
+    /// remain in the task graph.
+    ///
+    /// The default value is `false`.
+    pub infer_edges: bool,
+
+    /// Whether to write timestamps before and after the execution of each task node. The
+    /// timestamps of a frame can be retrieved with [`ExecutableTaskGraph::task_timestamps`] once
+    /// the frame has finished executing.
+    ///
+    /// If this is `true`, all queues in the [`queues`] field must support timestamps and the
+    /// [`flight_id`] field must be valid.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`queues`]: Self::queues
+    /// [`flight_id`]: Self::flight_id
+    pub task_timestamps: bool,
+
+    /// Which task nodes with [`QueueFamilyType::Compute`] to execute on an async compute queue,
+    /// meaning a queue in the [`queues`] field that supports compute operations but not graphics
+    /// operations. If there is no such queue, this has no effect.
+    ///
+    /// The default value is [`AsyncComputePolicy::Always`].
+    ///
+    /// [`queues`]: Self::queues
+    pub async_compute_policy: AsyncComputePolicy,
+
+    /// The [schedule] of a previous compilation of the task graph to reuse.
+    ///
+    /// If the schedule's [fingerprint] matches that of the task graph, the topological sort, the
+    /// hazard detection, the queue family assignment, the render pass merging and the barrier
+    /// placement are skipped and the schedule's results are used instead. Otherwise, the schedule is ignored and the task graph is compiled from
+    /// scratch.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [schedule]: ExecutableTaskGraph::schedule
+    /// [fingerprint]: TaskGraph::fingerprint
+    pub schedule: Option<&'a Schedule>,
+
     pub _ne: crate::NonExhaustive<'a>,
 }
 
@@ -1737,11 +2910,42 @@ impl CompileInfo<'_> {
             queues: &[],
             present_queue: None,
             flight_id: Id::INVALID,
+            detect_hazards: false,
+            infer_edges: false,
+            task_timestamps: false,
+            async_compute_policy: AsyncComputePolicy::Always,
+            schedule: None,
             _ne: crate::NE,
         }
     }
 }
 
+/// Specifies which compute task nodes the task graph compiler executes on an async compute queue.
+///
+/// Executing a task node on an async compute queue allows it to overlap with graphics work, but
+/// requires the compiler to insert semaphores and queue family ownership transfers between it and
+/// the task nodes on the graphics queue that it depends on or that depend on it. Whether that pays
+/// off depends on the workload and the hardware.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AsyncComputePolicy {
+    /// Every compute task node is executed on the async compute queue.
+    #[default]
+    Always,
+
+    /// No task node is executed on the async compute queue. Compute task nodes are executed on
+    /// the graphics queue instead.
+    Never,
+
+    /// Only compute task nodes that are off the critical path of the graph are executed on the
+    /// async compute queue. The critical path is a longest path through the graph in terms of
+    /// the number of task nodes. Task nodes on the critical path can't overlap with any other
+    /// work along it, so they're executed on the graphics queue to avoid the cost of the
+    /// synchronization between queues, while the remaining compute task nodes can overlap with
+    /// the critical path on the async compute queue.
+    OffCriticalPath,
+}
+
 /// Error that can happen when [compiling] a [`TaskGraph`].
 ///
 /// [compiling]: TaskGraph::compile
@@ -1756,6 +2960,11 @@ pub enum CompileErrorKind {
     Unconnected,
     Cycle,
     InsufficientQueues,
+    Hazard {
+        node1: NodeId,
+        node2: NodeId,
+        resource: Id,
+    },
     VulkanError(VulkanError),
 }
 
@@ -1779,6 +2988,10 @@ impl<W: ?Sized> fmt::Display for CompileError<W> {
             CompileErrorKind::InsufficientQueues => {
                 f.write_str("the given queues are not sufficient for the requirements of a task")
             }
+            CompileErrorKind::Hazard { .. } => f.write_str(
+                "two task nodes with no path between them have conflicting accesses to the same \
+                resource",
+            ),
             CompileErrorKind::VulkanError(_) => f.write_str("a runtime error occurred"),
         }
     }
@@ -1798,12 +3011,14 @@ mod tests {
     use super::*;
     use crate::{
         graph::AttachmentInfo,
-        resource::{AccessTypes, ImageLayoutType},
+        resource::{AccessTypes, HostAccessType, ImageLayoutType},
         tests::test_queues,
     };
     use std::marker::PhantomData;
     use vulkano::{
-        buffer::BufferCreateInfo, image::ImageCreateInfo, swapchain::SwapchainCreateInfo,
+        buffer::BufferCreateInfo,
+        image::{ImageCreateInfo, ImageMemory, ImageUsage},
+        swapchain::SwapchainCreateInfo,
         sync::Sharing,
     };
 
@@ -2072,33 +3287,197 @@ mod tests {
     }
 
     #[test]
-    fn initial_pipeline_barrier() {
+    fn hazard1() {
         let (resources, queues) = test_queues!();
 
+        //        ┌───┐
+        //      ┌►│ B │
+        // ┌───┐│ └───┘
+        // │ A ├┤
+        // └───┘│ ┌───┐
+        //      └►│ C │
+        //        └───┘
         let mut graph = TaskGraph::<()>::new(&resources);
         let buffer = graph.add_buffer(&BufferCreateInfo::default());
-        let image = graph.add_image(&ImageCreateInfo::default());
-        let node1 = graph
-            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
-            .buffer_access(buffer, AccessTypes::VERTEX_SHADER_UNIFORM_READ)
-            .image_access(
-                image,
-                AccessTypes::FRAGMENT_SHADER_SAMPLED_READ,
-                ImageLayoutType::General,
-            )
+        let a = graph
+            .create_task_node("A", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COPY_TRANSFER_WRITE)
             .build();
-        let node2 = graph
-            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
-            .buffer_access(buffer, AccessTypes::VERTEX_SHADER_SAMPLED_READ)
-            .image_access(
-                image,
-                AccessTypes::FRAGMENT_SHADER_STORAGE_READ,
-                ImageLayoutType::General,
-            )
+        let b = graph
+            .create_task_node("B", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COMPUTE_SHADER_STORAGE_WRITE)
             .build();
-        graph.add_edge(node1, node2).unwrap();
+        let c = graph
+            .create_task_node("C", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COMPUTE_SHADER_STORAGE_READ)
+            .build();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
 
-        let graph = unsafe {
+        let err = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                detect_hazards: true,
+                ..Default::default()
+            })
+        }
+        .unwrap_err();
+
+        let CompileErrorKind::Hazard {
+            node1,
+            node2,
+            resource,
+        } = err.kind
+        else {
+            panic!("expected a hazard, got {:?}", err.kind);
+        };
+        assert!((node1, node2) == (b, c) || (node1, node2) == (c, b));
+        assert_eq!(resource, buffer.erase());
+
+        //        ┌───┐
+        //      ┌►│ B ├┐
+        // ┌───┐│ └───┘│
+        // │ A ├┤      │
+        // └───┘│ ┌───┐│
+        //      └►│ C │◄┘
+        //        └───┘
+        let mut graph = err.graph;
+        graph.add_edge(b, c).unwrap();
+
+        unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                detect_hazards: true,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+    }
+
+    #[test]
+    fn hazard2() {
+        let (resources, queues) = test_queues!();
+
+        // Reads of the same image in different layouts conflict.
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let image = graph.add_image(&ImageCreateInfo::default());
+        let a = graph
+            .create_task_node("A", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image,
+                AccessTypes::COPY_TRANSFER_WRITE,
+                ImageLayoutType::Optimal,
+            )
+            .build();
+        let b = graph
+            .create_task_node("B", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image,
+                AccessTypes::FRAGMENT_SHADER_SAMPLED_READ,
+                ImageLayoutType::Optimal,
+            )
+            .build();
+        let c = graph
+            .create_task_node("C", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image,
+                AccessTypes::FRAGMENT_SHADER_SAMPLED_READ,
+                ImageLayoutType::General,
+            )
+            .build();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+
+        let err = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                detect_hazards: true,
+                ..Default::default()
+            })
+        }
+        .unwrap_err();
+
+        assert!(matches!(
+            err.kind,
+            CompileErrorKind::Hazard { resource, .. } if resource == image.erase(),
+        ));
+    }
+
+    #[test]
+    fn infer_edges1() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let a = graph
+            .create_task_node("A", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+        let b = graph
+            .create_task_node("B", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::VERTEX_ATTRIBUTE_READ)
+            .build();
+        let c = graph
+            .create_task_node("C", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::INDEX_READ)
+            .build();
+        let d = graph
+            .create_task_node("D", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                detect_hazards: true,
+                infer_edges: true,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        //        ┌───┐
+        //      ┌►│ B ├┐
+        // ┌───┐│ └───┘│ ┌───┐
+        // │ A ├┤      ├►│ D │
+        // └───┘│ ┌───┐│ └───┘
+        //      └►│ C ├┘
+        //        └───┘
+        let out_edges = |id: NodeId| &graph.graph.nodes.node(id).unwrap().out_edges;
+        assert_eq!(out_edges(a), &[b.index(), c.index()]);
+        assert_eq!(out_edges(b), &[d.index()]);
+        assert_eq!(out_edges(c), &[d.index()]);
+        assert!(out_edges(d).is_empty());
+    }
+
+    #[test]
+    fn initial_pipeline_barrier() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let image = graph.add_image(&ImageCreateInfo::default());
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::VERTEX_SHADER_UNIFORM_READ)
+            .image_access(
+                image,
+                AccessTypes::FRAGMENT_SHADER_SAMPLED_READ,
+                ImageLayoutType::General,
+            )
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::VERTEX_SHADER_SAMPLED_READ)
+            .image_access(
+                image,
+                AccessTypes::FRAGMENT_SHADER_STORAGE_READ,
+                ImageLayoutType::General,
+            )
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+
+        let graph = unsafe {
             graph.compile(&CompileInfo {
                 queues: &queues.iter().collect::<Vec<_>>(),
                 ..Default::default()
@@ -2307,6 +3686,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn acceleration_structure1() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let geometry_buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let scratch_buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let blas_buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let tlas_buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Compute, PhantomData)
+            .buffer_access(
+                geometry_buffer,
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_SHADER_READ,
+            )
+            .buffer_access(
+                scratch_buffer,
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ
+                    | AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE,
+            )
+            .buffer_access(
+                blas_buffer,
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE,
+            )
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Compute, PhantomData)
+            .buffer_access(
+                scratch_buffer,
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ
+                    | AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE,
+            )
+            .buffer_access(
+                blas_buffer,
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_READ,
+            )
+            .buffer_access(
+                tlas_buffer,
+                AccessTypes::ACCELERATION_STRUCTURE_BUILD_ACCELERATION_STRUCTURE_WRITE,
+            )
+            .build();
+        let node3 = graph
+            .create_task_node("", QueueFamilyType::Compute, PhantomData)
+            .buffer_access(
+                tlas_buffer,
+                AccessTypes::RAY_TRACING_SHADER_ACCELERATION_STRUCTURE_READ,
+            )
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+        graph.add_edge(node2, node3).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        assert_matches_instructions!(
+            graph,
+            ExecuteTask { node: node1 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: ACCELERATION_STRUCTURE_BUILD,
+                        src_access_mask: ACCELERATION_STRUCTURE_READ | ACCELERATION_STRUCTURE_WRITE,
+                        dst_stage_mask: ACCELERATION_STRUCTURE_BUILD,
+                        dst_access_mask: ACCELERATION_STRUCTURE_READ | ACCELERATION_STRUCTURE_WRITE,
+                        old_layout: Undefined,
+                        new_layout: Undefined,
+                        resource: scratch_buffer,
+                    },
+                    {
+                        src_stage_mask: ACCELERATION_STRUCTURE_BUILD,
+                        src_access_mask: ACCELERATION_STRUCTURE_WRITE,
+                        dst_stage_mask: ACCELERATION_STRUCTURE_BUILD,
+                        dst_access_mask: ACCELERATION_STRUCTURE_READ,
+                        old_layout: Undefined,
+                        new_layout: Undefined,
+                        resource: blas_buffer,
+                    },
+                ],
+            },
+            ExecuteTask { node: node2 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: ACCELERATION_STRUCTURE_BUILD,
+                        src_access_mask: ACCELERATION_STRUCTURE_WRITE,
+                        dst_stage_mask: RAY_TRACING_SHADER,
+                        dst_access_mask: ACCELERATION_STRUCTURE_READ,
+                        old_layout: Undefined,
+                        new_layout: Undefined,
+                        resource: tlas_buffer,
+                    },
+                ],
+            },
+            ExecuteTask { node: node3 },
+            FlushSubmit,
+            Submit,
+        );
+    }
+
     #[test]
     fn semaphore1() {
         let (resources, queues) = test_queues!();
@@ -4044,6 +5527,531 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transient1() {
+        let (resources, queues) = test_queues!();
+
+        let create_info = ImageCreateInfo {
+            format: Format::R8G8B8A8_UNORM,
+            extent: [4, 4, 1],
+            usage: ImageUsage::STORAGE,
+            ..Default::default()
+        };
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let image1 = graph.add_transient_image(&create_info);
+        let image2 = graph.add_transient_image(&create_info);
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image1,
+                AccessTypes::COMPUTE_SHADER_STORAGE_WRITE,
+                ImageLayoutType::General,
+            )
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image1,
+                AccessTypes::COMPUTE_SHADER_STORAGE_READ,
+                ImageLayoutType::General,
+            )
+            .build();
+        let node3 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image2,
+                AccessTypes::COMPUTE_SHADER_STORAGE_WRITE,
+                ImageLayoutType::General,
+            )
+            .build();
+        let node4 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                image2,
+                AccessTypes::COMPUTE_SHADER_STORAGE_READ,
+                ImageLayoutType::General,
+            )
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+        graph.add_edge(node2, node3).unwrap();
+        graph.add_edge(node3, node4).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        assert_eq!(graph.transient_resources.len(), 2);
+
+        // The two images are never alive at the same time, so they must share the same memory.
+        let memory = |&physical_id: &Id| {
+            let image = resources.image(unsafe { physical_id.parametrize() });
+            let ImageMemory::Normal(allocations) = image.image().memory() else {
+                unreachable!();
+            };
+
+            allocations[0].device_memory().clone()
+        };
+        assert!(Arc::ptr_eq(
+            &memory(&graph.transient_resources[0]),
+            &memory(&graph.transient_resources[1]),
+        ));
+
+        assert_matches_instructions!(
+            graph,
+            ExecuteTask { node: node1 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: COMPUTE_SHADER,
+                        src_access_mask: SHADER_STORAGE_WRITE,
+                        dst_stage_mask: COMPUTE_SHADER,
+                        dst_access_mask: SHADER_STORAGE_READ,
+                        old_layout: General,
+                        new_layout: General,
+                        resource: image1,
+                    },
+                ],
+            },
+            ExecuteTask { node: node2 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: COMPUTE_SHADER,
+                        src_access_mask: SHADER_STORAGE_READ,
+                        dst_stage_mask: COMPUTE_SHADER,
+                        dst_access_mask: SHADER_STORAGE_WRITE,
+                        old_layout: Undefined,
+                        new_layout: General,
+                        resource: image2,
+                    },
+                ],
+            },
+            ExecuteTask { node: node3 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: COMPUTE_SHADER,
+                        src_access_mask: SHADER_STORAGE_WRITE,
+                        dst_stage_mask: COMPUTE_SHADER,
+                        dst_access_mask: SHADER_STORAGE_READ,
+                        old_layout: General,
+                        new_layout: General,
+                        resource: image2,
+                    },
+                ],
+            },
+            ExecuteTask { node: node4 },
+            FlushSubmit,
+            Submit,
+        );
+    }
+
+    #[test]
+    fn history1() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let image = graph.add_history_image(
+            &ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::STORAGE,
+                ..Default::default()
+            },
+            1,
+        );
+        let previous_image = graph.previous_image_id(image, 1).unwrap();
+        let node = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                previous_image,
+                AccessTypes::COMPUTE_SHADER_STORAGE_READ,
+                ImageLayoutType::General,
+            )
+            .image_access(
+                image,
+                AccessTypes::COMPUTE_SHADER_STORAGE_WRITE,
+                ImageLayoutType::General,
+            )
+            .build();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        assert!(graph.transient_resources.is_empty());
+        assert_eq!(graph.history_images.len(), 1);
+
+        // The physical images must be rotated such that what's written during one execution is
+        // what's read during the next.
+        let history_images = &graph.history_images[0];
+        let [physical_id1, physical_id2] = history_images.physical_ids[..] else {
+            unreachable!();
+        };
+        assert_ne!(physical_id1, physical_id2);
+        assert_eq!(history_images.physical_id(0, 0), physical_id1);
+        assert_eq!(history_images.physical_id(0, 1), physical_id2);
+        assert_eq!(history_images.physical_id(1, 0), physical_id2);
+        assert_eq!(history_images.physical_id(1, 1), physical_id1);
+
+        assert_matches_instructions!(
+            graph,
+            InitialPipelineBarrier {
+                barriers: [
+                    {
+                        dst_stage_mask: COMPUTE_SHADER,
+                        dst_access_mask: SHADER_STORAGE_READ,
+                        new_layout: General,
+                        resource: previous_image,
+                    },
+                    {
+                        dst_stage_mask: COMPUTE_SHADER,
+                        dst_access_mask: SHADER_STORAGE_WRITE,
+                        new_layout: General,
+                        resource: image,
+                    },
+                ],
+            },
+            ExecuteTask { node: node },
+            FlushSubmit,
+            Submit,
+        );
+    }
+
+    #[test]
+    fn host_read1() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer1 = graph.add_buffer(&BufferCreateInfo::default());
+        let buffer2 = graph.add_buffer(&BufferCreateInfo::default());
+        graph.add_host_buffer_access(buffer1, HostAccessType::Read);
+        graph.add_host_buffer_access(buffer2, HostAccessType::Read);
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer1, AccessTypes::COMPUTE_SHADER_STORAGE_WRITE)
+            .buffer_access(buffer2, AccessTypes::COMPUTE_SHADER_STORAGE_WRITE)
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer2, AccessTypes::COPY_TRANSFER_READ)
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        assert_matches_instructions!(
+            graph,
+            ExecuteTask { node: node1 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: COMPUTE_SHADER,
+                        src_access_mask: SHADER_STORAGE_WRITE,
+                        dst_stage_mask: HOST,
+                        dst_access_mask: HOST_READ,
+                        old_layout: Undefined,
+                        new_layout: Undefined,
+                        resource: buffer1,
+                    },
+                    {
+                        src_stage_mask: COMPUTE_SHADER,
+                        src_access_mask: SHADER_STORAGE_WRITE,
+                        dst_stage_mask: COPY,
+                        dst_access_mask: TRANSFER_READ,
+                        old_layout: Undefined,
+                        new_layout: Undefined,
+                        resource: buffer2,
+                    },
+                ],
+            },
+            ExecuteTask { node: node2 },
+            PipelineBarrier {
+                barriers: [
+                    {
+                        src_stage_mask: COPY,
+                        src_access_mask: ,
+                        dst_stage_mask: HOST,
+                        dst_access_mask: HOST_READ,
+                        old_layout: Undefined,
+                        new_layout: Undefined,
+                        resource: buffer2,
+                    },
+                ],
+            },
+            FlushSubmit,
+            Submit,
+        );
+    }
+
+    #[test]
+    fn async_compute1() {
+        let (resources, queues) = test_queues!();
+
+        let queue_family_properties = resources
+            .device()
+            .physical_device()
+            .queue_family_properties();
+        let queue_flags = |queue: &Arc<Queue>| {
+            queue_family_properties[queue.queue_family_index() as usize].queue_flags
+        };
+        let Some(graphics_queue) = queues
+            .iter()
+            .find(|q| queue_flags(q).contains(QueueFlags::GRAPHICS | QueueFlags::COMPUTE))
+        else {
+            return;
+        };
+        let Some(compute_queue) = queues.iter().find(|q| {
+            queue_flags(q).contains(QueueFlags::COMPUTE)
+                && !queue_flags(q).intersects(QueueFlags::GRAPHICS)
+        }) else {
+            return;
+        };
+        let graphics_queue_family_index = graphics_queue.queue_family_index();
+        let compute_queue_family_index = compute_queue.queue_family_index();
+
+        // ┌───┐  ┌───┐  ┌───┐  ┌───┐
+        // │ A ├┬►│ B ├─►│ C ├─►│   │
+        // └───┘│ └───┘  └───┘┌►│ E │
+        //      │ ┌───┐       │ │   │
+        //      └►│ D ├───────┘ └───┘
+        //        └───┘
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let a = graph
+            .create_task_node("A", QueueFamilyType::Graphics, PhantomData)
+            .build();
+        let b = graph
+            .create_task_node("B", QueueFamilyType::Compute, PhantomData)
+            .build();
+        let c = graph
+            .create_task_node("C", QueueFamilyType::Graphics, PhantomData)
+            .build();
+        let d = graph
+            .create_task_node("D", QueueFamilyType::Compute, PhantomData)
+            .build();
+        let e = graph
+            .create_task_node("E", QueueFamilyType::Graphics, PhantomData)
+            .build();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, e).unwrap();
+        graph.add_edge(a, d).unwrap();
+        graph.add_edge(d, e).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &[graphics_queue, compute_queue],
+                async_compute_policy: AsyncComputePolicy::OffCriticalPath,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        // B is on the critical path, while D can overlap with B and C.
+        let queue_family_index =
+            |graph: &ExecutableTaskGraph<()>, id| graph.task_node(id).unwrap().queue_family_index;
+        assert_eq!(queue_family_index(&graph, b), graphics_queue_family_index);
+        assert_eq!(queue_family_index(&graph, d), compute_queue_family_index);
+
+        let graph = unsafe {
+            graph.decompile().compile(&CompileInfo {
+                queues: &[graphics_queue, compute_queue],
+                async_compute_policy: AsyncComputePolicy::Never,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        assert_eq!(queue_family_index(&graph, b), graphics_queue_family_index);
+        assert_eq!(queue_family_index(&graph, d), graphics_queue_family_index);
+
+        let graph = unsafe {
+            graph.decompile().compile(&CompileInfo {
+                queues: &[graphics_queue, compute_queue],
+                async_compute_policy: AsyncComputePolicy::Always,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        assert_eq!(queue_family_index(&graph, b), compute_queue_family_index);
+        assert_eq!(queue_family_index(&graph, d), compute_queue_family_index);
+    }
+
+    #[test]
+    fn task_timestamps1() {
+        let (resources, queues) = test_queues!();
+
+        let queue_family_properties = resources
+            .device()
+            .physical_device()
+            .queue_family_properties();
+
+        if queues.iter().any(|q| {
+            queue_family_properties[q.queue_family_index() as usize]
+                .timestamp_valid_bits
+                .is_none()
+        }) {
+            return;
+        }
+
+        let flight_id = resources.create_flight(2).unwrap();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                flight_id,
+                task_timestamps: true,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        let task_timestamps = graph.task_timestamps.as_ref().unwrap();
+        assert_eq!(task_timestamps.node_ids, [node1, node2]);
+        // Two queries per task node per frame.
+        assert_eq!(task_timestamps.query_pool.query_count(), 8);
+        assert_eq!(task_timestamps.first_query(1, 1), 6);
+    }
+
+    #[test]
+    fn task_timestamps2() {
+        let (resources, queues) = test_queues!();
+
+        let queue_family_properties = resources
+            .device()
+            .physical_device()
+            .queue_family_properties();
+
+        if queues.iter().any(|q| {
+            queue_family_properties[q.queue_family_index() as usize]
+                .timestamp_valid_bits
+                .is_none()
+        }) {
+            return;
+        }
+
+        let flight_id = resources.create_flight(1).unwrap();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let color_image = graph.add_image(&ImageCreateInfo {
+            format: Format::R8G8B8A8_UNORM,
+            ..Default::default()
+        });
+        let framebuffer = graph.add_framebuffer();
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .framebuffer(framebuffer)
+            .color_attachment(
+                color_image,
+                AccessTypes::COLOR_ATTACHMENT_WRITE,
+                ImageLayoutType::Optimal,
+                &AttachmentInfo {
+                    clear: true,
+                    ..Default::default()
+                },
+            )
+            .build();
+        let node3 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .framebuffer(framebuffer)
+            .color_attachment(
+                color_image,
+                AccessTypes::COLOR_ATTACHMENT_READ | AccessTypes::COLOR_ATTACHMENT_WRITE,
+                ImageLayoutType::Optimal,
+                &AttachmentInfo::default(),
+            )
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+        graph.add_edge(node2, node3).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                flight_id,
+                task_timestamps: true,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        let task_timestamps = graph.task_timestamps.as_ref().unwrap();
+        assert_eq!(task_timestamps.node_ids, [node1, node2, node3]);
+        // The task nodes within the render pass instance share one pair of queries.
+        assert_eq!(task_timestamps.pair_indices, [0, 1, 1]);
+        assert_eq!(task_timestamps.query_pool.query_count(), 4);
+        assert_eq!(task_timestamps.task_first_query(0, 2), 2);
+    }
+
+    #[test]
+    fn conditional1() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer1 = graph.add_buffer(&BufferCreateInfo::default());
+        let buffer2 = graph.add_buffer(&BufferCreateInfo::default());
+        graph
+            .create_task_node("A", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer1, AccessTypes::COPY_TRANSFER_WRITE)
+            .buffer_access(buffer2, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+        let b = graph
+            .create_task_node("B", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer1, AccessTypes::COPY_TRANSFER_READ)
+            .buffer_access(buffer2, AccessTypes::COPY_TRANSFER_WRITE)
+            .conditional()
+            .build();
+        graph
+            .create_task_node("C", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer1, AccessTypes::VERTEX_ATTRIBUTE_READ)
+            .buffer_access(buffer2, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                infer_edges: true,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        // The barrier of `buffer1` before B is also used by C's read, so only the barrier of
+        // `buffer2` can be skipped.
+        let range = graph.task_node(b).unwrap().skippable_barrier_range.clone();
+        let skippable_barriers = &graph.barriers[range.start as usize..range.end as usize];
+        assert_eq!(skippable_barriers.len(), 1);
+        assert_eq!(skippable_barriers[0].resource, buffer2.erase());
+        assert_eq!(skippable_barriers[0].dst_stage_mask, PipelineStages::COPY,);
+    }
+
     fn has_compute_only_queue(queues: &[Arc<Queue>]) -> bool {
         let queue_family_properties = queues[0]
             .device()
@@ -0,0 +1,553 @@
+use super::{ExecutableTaskGraph, Instruction, MemoryBarrier, NodeIndex, Submission};
+use crate::Id;
+use ash::vk;
+use std::fmt::{self, Write};
+
+impl<W: ?Sized> ExecutableTaskGraph<W> {
+    /// Returns the compiled schedule of the task graph in the [DOT] format, for visualization
+    /// with tools such as GraphViz.
+    ///
+    /// Every submission is drawn as a cluster containing its instructions in the order in which
+    /// they are recorded, including the pipeline barriers and render pass instances that the
+    /// compiler inserted. Semaphore signal operations are connected to the corresponding wait
+    /// operations in other submissions.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+
+        // Writing to a `String` can't fail.
+        self.write_dot(&mut out).unwrap();
+
+        out
+    }
+
+    /// Returns the compiled schedule of the task graph in the JSON format, for consumption by
+    /// tooling.
+    ///
+    /// The top-level object contains the `submissions` in the order in which they are submitted.
+    /// Each submission lists the queue it's submitted to, the barriers that are executed before
+    /// it, and its `instructions`. Each instruction is an object with a `type` field and fields
+    /// specific to that type. Resources are referred to by their object type and index.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+
+        // Writing to a `String` can't fail.
+        self.write_json(&mut out).unwrap();
+
+        out
+    }
+
+    fn write_dot(&self, out: &mut String) -> fmt::Result {
+        let mut semaphore_signals = vec![None; self.semaphores.borrow().len()];
+        let mut semaphore_waits = Vec::new();
+
+        writeln!(out, "digraph {{")?;
+        writeln!(out, "  node [shape=box];")?;
+
+        for (submission_index, submission) in self.submissions.iter().enumerate() {
+            writeln!(out, "  subgraph cluster_{submission_index} {{")?;
+            write!(out, "    label=")?;
+            write_dot_string(out, &submission_label(submission_index, submission))?;
+            writeln!(out, ";")?;
+
+            if !submission.initial_barrier_range.is_empty() {
+                write!(out, "    s{submission_index}_initial [shape=note, label=")?;
+                let barriers = &self.barriers[submission.initial_barrier_range.start as usize
+                    ..submission.initial_barrier_range.end as usize];
+                write_dot_string(out, &barriers_label("initial barriers", barriers))?;
+                writeln!(out, "];")?;
+            }
+
+            let mut prev_instruction = (!submission.initial_barrier_range.is_empty())
+                .then(|| format!("s{submission_index}_initial"));
+
+            for instruction_index in submission.instruction_range.clone() {
+                let instruction = &self.instructions[instruction_index];
+                let name = format!("s{submission_index}_i{instruction_index}");
+                let (label, shape) = match instruction {
+                    Instruction::WaitAcquire {
+                        swapchain_id,
+                        stage_mask,
+                    } => (
+                        format!(
+                            "wait acquire {}\n{stage_mask:?}",
+                            resource_name(swapchain_id.erase()),
+                        ),
+                        "ellipse",
+                    ),
+                    &Instruction::WaitSemaphore {
+                        semaphore_index,
+                        stage_mask,
+                    } => {
+                        semaphore_waits.push((semaphore_index, name.clone()));
+
+                        (
+                            format!("wait semaphore {semaphore_index}\n{stage_mask:?}"),
+                            "ellipse",
+                        )
+                    }
+                    &Instruction::ExecuteTask { node_index } => {
+                        (self.node_name(node_index).to_owned(), "box")
+                    }
+                    Instruction::PipelineBarrier { barrier_range } => {
+                        let barriers = &self.barriers
+                            [barrier_range.start as usize..barrier_range.end as usize];
+
+                        (barriers_label("pipeline barrier", barriers), "note")
+                    }
+                    &Instruction::BeginRenderPass { render_pass_index } => {
+                        let render_passes = self.render_passes.borrow();
+                        let render_pass_state = &render_passes[render_pass_index];
+                        let mut label = format!(
+                            "begin render pass {render_pass_index}\n{} subpasses",
+                            render_pass_state.render_pass.subpasses().len(),
+                        );
+
+                        for &id in render_pass_state.attachments.keys() {
+                            write!(label, "\n{}", resource_name(id))?;
+                        }
+
+                        (label, "house")
+                    }
+                    Instruction::NextSubpass => ("next subpass".to_owned(), "house"),
+                    Instruction::EndRenderPass => ("end render pass".to_owned(), "invhouse"),
+                    Instruction::ClearAttachments {
+                        node_index,
+                        clear_attachment_range,
+                        ..
+                    } => {
+                        let mut label =
+                            format!("clear attachments ({})", self.node_name(*node_index));
+
+                        for &id in &self.clear_attachments[clear_attachment_range.clone()] {
+                            write!(label, "\n{}", resource_name(id))?;
+                        }
+
+                        (label, "box")
+                    }
+                    &Instruction::SignalSemaphore {
+                        semaphore_index,
+                        stage_mask,
+                    } => {
+                        semaphore_signals[semaphore_index] = Some(name.clone());
+
+                        (
+                            format!("signal semaphore {semaphore_index}\n{stage_mask:?}"),
+                            "ellipse",
+                        )
+                    }
+                    Instruction::SignalPrePresent {
+                        swapchain_id,
+                        stage_mask,
+                    } => (
+                        format!(
+                            "signal pre-present {}\n{stage_mask:?}",
+                            resource_name(swapchain_id.erase()),
+                        ),
+                        "ellipse",
+                    ),
+                    Instruction::WaitPrePresent {
+                        swapchain_id,
+                        stage_mask,
+                    } => (
+                        format!(
+                            "wait pre-present {}\n{stage_mask:?}",
+                            resource_name(swapchain_id.erase()),
+                        ),
+                        "ellipse",
+                    ),
+                    Instruction::SignalPresent {
+                        swapchain_id,
+                        stage_mask,
+                    } => (
+                        format!(
+                            "signal present {}\n{stage_mask:?}",
+                            resource_name(swapchain_id.erase()),
+                        ),
+                        "ellipse",
+                    ),
+                    Instruction::FlushSubmit | Instruction::Submit => continue,
+                };
+
+                write!(out, "    {name} [shape={shape}, label=")?;
+                write_dot_string(out, &label)?;
+                writeln!(out, "];")?;
+
+                if let Some(prev_instruction) = &prev_instruction {
+                    writeln!(out, "    {prev_instruction} -> {name};")?;
+                }
+
+                prev_instruction = Some(name);
+            }
+
+            writeln!(out, "  }}")?;
+        }
+
+        for (semaphore_index, wait_name) in semaphore_waits {
+            if let Some(signal_name) = &semaphore_signals[semaphore_index] {
+                writeln!(out, "  {signal_name} -> {wait_name} [style=dashed];")?;
+            }
+        }
+
+        writeln!(out, "}}")
+    }
+
+    fn write_json(&self, out: &mut String) -> fmt::Result {
+        write!(out, "{{\"submissions\":[")?;
+
+        for (submission_index, submission) in self.submissions.iter().enumerate() {
+            if submission_index != 0 {
+                out.push(',');
+            }
+
+            write!(
+                out,
+                "{{\"queue_family_index\":{},\"queue_index\":{},\"initial_barriers\":",
+                submission.queue.queue_family_index(),
+                submission.queue.queue_index(),
+            )?;
+            write_json_barriers(
+                out,
+                &self.barriers[submission.initial_barrier_range.start as usize
+                    ..submission.initial_barrier_range.end as usize],
+            )?;
+            write!(out, ",\"instructions\":[")?;
+
+            for (i, instruction_index) in submission.instruction_range.clone().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+
+                self.write_json_instruction(out, &self.instructions[instruction_index])?;
+            }
+
+            write!(out, "]}}")?;
+        }
+
+        write!(out, "]}}")
+    }
+
+    fn write_json_instruction(&self, out: &mut String, instruction: &Instruction) -> fmt::Result {
+        match instruction {
+            Instruction::WaitAcquire {
+                swapchain_id,
+                stage_mask,
+            } => {
+                write!(out, "{{\"type\":\"wait_acquire\",\"swapchain\":")?;
+                write_json_string(out, &resource_name(swapchain_id.erase()))?;
+                write!(out, ",\"stage_mask\":")?;
+                write_json_string(out, &format!("{stage_mask:?}"))?;
+            }
+            Instruction::WaitSemaphore {
+                semaphore_index,
+                stage_mask,
+            } => {
+                write!(
+                    out,
+                    "{{\"type\":\"wait_semaphore\",\"semaphore\":{semaphore_index},\"stage_mask\":",
+                )?;
+                write_json_string(out, &format!("{stage_mask:?}"))?;
+            }
+            &Instruction::ExecuteTask { node_index } => {
+                write!(
+                    out,
+                    "{{\"type\":\"execute_task\",\"node_index\":{node_index},\"name\":",
+                )?;
+                write_json_string(out, self.node_name(node_index))?;
+            }
+            Instruction::PipelineBarrier { barrier_range } => {
+                write!(out, "{{\"type\":\"pipeline_barrier\",\"barriers\":")?;
+                write_json_barriers(
+                    out,
+                    &self.barriers[barrier_range.start as usize..barrier_range.end as usize],
+                )?;
+            }
+            &Instruction::BeginRenderPass { render_pass_index } => {
+                let render_passes = self.render_passes.borrow();
+                let render_pass_state = &render_passes[render_pass_index];
+
+                write!(
+                    out,
+                    "{{\"type\":\"begin_render_pass\",\"render_pass\":{render_pass_index},\
+                    \"subpass_count\":{},\"attachments\":",
+                    render_pass_state.render_pass.subpasses().len(),
+                )?;
+                write_json_resources(out, render_pass_state.attachments.keys().copied())?;
+            }
+            Instruction::NextSubpass => write!(out, "{{\"type\":\"next_subpass\"")?,
+            Instruction::EndRenderPass => write!(out, "{{\"type\":\"end_render_pass\"")?,
+            Instruction::ClearAttachments {
+                node_index,
+                render_pass_index,
+                clear_attachment_range,
+            } => {
+                write!(
+                    out,
+                    "{{\"type\":\"clear_attachments\",\"node_index\":{node_index},\
+                    \"render_pass\":{render_pass_index},\"attachments\":",
+                )?;
+                write_json_resources(
+                    out,
+                    self.clear_attachments[clear_attachment_range.clone()]
+                        .iter()
+                        .copied(),
+                )?;
+            }
+            Instruction::SignalSemaphore {
+                semaphore_index,
+                stage_mask,
+            } => {
+                write!(
+                    out,
+                    "{{\"type\":\"signal_semaphore\",\"semaphore\":{semaphore_index},\
+                    \"stage_mask\":",
+                )?;
+                write_json_string(out, &format!("{stage_mask:?}"))?;
+            }
+            Instruction::SignalPrePresent {
+                swapchain_id,
+                stage_mask,
+            } => {
+                write!(out, "{{\"type\":\"signal_pre_present\",\"swapchain\":")?;
+                write_json_string(out, &resource_name(swapchain_id.erase()))?;
+                write!(out, ",\"stage_mask\":")?;
+                write_json_string(out, &format!("{stage_mask:?}"))?;
+            }
+            Instruction::WaitPrePresent {
+                swapchain_id,
+                stage_mask,
+            } => {
+                write!(out, "{{\"type\":\"wait_pre_present\",\"swapchain\":")?;
+                write_json_string(out, &resource_name(swapchain_id.erase()))?;
+                write!(out, ",\"stage_mask\":")?;
+                write_json_string(out, &format!("{stage_mask:?}"))?;
+            }
+            Instruction::SignalPresent {
+                swapchain_id,
+                stage_mask,
+            } => {
+                write!(out, "{{\"type\":\"signal_present\",\"swapchain\":")?;
+                write_json_string(out, &resource_name(swapchain_id.erase()))?;
+                write!(out, ",\"stage_mask\":")?;
+                write_json_string(out, &format!("{stage_mask:?}"))?;
+            }
+            Instruction::FlushSubmit => write!(out, "{{\"type\":\"flush_submit\"")?,
+            Instruction::Submit => write!(out, "{{\"type\":\"submit\"")?,
+        }
+
+        write!(out, "}}")
+    }
+
+    fn node_name(&self, node_index: NodeIndex) -> &str {
+        // SAFETY: The instructions only contain valid node indices.
+        &unsafe { self.graph.nodes.node_unchecked(node_index) }.name
+    }
+}
+
+fn submission_label(submission_index: usize, submission: &Submission) -> String {
+    format!(
+        "submission {submission_index} (queue family {}, queue {})",
+        submission.queue.queue_family_index(),
+        submission.queue.queue_index(),
+    )
+}
+
+fn barriers_label(title: &str, barriers: &[MemoryBarrier]) -> String {
+    let mut label = title.to_owned();
+
+    for barrier in barriers {
+        label.push_str(&format!(
+            "\n{}: {:?} {:?} -> {:?} {:?}",
+            resource_name(barrier.resource),
+            barrier.src_stage_mask,
+            barrier.src_access_mask,
+            barrier.dst_stage_mask,
+            barrier.dst_access_mask,
+        ));
+
+        if barrier.old_layout != barrier.new_layout {
+            label.push_str(&format!(
+                ", {:?} -> {:?}",
+                barrier.old_layout, barrier.new_layout,
+            ));
+        }
+
+        if barrier.src_queue_family_index != barrier.dst_queue_family_index {
+            label.push_str(&format!(
+                ", queue family {} -> {}",
+                barrier.src_queue_family_index, barrier.dst_queue_family_index,
+            ));
+        }
+    }
+
+    label
+}
+
+fn resource_name(id: Id) -> String {
+    format!("{:?} {}", id.object_type(), id.index())
+}
+
+fn write_dot_string(out: &mut String, s: &str) -> fmt::Result {
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\l"),
+            c => out.push(c),
+        }
+    }
+
+    // Left-justify the last line as well.
+    if s.contains('\n') {
+        out.push_str("\\l");
+    }
+
+    out.push('"');
+
+    Ok(())
+}
+
+fn write_json_string(out: &mut String, s: &str) -> fmt::Result {
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+
+    Ok(())
+}
+
+fn write_json_resources(out: &mut String, ids: impl Iterator<Item = Id>) -> fmt::Result {
+    out.push('[');
+
+    for (i, id) in ids.enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+
+        write_json_string(out, &resource_name(id))?;
+    }
+
+    out.push(']');
+
+    Ok(())
+}
+
+fn write_json_barriers(out: &mut String, barriers: &[MemoryBarrier]) -> fmt::Result {
+    out.push('[');
+
+    for (i, barrier) in barriers.iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+
+        write!(out, "{{\"resource\":")?;
+        write_json_string(out, &resource_name(barrier.resource))?;
+        write!(out, ",\"src_stage_mask\":")?;
+        write_json_string(out, &format!("{:?}", barrier.src_stage_mask))?;
+        write!(out, ",\"src_access_mask\":")?;
+        write_json_string(out, &format!("{:?}", barrier.src_access_mask))?;
+        write!(out, ",\"dst_stage_mask\":")?;
+        write_json_string(out, &format!("{:?}", barrier.dst_stage_mask))?;
+        write!(out, ",\"dst_access_mask\":")?;
+        write_json_string(out, &format!("{:?}", barrier.dst_access_mask))?;
+        write!(out, ",\"old_layout\":")?;
+        write_json_string(out, &format!("{:?}", barrier.old_layout))?;
+        write!(out, ",\"new_layout\":")?;
+        write_json_string(out, &format!("{:?}", barrier.new_layout))?;
+        write!(out, ",\"src_queue_family_index\":")?;
+        write_json_queue_family_index(out, barrier.src_queue_family_index)?;
+        write!(out, ",\"dst_queue_family_index\":")?;
+        write_json_queue_family_index(out, barrier.dst_queue_family_index)?;
+        out.push('}');
+    }
+
+    out.push(']');
+
+    Ok(())
+}
+
+fn write_json_queue_family_index(out: &mut String, queue_family_index: u32) -> fmt::Result {
+    if queue_family_index == vk::QUEUE_FAMILY_IGNORED {
+        write!(out, "null")
+    } else {
+        write!(out, "{queue_family_index}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{CompileInfo, TaskGraph},
+        resource::AccessTypes,
+        tests::test_queues,
+        QueueFamilyType,
+    };
+    use std::marker::PhantomData;
+    use vulkano::buffer::BufferCreateInfo;
+
+    #[test]
+    fn escape() {
+        let mut out = String::new();
+        write_json_string(&mut out, "a \"b\"\\\n\u{1}").unwrap();
+        assert_eq!(out, r#""a \"b\"\\\n\u0001""#);
+
+        let mut out = String::new();
+        write_dot_string(&mut out, "a \"b\"\nc").unwrap();
+        assert_eq!(out, r#""a \"b\"\lc\l""#);
+    }
+
+    #[test]
+    fn dump1() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let node1 = graph
+            .create_task_node("upload", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+        let node2 = graph
+            .create_task_node("draw", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::VERTEX_ATTRIBUTE_READ)
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("label=\"upload\""));
+        assert!(dot.contains("label=\"draw\""));
+        assert!(dot.contains("pipeline barrier"));
+
+        let json = graph.to_json();
+        assert!(json.starts_with("{\"submissions\":[{"));
+        assert!(json.contains("{\"type\":\"execute_task\",\"node_index\":"));
+        assert!(json.contains(
+            "\"src_stage_mask\":\"COPY\",\"src_access_mask\":\"TRANSFER_WRITE\",\
+            \"dst_stage_mask\":\"VERTEX_ATTRIBUTE_INPUT\",\
+            \"dst_access_mask\":\"VERTEX_ATTRIBUTE_READ\"",
+        ));
+    }
+}
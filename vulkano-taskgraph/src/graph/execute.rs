@@ -15,8 +15,10 @@ use crate::{
 };
 use ash::vk;
 use concurrent_slotmap::hyaline;
+use parking_lot::Mutex;
 use smallvec::{smallvec, SmallVec};
 use std::{
+    borrow::{BorrowMut, Cow},
     error::Error,
     fmt, iter,
     mem::{self, ManuallyDrop},
@@ -28,7 +30,7 @@ use vulkano::{
     buffer::{Buffer, BufferMemory},
     command_buffer as raw,
     device::{Device, DeviceOwned, Queue},
-    format::ClearValue,
+    format::{ClearValue, Format},
     image::{
         view::{ImageView, ImageViewCreateInfo},
         Image, ImageSubresourceRange,
@@ -38,7 +40,7 @@ use vulkano::{
     sync::{
         fence::{Fence, FenceCreateFlags, FenceCreateInfo},
         semaphore::Semaphore,
-        AccessFlags, PipelineStages,
+        AccessFlags, PipelineStage, PipelineStages,
     },
     Validated, Version, VulkanError, VulkanObject,
 };
@@ -69,13 +71,13 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
     /// [`try_execute`]: Self::try_execute
     /// [waited]: crate::resource::Flight::wait
     #[track_caller]
-    pub unsafe fn execute(
+    pub unsafe fn execute<'a>(
         &self,
-        resource_map: ResourceMap<'_>,
+        mut resource_map: impl BorrowMut<ResourceMap<'a>>,
         world: &W,
         pre_present_notify: impl FnOnce(),
     ) -> Result<(), ExecuteError> {
-        match unsafe { self.try_execute(resource_map, world, pre_present_notify) } {
+        match unsafe { self.try_execute(resource_map.borrow_mut(), world, pre_present_notify) } {
             Ok(res) => Ok(res),
             Err(err) => Err(err.unwrap()),
         }
@@ -83,6 +85,106 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
 
     /// Executes the next frame of the [flight] given by `flight_id`.
     ///
+    /// `resource_map` can be passed either by value or by mutable reference. The latter allows
+    /// you to keep the [`ResourceMap`] around and reuse it for the next execution. If a physical
+    /// resource that `resource_map` maps to has been removed since it was inserted, the mapping is
+    /// removed from `resource_map` and [`ExecuteError::ResourceRemoved`] is returned.
+    ///
+    /// # Safety
+    ///
+    /// - There must be no other task graphs executing that access any of the same subresources as
+    ///   `self`.
+    /// - A subresource in flight must not be accessed in more than one frame in flight.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `resource_map` doesn't map the virtual resources of `self` exhaustively.
+    /// - Panics if `self.flight_id()` is invalid.
+    /// - Panics if another thread is already executing a task graph using the flight.
+    /// - Panics if another thread is already executing a task graph using any of the swapchains
+    ///   used by the task graph.
+    /// - Panics if any swapchain used by the task graph has already been recreated or removed.
+    /// - Panics if the oldest frame of the flight wasn't [waited] on.
+    ///
+    /// [waited]: crate::resource::Flight::wait
+    #[track_caller]
+    pub unsafe fn try_execute<'a>(
+        &self,
+        mut resource_map: impl BorrowMut<ResourceMap<'a>>,
+        world: &W,
+        pre_present_notify: impl FnOnce(),
+    ) -> Result<(), Validated<ExecuteError>> {
+        unsafe {
+            self.try_execute_inner(
+                resource_map.borrow_mut(),
+                world,
+                pre_present_notify,
+                |_, _, _, _| Vec::new(),
+            )
+        }
+    }
+
+    /// Executes the next frame of the [flight] given by `flight_id`, recording the task nodes
+    /// that were [set to be recorded in parallel] using `thread_pool`, panicking on a validation
+    /// error.
+    ///
+    /// This is a shortcut for `try_execute_parallel().map_err(Validated::unwrap)`.
+    ///
+    /// # Safety
+    ///
+    /// - There must be no other task graphs executing that access any of the same subresources as
+    ///   `self`.
+    /// - A subresource in flight must not be accessed in more than one frame in flight.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_execute_parallel`] returns a [`ValidationError`].
+    /// - Panics if `resource_map` doesn't map the virtual resources of `self` exhaustively.
+    /// - Panics if `self.flight_id()` is invalid.
+    /// - Panics if another thread is already executing a task graph using the flight.
+    /// - Panics if another thread is already executing a task graph using any of the swapchains
+    ///   used by the task graph.
+    /// - Panics if any swapchain used by the task graph has already been recreated or removed.
+    /// - Panics if the oldest frame of the flight wasn't [waited] on.
+    ///
+    /// [set to be recorded in parallel]: super::TaskNodeBuilder::record_in_parallel
+    /// [`try_execute_parallel`]: Self::try_execute_parallel
+    /// [waited]: crate::resource::Flight::wait
+    #[track_caller]
+    pub unsafe fn execute_parallel<'a>(
+        &self,
+        mut resource_map: impl BorrowMut<ResourceMap<'a>>,
+        world: &W,
+        pre_present_notify: impl FnOnce(),
+        thread_pool: &dyn ThreadPool,
+    ) -> Result<(), ExecuteError>
+    where
+        W: Sync,
+    {
+        match unsafe {
+            self.try_execute_parallel(
+                resource_map.borrow_mut(),
+                world,
+                pre_present_notify,
+                thread_pool,
+            )
+        } {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    /// Executes the next frame of the [flight] given by `flight_id`, recording the task nodes
+    /// that were [set to be recorded in parallel] using `thread_pool`.
+    ///
+    /// Before any commands are submitted, all such task nodes are recorded on `thread_pool`, each
+    /// into its own command buffer. The remaining task nodes are then recorded on the current
+    /// thread, and the command buffers are submitted in the order in which the task nodes would
+    /// have been executed by [`try_execute`].
+    ///
+    /// `resource_map` can be passed either by value or by mutable reference, as for
+    /// [`try_execute`].
+    ///
     /// # Safety
     ///
     /// - There must be no other task graphs executing that access any of the same subresources as
@@ -99,18 +201,63 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
     /// - Panics if any swapchain used by the task graph has already been recreated or removed.
     /// - Panics if the oldest frame of the flight wasn't [waited] on.
     ///
+    /// [set to be recorded in parallel]: super::TaskNodeBuilder::record_in_parallel
+    /// [`try_execute`]: Self::try_execute
     /// [waited]: crate::resource::Flight::wait
     #[track_caller]
-    pub unsafe fn try_execute(
+    pub unsafe fn try_execute_parallel<'a>(
+        &self,
+        mut resource_map: impl BorrowMut<ResourceMap<'a>>,
+        world: &W,
+        pre_present_notify: impl FnOnce(),
+        thread_pool: &dyn ThreadPool,
+    ) -> Result<(), Validated<ExecuteError>>
+    where
+        W: Sync,
+    {
+        let record_tasks = |resource_map: &ResourceMap<'_>,
+                            current_frame_index: u32,
+                            enabled_tasks: &[bool],
+                            deferreds: &mut Vec<Deferred>| unsafe {
+            self.record_tasks_in_parallel(
+                resource_map,
+                world,
+                current_frame_index,
+                enabled_tasks,
+                deferreds,
+                thread_pool,
+            )
+        };
+
+        unsafe {
+            self.try_execute_inner(
+                resource_map.borrow_mut(),
+                world,
+                pre_present_notify,
+                record_tasks,
+            )
+        }
+    }
+
+    #[track_caller]
+    unsafe fn try_execute_inner(
         &self,
-        resource_map: ResourceMap<'_>,
+        resource_map: &mut ResourceMap<'_>,
         world: &W,
         pre_present_notify: impl FnOnce(),
+        record_tasks: impl FnOnce(&ResourceMap<'_>, u32, &[bool], &mut Vec<Deferred>) -> RecordedTasks,
     ) -> Result<(), Validated<ExecuteError>> {
         assert!(ptr::eq(
             resource_map.virtual_resources,
             &self.graph.resources,
         ));
+
+        resource_map
+            .refresh(self)
+            .map_err(ExecuteError::ResourceRemoved)?;
+
+        let resource_map = &*resource_map;
+
         assert!(resource_map.is_exhaustive());
 
         let flight_id = self.flight_id;
@@ -131,28 +278,32 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
             "you must wait on the oldest frame in flight to finish before submitting more work",
         );
 
-        let _resource_lock_guard = unsafe { self.lock_resources(&resource_map) };
+        let _resource_lock_guard = unsafe { self.lock_resources(resource_map) };
 
         let current_frame_index = flight.current_frame_index();
 
-        unsafe { self.collect_swapchains(&resource_map) }?;
+        unsafe { self.collect_swapchains(resource_map) }?;
 
-        unsafe { self.acquire_images_khr(&resource_map) }?;
+        unsafe { self.acquire_images_khr(resource_map) }?;
 
         let mut current_fence = flight.current_fence().write();
 
         // SAFETY: We checked that the fence has been signaled.
         unsafe { current_fence.reset_unchecked() }.map_err(ExecuteError::VulkanError)?;
 
-        unsafe { self.invalidate_mapped_memory_ranges(&resource_map) }?;
+        unsafe { self.invalidate_mapped_memory_ranges(resource_map) }?;
 
-        unsafe { self.create_framebuffers(&resource_map) }?;
+        unsafe { self.create_framebuffers(resource_map) }?;
 
         unsafe { flight.start_next_frame() };
 
+        if let Some(task_timestamps) = &self.task_timestamps {
+            task_timestamps.executed.borrow_mut()[current_frame_index as usize].clear();
+        }
+
         let mut state_guard = StateGuard {
             executable: self,
-            resource_map: &resource_map,
+            resource_map,
             flight,
             current_fence: &mut current_fence,
             deferred_batch: Some(resource_map.resources().create_deferred_batch()),
@@ -160,6 +311,12 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
         };
         let deferreds = state_guard.deferred_batch.as_mut().unwrap().deferreds_mut();
 
+        // Whether the conditional task nodes are enabled is decided up front, since the barriers
+        // of the task graph depend on it as well.
+        let enabled_tasks = self.enabled_tasks(world);
+        let recorded_tasks =
+            record_tasks(resource_map, current_frame_index, &enabled_tasks, deferreds);
+
         let execute_instructions = if self.device().enabled_features().synchronization2 {
             Self::execute_instructions2
         } else {
@@ -169,18 +326,21 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
         unsafe {
             execute_instructions(
                 self,
-                &resource_map,
+                resource_map,
                 deferreds,
                 current_frame_index,
                 state_guard.current_fence,
                 &mut state_guard.submission_count,
                 world,
+                &enabled_tasks,
+                recorded_tasks,
             )
         }?;
 
         let mut state_guard = ManuallyDrop::new(state_guard);
 
         self.last_frame.set(Some(flight.current_frame()));
+        self.execution_index.set(self.execution_index.get() + 1);
 
         unsafe { flight.next_frame() };
 
@@ -188,9 +348,9 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
 
         let mut deferred_batch = state_guard.deferred_batch.take().unwrap();
 
-        let res = unsafe { self.present_images_khr(&resource_map, &mut deferred_batch) };
+        let res = unsafe { self.present_images_khr(resource_map, &mut deferred_batch) };
 
-        unsafe { self.update_resource_state(&resource_map, &self.last_accesses) };
+        unsafe { self.update_resource_state(resource_map, &self.last_accesses) };
 
         // SAFETY: We only defer the destruction of objects that are frame-local.
         unsafe { deferred_batch.enqueue_with_flights(iter::once(self.flight_id)) };
@@ -380,6 +540,87 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
         Ok(())
     }
 
+    fn enabled_tasks(&self, world: &W) -> Vec<bool> {
+        self.instructions
+            .iter()
+            .filter_map(|instruction| match *instruction {
+                Instruction::ExecuteTask { node_index } => {
+                    let task_node = unsafe { self.graph.nodes.task_node_unchecked(node_index) };
+
+                    Some(task_node.should_execute(world))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    unsafe fn record_tasks_in_parallel(
+        &self,
+        resource_map: &ResourceMap<'_>,
+        world: &W,
+        current_frame_index: u32,
+        enabled_tasks: &[bool],
+        deferreds: &mut Vec<Deferred>,
+        thread_pool: &dyn ThreadPool,
+    ) -> RecordedTasks
+    where
+        W: Sync,
+    {
+        let mut task_count = 0;
+        let mut tasks = Vec::new();
+
+        for submission in &self.submissions {
+            for instruction in &self.instructions[submission.instruction_range.clone()] {
+                let &Instruction::ExecuteTask { node_index } = instruction else {
+                    continue;
+                };
+                let task_node = unsafe { self.graph.nodes.task_node_unchecked(node_index) };
+
+                // `TaskGraph::compile` ensures that task nodes within a render pass instance
+                // aren't recorded in parallel.
+                if task_node.record_in_parallel {
+                    tasks.push(ParallelTask {
+                        task_index: task_count,
+                        node_index,
+                        queue: &submission.queue,
+                        result: Mutex::new(None),
+                    });
+                }
+
+                task_count += 1;
+            }
+        }
+
+        if tasks.is_empty() {
+            return Vec::new();
+        }
+
+        let context = RecordContext {
+            executable: self,
+            resource_map,
+            world,
+            current_frame_index,
+            enabled_tasks,
+            tasks: &tasks,
+        };
+
+        thread_pool.for_each(tasks.len(), &|index| unsafe { context.record_task(index) });
+
+        let mut recorded_tasks = Vec::new();
+        recorded_tasks.resize_with(task_count, || None);
+
+        for task in tasks {
+            // If the thread pool didn't record the task for whatever reason, it is recorded on the
+            // current thread as usual.
+            if let Some((result, task_deferreds)) = task.result.into_inner() {
+                recorded_tasks[task.task_index] = Some(result);
+                deferreds.extend(task_deferreds);
+            }
+        }
+
+        recorded_tasks
+    }
+
     unsafe fn execute_instructions2(
         &self,
         resource_map: &ResourceMap<'_>,
@@ -388,6 +629,8 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
         current_fence: &Fence,
         submission_count: &mut usize,
         world: &W,
+        enabled_tasks: &[bool],
+        recorded_tasks: RecordedTasks,
     ) -> Result {
         let mut state = ExecuteState2::new(
             self,
@@ -397,6 +640,8 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
             current_fence,
             submission_count,
             world,
+            enabled_tasks,
+            recorded_tasks,
         )?;
         let mut execute_initial_barriers = true;
 
@@ -404,6 +649,7 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
             if execute_initial_barriers {
                 let submission = current_submission!(state);
                 state.initial_pipeline_barrier(submission.initial_barrier_range.clone());
+                state.reset_task_timestamps()?;
                 execute_initial_barriers = false;
             }
 
@@ -491,6 +737,8 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
         current_fence: &Fence,
         submission_count: &mut usize,
         world: &W,
+        enabled_tasks: &[bool],
+        recorded_tasks: RecordedTasks,
     ) -> Result {
         let mut state = ExecuteState::new(
             self,
@@ -500,6 +748,8 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
             current_fence,
             submission_count,
             world,
+            enabled_tasks,
+            recorded_tasks,
         )?;
         let mut execute_initial_barriers = true;
 
@@ -507,6 +757,7 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
             if execute_initial_barriers {
                 let submission = current_submission!(state);
                 state.initial_pipeline_barrier(submission.initial_barrier_range.clone());
+                state.reset_task_timestamps()?;
                 execute_initial_barriers = false;
             }
 
@@ -704,6 +955,12 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
         for (id, _) in self.graph.resources.iter() {
             let access = last_accesses[id.index() as usize];
 
+            // The physical image behind a history image that wasn't accessed must keep its state,
+            // as it's going to be accessed during a later execution.
+            if access.stage_mask.is_empty() && self.graph.resources.is_history_image(id) {
+                continue;
+            }
+
             match id.object_type() {
                 ObjectType::Buffer => {
                     let id = unsafe { id.parametrize() };
@@ -743,6 +1000,225 @@ impl<W: ?Sized + 'static> ExecutableTaskGraph<W> {
     }
 }
 
+/// A pool of threads that task nodes can be recorded on in parallel.
+///
+/// This is used by [`ExecutableTaskGraph::execute_parallel`] to record the task nodes that were
+/// [set to be recorded in parallel]. You would typically implement this trait for the thread pool
+/// that your application already uses. For example, using scoped threads from the standard
+/// library:
+///
+/// ```
+/// use vulkano_taskgraph::graph::ThreadPool;
+///
+/// struct ScopedThreads;
+///
+/// impl ThreadPool for ScopedThreads {
+///     fn for_each(&self, len: usize, f: &(dyn Fn(usize) + Sync)) {
+///         std::thread::scope(|scope| {
+///             for index in 0..len {
+///                 scope.spawn(move || f(index));
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+/// [set to be recorded in parallel]: super::TaskNodeBuilder::record_in_parallel
+pub trait ThreadPool: Sync {
+    /// Calls `f` once for every index in `0..len`, potentially in parallel, and returns once all
+    /// calls have returned.
+    ///
+    /// If `f` isn't called for some index, the corresponding task node is recorded on the
+    /// executing thread instead.
+    fn for_each(&self, len: usize, f: &(dyn Fn(usize) + Sync));
+}
+
+type RecordedTasks = Vec<Option<Result<RecordedTask>>>;
+
+struct RecordedTask {
+    // `None` if the task is conditional and was disabled.
+    command_buffer: Option<raw::CommandBuffer>,
+    command_buffers: Vec<Arc<raw::CommandBuffer>>,
+}
+
+struct ParallelTask<'a> {
+    task_index: usize,
+    node_index: NodeIndex,
+    queue: &'a Arc<Queue>,
+    result: Mutex<Option<(Result<RecordedTask>, Vec<Deferred>)>>,
+}
+
+struct RecordContext<'a, W: ?Sized + 'static> {
+    executable: &'a ExecutableTaskGraph<W>,
+    resource_map: &'a ResourceMap<'a>,
+    world: &'a W,
+    current_frame_index: u32,
+    enabled_tasks: &'a [bool],
+    tasks: &'a [ParallelTask<'a>],
+}
+
+// SAFETY: The task graph and resource map are only ever read from on the worker threads, and only
+// the parts of them that aren't mutated during execution are accessed. Each worker thread creates
+// its own copy of the resource map using `ResourceMap::fork`.
+unsafe impl<W: ?Sized + Sync> Sync for RecordContext<'_, W> {}
+
+impl<W: ?Sized + 'static> RecordContext<'_, W> {
+    unsafe fn record_task(&self, index: usize) {
+        let task = &self.tasks[index];
+        let mut result = task.result.lock();
+
+        if result.is_some() {
+            return;
+        }
+
+        let mut deferreds = Vec::new();
+        let res = unsafe { self.record_task_inner(task, &mut deferreds) };
+        *result = Some((res, deferreds));
+    }
+
+    unsafe fn record_task_inner(
+        &self,
+        task: &ParallelTask<'_>,
+        deferreds: &mut Vec<Deferred>,
+    ) -> Result<RecordedTask> {
+        let node_index = task.node_index;
+        let task_node = unsafe { self.executable.graph.nodes.task_node_unchecked(node_index) };
+
+        if !self.enabled_tasks[task.task_index] {
+            return Ok(RecordedTask {
+                command_buffer: None,
+                command_buffers: Vec::new(),
+            });
+        }
+
+        // SAFETY: `self.resource_map` outlives the recording of all tasks.
+        let resource_map = unsafe { self.resource_map.fork() };
+
+        let mut command_buffer =
+            create_command_buffer(&resource_map, task.queue).map_err(ExecuteError::VulkanError)?;
+        let mut command_buffer_state = CommandBufferState::default();
+        let mut command_buffers = Vec::new();
+        let mut current_command_buffer = unsafe {
+            RecordingCommandBuffer::new(
+                &mut command_buffer,
+                &mut command_buffer_state,
+                &resource_map,
+                &task_node.accesses,
+                deferreds,
+            )
+        };
+        let mut task_deferreds = Vec::new();
+        let mut context = TaskContext {
+            resource_map: &resource_map,
+            current_frame_index: self.current_frame_index,
+            command_buffers: &mut command_buffers,
+            deferreds: &mut task_deferreds,
+        };
+
+        unsafe {
+            task_node
+                .task
+                .execute(&mut current_command_buffer, &mut context, self.world)
+        }
+        .map_err(|err| err.map(|error| ExecuteError::Task { node_index, error }))?;
+
+        deferreds.append(&mut task_deferreds);
+
+        let command_buffer = unsafe { command_buffer.end() }.map_err(ExecuteError::VulkanError)?;
+
+        Ok(RecordedTask {
+            command_buffer: Some(command_buffer),
+            command_buffers,
+        })
+    }
+}
+
+/// Keeps track of the barriers of the disabled conditional task nodes.
+///
+/// Rather than being executed where they were compiled, the skippable barriers of a disabled task
+/// node are merged with the next barrier on the same resource. This way, the accesses before the
+/// task node are synchronized directly with the accesses after it.
+struct SkippedBarriers {
+    ranges: Vec<Range<BarrierIndex>>,
+    pending: Vec<super::MemoryBarrier>,
+}
+
+impl SkippedBarriers {
+    fn new<W: ?Sized>(executable: &ExecutableTaskGraph<W>, enabled_tasks: &[bool]) -> Self {
+        let ranges = executable
+            .instructions
+            .iter()
+            .filter_map(|instruction| match *instruction {
+                Instruction::ExecuteTask { node_index } => Some(node_index),
+                _ => None,
+            })
+            .zip(enabled_tasks)
+            .filter(|&(_, &is_enabled)| !is_enabled)
+            .map(|(node_index, _)| {
+                let task_node = unsafe { executable.graph.nodes.task_node_unchecked(node_index) };
+
+                task_node.skippable_barrier_range.clone()
+            })
+            .filter(|range| !range.is_empty())
+            .collect();
+
+        SkippedBarriers {
+            ranges,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the barriers in `barrier_range` that should be executed, with the pending skipped
+    /// barriers merged into them.
+    fn resolve<'b>(
+        &mut self,
+        barriers: &'b [super::MemoryBarrier],
+        barrier_range: Range<BarrierIndex>,
+    ) -> Cow<'b, [super::MemoryBarrier]> {
+        let range = barrier_range.start as usize..barrier_range.end as usize;
+
+        if self.ranges.is_empty() && self.pending.is_empty() {
+            return Cow::Borrowed(&barriers[range]);
+        }
+
+        let mut resolved = Vec::with_capacity(range.len());
+
+        for (barrier_index, barrier) in barrier_range.zip(&barriers[range]) {
+            let mut barrier = barrier.clone();
+
+            if let Some(index) = self
+                .pending
+                .iter()
+                .position(|pending| pending.resource == barrier.resource)
+            {
+                // The barrier now has to synchronize with the accesses that came before the
+                // skipped barrier.
+                let pending = self.pending.swap_remove(index);
+                barrier.src_stage_mask = pending.src_stage_mask;
+                barrier.src_access_mask = pending.src_access_mask;
+                barrier.old_layout = pending.old_layout;
+            }
+
+            if self
+                .ranges
+                .iter()
+                .any(|range| range.contains(&barrier_index))
+            {
+                self.pending.push(barrier);
+            } else {
+                resolved.push(barrier);
+            }
+        }
+
+        Cow::Owned(resolved)
+    }
+
+    /// Returns the skipped barriers that haven't been merged with any other barrier yet.
+    fn take_pending(&mut self) -> Vec<super::MemoryBarrier> {
+        mem::take(&mut self.pending)
+    }
+}
+
 struct ResourceLockGuard<'a> {
     resource_map: &'a ResourceMap<'a>,
     swapchains: &'a [Id<Swapchain>],
@@ -874,9 +1350,13 @@ struct ExecuteState2<'a, W: ?Sized + 'static> {
     resource_map: &'a ResourceMap<'a>,
     deferreds: &'a mut Vec<Deferred>,
     current_frame_index: u32,
+    current_task_index: u32,
     current_fence: &'a Fence,
     submission_count: &'a mut usize,
     world: &'a W,
+    enabled_tasks: &'a [bool],
+    recorded_tasks: RecordedTasks,
+    skipped_barriers: SkippedBarriers,
     cmd_pipeline_barrier2: vk::PFN_vkCmdPipelineBarrier2,
     queue_submit2: vk::PFN_vkQueueSubmit2,
     per_submits: SmallVec<[PerSubmitInfo2; 4]>,
@@ -910,6 +1390,8 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
         current_fence: &'a Fence,
         submission_count: &'a mut usize,
         world: &'a W,
+        enabled_tasks: &'a [bool],
+        recorded_tasks: RecordedTasks,
     ) -> Result<Self> {
         let fns = executable.device().fns();
         let (cmd_pipeline_barrier2, queue_submit2);
@@ -927,9 +1409,13 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
             resource_map,
             deferreds,
             current_frame_index,
+            current_task_index: 0,
             current_fence,
             submission_count,
             world,
+            enabled_tasks,
+            recorded_tasks,
+            skipped_barriers: SkippedBarriers::new(executable, enabled_tasks),
             cmd_pipeline_barrier2,
             queue_submit2,
             per_submits: SmallVec::new(),
@@ -1102,6 +1588,36 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
         );
     }
 
+    fn reset_task_timestamps(&mut self) -> Result {
+        let Some(task_timestamps) = &self.executable.task_timestamps else {
+            return Ok(());
+        };
+
+        let submission = current_submission!(self);
+        let task_count = self.executable.instructions[submission.instruction_range.clone()]
+            .iter()
+            .filter(|instruction| matches!(instruction, Instruction::ExecuteTask { .. }))
+            .count() as u32;
+
+        if task_count != 0 {
+            let first_pair_index = task_timestamps.pair_indices[self.current_task_index as usize];
+            let last_pair_index =
+                task_timestamps.pair_indices[(self.current_task_index + task_count - 1) as usize];
+            let first_query =
+                task_timestamps.first_query(self.current_frame_index, first_pair_index);
+
+            unsafe {
+                current_command_buffer!(self).reset_query_pool_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query,
+                    2 * (last_pair_index - first_pair_index + 1),
+                )
+            };
+        }
+
+        Ok(())
+    }
+
     fn wait_semaphore(&mut self, semaphore_index: SemaphoreIndex, stage_mask: PipelineStages) {
         self.current_per_submit.wait_semaphore_infos_vk.push(
             vk::SemaphoreSubmitInfo::default()
@@ -1117,48 +1633,134 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
         }
 
         let task_node = unsafe { self.executable.graph.nodes.task_node_unchecked(node_index) };
-        let task = &task_node.task;
-        let mut current_command_buffer = unsafe {
-            RecordingCommandBuffer::new(
-                current_command_buffer!(self),
-                &mut self.command_buffer_state,
-                self.resource_map,
-                self.deferreds,
-            )
-        };
-        let mut context = TaskContext {
-            resource_map: self.resource_map,
-            current_frame_index: self.current_frame_index,
-            command_buffers: &mut self.command_buffers,
-        };
+        let recorded_task = self
+            .recorded_tasks
+            .get_mut(self.current_task_index as usize)
+            .and_then(Option::take)
+            .transpose()?;
+        let should_execute = self.enabled_tasks[self.current_task_index as usize];
+
+        if let Some(task_timestamps) = &self.executable.task_timestamps {
+            task_timestamps.executed.borrow_mut()[self.current_frame_index as usize]
+                .push(should_execute);
+        }
+
+        // Task nodes within a render pass instance share the timestamps of the render pass
+        // instance, which are written outside of it.
+        let task_timestamps = self
+            .executable
+            .task_timestamps
+            .as_ref()
+            .filter(|_| should_execute && task_node.subpass.is_none());
+        let first_query = task_timestamps.map(|task_timestamps| {
+            task_timestamps.task_first_query(self.current_frame_index, self.current_task_index)
+        });
+
+        if let Some(task_timestamps) = task_timestamps {
+            unsafe {
+                current_command_buffer!(self).write_timestamp_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query.unwrap(),
+                    PipelineStage::TopOfPipe,
+                )
+            };
+        }
 
-        unsafe { task.execute(&mut current_command_buffer, &mut context, self.world) }
-            .map_err(|err| err.map(|error| ExecuteError::Task { node_index, error }))?;
+        if let Some(recorded_task) = recorded_task {
+            let RecordedTask {
+                command_buffer,
+                command_buffers,
+            } = recorded_task;
 
-        if !self.command_buffers.is_empty() {
-            unsafe { self.flush_current_command_buffer() }?;
+            if let Some(command_buffer) = command_buffer {
+                if self.current_command_buffer.is_some() {
+                    unsafe { self.flush_current_command_buffer() }?;
+                }
 
-            for command_buffer in self.command_buffers.drain(..) {
                 self.current_per_submit.command_buffer_infos_vk.push(
                     vk::CommandBufferSubmitInfo::default().command_buffer(command_buffer.handle()),
                 );
                 self.deferreds.push(Deferred::destroy(command_buffer));
             }
-        }
-
-        Ok(())
-    }
+
+            for command_buffer in command_buffers {
+                self.current_per_submit.command_buffer_infos_vk.push(
+                    vk::CommandBufferSubmitInfo::default().command_buffer(command_buffer.handle()),
+                );
+                self.deferreds.push(Deferred::destroy(command_buffer));
+            }
+        } else if should_execute {
+            let task = &task_node.task;
+            let mut current_command_buffer = unsafe {
+                RecordingCommandBuffer::new(
+                    current_command_buffer!(self),
+                    &mut self.command_buffer_state,
+                    self.resource_map,
+                    &task_node.accesses,
+                    self.deferreds,
+                )
+            };
+            let mut task_deferreds = Vec::new();
+            let mut context = TaskContext {
+                resource_map: self.resource_map,
+                current_frame_index: self.current_frame_index,
+                command_buffers: &mut self.command_buffers,
+                deferreds: &mut task_deferreds,
+            };
+
+            unsafe { task.execute(&mut current_command_buffer, &mut context, self.world) }
+                .map_err(|err| err.map(|error| ExecuteError::Task { node_index, error }))?;
+
+            self.deferreds.append(&mut task_deferreds);
+
+            if !self.command_buffers.is_empty() {
+                unsafe { self.flush_current_command_buffer() }?;
+
+                for command_buffer in self.command_buffers.drain(..) {
+                    self.current_per_submit.command_buffer_infos_vk.push(
+                        vk::CommandBufferSubmitInfo::default()
+                            .command_buffer(command_buffer.handle()),
+                    );
+                    self.deferreds.push(Deferred::destroy(command_buffer));
+                }
+            }
+        }
+
+        if let Some(task_timestamps) = task_timestamps {
+            unsafe {
+                current_command_buffer!(self).write_timestamp_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query.unwrap() + 1,
+                    PipelineStage::BottomOfPipe,
+                )
+            };
+        }
+
+        self.current_task_index += 1;
+
+        Ok(())
+    }
 
     fn pipeline_barrier(&mut self, barrier_range: Range<BarrierIndex>) -> Result {
-        self.convert_barriers(barrier_range);
+        let barriers = self
+            .skipped_barriers
+            .resolve(&self.executable.barriers, barrier_range);
+        self.convert_barriers(&barriers);
+
+        if self.current_buffer_barriers_vk.is_empty() && self.current_image_barriers_vk.is_empty() {
+            return Ok(());
+        }
 
         self.flush_barriers()
     }
 
-    fn convert_barriers(&mut self, barrier_range: Range<BarrierIndex>) {
-        let barrier_range = barrier_range.start as usize..barrier_range.end as usize;
+    fn convert_skipped_barriers(&mut self) {
+        let barriers = self.skipped_barriers.take_pending();
+        self.convert_barriers(&barriers);
+    }
 
-        for barrier in &self.executable.barriers[barrier_range] {
+    fn convert_barriers(&mut self, barriers: &[super::MemoryBarrier]) {
+        for barrier in barriers {
             match barrier.resource.object_type() {
                 ObjectType::Buffer => {
                     let buffer_id = unsafe { barrier.resource.parametrize() };
@@ -1221,11 +1823,28 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
     }
 
     fn begin_render_pass(&mut self, render_pass_index: RenderPassIndex) -> Result {
+        // The render pass instance might depend on the skipped barriers through its subpass
+        // dependencies.
+        self.convert_skipped_barriers();
+
         if !self.current_buffer_barriers_vk.is_empty() || !self.current_image_barriers_vk.is_empty()
         {
             self.flush_barriers()?;
         }
 
+        if let Some(task_timestamps) = &self.executable.task_timestamps {
+            let first_query =
+                task_timestamps.task_first_query(self.current_frame_index, self.current_task_index);
+
+            unsafe {
+                current_command_buffer!(self).write_timestamp_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query,
+                    PipelineStage::TopOfPipe,
+                )
+            };
+        }
+
         let render_pass_state = &self.executable.render_passes.borrow()[render_pass_index];
         let framebuffer_index =
             unsafe { framebuffer_index(self.resource_map, &self.executable.swapchains) };
@@ -1313,6 +1932,22 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
         unsafe {
             (fns.v1_0.cmd_end_render_pass)(self.current_command_buffer.as_ref().unwrap().handle())
         };
+
+        if let Some(task_timestamps) = &self.executable.task_timestamps {
+            let first_query = task_timestamps
+                .task_first_query(self.current_frame_index, self.current_task_index - 1);
+
+            unsafe {
+                self.current_command_buffer
+                    .as_mut()
+                    .unwrap()
+                    .write_timestamp_unchecked(
+                        &task_timestamps.query_pool,
+                        first_query + 1,
+                        PipelineStage::BottomOfPipe,
+                    )
+            };
+        }
     }
 
     fn clear_attachments(
@@ -1418,6 +2053,15 @@ impl<'a, W: ?Sized + 'static> ExecuteState2<'a, W> {
     }
 
     fn flush_submit(&mut self) -> Result {
+        // Any barriers that were skipped and not yet merged with a later barrier must be executed
+        // before the semaphores are signaled.
+        self.convert_skipped_barriers();
+
+        if !self.current_buffer_barriers_vk.is_empty() || !self.current_image_barriers_vk.is_empty()
+        {
+            self.flush_barriers()?;
+        }
+
         unsafe { self.flush_current_command_buffer() }?;
 
         self.per_submits
@@ -1492,9 +2136,13 @@ struct ExecuteState<'a, W: ?Sized + 'static> {
     resource_map: &'a ResourceMap<'a>,
     deferreds: &'a mut Vec<Deferred>,
     current_frame_index: u32,
+    current_task_index: u32,
     current_fence: &'a Fence,
     submission_count: &'a mut usize,
     world: &'a W,
+    enabled_tasks: &'a [bool],
+    recorded_tasks: RecordedTasks,
+    skipped_barriers: SkippedBarriers,
     cmd_pipeline_barrier: vk::PFN_vkCmdPipelineBarrier,
     queue_submit: vk::PFN_vkQueueSubmit,
     per_submits: SmallVec<[PerSubmitInfo; 4]>,
@@ -1531,6 +2179,8 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
         current_fence: &'a Fence,
         submission_count: &'a mut usize,
         world: &'a W,
+        enabled_tasks: &'a [bool],
+        recorded_tasks: RecordedTasks,
     ) -> Result<Self> {
         let fns = executable.device().fns();
         let cmd_pipeline_barrier = fns.v1_0.cmd_pipeline_barrier;
@@ -1541,9 +2191,13 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
             resource_map,
             deferreds,
             current_frame_index,
+            current_task_index: 0,
             current_fence,
             submission_count,
             world,
+            enabled_tasks,
+            recorded_tasks,
+            skipped_barriers: SkippedBarriers::new(executable, enabled_tasks),
             cmd_pipeline_barrier,
             queue_submit,
             per_submits: SmallVec::new(),
@@ -1722,6 +2376,36 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
             .push(convert_stage_mask(stage_mask));
     }
 
+    fn reset_task_timestamps(&mut self) -> Result {
+        let Some(task_timestamps) = &self.executable.task_timestamps else {
+            return Ok(());
+        };
+
+        let submission = current_submission!(self);
+        let task_count = self.executable.instructions[submission.instruction_range.clone()]
+            .iter()
+            .filter(|instruction| matches!(instruction, Instruction::ExecuteTask { .. }))
+            .count() as u32;
+
+        if task_count != 0 {
+            let first_pair_index = task_timestamps.pair_indices[self.current_task_index as usize];
+            let last_pair_index =
+                task_timestamps.pair_indices[(self.current_task_index + task_count - 1) as usize];
+            let first_query =
+                task_timestamps.first_query(self.current_frame_index, first_pair_index);
+
+            unsafe {
+                current_command_buffer!(self).reset_query_pool_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query,
+                    2 * (last_pair_index - first_pair_index + 1),
+                )
+            };
+        }
+
+        Ok(())
+    }
+
     fn wait_semaphore(&mut self, semaphore_index: SemaphoreIndex, stage_mask: PipelineStages) {
         self.current_per_submit
             .wait_semaphores_vk
@@ -1738,48 +2422,133 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
         }
 
         let task_node = unsafe { self.executable.graph.nodes.task_node_unchecked(node_index) };
-        let task = &task_node.task;
-        let mut current_command_buffer = unsafe {
-            RecordingCommandBuffer::new(
-                current_command_buffer!(self),
-                &mut self.command_buffer_state,
-                self.resource_map,
-                self.deferreds,
-            )
-        };
-        let mut context = TaskContext {
-            resource_map: self.resource_map,
-            current_frame_index: self.current_frame_index,
-            command_buffers: &mut self.command_buffers,
-        };
+        let recorded_task = self
+            .recorded_tasks
+            .get_mut(self.current_task_index as usize)
+            .and_then(Option::take)
+            .transpose()?;
+        let should_execute = self.enabled_tasks[self.current_task_index as usize];
+
+        if let Some(task_timestamps) = &self.executable.task_timestamps {
+            task_timestamps.executed.borrow_mut()[self.current_frame_index as usize]
+                .push(should_execute);
+        }
+
+        // Task nodes within a render pass instance share the timestamps of the render pass
+        // instance, which are written outside of it.
+        let task_timestamps = self
+            .executable
+            .task_timestamps
+            .as_ref()
+            .filter(|_| should_execute && task_node.subpass.is_none());
+        let first_query = task_timestamps.map(|task_timestamps| {
+            task_timestamps.task_first_query(self.current_frame_index, self.current_task_index)
+        });
+
+        if let Some(task_timestamps) = task_timestamps {
+            unsafe {
+                current_command_buffer!(self).write_timestamp_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query.unwrap(),
+                    PipelineStage::TopOfPipe,
+                )
+            };
+        }
 
-        unsafe { task.execute(&mut current_command_buffer, &mut context, self.world) }
-            .map_err(|err| err.map(|error| ExecuteError::Task { node_index, error }))?;
+        if let Some(recorded_task) = recorded_task {
+            let RecordedTask {
+                command_buffer,
+                command_buffers,
+            } = recorded_task;
 
-        if !self.command_buffers.is_empty() {
-            unsafe { self.flush_current_command_buffer() }?;
+            if let Some(command_buffer) = command_buffer {
+                if self.current_command_buffer.is_some() {
+                    unsafe { self.flush_current_command_buffer() }?;
+                }
+
+                self.current_per_submit
+                    .command_buffers_vk
+                    .push(command_buffer.handle());
+                self.deferreds.push(Deferred::destroy(command_buffer));
+            }
 
-            for command_buffer in self.command_buffers.drain(..) {
+            for command_buffer in command_buffers {
                 self.current_per_submit
                     .command_buffers_vk
                     .push(command_buffer.handle());
                 self.deferreds.push(Deferred::destroy(command_buffer));
             }
+        } else if should_execute {
+            let task = &task_node.task;
+            let mut current_command_buffer = unsafe {
+                RecordingCommandBuffer::new(
+                    current_command_buffer!(self),
+                    &mut self.command_buffer_state,
+                    self.resource_map,
+                    &task_node.accesses,
+                    self.deferreds,
+                )
+            };
+            let mut task_deferreds = Vec::new();
+            let mut context = TaskContext {
+                resource_map: self.resource_map,
+                current_frame_index: self.current_frame_index,
+                command_buffers: &mut self.command_buffers,
+                deferreds: &mut task_deferreds,
+            };
+
+            unsafe { task.execute(&mut current_command_buffer, &mut context, self.world) }
+                .map_err(|err| err.map(|error| ExecuteError::Task { node_index, error }))?;
+
+            self.deferreds.append(&mut task_deferreds);
+
+            if !self.command_buffers.is_empty() {
+                unsafe { self.flush_current_command_buffer() }?;
+
+                for command_buffer in self.command_buffers.drain(..) {
+                    self.current_per_submit
+                        .command_buffers_vk
+                        .push(command_buffer.handle());
+                    self.deferreds.push(Deferred::destroy(command_buffer));
+                }
+            }
+        }
+
+        if let Some(task_timestamps) = task_timestamps {
+            unsafe {
+                current_command_buffer!(self).write_timestamp_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query.unwrap() + 1,
+                    PipelineStage::BottomOfPipe,
+                )
+            };
         }
 
+        self.current_task_index += 1;
+
         Ok(())
     }
 
     fn pipeline_barrier(&mut self, barrier_range: Range<BarrierIndex>) -> Result {
-        self.convert_barriers(barrier_range);
+        let barriers = self
+            .skipped_barriers
+            .resolve(&self.executable.barriers, barrier_range);
+        self.convert_barriers(&barriers);
+
+        if self.current_buffer_barriers_vk.is_empty() && self.current_image_barriers_vk.is_empty() {
+            return Ok(());
+        }
 
         self.flush_barriers()
     }
 
-    fn convert_barriers(&mut self, barrier_range: Range<BarrierIndex>) {
-        let barrier_range = barrier_range.start as usize..barrier_range.end as usize;
+    fn convert_skipped_barriers(&mut self) {
+        let barriers = self.skipped_barriers.take_pending();
+        self.convert_barriers(&barriers);
+    }
 
-        for barrier in &self.executable.barriers[barrier_range] {
+    fn convert_barriers(&mut self, barriers: &[super::MemoryBarrier]) {
+        for barrier in barriers {
             match barrier.resource.object_type() {
                 ObjectType::Buffer => {
                     let buffer_id = unsafe { barrier.resource.parametrize() };
@@ -1845,11 +2614,28 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
     }
 
     fn begin_render_pass(&mut self, render_pass_index: RenderPassIndex) -> Result {
+        // The render pass instance might depend on the skipped barriers through its subpass
+        // dependencies.
+        self.convert_skipped_barriers();
+
         if !self.current_buffer_barriers_vk.is_empty() || !self.current_image_barriers_vk.is_empty()
         {
             self.flush_barriers()?;
         }
 
+        if let Some(task_timestamps) = &self.executable.task_timestamps {
+            let first_query =
+                task_timestamps.task_first_query(self.current_frame_index, self.current_task_index);
+
+            unsafe {
+                current_command_buffer!(self).write_timestamp_unchecked(
+                    &task_timestamps.query_pool,
+                    first_query,
+                    PipelineStage::TopOfPipe,
+                )
+            };
+        }
+
         let render_pass_state = &self.executable.render_passes.borrow()[render_pass_index];
         let framebuffer_index =
             unsafe { framebuffer_index(self.resource_map, &self.executable.swapchains) };
@@ -1937,6 +2723,22 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
         unsafe {
             (fns.v1_0.cmd_end_render_pass)(self.current_command_buffer.as_ref().unwrap().handle())
         };
+
+        if let Some(task_timestamps) = &self.executable.task_timestamps {
+            let first_query = task_timestamps
+                .task_first_query(self.current_frame_index, self.current_task_index - 1);
+
+            unsafe {
+                self.current_command_buffer
+                    .as_mut()
+                    .unwrap()
+                    .write_timestamp_unchecked(
+                        &task_timestamps.query_pool,
+                        first_query + 1,
+                        PipelineStage::BottomOfPipe,
+                    )
+            };
+        }
     }
 
     fn clear_attachments(
@@ -2021,6 +2823,15 @@ impl<'a, W: ?Sized + 'static> ExecuteState<'a, W> {
     }
 
     fn flush_submit(&mut self) -> Result {
+        // Any barriers that were skipped and not yet merged with a later barrier must be executed
+        // before the semaphores are signaled.
+        self.convert_skipped_barriers();
+
+        if !self.current_buffer_barriers_vk.is_empty() || !self.current_image_barriers_vk.is_empty()
+        {
+            self.flush_barriers()?;
+        }
+
         unsafe { self.flush_current_command_buffer() }?;
 
         self.per_submits
@@ -2478,10 +3289,20 @@ impl<W: ?Sized + 'static> Drop for StateGuard<'_, W> {
 }
 
 /// Maps [virtual resources] to physical resources.
+///
+/// A `ResourceMap` doesn't need to be recreated for every execution: you can keep it around and
+/// execute the same [`ExecutableTaskGraph`] with it repeatedly by passing it by mutable reference
+/// to [`execute`]. In between executions, you can remap individual virtual resources, for
+/// example after recreating a render target with a new extent or after streaming in a texture.
+/// Only the changed mapping is validated against the virtual resource; the rest of the map stays
+/// untouched.
+///
+/// [`execute`]: ExecutableTaskGraph::execute
 pub struct ResourceMap<'a> {
     virtual_resources: &'a super::Resources,
     physical_resources: Arc<Resources>,
     map: Vec<*const ()>,
+    physical_ids: Vec<Id>,
     len: u32,
     guard: hyaline::Guard<'a>,
 }
@@ -2491,44 +3312,110 @@ impl<'a> ResourceMap<'a> {
     pub fn new(executable: &'a ExecutableTaskGraph<impl ?Sized>) -> Result<Self, InvalidSlotError> {
         let virtual_resources = &executable.graph.resources;
         let physical_resources = virtual_resources.physical_resources.clone();
-        let mut map = vec![ptr::null(); virtual_resources.reserved_len() as usize];
+        let reserved_len = virtual_resources.reserved_len() as usize;
         let guard = virtual_resources.physical_resources.pin();
 
+        let mut map = ResourceMap {
+            virtual_resources,
+            physical_resources,
+            map: vec![ptr::null(); reserved_len],
+            physical_ids: vec![Id::INVALID; reserved_len],
+            len: 0,
+            guard,
+        };
+
         for (&physical_id, &virtual_id) in &virtual_resources.physical_map {
+            let ptr = resolve(&map.physical_resources, &map.guard, physical_id)?;
+
             // SAFETY: Virtual IDs inside the `physical_map` are always valid.
-            let slot = unsafe { map.get_unchecked_mut(virtual_id.index() as usize) };
+            unsafe { map.insert_inner(virtual_id, physical_id, ptr) };
+        }
 
-            *slot = match physical_id.object_type() {
-                ObjectType::Buffer => {
-                    let physical_id = unsafe { physical_id.parametrize() };
+        map.insert_history_images(executable)?;
 
-                    <*const _>::cast(physical_resources.try_buffer_protected(physical_id, &guard)?)
-                }
-                ObjectType::Image => {
-                    let physical_id = unsafe { physical_id.parametrize() };
+        Ok(map)
+    }
 
-                    <*const _>::cast(physical_resources.try_image_protected(physical_id, &guard)?)
-                }
-                ObjectType::Swapchain => {
-                    let physical_id = unsafe { physical_id.parametrize() };
+    /// Maps the history images of `executable` to the physical images of the next execution.
+    fn insert_history_images(
+        &mut self,
+        executable: &ExecutableTaskGraph<impl ?Sized>,
+    ) -> Result<(), InvalidSlotError> {
+        let execution_index = executable.execution_index.get();
+
+        for history_images in &executable.history_images {
+            for (n, &virtual_id) in history_images.ids.iter().enumerate() {
+                let virtual_id = virtual_id.erase();
+                let physical_id = history_images
+                    .physical_id(execution_index, n as u32)
+                    .erase();
+                let ptr = resolve(&self.physical_resources, &self.guard, physical_id)?;
+
+                // SAFETY: The history images are always valid virtual IDs.
+                unsafe { self.insert_inner(virtual_id, physical_id, ptr) };
+            }
+        }
 
-                    <*const _>::cast(
-                        physical_resources.try_swapchain_protected(physical_id, &guard)?,
-                    )
+        Ok(())
+    }
+
+    /// Prepares the map for the next execution of `executable`.
+    ///
+    /// This re-pins the map, such that keeping the map around between executions doesn't prevent
+    /// the reclamation of physical resources that were removed in the meantime, and maps the
+    /// history images to the physical images of the next execution.
+    pub(crate) fn refresh(
+        &mut self,
+        executable: &ExecutableTaskGraph<impl ?Sized>,
+    ) -> Result<(), InvalidSlotError> {
+        self.guard = self.virtual_resources.physical_resources.pin();
+
+        let mut res = Ok(());
+
+        for (slot, physical_id) in self.map.iter_mut().zip(&mut self.physical_ids) {
+            if *physical_id == Id::INVALID {
+                continue;
+            }
+
+            match resolve(&self.physical_resources, &self.guard, *physical_id) {
+                Ok(ptr) => *slot = ptr,
+                Err(err) => {
+                    // Remove the mapping such that we don't keep a dangling pointer around.
+                    *slot = ptr::null();
+                    *physical_id = Id::INVALID;
+                    self.len -= 1;
+                    res = Err(err);
                 }
-                _ => unreachable!(),
-            };
+            }
         }
 
-        let len = virtual_resources.physical_map.len() as u32;
+        res?;
 
-        Ok(ResourceMap {
-            virtual_resources,
-            physical_resources,
-            map,
-            len,
-            guard,
-        })
+        self.insert_history_images(executable)
+    }
+
+    /// # Safety
+    ///
+    /// - `virtual_id` must be a valid virtual resource ID.
+    /// - `ptr` must be the state of the physical resource corresponding to `physical_id`.
+    unsafe fn insert_inner(&mut self, virtual_id: Id, physical_id: Id, ptr: *const ()) {
+        let index = virtual_id.index() as usize;
+
+        // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID, and since we
+        // initialized `self.map` with a length at least that of `self.virtual_resources`, the
+        // index must be in bounds.
+        let slot = unsafe { self.map.get_unchecked_mut(index) };
+
+        if slot.is_null() {
+            self.len += 1;
+        }
+
+        *slot = ptr;
+
+        // SAFETY: `self.physical_ids` has the same length as `self.map`.
+        let physical_id_slot = unsafe { self.physical_ids.get_unchecked_mut(index) };
+
+        *physical_id_slot = physical_id;
     }
 
     #[doc(hidden)]
@@ -2542,11 +3429,15 @@ impl<'a> ResourceMap<'a> {
     }
 
     /// Inserts a mapping from the [virtual buffer resource] corresponding to `virtual_id` to the
-    /// physical resource corresponding to `physical_id`.
+    /// physical resource corresponding to `physical_id`. If `virtual_id` is already mapped, the
+    /// mapping is replaced.
     ///
     /// # Panics
     ///
     /// - Panics if the physical resource doesn't match the virtual resource.
+    /// - Panics if the physical resource doesn't have all the usage flags of the virtual
+    ///   resource.
+    /// - Panics if the physical resource is smaller than the size of the virtual resource.
     /// - Panics if the physical resource already has a mapping from another virtual resource.
     #[inline]
     pub fn insert_buffer(
@@ -2554,40 +3445,28 @@ impl<'a> ResourceMap<'a> {
         virtual_id: Id<Buffer>,
         physical_id: Id<Buffer>,
     ) -> Result<(), InvalidSlotError> {
-        self.virtual_resources.get(virtual_id.erase())?;
+        let resource_info = self.virtual_resources.get(virtual_id.erase())?;
 
         let state = self
             .physical_resources
             .try_buffer_protected(physical_id, &self.guard)?;
+        let buffer = state.buffer();
 
-        assert_eq!(
-            state.buffer().sharing().is_exclusive(),
-            virtual_id.is_exclusive(),
-        );
+        assert_eq!(buffer.sharing().is_exclusive(), virtual_id.is_exclusive());
+        assert!(buffer.usage().contains(resource_info.buffer_usage));
+        assert!(buffer.size() >= resource_info.size);
 
         let ptr = <*const _>::cast(state);
-        let is_duplicate = self.map.contains(&ptr);
-
-        // SAFETY: We checked that `virtual_id` is present in `self.virtual_resources` above, and
-        // since we initialized `self.map` with a length at least that of `self.virtual_resources`,
-        // the index must be in bounds.
-        let slot = unsafe { self.map.get_unchecked_mut(virtual_id.index() as usize) };
-
-        if *slot != ptr {
-            assert!(!is_duplicate);
-        }
 
-        if slot.is_null() {
-            self.len += 1;
-        }
-
-        *slot = ptr;
+        // SAFETY: We checked that `virtual_id` is present in `self.virtual_resources` above.
+        unsafe { self.insert_checked(virtual_id.erase(), physical_id.erase(), ptr) };
 
         Ok(())
     }
 
     /// Inserts a mapping from the [virtual buffer resource] corresponding to `virtual_id` to the
-    /// physical resource corresponding to `physical_id` without doing any checks.
+    /// physical resource corresponding to `physical_id` without doing any checks. If
+    /// `virtual_id` is already mapped, the mapping is replaced.
     ///
     /// # Safety
     ///
@@ -2607,24 +3486,32 @@ impl<'a> ResourceMap<'a> {
                 .buffer_unchecked_protected(physical_id, &self.guard)
         };
 
-        // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID, and since we
-        // initialized `self.map` with a length at least that of `self.virtual_resources`, the
-        // index must be in bounds.
-        let slot = unsafe { self.map.get_unchecked_mut(virtual_id.index() as usize) };
-
-        if slot.is_null() {
-            self.len += 1;
-        }
-
-        *slot = <*const _>::cast(state);
+        // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID.
+        unsafe {
+            self.insert_inner(
+                virtual_id.erase(),
+                physical_id.erase(),
+                <*const _>::cast(state),
+            )
+        };
     }
 
     /// Inserts a mapping from the [virtual image resource] corresponding to `virtual_id` to the
-    /// physical resource corresponding to `physical_id`.
+    /// physical resource corresponding to `physical_id`. If `virtual_id` is already mapped, the
+    /// mapping is replaced.
+    ///
+    /// The physical image may have a different extent than the one the virtual image was created
+    /// with, which allows you to remap an image after resizing it without recompiling the task
+    /// graph. However, if the virtual image was created with a format, the physical image must
+    /// have the same format and sample count, as those are baked into the compiled render passes.
     ///
     /// # Panics
     ///
     /// - Panics if the physical resource doesn't match the virtual resource.
+    /// - Panics if the virtual resource was created with a format, and the physical resource
+    ///   doesn't have the same format and sample count.
+    /// - Panics if the physical resource doesn't have all the usage flags of the virtual
+    ///   resource.
     /// - Panics if the physical resource already has a mapping from another virtual resource.
     /// - Panics if `virtual_id` refers to a swapchain image.
     #[inline]
@@ -2635,40 +3522,33 @@ impl<'a> ResourceMap<'a> {
     ) -> Result<(), InvalidSlotError> {
         assert_ne!(virtual_id.object_type(), ObjectType::Swapchain);
 
-        self.virtual_resources.get(virtual_id.erase())?;
+        let resource_info = self.virtual_resources.get(virtual_id.erase())?;
 
         let state = self
             .physical_resources
             .try_image_protected(physical_id, &self.guard)?;
+        let image = state.image();
 
-        assert_eq!(
-            state.image().sharing().is_exclusive(),
-            virtual_id.is_exclusive(),
-        );
-
-        let ptr = <*const _>::cast(state);
-        let is_duplicate = self.map.contains(&ptr);
+        assert_eq!(image.sharing().is_exclusive(), virtual_id.is_exclusive());
 
-        // SAFETY: We checked that `virtual_id` is present in `self.virtual_resources` above, and
-        // since we initialized `self.map` with a length at least that of `self.virtual_resources`,
-        // the index must be in bounds.
-        let slot = unsafe { self.map.get_unchecked_mut(virtual_id.index() as usize) };
-
-        if *slot != ptr {
-            assert!(!is_duplicate);
+        if resource_info.format != Format::UNDEFINED {
+            assert_eq!(image.format(), resource_info.format);
+            assert_eq!(image.samples(), resource_info.samples);
         }
 
-        if slot.is_null() {
-            self.len += 1;
-        }
+        assert!(image.usage().contains(resource_info.usage));
 
-        *slot = ptr;
+        let ptr = <*const _>::cast(state);
+
+        // SAFETY: We checked that `virtual_id` is present in `self.virtual_resources` above.
+        unsafe { self.insert_checked(virtual_id.erase(), physical_id.erase(), ptr) };
 
         Ok(())
     }
 
     /// Inserts a mapping from the [virtual image resource] corresponding to `virtual_id` to the
-    /// physical resource corresponding to `physical_id` without doing any checks.
+    /// physical resource corresponding to `physical_id` without doing any checks. If
+    /// `virtual_id` is already mapped, the mapping is replaced.
     ///
     /// # Safety
     ///
@@ -2684,24 +3564,27 @@ impl<'a> ResourceMap<'a> {
                 .image_unchecked_protected(physical_id, &self.guard)
         };
 
-        // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID, and since we
-        // initialized `self.map` with a length at least that of `self.virtual_resources`, the
-        // index must be in bounds.
-        let slot = unsafe { self.map.get_unchecked_mut(virtual_id.index() as usize) };
-
-        if slot.is_null() {
-            self.len += 1;
-        }
-
-        *slot = <*const _>::cast(state);
+        // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID.
+        unsafe {
+            self.insert_inner(
+                virtual_id.erase(),
+                physical_id.erase(),
+                <*const _>::cast(state),
+            )
+        };
     }
 
     /// Inserts a mapping from the [virtual swapchain resource] corresponding to `virtual_id` to
-    /// the physical resource corresponding to `physical_id`.
+    /// the physical resource corresponding to `physical_id`. If `virtual_id` is already mapped,
+    /// the mapping is replaced.
     ///
     /// # Panics
     ///
     /// - Panics if the physical resource doesn't match the virtual resource.
+    /// - Panics if the virtual resource was created with an image format, and the physical
+    ///   resource doesn't have the same image format.
+    /// - Panics if the physical resource doesn't have all the image usage flags of the virtual
+    ///   resource.
     /// - Panics if the physical resource already has a mapping from another virtual resource.
     #[inline]
     pub fn insert_swapchain(
@@ -2709,40 +3592,35 @@ impl<'a> ResourceMap<'a> {
         virtual_id: Id<Swapchain>,
         physical_id: Id<Swapchain>,
     ) -> Result<(), InvalidSlotError> {
-        self.virtual_resources.get(virtual_id.erase())?;
+        let resource_info = self.virtual_resources.get(virtual_id.erase())?;
 
         let state = self
             .physical_resources
             .try_swapchain_protected(physical_id, &self.guard)?;
+        let swapchain = state.swapchain();
 
         assert_eq!(
-            state.swapchain().image_sharing().is_exclusive(),
+            swapchain.image_sharing().is_exclusive(),
             virtual_id.is_exclusive(),
         );
 
-        let ptr = <*const _>::cast(state);
-        let is_duplicate = self.map.contains(&ptr);
-
-        // SAFETY: We checked that `virtual_id` is present in `self.virtual_resources` above, and
-        // since we initialized `self.map` with a length at least that of `self.virtual_resources`,
-        // the index must be in bounds.
-        let slot = unsafe { self.map.get_unchecked_mut(virtual_id.index() as usize) };
-
-        if *slot != ptr {
-            assert!(!is_duplicate);
+        if resource_info.format != Format::UNDEFINED {
+            assert_eq!(swapchain.image_format(), resource_info.format);
         }
 
-        if slot.is_null() {
-            self.len += 1;
-        }
+        assert!(swapchain.image_usage().contains(resource_info.usage));
 
-        *slot = ptr;
+        let ptr = <*const _>::cast(state);
+
+        // SAFETY: We checked that `virtual_id` is present in `self.virtual_resources` above.
+        unsafe { self.insert_checked(virtual_id.erase(), physical_id.erase(), ptr) };
 
         Ok(())
     }
 
     /// Inserts a mapping from the [virtual swapchain resource] corresponding to `virtual_id` to
-    /// the physical resource corresponding to `physical_id` without doing any checks.
+    /// the physical resource corresponding to `physical_id` without doing any checks. If
+    /// `virtual_id` is already mapped, the mapping is replaced.
     ///
     /// # Safety
     ///
@@ -2762,16 +3640,50 @@ impl<'a> ResourceMap<'a> {
                 .swapchain_unchecked_protected(physical_id, &self.guard)
         };
 
+        // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID.
+        unsafe {
+            self.insert_inner(
+                virtual_id.erase(),
+                physical_id.erase(),
+                <*const _>::cast(state),
+            )
+        };
+    }
+
+    /// # Safety
+    ///
+    /// - `virtual_id` must be a valid virtual resource ID.
+    /// - `ptr` must be the state of the physical resource corresponding to `physical_id`.
+    unsafe fn insert_checked(&mut self, virtual_id: Id, physical_id: Id, ptr: *const ()) {
+        let is_duplicate = self.map.contains(&ptr);
+
         // SAFETY: The caller must ensure that `virtual_id` is a valid virtual ID, and since we
         // initialized `self.map` with a length at least that of `self.virtual_resources`, the
         // index must be in bounds.
-        let slot = unsafe { self.map.get_unchecked_mut(virtual_id.index() as usize) };
+        let &slot = unsafe { self.map.get_unchecked(virtual_id.index() as usize) };
 
-        if slot.is_null() {
-            self.len += 1;
+        if slot != ptr {
+            assert!(!is_duplicate);
         }
 
-        *slot = <*const _>::cast(state);
+        // SAFETY: Enforced by the caller.
+        unsafe { self.insert_inner(virtual_id, physical_id, ptr) };
+    }
+
+    /// Creates a copy of the `ResourceMap` for use on another thread.
+    ///
+    /// # Safety
+    ///
+    /// - `self` must outlive the returned `ResourceMap`.
+    pub(crate) unsafe fn fork(&self) -> ResourceMap<'a> {
+        ResourceMap {
+            virtual_resources: self.virtual_resources,
+            physical_resources: self.physical_resources.clone(),
+            map: self.map.clone(),
+            physical_ids: self.physical_ids.clone(),
+            len: self.len,
+            guard: self.virtual_resources.physical_resources.pin(),
+        }
     }
 
     pub(crate) fn virtual_resources(&self) -> &super::Resources {
@@ -2915,6 +3827,31 @@ impl Resource for Swapchain {
     }
 }
 
+fn resolve(
+    physical_resources: &Resources,
+    guard: &hyaline::Guard<'_>,
+    physical_id: Id,
+) -> Result<*const (), InvalidSlotError> {
+    Ok(match physical_id.object_type() {
+        ObjectType::Buffer => {
+            let physical_id = unsafe { physical_id.parametrize() };
+
+            <*const _>::cast(physical_resources.try_buffer_protected(physical_id, guard)?)
+        }
+        ObjectType::Image => {
+            let physical_id = unsafe { physical_id.parametrize() };
+
+            <*const _>::cast(physical_resources.try_image_protected(physical_id, guard)?)
+        }
+        ObjectType::Swapchain => {
+            let physical_id = unsafe { physical_id.parametrize() };
+
+            <*const _>::cast(physical_resources.try_swapchain_protected(physical_id, guard)?)
+        }
+        _ => unreachable!(),
+    })
+}
+
 /// Creates a [`ResourceMap`] containing the given mappings.
 #[macro_export]
 macro_rules! resource_map {
@@ -2948,6 +3885,7 @@ pub enum ExecuteError {
         error: VulkanError,
     },
     VulkanError(VulkanError),
+    ResourceRemoved(InvalidSlotError),
 }
 
 impl From<ExecuteError> for Validated<ExecuteError> {
@@ -2967,6 +3905,9 @@ impl fmt::Display for ExecuteError {
                 "an error occurred while using swapchain {swapchain_id:?}",
             ),
             Self::VulkanError(_) => f.write_str("a runtime error occurred"),
+            Self::ResourceRemoved(_) => {
+                f.write_str("the resource map maps to a physical resource that has been removed")
+            }
         }
     }
 }
@@ -2977,6 +3918,595 @@ impl Error for ExecuteError {
             Self::Task { error, .. } => Some(error),
             Self::Swapchain { error, .. } => Some(error),
             Self::VulkanError(err) => Some(err),
+            Self::ResourceRemoved(err) => Some(err),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command_buffer::{BufferCopy, CopyBufferInfo, FillBufferInfo},
+        graph::{AttachmentInfo, CompileInfo, TaskGraph, TaskNodeBuilder},
+        resource::{AccessTypes, HostAccessType, ImageLayoutType},
+        tests::test_queues,
+        QueueFamilyType, Task, TaskResult,
+    };
+    use std::{
+        marker::PhantomData,
+        panic::{self, AssertUnwindSafe},
+        thread::{self, ThreadId},
+    };
+    use vulkano::{
+        buffer::{BufferCreateInfo, BufferUsage},
+        device::QueueFlags,
+        image::ImageCreateInfo,
+        memory::allocator::{AllocationCreateInfo, DeviceLayout, MemoryTypeFilter},
+        DeviceSize,
+    };
+
+    const LEN: DeviceSize = 64;
+
+    struct ScopedThreads;
+
+    impl ThreadPool for ScopedThreads {
+        fn for_each(&self, len: usize, f: &(dyn Fn(usize) + Sync)) {
+            thread::scope(|scope| {
+                for index in 0..len {
+                    scope.spawn(move || f(index));
+                }
+            });
+        }
+    }
+
+    type Log = Arc<Mutex<Vec<(&'static str, ThreadId)>>>;
+
+    struct LoggingTask<F> {
+        name: &'static str,
+        log: Log,
+        is_enabled: bool,
+        f: F,
+    }
+
+    impl<F> Task for LoggingTask<F>
+    where
+        F: Fn(&mut RecordingCommandBuffer<'_>) + Send + Sync + 'static,
+    {
+        type World = ();
+
+        unsafe fn execute(
+            &self,
+            cbf: &mut RecordingCommandBuffer<'_>,
+            _tcx: &mut TaskContext<'_>,
+            _world: &Self::World,
+        ) -> TaskResult {
+            self.log.lock().push((self.name, thread::current().id()));
+            (self.f)(cbf);
+
+            Ok(())
+        }
+
+        fn is_enabled(&self, _world: &Self::World) -> bool {
+            self.is_enabled
+        }
+    }
+
+    fn add_task<'a>(
+        graph: &'a mut TaskGraph<()>,
+        log: &Log,
+        name: &'static str,
+        accesses: &[(Id<Buffer>, AccessTypes)],
+        is_enabled: bool,
+        f: impl Fn(&mut RecordingCommandBuffer<'_>) + Send + Sync + 'static,
+    ) -> TaskNodeBuilder<'a> {
+        let mut node = graph.create_task_node(
+            name,
+            QueueFamilyType::Graphics,
+            LoggingTask {
+                name,
+                log: log.clone(),
+                is_enabled,
+                f,
+            },
+        );
+
+        for &(id, access_types) in accesses {
+            node.buffer_access(id, access_types);
+        }
+
+        node
+    }
+
+    fn fill(dst_buffer: Id<Buffer>, data: u32) -> impl Fn(&mut RecordingCommandBuffer<'_>) {
+        move |cbf| {
+            unsafe {
+                cbf.fill_buffer(&FillBufferInfo {
+                    dst_buffer,
+                    data,
+                    ..Default::default()
+                })
+            };
+        }
+    }
+
+    fn copy(
+        src_buffer: Id<Buffer>,
+        dst_buffer: Id<Buffer>,
+        dst_offset: DeviceSize,
+    ) -> impl Fn(&mut RecordingCommandBuffer<'_>) {
+        move |cbf| {
+            unsafe {
+                cbf.copy_buffer(&CopyBufferInfo {
+                    src_buffer,
+                    dst_buffer,
+                    regions: &[BufferCopy {
+                        dst_offset,
+                        size: LEN * 4,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+            };
+        }
+    }
+
+    // Builds a task graph with three buffers of `2 * LEN` `u32`s using `build` and executes it
+    // once. Returns the contents of the last buffer, along with the names of the tasks that were
+    // executed and whether each was recorded on a thread other than the current one.
+    fn execute_transfers(
+        resources: &Arc<Resources>,
+        queues: &[Arc<Queue>],
+        thread_pool: Option<&dyn ThreadPool>,
+        build: impl FnOnce(&mut TaskGraph<()>, &Log, [Id<Buffer>; 3]),
+    ) -> (Vec<u32>, Vec<(&'static str, bool)>) {
+        let queue = queues
+            .iter()
+            .find(|queue| {
+                queue.device().physical_device().queue_family_properties()
+                    [queue.queue_family_index() as usize]
+                    .queue_flags
+                    .contains(QueueFlags::GRAPHICS)
+            })
+            .unwrap();
+        let flight_id = resources.create_flight(1).unwrap();
+        let physical_buffers = [(); 3].map(|_| {
+            resources
+                .create_buffer(
+                    &BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    &AllocationCreateInfo {
+                        memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                            | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                        ..Default::default()
+                    },
+                    DeviceLayout::new_unsized::<[u32]>(LEN * 2).unwrap(),
+                )
+                .unwrap()
+        });
+        let log = Log::default();
+
+        let mut graph = TaskGraph::new(resources);
+        let virtual_buffers = [(); 3].map(|_| {
+            graph.add_buffer(&BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            })
+        });
+        build(&mut graph, &log, virtual_buffers);
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                flight_id,
+                infer_edges: true,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        let mut resource_map = ResourceMap::new(&graph).unwrap();
+
+        for (virtual_id, physical_id) in virtual_buffers.into_iter().zip(physical_buffers) {
+            resource_map.insert_buffer(virtual_id, physical_id).unwrap();
+        }
+
+        match thread_pool {
+            Some(thread_pool) => unsafe {
+                graph.execute_parallel(&mut resource_map, &(), || {}, thread_pool)
+            },
+            None => unsafe { graph.execute(&mut resource_map, &(), || {}) },
+        }
+        .unwrap();
+
+        resources.flight(flight_id).wait_idle().unwrap();
+
+        let mut contents = Vec::new();
+
+        unsafe {
+            crate::execute(
+                queue,
+                resources,
+                flight_id,
+                |_, tcx| {
+                    contents = tcx.read_buffer::<[u32]>(physical_buffers[2], ..).to_vec();
+
+                    Ok(())
+                },
+                [(physical_buffers[2], HostAccessType::Read)],
+                [],
+                [],
+            )
+        }
+        .unwrap();
+
+        resources.flight(flight_id).wait_idle().unwrap();
+
+        let current_thread = thread::current().id();
+        let mut log = log
+            .lock()
+            .iter()
+            .map(|&(name, thread_id)| (name, thread_id != current_thread))
+            .collect::<Vec<_>>();
+        log.sort_unstable();
+
+        (contents, log)
+    }
+
+    // A chain of transfer operations in which every task node depends on the previous one, such
+    // that the result is only correct if the command buffers are submitted in the right order with
+    // the right barriers in between.
+    fn fill_and_copy(
+        record_in_parallel: bool,
+    ) -> impl FnOnce(&mut TaskGraph<()>, &Log, [Id<Buffer>; 3]) {
+        move |graph, log, [a, b, c]| {
+            let mut node = add_task(
+                graph,
+                log,
+                "fill a",
+                &[(a, AccessTypes::CLEAR_TRANSFER_WRITE)],
+                true,
+                fill(a, 1),
+            );
+
+            if record_in_parallel {
+                node.record_in_parallel();
+            }
+
+            add_task(
+                graph,
+                log,
+                "copy a to b",
+                &[
+                    (a, AccessTypes::COPY_TRANSFER_READ),
+                    (b, AccessTypes::COPY_TRANSFER_WRITE),
+                ],
+                true,
+                copy(a, b, 0),
+            );
+
+            let mut node = add_task(
+                graph,
+                log,
+                "fill a again",
+                &[(a, AccessTypes::CLEAR_TRANSFER_WRITE)],
+                true,
+                fill(a, 2),
+            );
+
+            if record_in_parallel {
+                node.record_in_parallel();
+            }
+
+            let mut node = add_task(
+                graph,
+                log,
+                "copy a to c",
+                &[
+                    (a, AccessTypes::COPY_TRANSFER_READ),
+                    (c, AccessTypes::COPY_TRANSFER_WRITE),
+                ],
+                true,
+                copy(a, c, 0),
+            );
+
+            if record_in_parallel {
+                node.record_in_parallel();
+            }
+
+            add_task(
+                graph,
+                log,
+                "copy b to c",
+                &[
+                    (b, AccessTypes::COPY_TRANSFER_READ),
+                    (c, AccessTypes::COPY_TRANSFER_WRITE),
+                ],
+                true,
+                copy(b, c, LEN * 4),
+            );
+        }
+    }
+
+    #[test]
+    fn execute_parallel_matches_execute() {
+        let (resources, queues) = test_queues!();
+
+        let (contents, log) = execute_transfers(&resources, &queues, None, fill_and_copy(false));
+        let (parallel_contents, parallel_log) = execute_transfers(
+            &resources,
+            &queues,
+            Some(&ScopedThreads),
+            fill_and_copy(true),
+        );
+
+        let mut expected = vec![2; LEN as usize];
+        expected.resize(LEN as usize * 2, 1);
+
+        assert_eq!(contents, expected);
+        assert_eq!(parallel_contents, contents);
+        assert_eq!(
+            log,
+            [
+                ("copy a to b", false),
+                ("copy a to c", false),
+                ("copy b to c", false),
+                ("fill a", false),
+                ("fill a again", false),
+            ],
+        );
+        assert_eq!(
+            parallel_log,
+            [
+                ("copy a to b", false),
+                ("copy a to c", true),
+                ("copy b to c", false),
+                ("fill a", true),
+                ("fill a again", true),
+            ],
+        );
+    }
+
+    #[test]
+    fn record_in_parallel_render_pass() {
+        let (resources, queues) = test_queues!();
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let image = graph.add_image(&ImageCreateInfo {
+            format: Format::R8G8B8A8_UNORM,
+            ..Default::default()
+        });
+        let framebuffer = graph.add_framebuffer();
+        graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .framebuffer(framebuffer)
+            .color_attachment(
+                image,
+                AccessTypes::COLOR_ATTACHMENT_WRITE,
+                ImageLayoutType::Optimal,
+                &AttachmentInfo::default(),
+            )
+            .record_in_parallel()
+            .build();
+
+        let res = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }));
+
+        assert!(res.is_err());
+    }
+
+    // Overwrites the first buffer in a conditional task node and copies it to the last buffer
+    // afterwards. When the task node is disabled, its barrier is merged with the barrier before
+    // the copy.
+    fn conditional_fill(
+        is_enabled: bool,
+        record_in_parallel: bool,
+    ) -> impl FnOnce(&mut TaskGraph<()>, &Log, [Id<Buffer>; 3]) {
+        move |graph, log, [a, _, c]| {
+            add_task(
+                graph,
+                log,
+                "fill a",
+                &[(a, AccessTypes::CLEAR_TRANSFER_WRITE)],
+                true,
+                fill(a, 1),
+            );
+
+            let mut node = add_task(
+                graph,
+                log,
+                "fill a again",
+                &[(a, AccessTypes::CLEAR_TRANSFER_WRITE)],
+                is_enabled,
+                fill(a, 2),
+            );
+            node.conditional();
+
+            if record_in_parallel {
+                node.record_in_parallel();
+            }
+
+            add_task(
+                graph,
+                log,
+                "copy a to c",
+                &[
+                    (a, AccessTypes::COPY_TRANSFER_READ),
+                    (c, AccessTypes::COPY_TRANSFER_WRITE),
+                ],
+                true,
+                copy(a, c, 0),
+            );
+        }
+    }
+
+    #[test]
+    fn conditional_barriers() {
+        let (resources, queues) = test_queues!();
+
+        for (thread_pool, record_in_parallel) in [
+            (None, false),
+            (Some(&ScopedThreads as &dyn ThreadPool), true),
+        ] {
+            let (contents, log) = execute_transfers(
+                &resources,
+                &queues,
+                thread_pool,
+                conditional_fill(true, record_in_parallel),
+            );
+
+            assert_eq!(contents[..LEN as usize], [2; LEN as usize]);
+            assert_eq!(log.len(), 3);
+
+            let (contents, log) = execute_transfers(
+                &resources,
+                &queues,
+                thread_pool,
+                conditional_fill(false, record_in_parallel),
+            );
+
+            assert_eq!(contents[..LEN as usize], [1; LEN as usize]);
+            assert_eq!(log, [("copy a to c", false), ("fill a", false)],);
+        }
+    }
+
+    #[test]
+    fn reuse_resource_map() {
+        let (resources, queues) = test_queues!();
+
+        let flight_id = resources.create_flight(1).unwrap();
+        let create_buffer = || {
+            resources
+                .create_buffer(
+                    &BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    &AllocationCreateInfo::default(),
+                    DeviceLayout::new_unsized::<[u32]>(LEN).unwrap(),
+                )
+                .unwrap()
+        };
+        let log = Log::default();
+
+        let mut graph = TaskGraph::new(&resources);
+        let virtual_buffer = graph.add_buffer(&BufferCreateInfo {
+            usage: BufferUsage::TRANSFER_DST,
+            ..Default::default()
+        });
+        add_task(
+            &mut graph,
+            &log,
+            "fill",
+            &[(virtual_buffer, AccessTypes::CLEAR_TRANSFER_WRITE)],
+            true,
+            fill(virtual_buffer, 1),
+        )
+        .build();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                flight_id,
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        let physical_buffer1 = create_buffer();
+        let mut resource_map = ResourceMap::new(&graph).unwrap();
+        resource_map
+            .insert_buffer(virtual_buffer, physical_buffer1)
+            .unwrap();
+
+        unsafe { graph.execute(&mut resource_map, &(), || {}) }.unwrap();
+        resources.flight(flight_id).wait_idle().unwrap();
+
+        let mut batch = resources.create_deferred_batch();
+        batch.destroy_buffer(physical_buffer1);
+        batch.enqueue();
+        resources.wait_idle().unwrap();
+
+        // The map still maps to the removed buffer, which must be reported as an error rather
+        // than a panic, leaving the mapping removed.
+        let res = unsafe { graph.execute(&mut resource_map, &(), || {}) };
+        assert!(matches!(res, Err(ExecuteError::ResourceRemoved(_))));
+        assert!(!resource_map.is_exhaustive());
+
+        let physical_buffer2 = create_buffer();
+        resource_map
+            .insert_buffer(virtual_buffer, physical_buffer2)
+            .unwrap();
+
+        unsafe { graph.execute(resource_map, &(), || {}) }.unwrap();
+        resources.flight(flight_id).wait_idle().unwrap();
+
+        assert_eq!(log.lock().len(), 2);
+    }
+
+    #[test]
+    fn read_back_buffer() {
+        let (resources, queues) = test_queues!();
+
+        let queue = queues
+            .iter()
+            .find(|queue| {
+                queue.device().physical_device().queue_family_properties()
+                    [queue.queue_family_index() as usize]
+                    .queue_flags
+                    .contains(QueueFlags::GRAPHICS)
+            })
+            .unwrap();
+        let flight_id = resources.create_flight(1).unwrap();
+        let buffer = resources
+            .create_buffer(
+                &BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+                &AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                        | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                    ..Default::default()
+                },
+                DeviceLayout::new_unsized::<[u32]>(LEN).unwrap(),
+            )
+            .unwrap();
+        let contents = Arc::new(Mutex::new(None));
+
+        unsafe {
+            crate::execute(
+                queue,
+                &resources,
+                flight_id,
+                |cbf, tcx| {
+                    fill(buffer, 42)(cbf);
+
+                    let contents = contents.clone();
+                    tcx.read_back_buffer::<[u32]>(buffer, .., move |data| {
+                        *contents.lock() = Some(data.to_vec());
+                    });
+
+                    Ok(())
+                },
+                [(buffer, HostAccessType::Read)],
+                [(buffer, AccessTypes::CLEAR_TRANSFER_WRITE)],
+                [],
+            )
+        }
+        .unwrap();
+
+        // The callback must only be called once the frame has been waited on.
+        assert!(contents.lock().is_none());
+
+        resources.flight(flight_id).wait_idle().unwrap();
+
+        assert_eq!(contents.lock().take(), Some(vec![42; LEN as usize]));
+    }
+}
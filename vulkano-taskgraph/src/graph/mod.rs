@@ -1,8 +1,9 @@
 //! The task graph data structure and associated types.
 
 pub use self::{
-    compile::{CompileError, CompileErrorKind, CompileInfo},
-    execute::{ExecuteError, ResourceMap},
+    compile::{AsyncComputePolicy, CompileError, CompileErrorKind, CompileInfo},
+    execute::{ExecuteError, ResourceMap, ThreadPool},
+    schedule::Schedule,
 };
 use crate::{
     linear_map::LinearMap,
@@ -25,20 +26,29 @@ use std::{
     sync::Arc,
 };
 use vulkano::{
-    buffer::{Buffer, BufferCreateInfo},
+    buffer::{Buffer, BufferCreateFlags, BufferCreateInfo, BufferUsage},
     device::{Device, DeviceOwned, Queue},
     format::Format,
     image::{
-        sampler::ComponentMapping, Image, ImageAspects, ImageCreateInfo, ImageLayout, ImageUsage,
-        SampleCount,
+        sampler::ComponentMapping, Image, ImageAspects, ImageCreateFlags, ImageCreateInfo,
+        ImageLayout, ImageTiling, ImageType, ImageUsage, SampleCount,
     },
+    query::{QueryPool, QueryResultFlags},
     render_pass::{Framebuffer, RenderPass, Subpass},
     swapchain::{Swapchain, SwapchainCreateInfo},
-    sync::{semaphore::Semaphore, AccessFlags, PipelineStages},
+    sync::{semaphore::Semaphore, AccessFlags, PipelineStages, Sharing},
+    DeviceSize, VulkanError,
 };
 
 mod compile;
+mod dump;
 mod execute;
+mod schedule;
+
+/// The maximum history count that a [history image] can be added with.
+///
+/// [history image]: TaskGraph::add_history_image
+pub const MAX_HISTORY_COUNT: u32 = 7;
 
 /// The task graph is a [directed acyclic graph] consisting of [`Task`] nodes, with edges
 /// representing happens-before relations.
@@ -51,11 +61,11 @@ pub struct TaskGraph<W: ?Sized> {
 
 struct Nodes<W: ?Sized> {
     inner: SlotMap<NodeId, Node<W>>,
+    // The indices of the nodes in the order in which they were added.
+    creation_order: Vec<NodeIndex>,
 }
 
 struct Node<W: ?Sized> {
-    // TODO:
-    #[allow(unused)]
     name: Cow<'static, str>,
     inner: NodeInner<W>,
     in_edges: Vec<NodeIndex>,
@@ -78,12 +88,49 @@ pub(crate) struct Resources {
     host_reads: Vec<Id<Buffer>>,
     host_writes: Vec<Id<Buffer>>,
     framebuffers: SlotMap<Id<Framebuffer>, ()>,
+    transients: LinearMap<Id, TransientInfo>,
+    histories: LinearMap<Id, HistoryInfo>,
 }
 
+#[derive(Hash)]
 struct ResourceInfo {
     format: Format,
     samples: SampleCount,
     usage: ImageUsage,
+    buffer_usage: BufferUsage,
+    size: DeviceSize,
+}
+
+#[derive(Hash)]
+enum TransientInfo {
+    Buffer {
+        flags: BufferCreateFlags,
+        size: DeviceSize,
+        usage: BufferUsage,
+        queue_family_indices: SmallVec<[u32; 4]>,
+    },
+    Image {
+        flags: ImageCreateFlags,
+        image_type: ImageType,
+        format: Format,
+        view_formats: Vec<Format>,
+        extent: [u32; 3],
+        array_layers: u32,
+        mip_levels: u32,
+        samples: SampleCount,
+        tiling: ImageTiling,
+        usage: ImageUsage,
+        stencil_usage: Option<ImageUsage>,
+        queue_family_indices: SmallVec<[u32; 4]>,
+    },
+}
+
+#[derive(Hash)]
+struct HistoryInfo {
+    // The IDs that refer to the image `n` executions ago, indexed by `n`. The first one is the ID
+    // of the history image itself.
+    ids: SmallVec<[Id<Image>; 2]>,
+    transient_info: TransientInfo,
 }
 
 impl<W: ?Sized> TaskGraph<W> {
@@ -93,6 +140,7 @@ impl<W: ?Sized> TaskGraph<W> {
         TaskGraph {
             nodes: Nodes {
                 inner: SlotMap::with_key(),
+                creation_order: Vec::new(),
             },
             resources: Resources {
                 inner: SlotMap::with_key(),
@@ -101,6 +149,8 @@ impl<W: ?Sized> TaskGraph<W> {
                 host_reads: Vec::new(),
                 host_writes: Vec::new(),
                 framebuffers: SlotMap::with_key(),
+                transients: LinearMap::new(),
+                histories: LinearMap::new(),
             },
         }
     }
@@ -128,6 +178,8 @@ impl<W: ?Sized> TaskGraph<W> {
             id,
             accesses: &mut task_node.accesses,
             attachments: &mut task_node.attachments,
+            record_in_parallel: &mut task_node.record_in_parallel,
+            conditional: &mut task_node.conditional,
             resources: &mut self.resources,
         }
     }
@@ -220,6 +272,96 @@ impl<W: ?Sized> TaskGraph<W> {
         self.resources.add_image(create_info)
     }
 
+    /// Add a transient [virtual buffer resource] to the task graph.
+    ///
+    /// Unlike a buffer added with [`add_buffer`], the physical resource backing a transient
+    /// buffer is owned by the task graph: it is created when the task graph is [compiled] and
+    /// mapped automatically, so you must not map it in the [`ResourceMap`] yourself. The contents
+    /// of a transient buffer are undefined at the start of every execution. Transient buffers
+    /// whose lifetimes within the graph don't overlap may share the same memory.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.external_memory_handle_types` is not empty.
+    ///
+    /// [`add_buffer`]: Self::add_buffer
+    /// [compiled]: Self::compile
+    #[must_use]
+    pub fn add_transient_buffer(&mut self, create_info: &BufferCreateInfo<'_>) -> Id<Buffer> {
+        self.resources.add_transient_buffer(create_info)
+    }
+
+    /// Add a transient [virtual image resource] to the task graph.
+    ///
+    /// Unlike an image added with [`add_image`], the physical resource backing a transient image
+    /// is owned by the task graph: it is created when the task graph is [compiled] and mapped
+    /// automatically, so you must not map it in the [`ResourceMap`] yourself. The contents of a
+    /// transient image are undefined at the start of every execution. Transient images whose
+    /// lifetimes within the graph don't overlap may share the same memory.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.flags` contains [`ImageCreateFlags::DISJOINT`].
+    /// - Panics if `create_info.tiling` is [`ImageTiling::DrmFormatModifier`].
+    /// - Panics if `create_info.initial_layout` is not [`ImageLayout::Undefined`].
+    /// - Panics if `create_info.external_memory_handle_types` is not empty.
+    ///
+    /// [`add_image`]: Self::add_image
+    /// [compiled]: Self::compile
+    #[must_use]
+    pub fn add_transient_image(&mut self, create_info: &ImageCreateInfo<'_>) -> Id<Image> {
+        self.resources.add_transient_image(create_info)
+    }
+
+    /// Add a [virtual image resource] with history to the task graph.
+    ///
+    /// A history image is made up of `history_count + 1` physical images, which are owned by the
+    /// task graph like those of [transient images]: they are created when the task graph is
+    /// [compiled] and mapped automatically, so you must not map them in the [`ResourceMap`]
+    /// yourself. On every execution, the returned ID refers to a different one of the physical
+    /// images, while the ID returned by [`previous_image_id(id, n)`] refers to the physical image
+    /// that the ID referred to `n` executions ago. This way, a task node can access what was
+    /// written during previous executions, for example for temporal effects, by accessing the
+    /// previous image in addition to `id`. Synchronization across executions is handled
+    /// automatically.
+    ///
+    /// Unlike transient images, the physical images are never aliased, so their contents persist
+    /// across executions. The contents of a physical image are undefined until it has been
+    /// written for the first time, which is also the case after the task graph is [decompiled]
+    /// and compiled again.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `history_count` is `0` or greater than [`MAX_HISTORY_COUNT`].
+    /// - Panics if `create_info.flags` contains [`ImageCreateFlags::DISJOINT`].
+    /// - Panics if `create_info.tiling` is [`ImageTiling::DrmFormatModifier`].
+    /// - Panics if `create_info.initial_layout` is not [`ImageLayout::Undefined`].
+    /// - Panics if `create_info.external_memory_handle_types` is not empty.
+    ///
+    /// [transient images]: Self::add_transient_image
+    /// [compiled]: Self::compile
+    /// [`previous_image_id(id, n)`]: Self::previous_image_id
+    /// [decompiled]: ExecutableTaskGraph::decompile
+    #[must_use]
+    pub fn add_history_image(
+        &mut self,
+        create_info: &ImageCreateInfo<'_>,
+        history_count: u32,
+    ) -> Id<Image> {
+        self.resources.add_history_image(create_info, history_count)
+    }
+
+    /// Returns the ID that refers to what `id` referred to `n` executions ago, where `id` is a
+    /// [history image]. `previous_image_id(id, 0)` returns `id`.
+    ///
+    /// Returns an error if `id` is not a history image of the task graph, or if `n` is greater
+    /// than the history count that the image was added with.
+    ///
+    /// [history image]: Self::add_history_image
+    pub fn previous_image_id(&self, id: Id<Image>, n: u32) -> Result<Id<Image>, InvalidSlotError> {
+        self.resources.previous_image_id(id, n)
+    }
+
     /// Add a [virtual swapchain resource] to the task graph.
     #[must_use]
     pub fn add_swapchain(&mut self, create_info: &SwapchainCreateInfo<'_>) -> Id<Swapchain> {
@@ -228,9 +370,15 @@ impl<W: ?Sized> TaskGraph<W> {
 
     /// Adds a host buffer access to the task graph.
     ///
+    /// For host reads, a device-to-host memory barrier is inserted after the last access of the
+    /// buffer on the device, such that the buffer's contents can be read on the host once the
+    /// frame has finished executing, for example using [`TaskContext::read_back_buffer`].
+    ///
     /// # Panics
     ///
     /// - Panics if `id` is not a valid virtual resource ID nor a valid physical ID.
+    ///
+    /// [`TaskContext::read_back_buffer`]: crate::TaskContext::read_back_buffer
     pub fn add_host_buffer_access(&mut self, id: Id<Buffer>, access_type: HostAccessType) {
         self.resources.add_host_buffer_access(id, access_type)
     }
@@ -244,17 +392,27 @@ impl<W: ?Sized> TaskGraph<W> {
 
 impl<W: ?Sized> Nodes<W> {
     fn add_node(&mut self, name: Cow<'static, str>, inner: NodeInner<W>) -> NodeId {
-        self.inner.insert(Node {
+        let id = self.inner.insert(Node {
             name,
             inner,
             in_edges: Vec::new(),
             out_edges: Vec::new(),
-        })
+        });
+        self.creation_order.push(id.index());
+
+        id
     }
 
     fn remove_node(&mut self, id: NodeId) -> Node<W> {
         let node = self.inner.remove(id).unwrap();
 
+        let order_index = self
+            .creation_order
+            .iter()
+            .position(|&i| i == id.index())
+            .unwrap();
+        self.creation_order.remove(order_index);
+
         // NOTE(Marc): We must not leave any broken edges because the rest of the code relies on
         // this being impossible.
 
@@ -378,6 +536,8 @@ impl Resources {
             format: Format::UNDEFINED,
             samples: SampleCount::Sample1,
             usage: ImageUsage::empty(),
+            buffer_usage: create_info.usage,
+            size: create_info.size,
         };
 
         let id = self.inner.insert_with_tag(resource_info, tag);
@@ -396,6 +556,8 @@ impl Resources {
             format: create_info.format,
             samples: create_info.samples,
             usage: create_info.usage,
+            buffer_usage: BufferUsage::empty(),
+            size: 0,
         };
 
         let id = self.inner.insert_with_tag(resource_info, tag);
@@ -403,6 +565,79 @@ impl Resources {
         unsafe { id.parametrize() }
     }
 
+    fn add_transient_buffer(&mut self, create_info: &BufferCreateInfo<'_>) -> Id<Buffer> {
+        assert!(create_info.external_memory_handle_types.is_empty());
+
+        let id = self.add_buffer(create_info);
+
+        self.transients.insert(
+            id.erase(),
+            TransientInfo::Buffer {
+                flags: create_info.flags,
+                size: create_info.size,
+                usage: create_info.usage,
+                queue_family_indices: queue_family_indices(create_info.sharing),
+            },
+        );
+
+        id
+    }
+
+    fn add_transient_image(&mut self, create_info: &ImageCreateInfo<'_>) -> Id<Image> {
+        assert!(!create_info.flags.intersects(ImageCreateFlags::DISJOINT));
+        assert_ne!(create_info.tiling, ImageTiling::DrmFormatModifier);
+        assert_eq!(create_info.initial_layout, ImageLayout::Undefined);
+        assert!(create_info.external_memory_handle_types.is_empty());
+
+        let id = self.add_image(create_info);
+
+        self.transients
+            .insert(id.erase(), TransientInfo::image(create_info));
+
+        id
+    }
+
+    fn add_history_image(
+        &mut self,
+        create_info: &ImageCreateInfo<'_>,
+        history_count: u32,
+    ) -> Id<Image> {
+        assert_ne!(history_count, 0);
+        assert!(history_count <= MAX_HISTORY_COUNT);
+        assert!(!create_info.flags.intersects(ImageCreateFlags::DISJOINT));
+        assert_ne!(create_info.tiling, ImageTiling::DrmFormatModifier);
+        assert_eq!(create_info.initial_layout, ImageLayout::Undefined);
+        assert!(create_info.external_memory_handle_types.is_empty());
+
+        let id = self.add_image(create_info);
+        let mut ids = SmallVec::with_capacity(history_count as usize + 1);
+        ids.push(id);
+
+        for n in 1..=history_count {
+            let tag = id.slot.tag() | n << Id::HISTORY_SHIFT;
+            let resource_info = ResourceInfo {
+                format: create_info.format,
+                samples: create_info.samples,
+                usage: create_info.usage,
+                buffer_usage: BufferUsage::empty(),
+                size: 0,
+            };
+            let previous_id = self.inner.insert_with_tag(resource_info, tag);
+
+            ids.push(unsafe { previous_id.parametrize() });
+        }
+
+        self.histories.insert(
+            id.erase(),
+            HistoryInfo {
+                ids,
+                transient_info: TransientInfo::image(create_info),
+            },
+        );
+
+        id
+    }
+
     fn add_swapchain(&mut self, create_info: &SwapchainCreateInfo<'_>) -> Id<Swapchain> {
         let mut tag = Swapchain::TAG | Id::VIRTUAL_BIT;
 
@@ -414,6 +649,8 @@ impl Resources {
             format: create_info.image_format,
             samples: SampleCount::Sample1,
             usage: create_info.image_usage,
+            buffer_usage: BufferUsage::empty(),
+            size: 0,
         };
 
         let id = self.inner.insert_with_tag(resource_info, tag);
@@ -512,6 +749,18 @@ impl Resources {
         self.inner.get(id).ok_or(InvalidSlotError::new(id))
     }
 
+    fn previous_image_id(&self, id: Id<Image>, n: u32) -> Result<Id<Image>, InvalidSlotError> {
+        self.histories
+            .get(&id.erase())
+            .and_then(|history_info| history_info.ids.get(n as usize))
+            .copied()
+            .ok_or(InvalidSlotError::new(id))
+    }
+
+    fn is_history_image(&self, id: Id) -> bool {
+        id.history_index() != 0 || self.histories.contains_key(&id)
+    }
+
     fn iter(&self) -> Iter<'_, Id, ResourceInfo> {
         self.inner.iter()
     }
@@ -542,6 +791,32 @@ impl Resources {
     }
 }
 
+impl TransientInfo {
+    fn image(create_info: &ImageCreateInfo<'_>) -> Self {
+        TransientInfo::Image {
+            flags: create_info.flags,
+            image_type: create_info.image_type,
+            format: create_info.format,
+            view_formats: create_info.view_formats.to_vec(),
+            extent: create_info.extent,
+            array_layers: create_info.array_layers,
+            mip_levels: create_info.mip_levels,
+            samples: create_info.samples,
+            tiling: create_info.tiling,
+            usage: create_info.usage,
+            stencil_usage: create_info.stencil_usage,
+            queue_family_indices: queue_family_indices(create_info.sharing),
+        }
+    }
+}
+
+fn queue_family_indices(sharing: Sharing<'_>) -> SmallVec<[u32; 4]> {
+    match sharing {
+        Sharing::Exclusive => SmallVec::new(),
+        Sharing::Concurrent(queue_family_indices) => queue_family_indices.into(),
+    }
+}
+
 impl<W: ?Sized> fmt::Debug for TaskGraph<W> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME:
@@ -576,6 +851,9 @@ pub struct TaskNode<W: ?Sized> {
     queue_family_index: u32,
     dependency_level_index: u32,
     subpass: Option<Subpass>,
+    record_in_parallel: bool,
+    conditional: bool,
+    skippable_barrier_range: Range<BarrierIndex>,
     task: Box<dyn Task<World = W>>,
 }
 
@@ -607,6 +885,9 @@ impl<W: ?Sized> TaskNode<W> {
             queue_family_index: 0,
             dependency_level_index: 0,
             subpass: None,
+            record_in_parallel: false,
+            conditional: false,
+            skippable_barrier_range: 0..0,
             task: Box::new(task),
         }
     }
@@ -637,6 +918,30 @@ impl<W: ?Sized> TaskNode<W> {
     pub fn subpass(&self) -> Option<&Subpass> {
         self.subpass.as_ref()
     }
+
+    /// Returns `true` if the task node was [set to be recorded in parallel].
+    ///
+    /// [set to be recorded in parallel]: TaskNodeBuilder::record_in_parallel
+    #[inline]
+    #[must_use]
+    pub fn records_in_parallel(&self) -> bool {
+        self.record_in_parallel
+    }
+
+    /// Returns `true` if the task node was [set to be conditional].
+    ///
+    /// [set to be conditional]: TaskNodeBuilder::conditional
+    #[inline]
+    #[must_use]
+    pub fn is_conditional(&self) -> bool {
+        self.conditional
+    }
+}
+
+impl<W: ?Sized + 'static> TaskNode<W> {
+    fn should_execute(&self, world: &W) -> bool {
+        !self.conditional || self.task.is_enabled(world)
+    }
 }
 
 impl ResourceAccesses {
@@ -706,6 +1011,24 @@ impl ResourceAccesses {
     fn iter(&self) -> impl Iterator<Item = (Id, &ResourceAccess)> + use<'_> {
         self.inner.iter().map(|(id, access)| (*id, access))
     }
+
+    /// Returns the IDs of the buffers that are accessed with at least `access_types`.
+    pub(crate) fn buffers_with_access(
+        &self,
+        access_types: AccessTypes,
+    ) -> impl Iterator<Item = Id<Buffer>> + use<'_> {
+        let stage_mask = access_types.stage_mask();
+        let access_mask = access_types.access_mask();
+
+        self.iter()
+            .filter(move |(id, access)| {
+                id.is::<Buffer>()
+                    && access.stage_mask.contains(stage_mask)
+                    && access.access_mask.contains(access_mask)
+            })
+            // SAFETY: We checked that `id` is a buffer ID.
+            .map(|(id, _)| unsafe { id.parametrize() })
+    }
 }
 
 impl Attachments {
@@ -748,6 +1071,8 @@ pub struct TaskNodeBuilder<'a> {
     id: NodeId,
     accesses: &'a mut ResourceAccesses,
     attachments: &'a mut Option<Attachments>,
+    record_in_parallel: &'a mut bool,
+    conditional: &'a mut bool,
     resources: &'a mut Resources,
 }
 
@@ -1122,6 +1447,48 @@ impl TaskNodeBuilder<'_> {
         self.image_access(id, access_types, layout_type)
     }
 
+    /// Sets the task node to be recorded in parallel with other task nodes.
+    ///
+    /// When the task graph is executed using [`execute_parallel`], the task is recorded on a
+    /// worker thread into its own command buffer, which is then executed in the same order as if
+    /// the task had been recorded on the executing thread. This only makes sense for tasks that
+    /// record a lot of commands, as each such task incurs the cost of an additional command
+    /// buffer.
+    ///
+    /// Task nodes that are part of a render pass can't be recorded in parallel, since their
+    /// commands must be recorded in the same command buffer as the render pass instance. Setting
+    /// this for a task node that has a [framebuffer] makes [`TaskGraph::compile`] panic.
+    ///
+    /// [`execute_parallel`]: ExecutableTaskGraph::execute_parallel
+    /// [framebuffer]: Self::framebuffer
+    #[inline]
+    pub fn record_in_parallel(&mut self) -> &mut Self {
+        *self.record_in_parallel = true;
+
+        self
+    }
+
+    /// Sets the task node to be conditional.
+    ///
+    /// Before every execution of a conditional task node, [`Task::is_enabled`] is invoked to
+    /// decide whether the task is executed. This allows you to turn a task on and off without
+    /// recompiling the task graph.
+    ///
+    /// When compiling, the barriers that only exist for the task node's own accesses are set
+    /// aside. If the task node is disabled, these barriers are not executed on their own but are
+    /// instead merged with the next barrier on the same resource, such that the accesses before
+    /// the task node are synchronized directly with the accesses after it. Barriers that other
+    /// task nodes rely on, such as queue family ownership transfers, barriers that later reads
+    /// were merged into and barriers within a render pass instance, are executed regardless. This
+    /// means that other task nodes observe the resources in the same state as they would if the
+    /// task had been executed, except for the lack of the task's writes.
+    #[inline]
+    pub fn conditional(&mut self) -> &mut Self {
+        *self.conditional = true;
+
+        self
+    }
+
     /// Finishes building the task node and returns the ID of the built node.
     #[inline]
     pub fn build(&mut self) -> NodeId {
@@ -1215,6 +1582,7 @@ impl AttachmentInfo<'_> {
 pub struct ExecutableTaskGraph<W: ?Sized> {
     graph: ManuallyDrop<TaskGraph<W>>,
     flight_id: Id<Flight>,
+    schedule: Schedule,
     instructions: Vec<Instruction>,
     submissions: Vec<Submission>,
     barriers: Vec<MemoryBarrier>,
@@ -1224,10 +1592,57 @@ pub struct ExecutableTaskGraph<W: ?Sized> {
     swapchains: SmallVec<[Id<Swapchain>; 1]>,
     present_queue: Option<Arc<Queue>>,
     last_accesses: Vec<ResourceAccess>,
+    transient_resources: Vec<Id>,
+    history_images: Vec<HistoryImages>,
+    execution_index: Cell<u64>,
+    task_timestamps: Option<TaskTimestampQueries>,
     last_frame: Cell<Option<u64>>,
     drop_graph: bool,
 }
 
+struct HistoryImages {
+    // The virtual IDs of the history image, indexed by how many executions ago they refer to.
+    ids: SmallVec<[Id<Image>; 2]>,
+    // The physical images backing the history image, `history_count + 1` in total.
+    physical_ids: SmallVec<[Id<Image>; 2]>,
+}
+
+impl HistoryImages {
+    // Returns the physical image that `self.ids[n]` refers to during the execution with the given
+    // index.
+    fn physical_id(&self, execution_index: u64, n: u32) -> Id<Image> {
+        let len = self.physical_ids.len() as u64;
+        let index = (execution_index % len + len - u64::from(n)) % len;
+
+        self.physical_ids[index as usize]
+    }
+}
+
+struct TaskTimestampQueries {
+    query_pool: Arc<QueryPool>,
+    // The IDs of the task nodes in the order in which they are executed.
+    node_ids: Vec<NodeId>,
+    // For each task node in `node_ids`, the index of the pair of queries its timestamps are
+    // written to. Task nodes within the same render pass instance share one pair, which is
+    // written outside the render pass instance.
+    pair_indices: Vec<u32>,
+    pair_count: u32,
+    // For each frame, whether each task node in `node_ids` was executed the last time the frame
+    // was recorded. Empty if the frame hasn't been recorded yet.
+    executed: RefCell<Vec<Vec<bool>>>,
+}
+
+impl TaskTimestampQueries {
+    // Each pair gets two queries per frame: one before and one after the execution.
+    fn first_query(&self, frame_index: u32, pair_index: u32) -> u32 {
+        (frame_index * self.pair_count + pair_index) * 2
+    }
+
+    fn task_first_query(&self, frame_index: u32, task_index: u32) -> u32 {
+        self.first_query(frame_index, self.pair_indices[task_index as usize])
+    }
+}
+
 // FIXME: Initial queue family ownership transfers
 #[derive(Debug)]
 struct Submission {
@@ -1361,6 +1776,103 @@ impl<W: ?Sized> ExecutableTaskGraph<W> {
     pub fn flight_id(&self) -> Id<Flight> {
         self.flight_id
     }
+
+    /// Returns the timestamps that were recorded around the execution of each task node, in the
+    /// order in which the task nodes are executed, for the frame with the given index within the
+    /// [flight]. Returns `None` if the results aren't available yet.
+    ///
+    /// The task graph must have been compiled with [`CompileInfo::task_timestamps`] enabled. The
+    /// results of a frame are available once the frame has finished executing, which you can
+    /// ensure by [waiting] on the flight. Note that the results are overwritten when the frame
+    /// index is reused by a later frame.
+    ///
+    /// Task nodes that are part of a render pass instance don't get timestamps of their own, as
+    /// timestamps are written outside of render pass instances; they all get the timestamps of
+    /// the render pass instance instead. [Conditional] task nodes that were disabled in the frame
+    /// are not included.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the task graph wasn't compiled with `task_timestamps` enabled.
+    /// - Panics if `frame_index` is not less than the frame count of the flight.
+    ///
+    /// [flight]: Flight
+    /// [waiting]: Flight::wait
+    /// [Conditional]: TaskNodeBuilder::conditional
+    pub fn task_timestamps(
+        &self,
+        frame_index: u32,
+    ) -> Result<Option<Vec<TaskTimestamp>>, VulkanError> {
+        let task_timestamps = self
+            .task_timestamps
+            .as_ref()
+            .expect("the task graph wasn't compiled with `task_timestamps` enabled");
+        let pair_count = task_timestamps.pair_count;
+
+        assert!(frame_index < task_timestamps.query_pool.query_count() / (2 * pair_count));
+
+        let executed = task_timestamps.executed.borrow();
+        let executed = &executed[frame_index as usize];
+
+        if executed.is_empty() {
+            return Ok(None);
+        }
+
+        // Each query's result is followed by its availability.
+        let mut results = vec![0u64; 4 * pair_count as usize];
+
+        task_timestamps.query_pool.get_results(
+            task_timestamps.first_query(frame_index, 0),
+            2 * pair_count,
+            &mut results,
+            QueryResultFlags::WITH_AVAILABILITY,
+        )?;
+
+        let mut timestamps = Vec::new();
+
+        for ((&node_id, &pair_index), &executed) in task_timestamps
+            .node_ids
+            .iter()
+            .zip(&task_timestamps.pair_indices)
+            .zip(executed)
+        {
+            if !executed {
+                continue;
+            }
+
+            let results = &results[4 * pair_index as usize..][..4];
+
+            if results[1] == 0 || results[3] == 0 {
+                return Ok(None);
+            }
+
+            timestamps.push(TaskTimestamp {
+                node_id,
+                start: results[0],
+                end: results[2],
+            });
+        }
+
+        Ok(Some(timestamps))
+    }
+}
+
+/// The timestamps recorded around the execution of a task node.
+///
+/// The timestamps are in device-specific units. Multiply them by the [`timestamp_period`] of the
+/// device to get nanoseconds.
+///
+/// [`timestamp_period`]: vulkano::device::DeviceProperties::timestamp_period
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaskTimestamp {
+    /// The ID of the task node.
+    pub node_id: NodeId,
+
+    /// The timestamp written before the task node's commands.
+    pub start: u64,
+
+    /// The timestamp written after the task node's commands.
+    pub end: u64,
 }
 
 impl<W: ?Sized> fmt::Debug for ExecutableTaskGraph<W> {
@@ -1384,10 +1896,24 @@ impl<W: ?Sized> fmt::Debug for ExecutableTaskGraph<W> {
 
 impl<W: ?Sized> Drop for ExecutableTaskGraph<W> {
     fn drop(&mut self) {
-        if let Some(last_frame) = self.last_frame.get() {
-            let resources = &self.graph.resources.physical_resources;
-            let mut batch = resources.create_deferred_batch();
+        let resources = &self.graph.resources.physical_resources;
+        let mut batch = resources.create_deferred_batch();
 
+        for &id in &self.transient_resources {
+            match id.object_type() {
+                ObjectType::Buffer => batch.destroy_buffer(unsafe { id.parametrize() }),
+                ObjectType::Image => batch.destroy_image(unsafe { id.parametrize() }),
+                _ => unreachable!(),
+            };
+        }
+
+        for history_images in &self.history_images {
+            for &id in &history_images.physical_ids {
+                batch.destroy_image(id);
+            }
+        }
+
+        if let Some(last_frame) = self.last_frame.get() {
             for semaphore in self.semaphores.get_mut().drain(..) {
                 batch.destroy_object(semaphore);
             }
@@ -1401,6 +1927,8 @@ impl<W: ?Sized> Drop for ExecutableTaskGraph<W> {
             // SAFETY: We only defer the destruction of objects that are graph-local and
             // `last_frame` is the last frame that the graph executed.
             unsafe { batch.enqueue_with_frames(iter::once((self.flight_id, last_frame))) };
+        } else {
+            batch.enqueue();
         }
 
         if self.drop_graph {
@@ -1549,7 +2077,7 @@ impl Error for TaskGraphError {}
 mod tests {
     use super::*;
     use crate::tests::test_queues;
-    use std::marker::PhantomData;
+    use std::{marker::PhantomData, panic};
 
     #[test]
     fn basic_usage1() {
@@ -1634,4 +2162,106 @@ mod tests {
 
         assert_eq!(graph.add_edge(x, x), Err(TaskGraphError::InvalidNode));
     }
+
+    #[test]
+    fn previous_image_id() {
+        let (resources, _) = test_queues!();
+        let mut graph = TaskGraph::<()>::new(&resources);
+
+        let create_info = ImageCreateInfo {
+            format: Format::R8G8B8A8_UNORM,
+            extent: [4, 4, 1],
+            usage: ImageUsage::STORAGE,
+            ..Default::default()
+        };
+        let image1 = graph.add_history_image(&create_info, 2);
+        let image2 = graph.add_image(&create_info);
+        let image3 = graph.add_history_image(&create_info, 1);
+
+        assert_eq!(graph.previous_image_id(image1, 0).unwrap(), image1);
+        let previous1 = graph.previous_image_id(image1, 1).unwrap();
+        let previous2 = graph.previous_image_id(image1, 2).unwrap();
+        assert_ne!(previous1, image1);
+        assert_ne!(previous2, image1);
+        assert_ne!(previous1, previous2);
+        assert!(graph.previous_image_id(image1, 3).is_err());
+
+        assert!(graph.previous_image_id(image2, 0).is_err());
+        assert!(graph.previous_image_id(previous1, 1).is_err());
+
+        let previous3 = graph.previous_image_id(image3, 1).unwrap();
+        assert_ne!(previous3, previous1);
+        assert_ne!(previous3, previous2);
+        assert!(graph.previous_image_id(image3, 2).is_err());
+    }
+
+    #[test]
+    fn resource_map_remap() {
+        let (resources, queues) = test_queues!();
+        let mut graph = TaskGraph::<()>::new(&resources);
+
+        let create_info = ImageCreateInfo {
+            format: Format::R8G8B8A8_UNORM,
+            extent: [4, 4, 1],
+            usage: ImageUsage::SAMPLED,
+            ..Default::default()
+        };
+        let virtual_image = graph.add_image(&create_info);
+        graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                virtual_image,
+                AccessTypes::FRAGMENT_SHADER_SAMPLED_READ,
+                ImageLayoutType::Optimal,
+            )
+            .build();
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                queues: &queues.iter().collect::<Vec<_>>(),
+                ..Default::default()
+            })
+        }
+        .unwrap();
+
+        let image1 = resources
+            .create_image(&create_info, &Default::default())
+            .unwrap();
+        let image2 = resources
+            .create_image(
+                &ImageCreateInfo {
+                    extent: [8, 8, 1],
+                    ..create_info.clone()
+                },
+                &Default::default(),
+            )
+            .unwrap();
+        let image3 = resources
+            .create_image(
+                &ImageCreateInfo {
+                    format: Format::R8G8B8A8_SRGB,
+                    ..create_info.clone()
+                },
+                &Default::default(),
+            )
+            .unwrap();
+
+        let mut resource_map = ResourceMap::new(&graph).unwrap();
+        assert!(!resource_map.is_exhaustive());
+
+        resource_map.insert_image(virtual_image, image1).unwrap();
+        assert!(resource_map.is_exhaustive());
+
+        // Remapping to an image with a different extent is fine.
+        resource_map.insert_image(virtual_image, image2).unwrap();
+        assert_eq!(resource_map.len(), 1);
+        assert!(resource_map.is_exhaustive());
+
+        // Remapping to an image with a different format isn't, as the format of the virtual image
+        // was specified.
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            resource_map.insert_image(virtual_image, image3)
+        }));
+        assert!(res.is_err());
+    }
 }
@@ -0,0 +1,862 @@
+use super::{CompileInfo, ExecutableTaskGraph, MemoryBarrier, NodeIndex, TaskGraph};
+use crate::Id;
+use ash::vk;
+use foldhash::HashMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::hash::{Hash, Hasher};
+use vulkano::{
+    image::ImageLayout,
+    sync::{AccessFlags, PipelineStages},
+};
+
+/// The schedule of a compiled task graph, which can be used to skip most of the analysis when
+/// compiling the same task graph again.
+///
+/// The schedule consists of the order in which the task nodes are executed, the queue family that
+/// each of them is executed on, the render pass instance and subpass that each of them was merged
+/// into and the pipeline barriers recorded around each of them. It's obtained using
+/// [`ExecutableTaskGraph::schedule`] and can be passed to [`TaskGraph::compile`] through
+/// [`CompileInfo::schedule`], which makes the compiler skip the topological sort, the hazard
+/// detection, the queue family assignment, the render pass merging and the barrier placement.
+/// When the `serde` feature is enabled, the schedule can be (de)serialized such that it can be
+/// stored alongside the pipeline cache and reused across runs of the application.
+///
+/// A schedule is only valid for task graphs that have the same [fingerprint] as the one it was
+/// obtained from.
+///
+/// [fingerprint]: TaskGraph::fingerprint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    fingerprint: u64,
+    // The indices of the task nodes in the order in which they are executed.
+    topological_order: Vec<NodeIndex>,
+    // The queue family index of each task node, in the same order as `topological_order`.
+    queue_family_indices: Vec<u32>,
+    // The render pass index and subpass index of each task node, in the same order as
+    // `topological_order`, or `None` if the task node isn't part of a render pass instance.
+    subpasses: Vec<Option<(u32, u32)>>,
+    // The initial barriers of each submission, in submission order.
+    initial_barriers: Vec<Vec<ScheduledBarrier>>,
+    // The barriers before and after each task node, in the same order as `topological_order`.
+    start_barriers: Vec<Vec<ScheduledBarrier>>,
+    end_barriers: Vec<Vec<ScheduledBarrier>>,
+}
+
+/// A [`MemoryBarrier`] in raw form, such that the schedule can be deserialized without having
+/// access to the task graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ScheduledBarrier {
+    src_stage_mask: u64,
+    src_access_mask: u64,
+    dst_stage_mask: u64,
+    dst_access_mask: u64,
+    old_layout: i32,
+    new_layout: i32,
+    src_queue_family_index: u32,
+    dst_queue_family_index: u32,
+    // The index and generation of the resource's ID.
+    resource: (u32, u32),
+}
+
+/// The barriers of a [`Schedule`] that has been validated for a task graph.
+pub(super) struct ScheduledBarriers {
+    pub(super) initial_barriers: Vec<Vec<MemoryBarrier>>,
+    pub(super) start_barriers: Vec<Vec<MemoryBarrier>>,
+    pub(super) end_barriers: Vec<Vec<MemoryBarrier>>,
+}
+
+impl Schedule {
+    pub(super) fn new<'a>(
+        fingerprint: u64,
+        topological_order: Vec<NodeIndex>,
+        queue_family_indices: Vec<u32>,
+        subpasses: Vec<Option<(u32, u32)>>,
+        initial_barriers: impl IntoIterator<Item = &'a [MemoryBarrier]>,
+        start_barriers: impl IntoIterator<Item = &'a [MemoryBarrier]>,
+        end_barriers: impl IntoIterator<Item = &'a [MemoryBarrier]>,
+    ) -> Self {
+        fn convert<'a>(
+            barriers: impl IntoIterator<Item = &'a [MemoryBarrier]>,
+        ) -> Vec<Vec<ScheduledBarrier>> {
+            barriers
+                .into_iter()
+                .map(|barriers| barriers.iter().map(ScheduledBarrier::new).collect())
+                .collect()
+        }
+
+        Schedule {
+            fingerprint,
+            topological_order,
+            queue_family_indices,
+            subpasses,
+            initial_barriers: convert(initial_barriers),
+            start_barriers: convert(start_barriers),
+            end_barriers: convert(end_barriers),
+        }
+    }
+
+    /// Returns the [fingerprint] of the task graph that the schedule was obtained from.
+    ///
+    /// [fingerprint]: TaskGraph::fingerprint
+    #[inline]
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    pub(super) fn topological_order(&self) -> &[NodeIndex] {
+        &self.topological_order
+    }
+
+    pub(super) fn queue_family_indices(&self) -> &[u32] {
+        &self.queue_family_indices
+    }
+
+    pub(super) fn subpasses(&self) -> &[Option<(u32, u32)>] {
+        &self.subpasses
+    }
+
+    /// Returns the barriers of the schedule if it can be used to compile `graph`, or `None`
+    /// otherwise. The fingerprint can't guarantee this on its own because the schedule may have
+    /// been deserialized from corrupted data. This must be called after the edges have been
+    /// inferred, if at all.
+    pub(super) fn validate<W: ?Sized>(
+        &self,
+        graph: &TaskGraph<W>,
+        fingerprint: u64,
+        compile_info: &CompileInfo<'_>,
+    ) -> Option<ScheduledBarriers> {
+        let node_count = graph.nodes.creation_order.len();
+
+        if self.fingerprint != fingerprint
+            || self.topological_order.len() != node_count
+            || self.queue_family_indices.len() != node_count
+            || self.subpasses.len() != node_count
+            || self.start_barriers.len() != node_count
+            || self.end_barriers.len() != node_count
+        {
+            return None;
+        }
+
+        let mut node_indices = self.topological_order.clone();
+        node_indices.sort_unstable();
+        let mut creation_order = graph.nodes.creation_order.clone();
+        creation_order.sort_unstable();
+
+        if node_indices != creation_order {
+            return None;
+        }
+
+        if !self.queue_family_indices.iter().all(|&queue_family_index| {
+            compile_info
+                .queues
+                .iter()
+                .any(|q| q.queue_family_index() == queue_family_index)
+        }) {
+            return None;
+        }
+
+        let submission_count = 1 + self
+            .queue_family_indices
+            .windows(2)
+            .filter(|w| w[0] != w[1])
+            .count();
+
+        if self.initial_barriers.len() != submission_count {
+            return None;
+        }
+
+        let mut positions = vec![0; graph.nodes.reserved_len() as usize];
+
+        for (position, &node_index) in self.topological_order.iter().enumerate() {
+            positions[node_index as usize] = position;
+        }
+
+        let mut render_pass_count = 0;
+
+        for (position, &node_index) in self.topological_order.iter().enumerate() {
+            // SAFETY: We checked that `self.topological_order` consists of exactly the indices of
+            // the nodes in the graph.
+            let node = unsafe { graph.nodes.node_unchecked(node_index) };
+            // SAFETY: All nodes are task nodes.
+            let task_node = unsafe { graph.nodes.task_node_unchecked(node_index) };
+
+            if !node.out_edges.iter().all(|&out_node_index| {
+                positions[node_index as usize] < positions[out_node_index as usize]
+            }) {
+                return None;
+            }
+
+            match (self.subpasses[position], &task_node.attachments) {
+                (None, None) => {}
+                (Some((render_pass_index, subpass_index)), Some(attachments)) => {
+                    if render_pass_index == render_pass_count {
+                        // The task node begins a new render pass instance.
+                        if subpass_index != 0 {
+                            return None;
+                        }
+
+                        render_pass_count += 1;
+                    } else {
+                        // The task node was merged into the render pass instance of the previous
+                        // task node, which must therefore be compatible.
+                        let (prev_render_pass_index, prev_subpass_index) =
+                            position.checked_sub(1).and_then(|i| self.subpasses[i])?;
+                        let prev_node_index = self.topological_order[position - 1];
+                        // SAFETY: Same as above.
+                        let prev_task_node =
+                            unsafe { graph.nodes.task_node_unchecked(prev_node_index) };
+
+                        if render_pass_index != prev_render_pass_index
+                            || (subpass_index != prev_subpass_index
+                                && subpass_index != prev_subpass_index + 1)
+                            || self.queue_family_indices[position]
+                                != self.queue_family_indices[position - 1]
+                            || prev_task_node.attachments.as_ref().unwrap().framebuffer_id
+                                != attachments.framebuffer_id
+                        {
+                            return None;
+                        }
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        // Queue family ownership transfers can also be to the present queue.
+        let is_valid_queue_family_index = |queue_family_index: u32| {
+            compile_info
+                .queues
+                .iter()
+                .copied()
+                .chain(compile_info.present_queue)
+                .any(|q| q.queue_family_index() == queue_family_index)
+        };
+        let ids: HashMap<_, _> = graph
+            .resources
+            .iter()
+            .map(|(id, _)| ((id.index(), id.slot.generation()), id))
+            .collect();
+        let convert = |barriers: &[Vec<ScheduledBarrier>]| {
+            barriers
+                .iter()
+                .map(|barriers| {
+                    barriers
+                        .iter()
+                        .map(|barrier| {
+                            barrier.to_memory_barrier(&ids, &is_valid_queue_family_index)
+                        })
+                        .collect::<Option<Vec<_>>>()
+                })
+                .collect::<Option<Vec<_>>>()
+        };
+
+        Some(ScheduledBarriers {
+            initial_barriers: convert(&self.initial_barriers)?,
+            start_barriers: convert(&self.start_barriers)?,
+            end_barriers: convert(&self.end_barriers)?,
+        })
+    }
+}
+
+impl ScheduledBarrier {
+    fn new(barrier: &MemoryBarrier) -> Self {
+        ScheduledBarrier {
+            src_stage_mask: vk::PipelineStageFlags2::from(barrier.src_stage_mask).as_raw(),
+            src_access_mask: vk::AccessFlags2::from(barrier.src_access_mask).as_raw(),
+            dst_stage_mask: vk::PipelineStageFlags2::from(barrier.dst_stage_mask).as_raw(),
+            dst_access_mask: vk::AccessFlags2::from(barrier.dst_access_mask).as_raw(),
+            old_layout: vk::ImageLayout::from(barrier.old_layout).as_raw(),
+            new_layout: vk::ImageLayout::from(barrier.new_layout).as_raw(),
+            src_queue_family_index: barrier.src_queue_family_index,
+            dst_queue_family_index: barrier.dst_queue_family_index,
+            resource: (barrier.resource.index(), barrier.resource.slot.generation()),
+        }
+    }
+
+    fn to_memory_barrier(
+        self,
+        ids: &HashMap<(u32, u32), Id>,
+        is_valid_queue_family_index: &impl Fn(u32) -> bool,
+    ) -> Option<MemoryBarrier> {
+        let is_valid_queue_family_index = |queue_family_index| {
+            queue_family_index == vk::QUEUE_FAMILY_IGNORED
+                || is_valid_queue_family_index(queue_family_index)
+        };
+
+        if !is_valid_queue_family_index(self.src_queue_family_index)
+            || !is_valid_queue_family_index(self.dst_queue_family_index)
+        {
+            return None;
+        }
+
+        Some(MemoryBarrier {
+            src_stage_mask: PipelineStages::from(vk::PipelineStageFlags2::from_raw(
+                self.src_stage_mask,
+            )),
+            src_access_mask: AccessFlags::from(vk::AccessFlags2::from_raw(self.src_access_mask)),
+            dst_stage_mask: PipelineStages::from(vk::PipelineStageFlags2::from_raw(
+                self.dst_stage_mask,
+            )),
+            dst_access_mask: AccessFlags::from(vk::AccessFlags2::from_raw(self.dst_access_mask)),
+            old_layout: ImageLayout::try_from(vk::ImageLayout::from_raw(self.old_layout)).ok()?,
+            new_layout: ImageLayout::try_from(vk::ImageLayout::from_raw(self.new_layout)).ok()?,
+            src_queue_family_index: self.src_queue_family_index,
+            dst_queue_family_index: self.dst_queue_family_index,
+            resource: *ids.get(&self.resource)?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Schedule {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (
+            self.fingerprint,
+            &self.topological_order,
+            &self.queue_family_indices,
+            &self.subpasses,
+            &self.initial_barriers,
+            &self.start_barriers,
+            &self.end_barriers,
+        )
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[allow(clippy::type_complexity)]
+        <(
+            u64,
+            Vec<NodeIndex>,
+            Vec<u32>,
+            Vec<Option<(u32, u32)>>,
+            Vec<Vec<ScheduledBarrier>>,
+            Vec<Vec<ScheduledBarrier>>,
+            Vec<Vec<ScheduledBarrier>>,
+        )>::deserialize(deserializer)
+        .map(
+            |(
+                fingerprint,
+                topological_order,
+                queue_family_indices,
+                subpasses,
+                initial_barriers,
+                start_barriers,
+                end_barriers,
+            )| Schedule {
+                fingerprint,
+                topological_order,
+                queue_family_indices,
+                subpasses,
+                initial_barriers,
+                start_barriers,
+                end_barriers,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ScheduledBarrier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (
+            self.src_stage_mask,
+            self.src_access_mask,
+            self.dst_stage_mask,
+            self.dst_access_mask,
+            self.old_layout,
+            self.new_layout,
+            self.src_queue_family_index,
+            self.dst_queue_family_index,
+            self.resource,
+        )
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ScheduledBarrier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <(u64, u64, u64, u64, i32, i32, u32, u32, (u32, u32))>::deserialize(deserializer).map(
+            |(
+                src_stage_mask,
+                src_access_mask,
+                dst_stage_mask,
+                dst_access_mask,
+                old_layout,
+                new_layout,
+                src_queue_family_index,
+                dst_queue_family_index,
+                resource,
+            )| ScheduledBarrier {
+                src_stage_mask,
+                src_access_mask,
+                dst_stage_mask,
+                dst_access_mask,
+                old_layout,
+                new_layout,
+                src_queue_family_index,
+                dst_queue_family_index,
+                resource,
+            },
+        )
+    }
+}
+
+/// A 64-bit FNV-1a hasher, which unlike the standard library's hashers is guaranteed to produce
+/// the same output across runs, versions of Rust and platforms.
+struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        StableHasher(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    // The default implementations use native endianness and `usize`/`isize` have
+    // platform-dependent sizes, so we fix both.
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+impl<W: ?Sized> TaskGraph<W> {
+    /// Returns a hash of the structure of the task graph together with the parts of
+    /// `compile_info` that affect its [schedule].
+    ///
+    /// The structure consists of the task nodes in the order in which they were created, their
+    /// queue family types, resource accesses and attachments, as well as the edges between them.
+    /// It also includes the virtual resources with their create infos, which of them are
+    /// transient or history images, and the host buffer accesses. The names of the task nodes
+    /// and the tasks themselves are not taken into account. Of
+    /// `compile_info`, the queue families of the queues, whether hazards are detected, whether
+    /// edges are inferred and the async compute policy are taken into account.
+    ///
+    /// The fingerprint is stable across runs, builds and platforms, but not necessarily across
+    /// different versions of vulkano.
+    ///
+    /// [schedule]: Schedule
+    #[must_use]
+    pub fn fingerprint(&self, compile_info: &CompileInfo<'_>) -> u64 {
+        let mut hasher = StableHasher::new();
+
+        self.nodes.creation_order.len().hash(&mut hasher);
+
+        for &node_index in &self.nodes.creation_order {
+            // SAFETY: `self.nodes.creation_order` only contains the indices of nodes in the graph.
+            let node = unsafe { self.nodes.node_unchecked(node_index) };
+            // SAFETY: All nodes are task nodes.
+            let task_node = unsafe { self.nodes.task_node_unchecked(node_index) };
+
+            node_index.hash(&mut hasher);
+            node.out_edges.hash(&mut hasher);
+            task_node.queue_family_type.hash(&mut hasher);
+            task_node.accesses.inner.len().hash(&mut hasher);
+
+            for (id, access) in task_node.accesses.iter() {
+                id.hash(&mut hasher);
+                access.stage_mask.hash(&mut hasher);
+                access.access_mask.hash(&mut hasher);
+                access.image_layout.hash(&mut hasher);
+            }
+
+            if let Some(attachments) = &task_node.attachments {
+                true.hash(&mut hasher);
+                attachments.framebuffer_id.hash(&mut hasher);
+
+                for id in attachments.keys() {
+                    id.hash(&mut hasher);
+                }
+            } else {
+                false.hash(&mut hasher);
+            }
+        }
+
+        // The resources decide which barriers are needed beyond those between the task nodes:
+        // host accesses need barriers of their own, and transient resources may alias.
+        self.resources.inner.len().hash(&mut hasher);
+
+        for (id, resource_info) in self.resources.iter() {
+            id.hash(&mut hasher);
+            resource_info.hash(&mut hasher);
+        }
+
+        self.resources.host_reads.hash(&mut hasher);
+        self.resources.host_writes.hash(&mut hasher);
+        self.resources.transients.len().hash(&mut hasher);
+
+        for (id, transient_info) in self.resources.transients.iter() {
+            id.hash(&mut hasher);
+            transient_info.hash(&mut hasher);
+        }
+
+        self.resources.histories.len().hash(&mut hasher);
+
+        for (id, history_info) in self.resources.histories.iter() {
+            id.hash(&mut hasher);
+            history_info.hash(&mut hasher);
+        }
+
+        compile_info.queues.len().hash(&mut hasher);
+
+        for queue in compile_info.queues {
+            queue.queue_family_index().hash(&mut hasher);
+        }
+
+        compile_info.detect_hazards.hash(&mut hasher);
+        compile_info.infer_edges.hash(&mut hasher);
+        compile_info.async_compute_policy.hash(&mut hasher);
+
+        hasher.finish()
+    }
+}
+
+impl<W: ?Sized> ExecutableTaskGraph<W> {
+    /// Returns the [fingerprint] of the task graph that was compiled.
+    ///
+    /// [fingerprint]: TaskGraph::fingerprint
+    #[inline]
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        self.schedule.fingerprint
+    }
+
+    /// Returns the schedule of the task graph, which can be used to skip most of the analysis
+    /// when compiling the same task graph again.
+    #[inline]
+    #[must_use]
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::AttachmentInfo,
+        resource::{AccessTypes, HostAccessType, ImageLayoutType},
+        tests::test_queues,
+        QueueFamilyType,
+    };
+    use std::marker::PhantomData;
+    use vulkano::{buffer::BufferCreateInfo, format::Format, image::ImageCreateInfo};
+
+    #[test]
+    fn schedule1() {
+        let (resources, queues) = test_queues!();
+        let queues = queues.iter().collect::<Vec<_>>();
+        let compile_info = CompileInfo {
+            queues: &queues,
+            ..Default::default()
+        };
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer = graph.add_buffer(&BufferCreateInfo::default());
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::VERTEX_ATTRIBUTE_READ)
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+
+        let fingerprint = graph.fingerprint(&compile_info);
+        let graph = unsafe { graph.compile(&compile_info) }.unwrap();
+        assert_eq!(graph.fingerprint(), fingerprint);
+
+        let schedule = graph.schedule().clone();
+        assert_eq!(schedule.fingerprint(), fingerprint);
+        assert_eq!(schedule.topological_order(), [node1.index(), node2.index()],);
+
+        let graph = graph.decompile();
+        assert_eq!(graph.fingerprint(&compile_info), fingerprint);
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                schedule: Some(&schedule),
+                ..compile_info.clone()
+            })
+        }
+        .unwrap();
+        assert_eq!(*graph.schedule(), schedule);
+
+        // A schedule with a different fingerprint must be ignored.
+        let mut graph = graph.decompile();
+        let node3 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::VERTEX_ATTRIBUTE_READ)
+            .build();
+        graph.add_edge(node1, node3).unwrap();
+        assert_ne!(graph.fingerprint(&compile_info), fingerprint);
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                schedule: Some(&schedule),
+                ..compile_info.clone()
+            })
+        }
+        .unwrap();
+        assert_eq!(graph.schedule().topological_order().len(), 3);
+    }
+
+    #[test]
+    fn schedule_host_access() {
+        let (resources, queues) = test_queues!();
+        let queues = queues.iter().collect::<Vec<_>>();
+        let compile_info = CompileInfo {
+            queues: &queues,
+            ..Default::default()
+        };
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let buffer = graph.add_buffer(&BufferCreateInfo::default());
+        graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .buffer_access(buffer, AccessTypes::COPY_TRANSFER_WRITE)
+            .build();
+
+        let graph = unsafe { graph.compile(&compile_info) }.unwrap();
+        let schedule = graph.schedule().clone();
+        let host_read =
+            |schedule: &Schedule| {
+                schedule.end_barriers.iter().flatten().any(|barrier| {
+                    barrier.dst_access_mask & vk::AccessFlags2::HOST_READ.as_raw() != 0
+                })
+            };
+        assert!(!host_read(&schedule));
+
+        // The host read needs a barrier that the schedule doesn't have, so it must be ignored.
+        let mut graph = graph.decompile();
+        graph.add_host_buffer_access(buffer, HostAccessType::Read);
+        assert_ne!(graph.fingerprint(&compile_info), schedule.fingerprint());
+
+        let graph = unsafe {
+            graph.compile(&CompileInfo {
+                schedule: Some(&schedule),
+                ..compile_info.clone()
+            })
+        }
+        .unwrap();
+        assert!(host_read(graph.schedule()));
+
+        // The same goes for a buffer becoming transient, which changes what can alias.
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let _ = graph.add_transient_buffer(&BufferCreateInfo::default());
+        let fingerprint = graph.fingerprint(&compile_info);
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let _ = graph.add_buffer(&BufferCreateInfo::default());
+        assert_ne!(graph.fingerprint(&compile_info), fingerprint);
+    }
+
+    #[test]
+    fn schedule2() {
+        let (resources, queues) = test_queues!();
+        let queues = queues.iter().collect::<Vec<_>>();
+        let compile_info = CompileInfo {
+            queues: &queues,
+            ..Default::default()
+        };
+
+        let mut graph = TaskGraph::<()>::new(&resources);
+        let color_image = graph.add_image(&ImageCreateInfo {
+            format: Format::R8G8B8A8_UNORM,
+            ..Default::default()
+        });
+        let depth_image = graph.add_image(&ImageCreateInfo {
+            format: Format::D16_UNORM,
+            ..Default::default()
+        });
+        let framebuffer = graph.add_framebuffer();
+        let node1 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .framebuffer(framebuffer)
+            .color_attachment(
+                color_image,
+                AccessTypes::COLOR_ATTACHMENT_WRITE,
+                ImageLayoutType::Optimal,
+                &AttachmentInfo {
+                    clear: true,
+                    ..Default::default()
+                },
+            )
+            .build();
+        let node2 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .framebuffer(framebuffer)
+            .color_attachment(
+                color_image,
+                AccessTypes::COLOR_ATTACHMENT_WRITE,
+                ImageLayoutType::Optimal,
+                &AttachmentInfo::default(),
+            )
+            .depth_stencil_attachment(
+                depth_image,
+                AccessTypes::DEPTH_STENCIL_ATTACHMENT_WRITE,
+                ImageLayoutType::Optimal,
+                &AttachmentInfo {
+                    clear: true,
+                    ..Default::default()
+                },
+            )
+            .build();
+        let node3 = graph
+            .create_task_node("", QueueFamilyType::Graphics, PhantomData)
+            .image_access(
+                color_image,
+                AccessTypes::FRAGMENT_SHADER_SAMPLED_READ,
+                ImageLayoutType::Optimal,
+            )
+            .build();
+        graph.add_edge(node1, node2).unwrap();
+        graph.add_edge(node2, node3).unwrap();
+
+        let graph = unsafe { graph.compile(&compile_info) }.unwrap();
+        let schedule = graph.schedule().clone();
+        assert_eq!(schedule.subpasses(), [Some((0, 0)), Some((0, 1)), None],);
+        assert!(!schedule.start_barriers[2].is_empty());
+        let instructions = format!("{:?}", graph.instructions);
+        let barriers = format!("{:?}", graph.barriers);
+
+        // The render pass merging and barrier placement must be taken from the schedule, giving
+        // the same result as compiling from scratch.
+        let graph = unsafe {
+            graph.decompile().compile(&CompileInfo {
+                schedule: Some(&schedule),
+                ..compile_info.clone()
+            })
+        }
+        .unwrap();
+        assert_eq!(*graph.schedule(), schedule);
+        assert_eq!(format!("{:?}", graph.instructions), instructions);
+        assert_eq!(format!("{:?}", graph.barriers), barriers);
+
+        // A schedule with an inconsistent render pass merging must be ignored.
+        let mut corrupted_schedule = schedule.clone();
+        corrupted_schedule.subpasses[1] = Some((0, 2));
+        let graph = unsafe {
+            graph.decompile().compile(&CompileInfo {
+                schedule: Some(&corrupted_schedule),
+                ..compile_info.clone()
+            })
+        }
+        .unwrap();
+        assert_eq!(*graph.schedule(), schedule);
+    }
+
+    #[test]
+    fn stable_hasher() {
+        let mut hasher = StableHasher::new();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        let mut hasher1 = StableHasher::new();
+        hasher1.write_u32(0x0403_0201);
+        hasher1.write_usize(5);
+        let mut hasher2 = StableHasher::new();
+        hasher2.write(&[1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(hasher1.finish(), hasher2.finish());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let barrier = ScheduledBarrier {
+            src_stage_mask: vk::PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT.as_raw(),
+            src_access_mask: vk::AccessFlags2::COLOR_ATTACHMENT_WRITE.as_raw(),
+            dst_stage_mask: vk::PipelineStageFlags2::FRAGMENT_SHADER.as_raw(),
+            dst_access_mask: vk::AccessFlags2::SHADER_SAMPLED_READ.as_raw(),
+            old_layout: vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL.as_raw(),
+            new_layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL.as_raw(),
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            resource: (3, 0x182),
+        };
+        let schedule = Schedule {
+            fingerprint: 0x0123_4567_89ab_cdef,
+            topological_order: vec![1, 0, 2],
+            queue_family_indices: vec![0, 0, 1],
+            subpasses: vec![Some((0, 0)), Some((0, 1)), None],
+            initial_barriers: vec![vec![], vec![barrier]],
+            start_barriers: vec![vec![], vec![], vec![barrier]],
+            end_barriers: vec![vec![barrier], vec![], vec![]],
+        };
+
+        let serialized = serde_json::to_string(&schedule).unwrap();
+        let deserialized: Schedule = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, schedule);
+
+        assert!(serde_json::from_str::<Schedule>("[0, [0], [0]]").is_err());
+    }
+}
@@ -2,9 +2,10 @@
 //! There is also currently no validation except the most bare-bones sanity checks. You may also
 //! get panics in random places.
 
+use collector::Deferred;
 use command_buffer::RecordingCommandBuffer;
 use concurrent_slotmap::{hyaline, Key, SlotId};
-use graph::{CompileInfo, ExecuteError, ResourceMap, TaskGraph};
+use graph::{AsyncComputePolicy, CompileInfo, ExecuteError, ResourceMap, TaskGraph};
 use linear_map::LinearMap;
 use resource::{
     AccessTypes, BufferState, Flight, HostAccessType, ImageLayoutType, ImageState, Resources,
@@ -29,6 +30,7 @@ use vulkano::{
     device::Queue,
     format::ClearValue,
     image::Image,
+    memory::MappedMemoryRange,
     render_pass::Framebuffer,
     swapchain::Swapchain,
     DeviceSize, Validated, ValidationError,
@@ -40,6 +42,7 @@ pub mod descriptor_set;
 pub mod graph;
 mod linear_map;
 pub mod resource;
+pub mod ring_buffer;
 mod slotmap;
 
 /// Creates a [`TaskGraph`] with one task node, compiles it, and executes it.
@@ -117,6 +120,11 @@ pub unsafe fn execute(
             queues: &[queue],
             present_queue: None,
             flight_id,
+            detect_hazards: false,
+            infer_edges: false,
+            task_timestamps: false,
+            async_compute_policy: AsyncComputePolicy::Always,
+            schedule: None,
             _ne: crate::NE,
         })
     }
@@ -149,6 +157,21 @@ pub trait Task: Any + Send + Sync {
     #[allow(unused)]
     fn clear_values(&self, clear_values: &mut ClearValues<'_>, world: &Self::World) {}
 
+    /// If the task node was [set to be conditional], this method is invoked to decide whether the
+    /// task is executed.
+    ///
+    /// This method is invoked once for every execution of the task graph, before any of the tasks
+    /// are executed. If it returns `false`, [`execute`] is not invoked for that execution.
+    ///
+    /// The default implementation returns `true`.
+    ///
+    /// [set to be conditional]: graph::TaskNodeBuilder::conditional
+    /// [`execute`]: Self::execute
+    #[allow(unused)]
+    fn is_enabled(&self, world: &Self::World) -> bool {
+        true
+    }
+
     /// Executes the task, which should record its commands using the provided command buffer and
     /// context.
     ///
@@ -253,6 +276,7 @@ pub struct TaskContext<'a> {
     resource_map: &'a ResourceMap<'a>,
     current_frame_index: u32,
     command_buffers: &'a mut Vec<Arc<raw::CommandBuffer>>,
+    deferreds: &'a mut Vec<Deferred>,
 }
 
 impl<'a> TaskContext<'a> {
@@ -417,7 +441,7 @@ impl<'a> TaskContext<'a> {
         id: Id<Buffer>,
         range: impl RangeBounds<DeviceSize>,
     ) -> Result<&T, Validated<TaskError>> {
-        self.validate_read_buffer(id)?;
+        self.validate_read_buffer(id, "TaskContext::read_buffer")?;
 
         // SAFETY: We checked that the task has read access to the buffer above, which also
         // includes the guarantee that no other tasks can be writing the subbuffer on neither the
@@ -426,14 +450,18 @@ impl<'a> TaskContext<'a> {
         Ok(unsafe { self.read_buffer_unchecked(id, range) }?)
     }
 
-    fn validate_read_buffer(&self, id: Id<Buffer>) -> Result<(), Box<ValidationError>> {
+    fn validate_read_buffer(
+        &self,
+        id: Id<Buffer>,
+        context: &'static str,
+    ) -> Result<(), Box<ValidationError>> {
         if !self
             .resource_map
             .virtual_resources()
             .contains_host_buffer_access(id, HostAccessType::Read)
         {
             return Err(Box::new(ValidationError {
-                context: "TaskContext::read_buffer".into(),
+                context: context.into(),
                 problem: "the task graph does not have an access of type `HostAccessType::Read` \
                     for the buffer"
                     .into(),
@@ -508,6 +536,143 @@ impl<'a> TaskContext<'a> {
         Ok(data)
     }
 
+    /// Queues a read of a portion of the buffer corresponding to `id` for after the current
+    /// [frame] has finished executing, panicking on a validation error or task error.
+    ///
+    /// `f` is called with the contents of the buffer once the [flight]'s fence for the current
+    /// frame has signaled and the frame has been [waited] on. This can be used to read back the
+    /// results of the device's work without stalling, for example for GPU picking, occlusion
+    /// query results or screenshots.
+    ///
+    /// If host read access for the buffer is not accounted for in the [task graph's host access
+    /// set], this method will return an error.
+    ///
+    /// If the memory backing the buffer is not managed by vulkano (i.e. the buffer was created
+    /// by [`RawBuffer::assume_bound`]), then it can't be read using this method and an error will
+    /// be returned.
+    ///
+    /// This is a shortcut for `try_read_back_buffer().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_read_back_buffer`] returns a [`ValidationError`] or [`TaskError`].
+    /// - Panics if the alignment of `T` is greater than 64.
+    /// - Panics if [`Subbuffer::slice`] with the given `range` panics.
+    /// - Panics if [`Subbuffer::reinterpret`] to the given `T` panics.
+    ///
+    /// [frame]: crate::resource::Flight
+    /// [flight]: crate::resource::Flight
+    /// [waited]: crate::resource::Flight::wait
+    /// [task graph's host access set]: crate::graph::TaskGraph::add_host_buffer_access
+    /// [`RawBuffer::assume_bound`]: vulkano::buffer::sys::RawBuffer::assume_bound
+    /// [`try_read_back_buffer`]: Self::try_read_back_buffer
+    #[track_caller]
+    pub fn read_back_buffer<T: BufferContents + ?Sized>(
+        &mut self,
+        id: Id<Buffer>,
+        range: impl RangeBounds<DeviceSize>,
+        f: impl FnOnce(&T) + Send + 'static,
+    ) {
+        self.try_read_back_buffer(id, range, f).unwrap();
+    }
+
+    /// Queues a read of a portion of the buffer corresponding to `id` for after the current
+    /// [frame] has finished executing.
+    ///
+    /// `f` is called with the contents of the buffer once the [flight]'s fence for the current
+    /// frame has signaled and the frame has been [waited] on.
+    ///
+    /// If host read access for the buffer is not accounted for in the [task graph's host access
+    /// set], this method will return an error.
+    ///
+    /// If the memory backing the buffer is not managed by vulkano (i.e. the buffer was created
+    /// by [`RawBuffer::assume_bound`]), then it can't be read using this method and an error will
+    /// be returned.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the alignment of `T` is greater than 64.
+    /// - Panics if [`Subbuffer::slice`] with the given `range` panics.
+    /// - Panics if [`Subbuffer::reinterpret`] to the given `T` panics.
+    ///
+    /// [frame]: crate::resource::Flight
+    /// [flight]: crate::resource::Flight
+    /// [waited]: crate::resource::Flight::wait
+    /// [task graph's host access set]: crate::graph::TaskGraph::add_host_buffer_access
+    /// [`RawBuffer::assume_bound`]: vulkano::buffer::sys::RawBuffer::assume_bound
+    pub fn try_read_back_buffer<T: BufferContents + ?Sized>(
+        &mut self,
+        id: Id<Buffer>,
+        range: impl RangeBounds<DeviceSize>,
+        f: impl FnOnce(&T) + Send + 'static,
+    ) -> Result<(), Validated<TaskError>> {
+        self.validate_read_buffer(id, "TaskContext::read_back_buffer")?;
+
+        assert!(T::LAYOUT.alignment().as_devicesize() <= 64);
+
+        let buffer = self
+            .try_buffer(id)
+            .map_err(TaskError::InvalidSlot)?
+            .buffer();
+        let subbuffer = Subbuffer::from(buffer.clone())
+            .slice(range)
+            .reinterpret::<T>();
+
+        let allocation = match buffer.memory() {
+            BufferMemory::Normal(a) => a,
+            BufferMemory::Sparse => {
+                todo!("`TaskContext::read_back_buffer` doesn't support sparse binding yet");
+            }
+            BufferMemory::External => {
+                return Err(TaskError::HostAccess(HostAccessError::Unmanaged).into());
+            }
+            _ => unreachable!(),
+        };
+
+        match unsafe { allocation.mapped_slice_unchecked(..) } {
+            Ok(_) => {}
+            Err(vulkano::sync::HostAccessError::NotHostMapped) => {
+                return Err(TaskError::HostAccess(HostAccessError::NotHostMapped).into());
+            }
+            Err(vulkano::sync::HostAccessError::OutOfMappedRange) => {
+                return Err(TaskError::HostAccess(HostAccessError::OutOfMappedRange).into());
+            }
+            Err(_) => unreachable!(),
+        }
+
+        self.deferreds.push(Deferred::new(move |_| {
+            let BufferMemory::Normal(allocation) = subbuffer.buffer().memory() else {
+                unreachable!();
+            };
+
+            if allocation.atom_size().is_some() {
+                // SAFETY:
+                // * The frame has finished executing, so all device writes have been made
+                //   available to the host by the host read barrier inserted by the task graph.
+                // * The caller of `ExecutableTaskGraph::execute` must ensure that the buffer isn't
+                //   accessed in any other frame in flight, and the next frame can't start
+                //   executing before the current one has been waited on.
+                let res =
+                    unsafe { allocation.invalidate_range_unchecked(&MappedMemoryRange::default()) };
+
+                if let Err(err) = res {
+                    panic!("failed to invalidate the mapped memory of a read-back buffer: {err}");
+                }
+            }
+
+            let mapped_slice = subbuffer.mapped_slice().unwrap();
+
+            // SAFETY: The frame has finished executing, which means that the device no longer
+            // accesses the buffer.
+            let data_ptr = unsafe { T::ptr_from_slice(mapped_slice) };
+            let data = unsafe { &*data_ptr };
+
+            f(data);
+        }));
+
+        Ok(())
+    }
+
     /// Tries to get write access to a portion of the buffer corresponding to `id`, panicking on a
     /// validation error or task error.
     ///
@@ -881,6 +1046,12 @@ impl<T> Id<T> {
         self.slot.tag() & Id::EXCLUSIVE_BIT != 0
     }
 
+    /// Returns the `n` such that `self` refers to what the history image `id` referred to `n`
+    /// executions ago, or `0` if `self` is not such an ID.
+    fn history_index(self) -> u32 {
+        (self.slot.tag() & Id::HISTORY_MASK) >> Id::HISTORY_SHIFT
+    }
+
     fn erase(self) -> Id {
         unsafe { Id::new(self.slot) }
     }
@@ -916,6 +1087,9 @@ impl Id<Swapchain> {
 impl Id {
     const OBJECT_TYPE_MASK: u32 = 0b111;
 
+    const HISTORY_SHIFT: u32 = 3;
+    const HISTORY_MASK: u32 = 0b111 << Id::HISTORY_SHIFT;
+
     const VIRTUAL_BIT: u32 = 1 << 7;
     const EXCLUSIVE_BIT: u32 = 1 << 6;
 }
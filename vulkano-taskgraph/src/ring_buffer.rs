@@ -0,0 +1,413 @@
+//! A ring buffer for streaming per-frame data, such as uniforms and staging uploads.
+
+use crate::{
+    resource::{Flight, Resources},
+    Id,
+};
+use parking_lot::Mutex;
+use std::{
+    cmp, collections::VecDeque, error::Error, fmt, num::NonZero, ops::Range, ptr, ptr::NonNull,
+    slice, sync::Arc,
+};
+use vulkano::{
+    buffer::{Buffer, BufferContents, BufferMemory, BufferUsage},
+    device::DeviceOwned,
+    memory::{
+        allocator::{align_up, DeviceLayout},
+        DeviceAlignment, MappedMemoryRange, ResourceMemory,
+    },
+    DeviceSize, VulkanError,
+};
+
+/// A ring buffer that hands out regions of a host-visible buffer for the current [frame] in
+/// [flight].
+///
+/// Allocations are made linearly, wrapping around to the beginning of the buffer once the end is
+/// reached. Each allocation belongs to the frame that was [current] at the time it was made, and
+/// the memory of all allocations of a frame is reclaimed at once when that frame has been [waited
+/// on]. As such, there is no per-allocation reference counting or deallocation: an allocation is
+/// simply a range of the buffer, which must not be used after its frame has finished executing.
+/// This makes it well suited for streaming data that is only used for the duration of a frame,
+/// such as uniforms and staging uploads.
+///
+/// The data is written to the buffer by the host as soon as it is allocated, bypassing the task
+/// graph. The memory of a frame is only reused once the fence of the frame has been waited on,
+/// which is why the buffer must not be [accessed by the host] through the task graph, and why
+/// the device must only access the regions of the buffer that were allocated for the frame that
+/// is being executed.
+///
+/// [frame]: Flight
+/// [flight]: Flight
+/// [current]: Flight::current_frame
+/// [waited on]: Flight::wait
+/// [accessed by the host]: crate::graph::TaskGraph::add_host_buffer_access
+#[derive(Debug)]
+pub struct RingBuffer {
+    resources: Arc<Resources>,
+    flight_id: Id<Flight>,
+    buffer_id: Id<Buffer>,
+    buffer: Arc<Buffer>,
+    // The mapped pointer to the start of the buffer's memory.
+    mapped_ptr: NonNull<u8>,
+    size: DeviceSize,
+    // The alignment of the allocations, which satisfies the offset alignments required by the
+    // buffer usage as well as the non-coherent atom size of the memory.
+    alignment: DeviceAlignment,
+    atom_size: Option<DeviceAlignment>,
+    state: Mutex<RingBufferState>,
+}
+
+// SAFETY: The mapped memory is only accessed through disjoint regions which are handed out while
+// the state is locked.
+unsafe impl Send for RingBuffer {}
+unsafe impl Sync for RingBuffer {}
+
+#[derive(Debug)]
+struct RingBufferState {
+    // The offset at which the next allocation starts, unless it needs to wrap around.
+    head: DeviceSize,
+    // The number of bytes between the oldest allocation that is still in use and `head`,
+    // including the bytes that were skipped when wrapping around.
+    used: DeviceSize,
+    // The frames whose memory hasn't been reclaimed yet, along with the number of bytes of the
+    // ring buffer that each of them used, in the order in which they were allocated.
+    frames: VecDeque<(u64, DeviceSize)>,
+}
+
+impl RingBuffer {
+    /// Creates a new `RingBuffer` which suballocates the buffer corresponding to `buffer_id`,
+    /// reclaiming the memory of each frame once the frame has been waited on using the flight
+    /// corresponding to `flight_id`.
+    ///
+    /// The buffer must be exclusively used through the ring buffer from now on. It is not removed
+    /// when the ring buffer is dropped.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `flight_id` or `buffer_id` is invalid.
+    /// - Panics if the buffer is not backed by normal memory that is host-mapped in full.
+    #[must_use]
+    pub fn new(resources: &Arc<Resources>, flight_id: Id<Flight>, buffer_id: Id<Buffer>) -> Self {
+        let _ = resources.flight(flight_id);
+        let buffer = resources.buffer(buffer_id).buffer().clone();
+
+        let memory = buffer_memory(&buffer);
+        let size = buffer.size();
+        let mapped_ptr = memory
+            .mapped_slice(..size)
+            .unwrap()
+            .expect("the memory of the buffer must be host-mapped")
+            .cast::<u8>();
+
+        let properties = buffer.device().physical_device().properties();
+        let usage = buffer.usage();
+        let atom_size = memory.atom_size();
+        let alignment = [
+            usage
+                .intersects(BufferUsage::UNIFORM_TEXEL_BUFFER | BufferUsage::STORAGE_TEXEL_BUFFER)
+                .then_some(properties.min_texel_buffer_offset_alignment),
+            usage
+                .contains(BufferUsage::UNIFORM_BUFFER)
+                .then_some(properties.min_uniform_buffer_offset_alignment),
+            usage
+                .contains(BufferUsage::STORAGE_BUFFER)
+                .then_some(properties.min_storage_buffer_offset_alignment),
+            atom_size,
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(DeviceAlignment::MIN);
+
+        RingBuffer {
+            resources: resources.clone(),
+            flight_id,
+            buffer_id,
+            buffer,
+            mapped_ptr,
+            size,
+            alignment,
+            atom_size,
+            state: Mutex::new(RingBufferState {
+                head: 0,
+                used: 0,
+                frames: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the ID of the flight that the ring buffer is tied to.
+    #[inline]
+    #[must_use]
+    pub fn flight_id(&self) -> Id<Flight> {
+        self.flight_id
+    }
+
+    /// Returns the ID of the buffer that the ring buffer suballocates.
+    #[inline]
+    #[must_use]
+    pub fn buffer_id(&self) -> Id<Buffer> {
+        self.buffer_id
+    }
+
+    /// Returns the size of the ring buffer in bytes.
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> DeviceSize {
+        self.size
+    }
+
+    /// Allocates a region for `data` in the current frame and copies `data` into it.
+    ///
+    /// The region is aligned to the alignment of `T` as well as to the offset alignment required
+    /// for the usage of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `data` is zero-sized.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`RingBufferError::Full`] if there is not enough free space in the ring buffer.
+    /// - Returns [`RingBufferError::VulkanError`] if flushing the non-coherent memory failed.
+    pub fn write<T: BufferContents + ?Sized>(
+        &self,
+        data: &T,
+    ) -> Result<RingAllocation, RingBufferError> {
+        let size =
+            NonZero::new(size_of_val(data) as DeviceSize).expect("`data` must not be zero-sized");
+        let layout = DeviceLayout::new(size, T::LAYOUT.alignment()).unwrap();
+
+        self.write_with(layout, |slice| {
+            // SAFETY: `slice` is exactly as large as `data` and is located in the mapped memory,
+            // which can't overlap with `data`.
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    ptr::from_ref(data).cast::<u8>(),
+                    slice.as_mut_ptr(),
+                    slice.len(),
+                )
+            };
+        })
+    }
+
+    /// Allocates a region for the given `layout` in the current frame and calls `f` with a
+    /// mutable slice of the region in order to write to it.
+    ///
+    /// The region is aligned to the alignment of `layout` as well as to the offset alignment
+    /// required for the usage of the buffer. The previous contents of the region are unspecified.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`RingBufferError::Full`] if there is not enough free space in the ring buffer.
+    /// - Returns [`RingBufferError::VulkanError`] if flushing the non-coherent memory failed.
+    pub fn write_with(
+        &self,
+        layout: DeviceLayout,
+        f: impl FnOnce(&mut [u8]),
+    ) -> Result<RingAllocation, RingBufferError> {
+        let allocation = self.allocate(layout)?;
+
+        // SAFETY: `allocate` ensures that the region falls within the mapped memory.
+        let ptr = unsafe { self.mapped_ptr.add(allocation.offset as usize) };
+
+        // SAFETY: `allocate` ensures that the region isn't in use by the device or by any other
+        // allocation.
+        let slice = unsafe { slice::from_raw_parts_mut(ptr.as_ptr(), allocation.size as usize) };
+
+        f(slice);
+
+        if let Some(atom_size) = self.atom_size {
+            let memory = buffer_memory(&self.buffer);
+            let size = cmp::min(
+                align_up(allocation.size, atom_size),
+                memory.size() - allocation.offset,
+            );
+
+            // SAFETY: The region isn't in use by the device, and the atom-aligned range is
+            // reserved as part of the allocation.
+            unsafe {
+                memory.flush_range_unchecked(&MappedMemoryRange {
+                    offset: allocation.offset,
+                    size: Some(size),
+                    ..Default::default()
+                })
+            }
+            .map_err(RingBufferError::VulkanError)?;
+        }
+
+        Ok(allocation)
+    }
+
+    fn allocate(&self, layout: DeviceLayout) -> Result<RingAllocation, RingBufferError> {
+        let alignment = cmp::max(layout.alignment(), self.alignment);
+        // Reserving whole atoms makes sure that flushing the allocation can't affect another one.
+        let reserved_size = align_up(
+            layout.size(),
+            self.atom_size.unwrap_or(DeviceAlignment::MIN),
+        );
+
+        let flight = self.resources.flight(self.flight_id);
+        let current_frame = flight.current_frame();
+        let biased_frame_offset = u64::from(flight.frame_count()) + 1;
+        let state = &mut *self.state.lock();
+
+        while let Some(&(frame, used)) = state.frames.front() {
+            if frame == current_frame
+                || !flight.is_biased_frame_complete(frame + biased_frame_offset)
+            {
+                break;
+            }
+
+            state.used -= used;
+            state.frames.pop_front();
+        }
+
+        if state.used == 0 {
+            state.head = 0;
+        }
+
+        let mut offset = align_up(state.head, alignment);
+
+        if offset + reserved_size > self.size {
+            // Wrap around to the beginning, skipping the remainder of the buffer.
+            offset = 0;
+        }
+
+        let used = if offset >= state.head {
+            offset + reserved_size - state.head
+        } else {
+            self.size - state.head + reserved_size
+        };
+
+        if state.used + used > self.size {
+            return Err(RingBufferError::Full);
+        }
+
+        state.head = offset + reserved_size;
+        state.used += used;
+
+        match state.frames.back_mut() {
+            Some((frame, frame_used)) if *frame == current_frame => *frame_used += used,
+            _ => state.frames.push_back((current_frame, used)),
+        }
+
+        Ok(RingAllocation {
+            offset,
+            size: layout.size(),
+        })
+    }
+}
+
+fn buffer_memory(buffer: &Buffer) -> &ResourceMemory {
+    match buffer.memory() {
+        BufferMemory::Normal(memory) => memory,
+        _ => panic!("the buffer must be backed by normal memory"),
+    }
+}
+
+/// An allocation made using a [`RingBuffer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RingAllocation {
+    /// The offset of the allocation from the start of the buffer.
+    pub offset: DeviceSize,
+
+    /// The size of the allocation. This is exactly equal to the requested size.
+    pub size: DeviceSize,
+}
+
+impl RingAllocation {
+    /// Returns the allocation as a `DeviceSize` range.
+    ///
+    /// This is identical to `self.offset..self.offset + self.size`.
+    #[inline]
+    pub fn as_range(&self) -> Range<DeviceSize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+/// Error that can happen when writing to a [`RingBuffer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RingBufferError {
+    /// There is not enough free space in the ring buffer. Either the ring buffer is too small for
+    /// the amount of data written per frame, or the oldest frame in flight hasn't been waited on.
+    Full,
+
+    /// Flushing the memory failed.
+    VulkanError(VulkanError),
+}
+
+impl fmt::Display for RingBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => f.write_str("the ring buffer doesn't have enough free space"),
+            Self::VulkanError(_) => f.write_str("a runtime error occurred"),
+        }
+    }
+}
+
+impl Error for RingBufferError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Full => None,
+            Self::VulkanError(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_queues;
+    use vulkano::{
+        buffer::BufferCreateInfo,
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter},
+    };
+
+    #[test]
+    fn ring_buffer() {
+        let (resources, _) = test_queues!();
+
+        let flight_id = resources.create_flight(2).unwrap();
+        let buffer_id = resources
+            .create_buffer(
+                &BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_SRC,
+                    ..Default::default()
+                },
+                &AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                        | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                DeviceLayout::from_size_alignment(1024, 1).unwrap(),
+            )
+            .unwrap();
+        let ring_buffer = RingBuffer::new(&resources, flight_id, buffer_id);
+
+        if ring_buffer.alignment.as_devicesize() > 256 {
+            return;
+        }
+
+        let data = [1u8; 256];
+        let allocation1 = ring_buffer.write(&data).unwrap();
+        let allocation2 = ring_buffer.write(&data).unwrap();
+        let allocation3 = ring_buffer.write(&data).unwrap();
+        assert_eq!(allocation1.as_range(), 0..256);
+        assert_eq!(allocation2.as_range(), 256..512);
+        assert_eq!(allocation3.as_range(), 512..768);
+        assert_eq!(ring_buffer.write(&[0u8; 512]), Err(RingBufferError::Full),);
+
+        let flight = resources.flight(flight_id);
+
+        // The memory of frame 0 must not be reclaimed while it's in flight.
+        unsafe { flight.next_frame() };
+        assert_eq!(ring_buffer.write(&[0u8; 512]), Err(RingBufferError::Full),);
+
+        unsafe { flight.next_frame() };
+        flight.wait(None).unwrap();
+
+        let allocation4 = ring_buffer.write(&[2u8; 512]).unwrap();
+        assert_eq!(allocation4.as_range(), 0..512);
+    }
+}
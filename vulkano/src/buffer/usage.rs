@@ -119,19 +119,19 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_encode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can hold sampler and combined image sampler descriptors, and can be bound as a
+    /// descriptor buffer.
     SAMPLER_DESCRIPTOR_BUFFER = SAMPLER_DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can hold descriptors other than samplers, and can be bound as a descriptor
+    /// buffer.
     RESOURCE_DESCRIPTOR_BUFFER = RESOURCE_DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -16,17 +16,21 @@ use crate::{
     },
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
     device::{Device, DeviceOwned},
-    image::{view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange},
+    image::{
+        view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange, SampleCount,
+    },
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilOps},
             fragment_shading_rate::FragmentShadingRateState,
             input_assembly::PrimitiveTopology,
             rasterization::{
-                ConservativeRasterizationMode, CullMode, DepthBiasState, FrontFace, LineStipple,
+                ConservativeRasterizationMode, CullMode, DepthBiasState, FrontFace,
+                LineRasterizationMode, LineStipple, PolygonMode, ProvokingVertexMode,
             },
             subpass::OwnedPipelineRenderingCreateInfo,
+            tessellation::TessellationDomainOrigin,
             vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
@@ -37,6 +41,7 @@ use crate::{
     range_map::RangeMap,
     range_set::RangeSet,
     render_pass::{Framebuffer, Subpass},
+    shader::{object::ShaderObject, ShaderStage},
     sync::{
         AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
         PipelineStageAccessFlags, PipelineStages,
@@ -1401,6 +1406,7 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
     pub(in crate::command_buffer) pipeline_compute: Option<Arc<ComputePipeline>>,
     pub(in crate::command_buffer) pipeline_graphics: Option<Arc<GraphicsPipeline>>,
     pub(in crate::command_buffer) pipeline_ray_tracing: Option<Arc<RayTracingPipeline>>,
+    pub(in crate::command_buffer) shaders: HashMap<ShaderStage, Option<Arc<ShaderObject>>>,
     pub(in crate::command_buffer) vertex_buffers: HashMap<u32, Subbuffer<[u8]>>,
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
@@ -1435,9 +1441,23 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
     pub(in crate::command_buffer) vertex_input: Option<VertexInputState>,
     pub(in crate::command_buffer) viewport: HashMap<u32, Viewport>,
     pub(in crate::command_buffer) viewport_with_count: Option<SmallVec<[Viewport; 2]>>,
+    pub(in crate::command_buffer) tessellation_domain_origin: Option<TessellationDomainOrigin>,
+    pub(in crate::command_buffer) depth_clamp_enable: Option<bool>,
+    pub(in crate::command_buffer) polygon_mode: Option<PolygonMode>,
+    pub(in crate::command_buffer) rasterization_samples: Option<SampleCount>,
+    pub(in crate::command_buffer) sample_mask: Option<[u32; 2]>,
+    pub(in crate::command_buffer) alpha_to_coverage_enable: Option<bool>,
+    pub(in crate::command_buffer) alpha_to_one_enable: Option<bool>,
+    pub(in crate::command_buffer) logic_op_enable: Option<bool>,
+    pub(in crate::command_buffer) color_blend_enable: HashMap<u32, bool>,
+    pub(in crate::command_buffer) color_blend_equation: HashMap<u32, AttachmentBlend>,
+    pub(in crate::command_buffer) color_write_mask: HashMap<u32, ColorComponents>,
     pub(in crate::command_buffer) conservative_rasterization_mode:
         Option<ConservativeRasterizationMode>,
     pub(in crate::command_buffer) extra_primitive_overestimation_size: Option<f32>,
+    pub(in crate::command_buffer) provoking_vertex_mode: Option<ProvokingVertexMode>,
+    pub(in crate::command_buffer) line_rasterization_mode: Option<LineRasterizationMode>,
+    pub(in crate::command_buffer) line_stipple_enable: Option<bool>,
     pub(in crate::command_buffer) fragment_shading_rate: Option<FragmentShadingRateState>,
 
     // Active queries
@@ -1452,6 +1472,14 @@ impl CommandBufferBuilderState {
         }
     }
 
+    /// Returns the shader object that is bound to `stage`, if any.
+    pub(in crate::command_buffer) fn shader(
+        &self,
+        stage: ShaderStage,
+    ) -> Option<&Arc<ShaderObject>> {
+        self.shaders.get(&stage).and_then(Option::as_ref)
+    }
+
     pub(in crate::command_buffer) fn reset_dynamic_states(
         &mut self,
         states: impl IntoIterator<Item = DynamicState>,
@@ -1495,40 +1523,41 @@ impl CommandBufferBuilderState {
                 // DynamicState::ViewportShadingRatePalette => todo!(),
                 // DynamicState::ViewportWScaling => todo!(),
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
-                // DynamicState::TessellationDomainOrigin => todo!(),
-                // DynamicState::DepthClampEnable => todo!(),
-                // DynamicState::PolygonMode => todo!(),
-                // DynamicState::RasterizationSamples => todo!(),
-                // DynamicState::SampleMask => todo!(),
-                // DynamicState::AlphaToCoverageEnable => todo!(),
-                // DynamicState::AlphaToOneEnable => todo!(),
-                // DynamicState::LogicOpEnable => todo!(),
-                // DynamicState::ColorBlendEnable => todo!(),
-                // DynamicState::ColorBlendEquation => todo!(),
-                // DynamicState::ColorWriteMask => todo!(),
+                DynamicState::TessellationDomainOrigin => self.tessellation_domain_origin = None,
+                DynamicState::DepthClampEnable => self.depth_clamp_enable = None,
+                DynamicState::PolygonMode => self.polygon_mode = None,
+                DynamicState::RasterizationSamples => self.rasterization_samples = None,
+                DynamicState::SampleMask => self.sample_mask = None,
+                DynamicState::AlphaToCoverageEnable => self.alpha_to_coverage_enable = None,
+                DynamicState::AlphaToOneEnable => self.alpha_to_one_enable = None,
+                DynamicState::LogicOpEnable => self.logic_op_enable = None,
+                DynamicState::ColorBlendEnable => self.color_blend_enable.clear(),
+                DynamicState::ColorBlendEquation => self.color_blend_equation.clear(),
+                DynamicState::ColorWriteMask => self.color_write_mask.clear(),
                 // DynamicState::RasterizationStream => todo!(),
                 DynamicState::ConservativeRasterizationMode => {
                     self.conservative_rasterization_mode = None
                 }
                 DynamicState::ExtraPrimitiveOverestimationSize => {
                     self.extra_primitive_overestimation_size = None
-                } /* DynamicState::DepthClipEnable => todo!(),
-                   * DynamicState::SampleLocationsEnable => todo!(),
-                   * DynamicState::ColorBlendAdvanced => todo!(),
-                   * DynamicState::ProvokingVertexMode => todo!(),
-                   * DynamicState::LineRasterizationMode => todo!(),
-                   * DynamicState::LineStippleEnable => todo!(),
-                   * DynamicState::DepthClipNegativeOneToOne => todo!(),
-                   * DynamicState::ViewportWScalingEnable => todo!(),
-                   * DynamicState::ViewportSwizzle => todo!(),
-                   * DynamicState::CoverageToColorEnable => todo!(),
-                   * DynamicState::CoverageToColorLocation => todo!(),
-                   * DynamicState::CoverageModulationMode => todo!(),
-                   * DynamicState::CoverageModulationTableEnable => todo!(),
-                   * DynamicState::CoverageModulationTable => todo!(),
-                   * DynamicState::ShadingRateImageEnable => todo!(),
-                   * DynamicState::RepresentativeFragmentTestEnable => todo!(),
-                   * DynamicState::CoverageReductionMode => todo!(), */
+                }
+                // DynamicState::DepthClipEnable => todo!(),
+                // DynamicState::SampleLocationsEnable => todo!(),
+                // DynamicState::ColorBlendAdvanced => todo!(),
+                DynamicState::ProvokingVertexMode => self.provoking_vertex_mode = None,
+                DynamicState::LineRasterizationMode => self.line_rasterization_mode = None,
+                DynamicState::LineStippleEnable => self.line_stipple_enable = None,
+                /* DynamicState::DepthClipNegativeOneToOne => todo!(),
+                 * DynamicState::ViewportWScalingEnable => todo!(),
+                 * DynamicState::ViewportSwizzle => todo!(),
+                 * DynamicState::CoverageToColorEnable => todo!(),
+                 * DynamicState::CoverageToColorLocation => todo!(),
+                 * DynamicState::CoverageModulationMode => todo!(),
+                 * DynamicState::CoverageModulationTableEnable => todo!(),
+                 * DynamicState::CoverageModulationTable => todo!(),
+                 * DynamicState::ShadingRateImageEnable => todo!(),
+                 * DynamicState::RepresentativeFragmentTestEnable => todo!(),
+                 * DynamicState::CoverageReductionMode => todo!(), */
             }
         }
     }
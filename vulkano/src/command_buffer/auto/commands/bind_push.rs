@@ -15,6 +15,7 @@ use crate::{
         graphics::vertex_input::VertexBuffersCollection, ray_tracing::RayTracingPipeline,
         ComputePipeline, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
     },
+    shader::{object::ShaderObject, ShaderStage},
     DeviceSize, ValidationError,
 };
 use smallvec::SmallVec;
@@ -356,6 +357,7 @@ impl<L> AutoCommandBufferBuilder<L> {
         pipeline: Arc<ComputePipeline>,
     ) -> &mut Self {
         self.builder_state.pipeline_compute = Some(pipeline.clone());
+        self.builder_state.shaders.remove(&ShaderStage::Compute);
         self.add_command(
             "bind_pipeline_compute",
             Default::default(),
@@ -399,6 +401,9 @@ impl<L> AutoCommandBufferBuilder<L> {
         self.builder_state
             .reset_dynamic_states(pipeline.fixed_state().iter().copied());
         self.builder_state.pipeline_graphics = Some(pipeline.clone());
+        self.builder_state
+            .shaders
+            .retain(|&stage, _| stage == ShaderStage::Compute);
         self.add_command(
             "bind_pipeline_graphics",
             Default::default(),
@@ -436,6 +441,74 @@ impl<L> AutoCommandBufferBuilder<L> {
         self
     }
 
+    /// Binds shader objects for future dispatch or draw calls.
+    ///
+    /// Each element of `shaders` is bound to the shader stage in the corresponding element of
+    /// `stages`. If an element of `shaders` is `None`, then no shader is bound to that stage.
+    ///
+    /// Binding a shader to the compute stage replaces any bound compute pipeline, and binding a
+    /// shader to any other stage replaces any bound graphics pipeline. When drawing with shader
+    /// objects, a shader (or `None`) must have been bound to every graphics stage that the device
+    /// supports, and all of the state that would otherwise be provided by the graphics pipeline
+    /// must have been set dynamically.
+    pub fn bind_shaders(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<Arc<ShaderObject>>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_shaders(stages, shaders)?;
+
+        Ok(unsafe { self.bind_shaders_unchecked(stages, shaders) })
+    }
+
+    fn validate_bind_shaders(
+        &self,
+        stages: &[ShaderStage],
+        shaders: &[Option<Arc<ShaderObject>>],
+    ) -> Result<(), Box<ValidationError>> {
+        let shaders = shaders
+            .iter()
+            .map(Option::as_deref)
+            .collect::<SmallVec<[_; 5]>>();
+        self.inner.validate_bind_shaders(stages, &shaders)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_shaders_unchecked(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<Arc<ShaderObject>>],
+    ) -> &mut Self {
+        for (&stage, shader) in stages.iter().zip(shaders) {
+            if stage == ShaderStage::Compute {
+                self.builder_state.pipeline_compute = None;
+            } else {
+                self.builder_state.pipeline_graphics = None;
+            }
+
+            self.builder_state.shaders.insert(stage, shader.clone());
+        }
+
+        let stages: SmallVec<[_; 5]> = stages.iter().copied().collect();
+        let shaders: SmallVec<[_; 5]> = shaders.iter().cloned().collect();
+
+        self.add_command(
+            "bind_shaders",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                let shaders = shaders
+                    .iter()
+                    .map(Option::as_deref)
+                    .collect::<SmallVec<[_; 5]>>();
+                unsafe { out.bind_shaders_unchecked(&stages, &shaders) };
+            },
+        );
+
+        self
+    }
+
     /// Binds vertex buffers for future draw calls.
     pub fn bind_vertex_buffers(
         &mut self,
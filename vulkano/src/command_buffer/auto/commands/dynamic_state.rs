@@ -1,14 +1,17 @@
 use crate::{
     command_buffer::{sys::RecordingCommandBuffer, AutoCommandBufferBuilder},
+    image::SampleCount,
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
             fragment_shading_rate::{FragmentShadingRateCombinerOp, FragmentShadingRateState},
             input_assembly::PrimitiveTopology,
             rasterization::{
-                ConservativeRasterizationMode, CullMode, DepthBiasState, FrontFace, LineStipple,
+                ConservativeRasterizationMode, CullMode, DepthBiasState, FrontFace,
+                LineRasterizationMode, LineStipple, PolygonMode, ProvokingVertexMode,
             },
+            tessellation::TessellationDomainOrigin,
             vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
@@ -1291,6 +1294,552 @@ impl<L> AutoCommandBufferBuilder<L> {
         self
     }
 
+    /// Sets the dynamic tessellation domain origin for future draw calls.
+    pub fn set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_tessellation_domain_origin(domain_origin)?;
+
+        Ok(unsafe { self.set_tessellation_domain_origin_unchecked(domain_origin) })
+    }
+
+    fn validate_set_tessellation_domain_origin(
+        &self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_tessellation_domain_origin(domain_origin)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::TessellationDomainOrigin)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_tessellation_domain_origin_unchecked(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        self.builder_state.tessellation_domain_origin = Some(domain_origin);
+
+        self.add_command(
+            "set_tessellation_domain_origin",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_tessellation_domain_origin_unchecked(domain_origin) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether dynamic depth clamping is enabled for future draw calls.
+    pub fn set_depth_clamp_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_depth_clamp_enable(enable)?;
+
+        Ok(unsafe { self.set_depth_clamp_enable_unchecked(enable) })
+    }
+
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_depth_clamp_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::DepthClampEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.depth_clamp_enable = Some(enable);
+
+        self.add_command(
+            "set_depth_clamp_enable",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_depth_clamp_enable_unchecked(enable) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic polygon mode for future draw calls.
+    pub fn set_polygon_mode(
+        &mut self,
+        polygon_mode: PolygonMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_polygon_mode(polygon_mode)?;
+
+        Ok(unsafe { self.set_polygon_mode_unchecked(polygon_mode) })
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_polygon_mode(polygon_mode)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::PolygonMode)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.builder_state.polygon_mode = Some(polygon_mode);
+
+        self.add_command(
+            "set_polygon_mode",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_polygon_mode_unchecked(polygon_mode) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic number of rasterization samples for future draw calls.
+    pub fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterization_samples(rasterization_samples)?;
+
+        Ok(unsafe { self.set_rasterization_samples_unchecked(rasterization_samples) })
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_rasterization_samples(rasterization_samples)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::RasterizationSamples)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        self.builder_state.rasterization_samples = Some(rasterization_samples);
+
+        self.add_command(
+            "set_rasterization_samples",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_rasterization_samples_unchecked(rasterization_samples) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic sample mask for future draw calls.
+    pub fn set_sample_mask(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_sample_mask(samples, sample_mask)?;
+
+        Ok(unsafe { self.set_sample_mask_unchecked(samples, sample_mask) })
+    }
+
+    fn validate_set_sample_mask(
+        &self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_sample_mask(samples, sample_mask)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::SampleMask)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_mask_unchecked(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        self.builder_state.sample_mask = Some(sample_mask);
+
+        self.add_command(
+            "set_sample_mask",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_sample_mask_unchecked(samples, sample_mask) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether dynamic alpha-to-coverage is enabled for future draw calls.
+    pub fn set_alpha_to_coverage_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_alpha_to_coverage_enable(enable)?;
+
+        Ok(unsafe { self.set_alpha_to_coverage_enable_unchecked(enable) })
+    }
+
+    fn validate_set_alpha_to_coverage_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_alpha_to_coverage_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::AlphaToCoverageEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.alpha_to_coverage_enable = Some(enable);
+
+        self.add_command(
+            "set_alpha_to_coverage_enable",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_alpha_to_coverage_enable_unchecked(enable) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether dynamic alpha-to-one is enabled for future draw calls.
+    pub fn set_alpha_to_one_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_alpha_to_one_enable(enable)?;
+
+        Ok(unsafe { self.set_alpha_to_one_enable_unchecked(enable) })
+    }
+
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_alpha_to_one_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::AlphaToOneEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.alpha_to_one_enable = Some(enable);
+
+        self.add_command(
+            "set_alpha_to_one_enable",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_alpha_to_one_enable_unchecked(enable) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether dynamic logic ops are enabled for future draw calls.
+    pub fn set_logic_op_enable(&mut self, enable: bool) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_logic_op_enable(enable)?;
+
+        Ok(unsafe { self.set_logic_op_enable_unchecked(enable) })
+    }
+
+    fn validate_set_logic_op_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_logic_op_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::LogicOpEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_logic_op_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.logic_op_enable = Some(enable);
+
+        self.add_command(
+            "set_logic_op_enable",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_logic_op_enable_unchecked(enable) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether dynamic color blending should be enabled for each attachment in the
+    /// framebuffer, starting at `first_attachment` for future draw calls.
+    pub fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: SmallVec<[bool; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_enable(first_attachment, &enables)?;
+
+        Ok(unsafe { self.set_color_blend_enable_unchecked(first_attachment, enables) })
+    }
+
+    fn validate_set_color_blend_enable(
+        &self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_blend_enable(first_attachment, enables)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ColorBlendEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: SmallVec<[bool; 4]>,
+    ) -> &mut Self {
+        for (num, enable) in enables.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_blend_enable.insert(num, *enable);
+        }
+
+        self.add_command(
+            "set_color_blend_enable",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_color_blend_enable_unchecked(first_attachment, &enables) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic color blend equation for each attachment in the framebuffer, starting at
+    /// `first_attachment` for future draw calls.
+    pub fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: SmallVec<[AttachmentBlend; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_equation(first_attachment, &equations)?;
+
+        Ok(unsafe { self.set_color_blend_equation_unchecked(first_attachment, equations) })
+    }
+
+    fn validate_set_color_blend_equation(
+        &self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_blend_equation(first_attachment, equations)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ColorBlendEquation)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_equation_unchecked(
+        &mut self,
+        first_attachment: u32,
+        equations: SmallVec<[AttachmentBlend; 4]>,
+    ) -> &mut Self {
+        for (num, equation) in equations.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state
+                .color_blend_equation
+                .insert(num, equation.clone());
+        }
+
+        self.add_command(
+            "set_color_blend_equation",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_color_blend_equation_unchecked(first_attachment, &equations) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic color write mask for each attachment in the framebuffer, starting at
+    /// `first_attachment` for future draw calls.
+    pub fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        masks: SmallVec<[ColorComponents; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_write_mask(first_attachment, &masks)?;
+
+        Ok(unsafe { self.set_color_write_mask_unchecked(first_attachment, masks) })
+    }
+
+    fn validate_set_color_write_mask(
+        &self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_write_mask(first_attachment, masks)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ColorWriteMask)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        masks: SmallVec<[ColorComponents; 4]>,
+    ) -> &mut Self {
+        for (num, mask) in masks.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_write_mask.insert(num, *mask);
+        }
+
+        self.add_command(
+            "set_color_write_mask",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_color_write_mask_unchecked(first_attachment, &masks) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic provoking vertex mode for future draw calls.
+    pub fn set_provoking_vertex_mode(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_provoking_vertex_mode(provoking_vertex_mode)?;
+
+        Ok(unsafe { self.set_provoking_vertex_mode_unchecked(provoking_vertex_mode) })
+    }
+
+    fn validate_set_provoking_vertex_mode(
+        &self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_provoking_vertex_mode(provoking_vertex_mode)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ProvokingVertexMode)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_provoking_vertex_mode_unchecked(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        self.builder_state.provoking_vertex_mode = Some(provoking_vertex_mode);
+
+        self.add_command(
+            "set_provoking_vertex_mode",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_provoking_vertex_mode_unchecked(provoking_vertex_mode) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic line rasterization mode for future draw calls.
+    pub fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_line_rasterization_mode(line_rasterization_mode)?;
+
+        Ok(unsafe { self.set_line_rasterization_mode_unchecked(line_rasterization_mode) })
+    }
+
+    fn validate_set_line_rasterization_mode(
+        &self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_line_rasterization_mode(line_rasterization_mode)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::LineRasterizationMode)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_rasterization_mode_unchecked(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        self.builder_state.line_rasterization_mode = Some(line_rasterization_mode);
+
+        self.add_command(
+            "set_line_rasterization_mode",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_line_rasterization_mode_unchecked(line_rasterization_mode) };
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether dynamic line stippling is enabled for future draw calls.
+    pub fn set_line_stipple_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_line_stipple_enable(enable)?;
+
+        Ok(unsafe { self.set_line_stipple_enable_unchecked(enable) })
+    }
+
+    fn validate_set_line_stipple_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_line_stipple_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::LineStippleEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_stipple_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.line_stipple_enable = Some(enable);
+
+        self.add_command(
+            "set_line_stipple_enable",
+            Default::default(),
+            move |out: &mut RecordingCommandBuffer| {
+                unsafe { out.set_line_stipple_enable_unchecked(enable) };
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic fragment shading rate for future draw calls.
     #[inline]
     pub fn set_fragment_shading_rate(
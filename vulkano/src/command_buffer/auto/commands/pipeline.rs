@@ -19,15 +19,20 @@ use crate::{
     image::{sampler::Sampler, view::ImageView, ImageAspects, ImageLayout, SampleCount},
     pipeline::{
         graphics::{
+            color_blend::BlendFactor,
             input_assembly::PrimitiveTopology,
+            rasterization::PolygonMode,
             subpass::PipelineSubpassType,
-            vertex_input::{RequiredVertexInputsVUIDs, VertexInputRate},
+            vertex_input::{RequiredVertexInputsVUIDs, VertexInputRate, VertexInputState},
         },
         ray_tracing::ShaderBindingTableAddresses,
         DynamicState, GraphicsPipeline, Pipeline, PipelineLayout,
     },
     query::QueryType,
-    shader::{DescriptorBindingRequirements, DescriptorIdentifier, ShaderStages},
+    shader::{
+        object::ShaderObject, DescriptorBindingRequirements, DescriptorIdentifier, ShaderStage,
+        ShaderStages,
+    },
     sync::{PipelineStageAccess, PipelineStageAccessFlags},
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
 };
@@ -84,18 +89,13 @@ impl<L> AutoCommandBufferBuilder<L> {
             }));
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no compute pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDispatch-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
+        let pipeline = self.compute_pipeline_or_shader().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no compute pipeline or compute shader object is currently bound".into(),
+                vuids: &["VUID-vkCmdDispatch-None-08606"],
+                ..Default::default()
+            })
+        })?;
 
         const VUID_TYPE: VUIDType = VUIDType::Dispatch;
         self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
@@ -106,12 +106,7 @@ impl<L> AutoCommandBufferBuilder<L> {
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn dispatch_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .unwrap()
-            .as_ref();
+        let pipeline = self.compute_pipeline_or_shader().unwrap();
 
         let mut used_resources = Vec::new();
         self.add_descriptor_sets_resources(&mut used_resources, pipeline);
@@ -163,18 +158,13 @@ impl<L> AutoCommandBufferBuilder<L> {
             }));
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no compute pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDispatchIndirect-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
+        let pipeline = self.compute_pipeline_or_shader().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no compute pipeline or compute shader object is currently bound".into(),
+                vuids: &["VUID-vkCmdDispatchIndirect-None-08606"],
+                ..Default::default()
+            })
+        })?;
 
         const VUID_TYPE: VUIDType = VUIDType::DispatchIndirect;
         self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
@@ -188,12 +178,7 @@ impl<L> AutoCommandBufferBuilder<L> {
         &mut self,
         indirect_buffer: Subbuffer<[DispatchIndirectCommand]>,
     ) -> &mut Self {
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .unwrap()
-            .as_ref();
+        let pipeline = self.compute_pipeline_or_shader().unwrap();
 
         let mut used_resources = Vec::new();
         self.add_descriptor_sets_resources(&mut used_resources, pipeline);
@@ -262,29 +247,23 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDraw-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::Draw;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
 
-        let view_mask = match pipeline.subpass() {
-            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
-            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+        let view_mask = if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+            match pipeline.subpass() {
+                PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+                PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+            }
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, false, render_pass_state)?;
+
+            render_pass_state.rendering_info.as_ref().view_mask
         };
 
         if view_mask != 0 {
@@ -304,14 +283,7 @@ impl<L> AutoCommandBufferBuilder<L> {
             }
         }
 
-        let vertex_input_state = if pipeline
-            .dynamic_state()
-            .contains(&DynamicState::VertexInput)
-        {
-            self.builder_state.vertex_input.as_ref().unwrap()
-        } else {
-            pipeline.vertex_input_state().unwrap()
-        };
+        let vertex_input_state = self.vertex_input_state().unwrap();
 
         for (&binding_num, binding_desc) in &vertex_input_state.bindings {
             let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
@@ -383,16 +355,9 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
+        self.add_vertex_buffers_resources(&mut used_resources);
 
         self.add_command(
             "draw",
@@ -459,25 +424,17 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndirect;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, false, render_pass_state)?;
+        }
 
         Ok(())
     }
@@ -495,16 +452,9 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
+        self.add_vertex_buffers_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
         self.add_command(
@@ -596,25 +546,17 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndirectCount-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndirectCount;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, false, render_pass_state)?;
+        }
 
         Ok(())
     }
@@ -633,16 +575,9 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
+        self.add_vertex_buffers_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
         self.add_indirect_buffer_resources(&mut used_resources, count_buffer.as_bytes());
 
@@ -746,25 +681,24 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexed-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndexed;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+        let view_mask = if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+            match pipeline.subpass() {
+                PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+                PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+            }
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, false, render_pass_state)?;
+
+            render_pass_state.rendering_info.as_ref().view_mask
+        };
 
         let index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
@@ -795,11 +729,6 @@ impl<L> AutoCommandBufferBuilder<L> {
             }
         }
 
-        let view_mask = match pipeline.subpass() {
-            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
-            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
-        };
-
         if view_mask != 0 {
             let properties = self.device().physical_device().properties();
 
@@ -817,14 +746,7 @@ impl<L> AutoCommandBufferBuilder<L> {
             }
         }
 
-        let vertex_input_state = if pipeline
-            .dynamic_state()
-            .contains(&DynamicState::VertexInput)
-        {
-            self.builder_state.vertex_input.as_ref().unwrap()
-        } else {
-            pipeline.vertex_input_state().unwrap()
-        };
+        let vertex_input_state = self.vertex_input_state().unwrap();
 
         for (&binding_num, binding_desc) in &vertex_input_state.bindings {
             let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
@@ -878,16 +800,9 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
+        self.add_vertex_buffers_resources(&mut used_resources);
         self.add_index_buffer_resources(&mut used_resources);
 
         self.add_command(
@@ -968,25 +883,17 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirect;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, false, render_pass_state)?;
+        }
 
         let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
@@ -1012,16 +919,9 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
+        self.add_vertex_buffers_resources(&mut used_resources);
         self.add_index_buffer_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
@@ -1120,25 +1020,17 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirectCount-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirectCount;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_primitive_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, false, render_pass_state)?;
+        }
 
         let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
@@ -1165,16 +1057,9 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
+        self.add_vertex_buffers_resources(&mut used_resources);
         self.add_index_buffer_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
         self.add_indirect_buffer_resources(&mut used_resources, count_buffer.as_bytes());
@@ -1229,40 +1114,38 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawMeshTasksEXT-None-08606"],
+        const VUID_TYPE: VUIDType = VUIDType::DrawMeshTasks;
+
+        let shader_stages = if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref()
+        {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_mesh_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+            if pipeline.mesh_is_nv() {
+                return Err(Box::new(ValidationError {
+                    problem:
+                        "the currently bound graphics pipeline uses NV mesh shaders instead of \
+                        EXT mesh shaders"
+                            .into(),
+                    vuids: &["VUID-vkCmdDrawMeshTasksEXT-MeshEXT-07087"],
                     ..Default::default()
-                })
-            })?
-            .as_ref();
+                }));
+            }
 
-        const VUID_TYPE: VUIDType = VUIDType::DrawMeshTasks;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_mesh_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+            pipeline.shader_stages()
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, true, render_pass_state)?;
 
-        if pipeline.mesh_is_nv() {
-            return Err(Box::new(ValidationError {
-                problem: "the currently bound graphics pipeline uses NV mesh shaders instead of \
-                    EXT mesh shaders"
-                    .into(),
-                vuids: &["VUID-vkCmdDrawMeshTasksEXT-MeshEXT-07087"],
-                ..Default::default()
-            }));
-        }
+            self.graphics_shader_object_stages()
+        };
 
         let properties = self.device().physical_device().properties();
         let group_counts_product = group_counts.into_iter().try_fold(1, u32::checked_mul);
 
-        if pipeline.shader_stages().intersects(ShaderStages::TASK) {
+        if shader_stages.intersects(ShaderStages::TASK) {
             if group_counts[0] > properties.max_task_work_group_count.unwrap_or_default()[0] {
                 return Err(Box::new(ValidationError {
                     context: "group_counts[0]".into(),
@@ -1365,15 +1248,8 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
 
         self.add_command(
             "draw_mesh_tasks",
@@ -1437,34 +1313,27 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawMeshTasksIndirect;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_mesh_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
 
-        if pipeline.mesh_is_nv() {
-            return Err(Box::new(ValidationError {
-                problem: "the currently bound graphics pipeline uses NV mesh shaders instead of \
-                    EXT mesh shaders"
-                    .into(),
-                vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-MeshEXT-07091"],
-                ..Default::default()
-            }));
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_mesh_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+            if pipeline.mesh_is_nv() {
+                return Err(Box::new(ValidationError {
+                    problem:
+                        "the currently bound graphics pipeline uses NV mesh shaders instead of \
+                        EXT mesh shaders"
+                            .into(),
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-MeshEXT-07091"],
+                    ..Default::default()
+                }));
+            }
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, true, render_pass_state)?;
         }
 
         Ok(())
@@ -1483,15 +1352,8 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
         self.add_command(
@@ -1582,34 +1444,27 @@ impl<L> AutoCommandBufferBuilder<L> {
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawMeshTasksIndirectCount;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_mesh_shading(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
 
-        if pipeline.mesh_is_nv() {
-            return Err(Box::new(ValidationError {
-                problem: "the currently bound graphics pipeline uses NV mesh shaders instead of \
-                    EXT mesh shaders"
-                    .into(),
-                vuids: &["VUID-vkCmdDrawMeshTasksIndirectCountEXT-MeshEXT-07100"],
-                ..Default::default()
-            }));
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+            self.validate_pipeline_graphics_mesh_shading(VUID_TYPE, pipeline.shader_stages())?;
+            self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+            if pipeline.mesh_is_nv() {
+                return Err(Box::new(ValidationError {
+                    problem:
+                        "the currently bound graphics pipeline uses NV mesh shaders instead of \
+                        EXT mesh shaders"
+                            .into(),
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectCountEXT-MeshEXT-07100"],
+                    ..Default::default()
+                }));
+            }
+        } else {
+            self.validate_shader_objects_graphics(VUID_TYPE, true, render_pass_state)?;
         }
 
         Ok(())
@@ -1629,15 +1484,8 @@ impl<L> AutoCommandBufferBuilder<L> {
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_graphics_descriptor_sets_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
         self.add_indirect_buffer_resources(&mut used_resources, count_buffer.as_bytes());
 
@@ -1787,7 +1635,52 @@ impl<L> AutoCommandBufferBuilder<L> {
         self
     }
 
-    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+    /// Returns the currently bound compute pipeline, or the shader object bound to the compute
+    /// stage if there is no compute pipeline.
+    fn compute_pipeline_or_shader(&self) -> Option<&dyn Pipeline> {
+        match self.builder_state.pipeline_compute.as_deref() {
+            Some(pipeline) => Some(pipeline),
+            None => self
+                .builder_state
+                .shader(ShaderStage::Compute)
+                .map(|shader| shader.as_ref() as &dyn Pipeline),
+        }
+    }
+
+    /// Returns the non-null shader objects that are bound to graphics shader stages.
+    fn graphics_shader_objects(&self) -> impl Iterator<Item = &Arc<ShaderObject>> {
+        self.builder_state
+            .shaders
+            .iter()
+            .filter(|&(&stage, _)| stage != ShaderStage::Compute)
+            .filter_map(|(_, shader)| shader.as_ref())
+    }
+
+    fn graphics_shader_object_stages(&self) -> ShaderStages {
+        self.graphics_shader_objects()
+            .fold(ShaderStages::empty(), |stages, shader| {
+                stages | ShaderStages::from(shader.stage())
+            })
+    }
+
+    /// Returns the vertex input state that will be used by the next draw command. This is the
+    /// state of the bound graphics pipeline, unless it was created with
+    /// `DynamicState::VertexInput` or shader objects are bound, in which case it is the dynamic
+    /// state.
+    fn vertex_input_state(&self) -> Option<&VertexInputState> {
+        match self.builder_state.pipeline_graphics.as_deref() {
+            Some(pipeline)
+                if !pipeline
+                    .dynamic_state()
+                    .contains(&DynamicState::VertexInput) =>
+            {
+                pipeline.vertex_input_state()
+            }
+            _ => self.builder_state.vertex_input.as_ref(),
+        }
+    }
+
+    fn validate_pipeline_descriptor_sets<Pl: Pipeline + ?Sized>(
         &self,
         vuid_type: VUIDType,
         pipeline: &Pl,
@@ -2465,14 +2358,13 @@ impl<L> AutoCommandBufferBuilder<L> {
     fn validate_pipeline_graphics_primitive_shading(
         &self,
         vuid_type: VUIDType,
-        pipeline: &GraphicsPipeline,
+        shader_stages: ShaderStages,
     ) -> Result<(), Box<ValidationError>> {
-        if pipeline
-            .shader_stages()
-            .intersects(ShaderStages::MESH | ShaderStages::TASK)
-        {
+        if shader_stages.intersects(ShaderStages::MESH | ShaderStages::TASK) {
             return Err(Box::new(ValidationError {
-                problem: "the currently bound graphics pipeline uses mesh shading".into(),
+                problem: "the currently bound graphics pipeline or shader objects use mesh \
+                    shading"
+                    .into(),
                 vuids: vuids!(vuid_type, "stage-06481"),
                 ..Default::default()
             }));
@@ -2510,28 +2402,21 @@ impl<L> AutoCommandBufferBuilder<L> {
             }
         }
 
-        let vertex_input_state = if pipeline
-            .dynamic_state()
-            .contains(&DynamicState::VertexInput)
-        {
-            self.builder_state.vertex_input.as_ref().unwrap()
-        } else {
-            pipeline.vertex_input_state().unwrap()
-        };
-
-        for &binding_num in vertex_input_state.bindings.keys() {
-            if !self.builder_state.vertex_buffers.contains_key(&binding_num) {
-                return Err(Box::new(ValidationError {
-                    problem: format!(
-                        "the currently bound graphics pipeline uses \
-                        vertex buffer binding {0}, but \
-                        no vertex buffer is currently bound to binding {0}",
-                        binding_num
-                    )
-                    .into(),
-                    vuids: vuids!(vuid_type, "None-04007"),
-                    ..Default::default()
-                }));
+        if let Some(vertex_input_state) = self.vertex_input_state() {
+            for &binding_num in vertex_input_state.bindings.keys() {
+                if !self.builder_state.vertex_buffers.contains_key(&binding_num) {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the current vertex input state uses \
+                            vertex buffer binding {0}, but \
+                            no vertex buffer is currently bound to binding {0}",
+                            binding_num
+                        )
+                        .into(),
+                        vuids: vuids!(vuid_type, "None-04007"),
+                        ..Default::default()
+                    }));
+                }
             }
         }
 
@@ -2541,16 +2426,18 @@ impl<L> AutoCommandBufferBuilder<L> {
     fn validate_pipeline_graphics_mesh_shading(
         &self,
         vuid_type: VUIDType,
-        pipeline: &GraphicsPipeline,
+        shader_stages: ShaderStages,
     ) -> Result<(), Box<ValidationError>> {
-        if pipeline.shader_stages().intersects(
+        if shader_stages.intersects(
             ShaderStages::VERTEX
                 | ShaderStages::TESSELLATION_CONTROL
                 | ShaderStages::TESSELLATION_EVALUATION
                 | ShaderStages::GEOMETRY,
         ) {
             return Err(Box::new(ValidationError {
-                problem: "the currently bound graphics pipeline uses primitive shading".into(),
+                problem: "the currently bound graphics pipeline or shader objects use primitive \
+                    shading"
+                    .into(),
                 vuids: vuids!(vuid_type, "stage-06480"),
                 ..Default::default()
             }));
@@ -3395,8 +3282,8 @@ impl<L> AutoCommandBufferBuilder<L> {
                     // the viewportCount parameter of
                     // vkCmdSetViewportWithCountEXT must be 1
                 }
-                DynamicState::ConservativeRasterizationMode => {
-                    if self.builder_state.conservative_rasterization_mode.is_none() {
+                DynamicState::TessellationDomainOrigin => {
+                    if self.builder_state.tessellation_domain_origin.is_none() {
                         return Err(Box::new(ValidationError {
                             problem: format!(
                                 "the currently bound graphics pipeline requires the \
@@ -3406,18 +3293,13 @@ impl<L> AutoCommandBufferBuilder<L> {
                                 dynamic_state
                             )
                             .into(),
-                            vuids: vuids!(vuid_type, "None-07631"),
+                            vuids: vuids!(vuid_type, "None-07619"),
                             ..Default::default()
                         }));
                     }
-                    // TODO: VUID-vkCmdDraw-conservativePointAndLineRasterization-07499
                 }
-                DynamicState::ExtraPrimitiveOverestimationSize => {
-                    if self
-                        .builder_state
-                        .extra_primitive_overestimation_size
-                        .is_none()
-                    {
+                DynamicState::DepthClampEnable => {
+                    if self.builder_state.depth_clamp_enable.is_none() {
                         return Err(Box::new(ValidationError {
                             problem: format!(
                                 "the currently bound graphics pipeline requires the \
@@ -3427,13 +3309,270 @@ impl<L> AutoCommandBufferBuilder<L> {
                                 dynamic_state
                             )
                             .into(),
-                            vuids: vuids!(vuid_type, "None-07632"),
+                            vuids: vuids!(vuid_type, "None-07620"),
                             ..Default::default()
                         }));
                     }
                 }
-            }
-        }
+                DynamicState::PolygonMode => {
+                    if self.builder_state.polygon_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07621"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::RasterizationSamples => {
+                    if self.builder_state.rasterization_samples.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07622"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::SampleMask => {
+                    if self.builder_state.sample_mask.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07623"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::AlphaToCoverageEnable => {
+                    if self.builder_state.alpha_to_coverage_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07624"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::AlphaToOneEnable => {
+                    if self.builder_state.alpha_to_one_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07625"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::LogicOpEnable => {
+                    if self.builder_state.logic_op_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07626"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::ColorBlendEnable => {
+                    let attachment_count = pipeline
+                        .color_blend_state()
+                        .map_or(0, |state| state.attachments.len() as u32);
+
+                    for num in 0..attachment_count {
+                        if !self.builder_state.color_blend_enable.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set, or it was overwritten by a \
+                                    more recent `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "firstAttachment-07476"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                DynamicState::ColorBlendEquation => {
+                    if let Some(color_blend_state) = pipeline.color_blend_state() {
+                        let dynamic_blend_enable = pipeline
+                            .dynamic_state()
+                            .contains(&DynamicState::ColorBlendEnable);
+
+                        for (num, attachment) in (0..).zip(color_blend_state.attachments.iter()) {
+                            let blend_enable = if dynamic_blend_enable {
+                                self.builder_state.color_blend_enable.get(&num) == Some(&true)
+                            } else {
+                                attachment.blend.is_some()
+                            };
+
+                            if blend_enable
+                                && !self.builder_state.color_blend_equation.contains_key(&num)
+                            {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound graphics pipeline requires the \
+                                        `DynamicState::{:?}` dynamic state, but \
+                                        this state was either not set, or it was overwritten by a \
+                                        more recent `bind_pipeline_graphics` command",
+                                        dynamic_state
+                                    )
+                                    .into(),
+                                    vuids: vuids!(vuid_type, "firstAttachment-07477"),
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                    }
+                }
+                DynamicState::ColorWriteMask => {
+                    let attachment_count = pipeline
+                        .color_blend_state()
+                        .map_or(0, |state| state.attachments.len() as u32);
+
+                    for num in 0..attachment_count {
+                        if !self.builder_state.color_write_mask.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set, or it was overwritten by a \
+                                    more recent `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "firstAttachment-07478"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                DynamicState::ConservativeRasterizationMode => {
+                    if self.builder_state.conservative_rasterization_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07631"),
+                            ..Default::default()
+                        }));
+                    }
+                    // TODO: VUID-vkCmdDraw-conservativePointAndLineRasterization-07499
+                }
+                DynamicState::ExtraPrimitiveOverestimationSize => {
+                    if self
+                        .builder_state
+                        .extra_primitive_overestimation_size
+                        .is_none()
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07632"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::ProvokingVertexMode => {
+                    if self.builder_state.provoking_vertex_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07636"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::LineRasterizationMode => {
+                    if self.builder_state.line_rasterization_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07637"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::LineStippleEnable => {
+                    if self.builder_state.line_stipple_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07638"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+        }
 
         Ok(())
     }
@@ -3621,7 +3760,573 @@ impl<L> AutoCommandBufferBuilder<L> {
         Ok(())
     }
 
-    fn add_descriptor_sets_resources<Pl: Pipeline>(
+    fn validate_shader_objects_graphics(
+        &self,
+        vuid_type: VUIDType,
+        mesh_shading: bool,
+        render_pass_state: &RenderPassState,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+        let shaders = &self.builder_state.shaders;
+
+        if !shaders.keys().any(|&stage| stage != ShaderStage::Compute) {
+            return Err(Box::new(ValidationError {
+                problem: "no graphics pipeline or graphics shader objects are currently bound"
+                    .into(),
+                vuids: vuids!(vuid_type, "None-08606"),
+                ..Default::default()
+            }));
+        }
+
+        let not_bound = |stage: ShaderStage, vuids: &'static [&'static str]| {
+            Box::new(ValidationError {
+                problem: format!(
+                    "graphics shader objects are currently bound, but no shader object (not even \
+                    a null one) has been bound to `ShaderStage::{:?}`",
+                    stage,
+                )
+                .into(),
+                vuids,
+                ..Default::default()
+            })
+        };
+
+        if !shaders.contains_key(&ShaderStage::Vertex) {
+            return Err(not_bound(
+                ShaderStage::Vertex,
+                vuids!(vuid_type, "None-08684"),
+            ));
+        }
+
+        if device.enabled_features().tessellation_shader {
+            if !shaders.contains_key(&ShaderStage::TessellationControl) {
+                return Err(not_bound(
+                    ShaderStage::TessellationControl,
+                    vuids!(vuid_type, "None-08685"),
+                ));
+            }
+
+            if !shaders.contains_key(&ShaderStage::TessellationEvaluation) {
+                return Err(not_bound(
+                    ShaderStage::TessellationEvaluation,
+                    vuids!(vuid_type, "None-08686"),
+                ));
+            }
+        }
+
+        if device.enabled_features().geometry_shader
+            && !shaders.contains_key(&ShaderStage::Geometry)
+        {
+            return Err(not_bound(
+                ShaderStage::Geometry,
+                vuids!(vuid_type, "None-08687"),
+            ));
+        }
+
+        if !shaders.contains_key(&ShaderStage::Fragment) {
+            return Err(not_bound(
+                ShaderStage::Fragment,
+                vuids!(vuid_type, "None-08688"),
+            ));
+        }
+
+        if device.enabled_features().task_shader && !shaders.contains_key(&ShaderStage::Task) {
+            return Err(not_bound(
+                ShaderStage::Task,
+                vuids!(vuid_type, "None-08689"),
+            ));
+        }
+
+        if device.enabled_features().mesh_shader && !shaders.contains_key(&ShaderStage::Mesh) {
+            return Err(not_bound(
+                ShaderStage::Mesh,
+                vuids!(vuid_type, "None-08690"),
+            ));
+        }
+
+        let shader_stages = self.graphics_shader_object_stages();
+
+        if mesh_shading {
+            self.validate_pipeline_graphics_mesh_shading(vuid_type, shader_stages)?;
+
+            if !shader_stages.intersects(ShaderStages::MESH) {
+                return Err(Box::new(ValidationError {
+                    problem: "no mesh shader object is currently bound".into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+        } else {
+            self.validate_pipeline_graphics_primitive_shading(vuid_type, shader_stages)?;
+
+            if !shader_stages.intersects(ShaderStages::VERTEX) {
+                return Err(Box::new(ValidationError {
+                    problem: "no vertex shader object is currently bound".into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+        }
+
+        for shader in self.graphics_shader_objects() {
+            self.validate_pipeline_descriptor_sets(vuid_type, shader.as_ref())?;
+            self.validate_pipeline_push_constants(vuid_type, shader.layout())?;
+        }
+
+        self.validate_shader_objects_graphics_dynamic_state(vuid_type, mesh_shading)?;
+        self.validate_shader_objects_graphics_render_pass(vuid_type, render_pass_state)?;
+
+        Ok(())
+    }
+
+    fn validate_shader_objects_graphics_render_pass(
+        &self,
+        vuid_type: VUIDType,
+        render_pass_state: &RenderPassState,
+    ) -> Result<(), Box<ValidationError>> {
+        if render_pass_state.contents != SubpassContents::Inline {
+            return Err(Box::new(ValidationError {
+                problem: "the contents of the current subpass instance is not \
+                    `SubpassContents::Inline`"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        // The attachment formats are not known when creating a shader object, so unlike with a
+        // graphics pipeline, there is nothing to check them against.
+        if let RenderPassStateType::BeginRenderPass(_) = render_pass_state.render_pass {
+            return Err(Box::new(ValidationError {
+                problem: "graphics shader objects are currently bound, but the current render \
+                    pass instance was begun with `begin_render_pass` instead of \
+                    `begin_rendering`"
+                    .into(),
+                vuids: vuids!(vuid_type, "None-08876"),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn validate_shader_objects_graphics_dynamic_state(
+        &self,
+        vuid_type: VUIDType,
+        mesh_shading: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+        let state = &self.builder_state;
+
+        let missing_state = |dynamic_state: DynamicState, vuids: &'static [&'static str]| {
+            Box::new(ValidationError {
+                problem: format!(
+                    "graphics shader objects are currently bound, which requires the \
+                    `DynamicState::{:?}` dynamic state, but this state was either not set, or \
+                    it was overwritten by a more recent `bind_pipeline_graphics` command",
+                    dynamic_state,
+                )
+                .into(),
+                vuids,
+                ..Default::default()
+            })
+        };
+
+        let rasterizer_discard_enable = state.rasterizer_discard_enable.ok_or_else(|| {
+            missing_state(
+                DynamicState::RasterizerDiscardEnable,
+                vuids!(vuid_type, "None-04876"),
+            )
+        })?;
+
+        let viewports = state.viewport_with_count.as_ref().ok_or_else(|| {
+            missing_state(
+                DynamicState::ViewportWithCount,
+                vuids!(vuid_type, "viewportCount-03417"),
+            )
+        })?;
+
+        let scissors = state.scissor_with_count.as_ref().ok_or_else(|| {
+            missing_state(
+                DynamicState::ScissorWithCount,
+                vuids!(vuid_type, "scissorCount-03418"),
+            )
+        })?;
+
+        if viewports.len() != scissors.len() {
+            return Err(Box::new(ValidationError {
+                problem: "graphics shader objects are currently bound, but the number of \
+                    viewports set with `set_viewport_with_count` is not equal to the number of \
+                    scissors set with `set_scissor_with_count`"
+                    .into(),
+                vuids: vuids!(vuid_type, "viewportCount-03419"),
+                ..Default::default()
+            }));
+        }
+
+        let mut topology = None;
+
+        if !mesh_shading {
+            topology = Some(state.primitive_topology.ok_or_else(|| {
+                missing_state(
+                    DynamicState::PrimitiveTopology,
+                    vuids!(vuid_type, "None-07842"),
+                )
+            })?);
+
+            if state.primitive_restart_enable.is_none() {
+                return Err(missing_state(
+                    DynamicState::PrimitiveRestartEnable,
+                    vuids!(vuid_type, "None-04879"),
+                ));
+            }
+
+            if state.shader(ShaderStage::TessellationControl).is_some()
+                && state.patch_control_points.is_none()
+            {
+                return Err(missing_state(
+                    DynamicState::PatchControlPoints,
+                    vuids!(vuid_type, "None-04875"),
+                ));
+            }
+
+            if state.shader(ShaderStage::TessellationEvaluation).is_some()
+                && state.tessellation_domain_origin.is_none()
+            {
+                return Err(missing_state(
+                    DynamicState::TessellationDomainOrigin,
+                    vuids!(vuid_type, "None-07619"),
+                ));
+            }
+
+            if let Some(vertex_shader) = state.shader(ShaderStage::Vertex) {
+                let vertex_input_state = state.vertex_input.as_ref().ok_or_else(|| {
+                    missing_state(DynamicState::VertexInput, vuids!(vuid_type, "None-04914"))
+                })?;
+
+                vertex_input_state
+                    .validate_required_vertex_inputs(
+                        vertex_shader.required_vertex_inputs().unwrap(),
+                        RequiredVertexInputsVUIDs {
+                            not_present: vuids!(vuid_type, "Input-07939"),
+                            numeric_type: vuids!(vuid_type, "Input-08734"),
+                            requires32: vuids!(vuid_type, "format-08936"),
+                            requires64: vuids!(vuid_type, "format-08937"),
+                            requires_second_half: vuids!(vuid_type, "None-09203"),
+                        },
+                    )
+                    .map_err(|mut err| {
+                        err.problem = format!(
+                            "the dynamic vertex input does not meet the requirements of the \
+                            currently bound vertex shader object: {}",
+                            err.problem,
+                        )
+                        .into();
+                        err
+                    })?;
+            }
+        }
+
+        if rasterizer_discard_enable {
+            return Ok(());
+        }
+
+        if state.cull_mode.is_none() {
+            return Err(missing_state(
+                DynamicState::CullMode,
+                vuids!(vuid_type, "None-07840"),
+            ));
+        }
+
+        if state.front_face.is_none() {
+            return Err(missing_state(
+                DynamicState::FrontFace,
+                vuids!(vuid_type, "None-07841"),
+            ));
+        }
+
+        let depth_test_enable = state.depth_test_enable.ok_or_else(|| {
+            missing_state(
+                DynamicState::DepthTestEnable,
+                vuids!(vuid_type, "None-07843"),
+            )
+        })?;
+
+        if state.depth_write_enable.is_none() {
+            return Err(missing_state(
+                DynamicState::DepthWriteEnable,
+                vuids!(vuid_type, "None-07844"),
+            ));
+        }
+
+        if depth_test_enable && state.depth_compare_op.is_none() {
+            return Err(missing_state(
+                DynamicState::DepthCompareOp,
+                vuids!(vuid_type, "None-07845"),
+            ));
+        }
+
+        if device.enabled_features().depth_bounds {
+            let depth_bounds_test_enable = state.depth_bounds_test_enable.ok_or_else(|| {
+                missing_state(
+                    DynamicState::DepthBoundsTestEnable,
+                    vuids!(vuid_type, "None-07846"),
+                )
+            })?;
+
+            if depth_bounds_test_enable && state.depth_bounds.is_none() {
+                return Err(missing_state(
+                    DynamicState::DepthBounds,
+                    vuids!(vuid_type, "None-07836"),
+                ));
+            }
+        }
+
+        let stencil_test_enable = state.stencil_test_enable.ok_or_else(|| {
+            missing_state(
+                DynamicState::StencilTestEnable,
+                vuids!(vuid_type, "None-07847"),
+            )
+        })?;
+
+        if stencil_test_enable {
+            if state.stencil_op.front.is_none() || state.stencil_op.back.is_none() {
+                return Err(missing_state(
+                    DynamicState::StencilOp,
+                    vuids!(vuid_type, "None-07848"),
+                ));
+            }
+
+            if state.stencil_compare_mask.front.is_none()
+                || state.stencil_compare_mask.back.is_none()
+            {
+                return Err(missing_state(
+                    DynamicState::StencilCompareMask,
+                    vuids!(vuid_type, "None-07837"),
+                ));
+            }
+
+            if state.stencil_write_mask.front.is_none() || state.stencil_write_mask.back.is_none() {
+                return Err(missing_state(
+                    DynamicState::StencilWriteMask,
+                    vuids!(vuid_type, "None-07838"),
+                ));
+            }
+
+            if state.stencil_reference.front.is_none() || state.stencil_reference.back.is_none() {
+                return Err(missing_state(
+                    DynamicState::StencilReference,
+                    vuids!(vuid_type, "None-07839"),
+                ));
+            }
+        }
+
+        let depth_bias_enable = state.depth_bias_enable.ok_or_else(|| {
+            missing_state(
+                DynamicState::DepthBiasEnable,
+                vuids!(vuid_type, "None-04877"),
+            )
+        })?;
+
+        if depth_bias_enable && state.depth_bias.is_none() {
+            return Err(missing_state(
+                DynamicState::DepthBias,
+                vuids!(vuid_type, "None-07834"),
+            ));
+        }
+
+        if matches!(
+            topology,
+            Some(
+                PrimitiveTopology::LineList
+                    | PrimitiveTopology::LineStrip
+                    | PrimitiveTopology::LineListWithAdjacency
+                    | PrimitiveTopology::LineStripWithAdjacency
+            )
+        ) && state.line_width.is_none()
+        {
+            return Err(missing_state(
+                DynamicState::LineWidth,
+                vuids!(vuid_type, "None-07833"),
+            ));
+        }
+
+        if device.enabled_features().color_write_enable && state.color_write_enable.is_none() {
+            return Err(missing_state(
+                DynamicState::ColorWriteEnable,
+                vuids!(vuid_type, "None-07749"),
+            ));
+        }
+
+        if device.enabled_features().depth_clamp && state.depth_clamp_enable.is_none() {
+            return Err(missing_state(
+                DynamicState::DepthClampEnable,
+                vuids!(vuid_type, "None-07620"),
+            ));
+        }
+
+        let polygon_mode = state.polygon_mode.ok_or_else(|| {
+            missing_state(DynamicState::PolygonMode, vuids!(vuid_type, "None-07621"))
+        })?;
+
+        if state.rasterization_samples.is_none() {
+            return Err(missing_state(
+                DynamicState::RasterizationSamples,
+                vuids!(vuid_type, "None-07622"),
+            ));
+        }
+
+        if state.sample_mask.is_none() {
+            return Err(missing_state(
+                DynamicState::SampleMask,
+                vuids!(vuid_type, "None-07623"),
+            ));
+        }
+
+        if state.alpha_to_coverage_enable.is_none() {
+            return Err(missing_state(
+                DynamicState::AlphaToCoverageEnable,
+                vuids!(vuid_type, "None-07624"),
+            ));
+        }
+
+        if device.enabled_features().alpha_to_one && state.alpha_to_one_enable.is_none() {
+            return Err(missing_state(
+                DynamicState::AlphaToOneEnable,
+                vuids!(vuid_type, "None-07625"),
+            ));
+        }
+
+        if device.enabled_features().logic_op {
+            let logic_op_enable = state.logic_op_enable.ok_or_else(|| {
+                missing_state(DynamicState::LogicOpEnable, vuids!(vuid_type, "None-07626"))
+            })?;
+
+            if logic_op_enable && state.logic_op.is_none() {
+                return Err(missing_state(
+                    DynamicState::LogicOp,
+                    vuids!(vuid_type, "logicOp-04878"),
+                ));
+            }
+        }
+
+        let attachment_count = state.render_pass.as_ref().map_or(0, |render_pass_state| {
+            render_pass_state
+                .rendering_info
+                .as_ref()
+                .color_attachment_formats
+                .len() as u32
+        });
+        let mut uses_blend_constants = false;
+
+        for num in 0..attachment_count {
+            let blend_enable = *state.color_blend_enable.get(&num).ok_or_else(|| {
+                missing_state(
+                    DynamicState::ColorBlendEnable,
+                    vuids!(vuid_type, "firstAttachment-07476"),
+                )
+            })?;
+
+            if blend_enable {
+                let equation = state.color_blend_equation.get(&num).ok_or_else(|| {
+                    missing_state(
+                        DynamicState::ColorBlendEquation,
+                        vuids!(vuid_type, "firstAttachment-07477"),
+                    )
+                })?;
+
+                uses_blend_constants |= [
+                    equation.src_color_blend_factor,
+                    equation.dst_color_blend_factor,
+                    equation.src_alpha_blend_factor,
+                    equation.dst_alpha_blend_factor,
+                ]
+                .into_iter()
+                .any(|factor| {
+                    matches!(
+                        factor,
+                        BlendFactor::ConstantColor
+                            | BlendFactor::OneMinusConstantColor
+                            | BlendFactor::ConstantAlpha
+                            | BlendFactor::OneMinusConstantAlpha
+                    )
+                });
+            }
+
+            if !state.color_write_mask.contains_key(&num) {
+                return Err(missing_state(
+                    DynamicState::ColorWriteMask,
+                    vuids!(vuid_type, "firstAttachment-07478"),
+                ));
+            }
+        }
+
+        if uses_blend_constants && state.blend_constants.is_none() {
+            return Err(missing_state(
+                DynamicState::BlendConstants,
+                vuids!(vuid_type, "None-07835"),
+            ));
+        }
+
+        if device.enabled_extensions().ext_provoking_vertex && state.provoking_vertex_mode.is_none()
+        {
+            return Err(missing_state(
+                DynamicState::ProvokingVertexMode,
+                vuids!(vuid_type, "None-07636"),
+            ));
+        }
+
+        if device.enabled_extensions().ext_line_rasterization
+            && (polygon_mode == PolygonMode::Line
+                || matches!(
+                    topology,
+                    Some(
+                        PrimitiveTopology::LineList
+                            | PrimitiveTopology::LineStrip
+                            | PrimitiveTopology::LineListWithAdjacency
+                            | PrimitiveTopology::LineStripWithAdjacency
+                    )
+                ))
+        {
+            if state.line_rasterization_mode.is_none() {
+                return Err(missing_state(
+                    DynamicState::LineRasterizationMode,
+                    vuids!(vuid_type, "None-07637"),
+                ));
+            }
+
+            let line_stipple_enable = state.line_stipple_enable.ok_or_else(|| {
+                missing_state(
+                    DynamicState::LineStippleEnable,
+                    vuids!(vuid_type, "None-07638"),
+                )
+            })?;
+
+            if line_stipple_enable && state.line_stipple.is_none() {
+                return Err(missing_state(
+                    DynamicState::LineStipple,
+                    vuids!(vuid_type, "None-07849"),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_graphics_descriptor_sets_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+    ) {
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.add_descriptor_sets_resources(used_resources, pipeline);
+        } else {
+            for shader in self.graphics_shader_objects() {
+                self.add_descriptor_sets_resources(used_resources, shader.as_ref());
+            }
+        }
+    }
+
+    fn add_descriptor_sets_resources<Pl: Pipeline + ?Sized>(
         &self,
         used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
         pipeline: &Pl,
@@ -3806,19 +4511,8 @@ impl<L> AutoCommandBufferBuilder<L> {
         }
     }
 
-    fn add_vertex_buffers_resources(
-        &self,
-        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
-        pipeline: &GraphicsPipeline,
-    ) {
-        let vertex_input_state = if pipeline
-            .dynamic_state()
-            .contains(&DynamicState::VertexInput)
-        {
-            self.builder_state.vertex_input.as_ref().unwrap()
-        } else {
-            pipeline.vertex_input_state().unwrap()
-        };
+    fn add_vertex_buffers_resources(&self, used_resources: &mut Vec<(ResourceUseRef2, Resource)>) {
+        let vertex_input_state = self.vertex_input_state().unwrap();
 
         used_resources.extend(vertex_input_state.bindings.iter().map(|(&binding, _)| {
             let vertex_buffer = &self.builder_state.vertex_buffers[&binding];
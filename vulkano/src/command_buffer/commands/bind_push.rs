@@ -4,14 +4,16 @@ use crate::{
     descriptor_set::{
         layout::{DescriptorBindingFlags, DescriptorSetLayoutCreateFlags, DescriptorType},
         sys::RawDescriptorSet,
+        update_template::{DescriptorUpdateTemplate, DescriptorUpdateTemplateType},
         WriteDescriptorSet,
     },
     device::{DeviceOwned, QueueFlags},
-    memory::is_aligned,
+    memory::{is_aligned, DeviceAlignment},
     pipeline::{
         ray_tracing::RayTracingPipeline, ComputePipeline, GraphicsPipeline, PipelineBindPoint,
         PipelineLayout,
     },
+    shader::{object::ShaderObject, ShaderStage, ShaderStages},
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
 use ash::vk;
@@ -19,6 +21,178 @@ use smallvec::SmallVec;
 use std::{cmp::min, ffi::c_void, ptr};
 
 impl RecordingCommandBuffer {
+    #[inline]
+    #[track_caller]
+    pub unsafe fn bind_descriptor_buffers(&mut self, buffers: &[&Buffer]) -> &mut Self {
+        unsafe { self.try_bind_descriptor_buffers(buffers) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_bind_descriptor_buffers(
+        &mut self,
+        buffers: &[&Buffer],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_descriptor_buffers(buffers)?;
+
+        Ok(unsafe { self.bind_descriptor_buffers_unchecked(buffers) })
+    }
+
+    pub(crate) fn validate_bind_descriptor_buffers(
+        &self,
+        buffers: &[&Buffer],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "descriptor_buffer",
+                )])]),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-None-08047"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+
+        if buffers.len() as u32 > properties.max_descriptor_buffer_bindings.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "buffers".into(),
+                problem: "the length exceeds the `max_descriptor_buffer_bindings` limit".into(),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-bufferCount-08051"],
+                ..Default::default()
+            }));
+        }
+
+        let descriptor_buffer_offset_alignment =
+            DeviceAlignment::new(properties.descriptor_buffer_offset_alignment.unwrap_or(1))
+                .unwrap();
+        let mut sampler_buffer_count = 0;
+        let mut resource_buffer_count = 0;
+
+        for (index, buffer) in buffers.iter().enumerate() {
+            // VUID-vkCmdBindDescriptorBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            let usage = buffer.usage();
+
+            if !usage.intersects(
+                BufferUsage::SAMPLER_DESCRIPTOR_BUFFER | BufferUsage::RESOURCE_DESCRIPTOR_BUFFER,
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: format!("buffers[{}].usage()", index).into(),
+                    problem: "does not contain `BufferUsage::SAMPLER_DESCRIPTOR_BUFFER` or \
+                        `BufferUsage::RESOURCE_DESCRIPTOR_BUFFER`"
+                        .into(),
+                    vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-pBindingInfos-08055"],
+                    ..Default::default()
+                }));
+            }
+
+            if usage.intersects(BufferUsage::SAMPLER_DESCRIPTOR_BUFFER) {
+                sampler_buffer_count += 1;
+            }
+
+            if usage.intersects(BufferUsage::RESOURCE_DESCRIPTOR_BUFFER) {
+                resource_buffer_count += 1;
+            }
+
+            let address = buffer
+                .try_device_address()
+                .map_err(|err| err.add_context(format!("buffers[{}]", index)))?;
+
+            if !is_aligned(address.get(), descriptor_buffer_offset_alignment) {
+                return Err(Box::new(ValidationError {
+                    context: format!("buffers[{}].device_address()", index).into(),
+                    problem: "is not a multiple of the `descriptor_buffer_offset_alignment` \
+                        limit"
+                        .into(),
+                    vuids: &["VUID-VkDescriptorBufferBindingInfoEXT-address-08057"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if sampler_buffer_count
+            > properties
+                .max_sampler_descriptor_buffer_bindings
+                .unwrap_or(0)
+        {
+            return Err(Box::new(ValidationError {
+                context: "buffers".into(),
+                problem: "the number of elements whose usage contains \
+                    `BufferUsage::SAMPLER_DESCRIPTOR_BUFFER` exceeds the \
+                    `max_sampler_descriptor_buffer_bindings` limit"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdBindDescriptorBuffersEXT-maxSamplerDescriptorBufferBindings-08048",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if resource_buffer_count
+            > properties
+                .max_resource_descriptor_buffer_bindings
+                .unwrap_or(0)
+        {
+            return Err(Box::new(ValidationError {
+                context: "buffers".into(),
+                problem: "the number of elements whose usage contains \
+                    `BufferUsage::RESOURCE_DESCRIPTOR_BUFFER` exceeds the \
+                    `max_resource_descriptor_buffer_bindings` limit"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdBindDescriptorBuffersEXT-maxResourceDescriptorBufferBindings-08049",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_descriptor_buffers_unchecked(&mut self, buffers: &[&Buffer]) -> &mut Self {
+        if buffers.is_empty() {
+            return self;
+        }
+
+        let binding_infos_vk: SmallVec<[_; 4]> = buffers
+            .iter()
+            .map(|buffer| {
+                vk::DescriptorBufferBindingInfoEXT::default()
+                    .address(unsafe { buffer.device_address_unchecked() }.get())
+                    .usage(buffer.usage().into())
+            })
+            .collect();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_descriptor_buffer.cmd_bind_descriptor_buffers_ext)(
+                self.handle(),
+                binding_infos_vk.len() as u32,
+                binding_infos_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
     #[inline]
     #[track_caller]
     pub unsafe fn bind_descriptor_sets(
@@ -635,6 +809,268 @@ impl RecordingCommandBuffer {
         self
     }
 
+    #[inline]
+    #[track_caller]
+    pub unsafe fn bind_shaders(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&ShaderObject>],
+    ) -> &mut Self {
+        unsafe { self.try_bind_shaders(stages, shaders) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_bind_shaders(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&ShaderObject>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_shaders(stages, shaders)?;
+
+        Ok(unsafe { self.bind_shaders_unchecked(stages, shaders) })
+    }
+
+    pub(crate) fn validate_bind_shaders(
+        &self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&ShaderObject>],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().shader_object {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "shader_object",
+                )])]),
+                vuids: &["VUID-vkCmdBindShadersEXT-None-08462"],
+                ..Default::default()
+            }));
+        }
+
+        let queue_flags = self.queue_family_properties().queue_flags;
+
+        if !queue_flags.intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE) {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindShadersEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        assert_eq!(shaders.len(), stages.len());
+
+        let mut seen_stages = ShaderStages::empty();
+        let mut bound_stages = ShaderStages::empty();
+
+        for (index, (&stage, &shader)) in stages.iter().zip(shaders).enumerate() {
+            stage.validate_device(device).map_err(|err| {
+                err.add_context(format!("stages[{}]", index))
+                    .set_vuids(&["VUID-vkCmdBindShadersEXT-pStages-parameter"])
+            })?;
+
+            if seen_stages.contains_enum(stage) {
+                return Err(Box::new(ValidationError {
+                    context: "stages".into(),
+                    problem: format!("contains `ShaderStage::{:?}` more than once", stage).into(),
+                    vuids: &["VUID-vkCmdBindShadersEXT-pStages-08463"],
+                    ..Default::default()
+                }));
+            }
+
+            seen_stages |= stage.into();
+
+            match stage {
+                ShaderStage::Vertex | ShaderStage::Fragment => (),
+                ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation => {
+                    if shader.is_some() && !device.enabled_features().tessellation_shader {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`stages[{0}]` is `ShaderStage::{1:?}`, and `shaders[{0}]` is \
+                                `Some`",
+                                index, stage,
+                            )
+                            .into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                Requires::DeviceFeature("tessellation_shader"),
+                            ])]),
+                            vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08474"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ShaderStage::Geometry => {
+                    if shader.is_some() && !device.enabled_features().geometry_shader {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`stages[{0}]` is `ShaderStage::Geometry`, and `shaders[{0}]` is \
+                                `Some`",
+                                index,
+                            )
+                            .into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                Requires::DeviceFeature("geometry_shader"),
+                            ])]),
+                            vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08475"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ShaderStage::Task => {
+                    if shader.is_some() && !device.enabled_features().task_shader {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`stages[{0}]` is `ShaderStage::Task`, and `shaders[{0}]` is \
+                                `Some`",
+                                index,
+                            )
+                            .into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                Requires::DeviceFeature("task_shader"),
+                            ])]),
+                            vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08490"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ShaderStage::Mesh => {
+                    if shader.is_some() && !device.enabled_features().mesh_shader {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`stages[{0}]` is `ShaderStage::Mesh`, and `shaders[{0}]` is \
+                                `Some`",
+                                index,
+                            )
+                            .into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                Requires::DeviceFeature("mesh_shader"),
+                            ])]),
+                            vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08491"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ShaderStage::Compute => {
+                    if !queue_flags.intersects(QueueFlags::COMPUTE) {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`stages[{}]` is `ShaderStage::Compute`, but the queue family of \
+                                the command buffer does not support compute operations",
+                                index,
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08476"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ShaderStage::SubpassShading => {
+                    return Err(Box::new(ValidationError {
+                        context: format!("stages[{}]", index).into(),
+                        problem: "is `ShaderStage::SubpassShading`".into(),
+                        vuids: &["VUID-vkCmdBindShadersEXT-pStages-08465"],
+                        ..Default::default()
+                    }));
+                }
+                _ => {
+                    return Err(Box::new(ValidationError {
+                        context: format!("stages[{}]", index).into(),
+                        problem: "is a ray tracing shader stage".into(),
+                        vuids: &["VUID-vkCmdBindShadersEXT-pStages-08464"],
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            if stage != ShaderStage::Compute && !queue_flags.intersects(QueueFlags::GRAPHICS) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`stages[{}]` is a graphics shader stage, but the queue family of the \
+                        command buffer does not support graphics operations",
+                        index,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08477"],
+                    ..Default::default()
+                }));
+            }
+
+            if let Some(shader) = shader {
+                // VUID-vkCmdBindShadersEXT-commonparent
+                assert_eq!(device, shader.device());
+
+                if shader.stage() != stage {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`shaders[{0}].stage()` does not equal `stages[{0}]`",
+                            index,
+                        )
+                        .into(),
+                        vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08469"],
+                        ..Default::default()
+                    }));
+                }
+
+                bound_stages |= stage.into();
+            }
+        }
+
+        if bound_stages.intersects(ShaderStages::VERTEX) {
+            if bound_stages.intersects(ShaderStages::TASK) {
+                return Err(Box::new(ValidationError {
+                    context: "shaders".into(),
+                    problem: "contains both a vertex shader and a task shader".into(),
+                    vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08470"],
+                    ..Default::default()
+                }));
+            }
+
+            if bound_stages.intersects(ShaderStages::MESH) {
+                return Err(Box::new(ValidationError {
+                    context: "shaders".into(),
+                    problem: "contains both a vertex shader and a mesh shader".into(),
+                    vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08471"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_shaders_unchecked(
+        &mut self,
+        stages: &[ShaderStage],
+        shaders: &[Option<&ShaderObject>],
+    ) -> &mut Self {
+        if stages.is_empty() {
+            return self;
+        }
+
+        let stages_vk = stages
+            .iter()
+            .map(|&stage| stage.into())
+            .collect::<SmallVec<[vk::ShaderStageFlags; 5]>>();
+        let shaders_vk = shaders
+            .iter()
+            .map(|shader| shader.map_or(vk::ShaderEXT::null(), VulkanObject::handle))
+            .collect::<SmallVec<[_; 5]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_shader_object.cmd_bind_shaders_ext)(
+                self.handle(),
+                stages_vk.len() as u32,
+                stages_vk.as_ptr(),
+                shaders_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
     #[inline]
     #[track_caller]
     pub unsafe fn bind_vertex_buffers(
@@ -1247,4 +1683,368 @@ impl RecordingCommandBuffer {
 
         self
     }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn push_descriptor_set_with_template(
+        &mut self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        layout: &PipelineLayout,
+        set: u32,
+        data: &(impl BufferContents + ?Sized),
+    ) -> &mut Self {
+        unsafe {
+            self.try_push_descriptor_set_with_template(
+                descriptor_update_template,
+                layout,
+                set,
+                data,
+            )
+        }
+        .unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_push_descriptor_set_with_template(
+        &mut self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        layout: &PipelineLayout,
+        set: u32,
+        data: &(impl BufferContents + ?Sized),
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_push_descriptor_set_with_template(
+            descriptor_update_template,
+            layout,
+            set,
+            size_of_val(data),
+        )?;
+
+        Ok(unsafe {
+            self.push_descriptor_set_with_template_unchecked(
+                descriptor_update_template,
+                layout,
+                set,
+                data,
+            )
+        })
+    }
+
+    pub(crate) fn validate_push_descriptor_set_with_template(
+        &self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        layout: &PipelineLayout,
+        set: u32,
+        data_size: usize,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().khr_push_descriptor {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "khr_push_descriptor",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdPushDescriptorSetWithTemplateKHR-commonparent
+        assert_eq!(self.device(), descriptor_update_template.device());
+        assert_eq!(self.device(), layout.device());
+
+        if descriptor_update_template.template_type()
+            != DescriptorUpdateTemplateType::PushDescriptors
+        {
+            return Err(Box::new(ValidationError {
+                context: "descriptor_update_template.template_type()".into(),
+                problem: "is not `DescriptorUpdateTemplateType::PushDescriptors`".into(),
+                vuids: &[
+                    "VUID-vkCmdPushDescriptorSetWithTemplateKHR-descriptorUpdateTemplate-07994",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        let required_queue_flags = match descriptor_update_template.pipeline_bind_point() {
+            PipelineBindPoint::Graphics => QueueFlags::GRAPHICS,
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => QueueFlags::COMPUTE,
+        };
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(required_queue_flags)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support the \
+                    operations of `descriptor_update_template.pipeline_bind_point()`"
+                    .into(),
+                vuids: &["VUID-vkCmdPushDescriptorSetWithTemplateKHR-commandBuffer-00366"],
+                ..Default::default()
+            }));
+        }
+
+        if set != descriptor_update_template.set() {
+            return Err(Box::new(ValidationError {
+                problem: "`set` does not equal `descriptor_update_template.set()`".into(),
+                vuids: &["VUID-vkCmdPushDescriptorSetWithTemplateKHR-set-07995"],
+                ..Default::default()
+            }));
+        }
+
+        let Some(descriptor_set_layout) = layout.set_layouts().get(set as usize) else {
+            return Err(Box::new(ValidationError {
+                problem: "`set` is not less than `layout.set_layouts().len()`".into(),
+                vuids: &["VUID-vkCmdPushDescriptorSetWithTemplateKHR-set-07304"],
+                ..Default::default()
+            }));
+        };
+
+        if !descriptor_set_layout
+            .flags()
+            .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`layout.set_layouts()[set].flags()` does not contain \
+                    `DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR`"
+                    .into(),
+                vuids: &["VUID-vkCmdPushDescriptorSetWithTemplateKHR-set-07305"],
+                ..Default::default()
+            }));
+        }
+
+        if !descriptor_set_layout
+            .is_compatible_with(descriptor_update_template.descriptor_set_layout())
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`layout.set_layouts()[set]` is not compatible with \
+                    `descriptor_update_template.descriptor_set_layout()`"
+                    .into(),
+                vuids: &["VUID-vkCmdPushDescriptorSetWithTemplateKHR-layout-07993"],
+                ..Default::default()
+            }));
+        }
+
+        descriptor_update_template.validate_data(data_size, 0)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn push_descriptor_set_with_template_unchecked(
+        &mut self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        layout: &PipelineLayout,
+        set: u32,
+        data: &(impl BufferContents + ?Sized),
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.khr_push_descriptor
+                .cmd_push_descriptor_set_with_template_khr)(
+                self.handle(),
+                descriptor_update_template.handle(),
+                layout.handle(),
+                set,
+                ptr::from_ref(data).cast(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> &mut Self {
+        unsafe {
+            self.try_set_descriptor_buffer_offsets(
+                pipeline_bind_point,
+                layout,
+                first_set,
+                buffer_indices,
+                offsets,
+            )
+        }
+        .unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_descriptor_buffer_offsets(
+            pipeline_bind_point,
+            layout,
+            first_set,
+            buffer_indices,
+            offsets,
+        )?;
+
+        Ok(unsafe {
+            self.set_descriptor_buffer_offsets_unchecked(
+                pipeline_bind_point,
+                layout,
+                first_set,
+                buffer_indices,
+                offsets,
+            )
+        })
+    }
+
+    pub(crate) fn validate_set_descriptor_buffer_offsets(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "descriptor_buffer",
+                )])]),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-None-08060"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-commonparent
+        assert_eq!(device, layout.device());
+
+        pipeline_bind_point.validate_device(device).map_err(|err| {
+            err.add_context("pipeline_bind_point")
+                .set_vuids(&["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-parameter"])
+        })?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        let required_queue_flags = match pipeline_bind_point {
+            PipelineBindPoint::Graphics => QueueFlags::GRAPHICS,
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => QueueFlags::COMPUTE,
+        };
+
+        if !queue_family_properties
+            .queue_flags
+            .intersects(required_queue_flags)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline_bind_point".into(),
+                problem: "the queue family of the command buffer does not support \
+                    the operations of this pipeline bind point"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-08067"],
+                ..Default::default()
+            }));
+        }
+
+        if buffer_indices.len() != offsets.len() {
+            return Err(Box::new(ValidationError {
+                problem: "`buffer_indices` and `offsets` do not have the same length".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if (first_set as usize)
+            .checked_add(offsets.len())
+            .is_none_or(|end| end > layout.set_layouts().len())
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`first_set + offsets.len()` is greater than \
+                    `layout.set_layouts().len()`"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-08066"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+        let descriptor_buffer_offset_alignment =
+            DeviceAlignment::new(properties.descriptor_buffer_offset_alignment.unwrap_or(1))
+                .unwrap();
+        let max_descriptor_buffer_bindings = properties.max_descriptor_buffer_bindings.unwrap_or(0);
+
+        for (index, (&buffer_index, &offset)) in buffer_indices.iter().zip(offsets).enumerate() {
+            let set_num = first_set as usize + index;
+
+            if !is_aligned(offset, descriptor_buffer_offset_alignment) {
+                return Err(Box::new(ValidationError {
+                    context: format!("offsets[{}]", index).into(),
+                    problem: "is not a multiple of the `descriptor_buffer_offset_alignment` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08061"],
+                    ..Default::default()
+                }));
+            }
+
+            if buffer_index >= max_descriptor_buffer_bindings {
+                return Err(Box::new(ValidationError {
+                    context: format!("buffer_indices[{}]", index).into(),
+                    problem: "is not less than the `max_descriptor_buffer_bindings` limit".into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pBufferIndices-08064"],
+                    ..Default::default()
+                }));
+            }
+
+            if !layout.set_layouts()[set_num]
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`layout.set_layouts()[{}].flags()` does not contain \
+                        `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`",
+                        set_num,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-09006"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_descriptor_buffer_offsets_unchecked(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> &mut Self {
+        if offsets.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_descriptor_buffer
+                .cmd_set_descriptor_buffer_offsets_ext)(
+                self.handle(),
+                pipeline_bind_point.into(),
+                layout.handle(),
+                first_set,
+                offsets.len() as u32,
+                buffer_indices.as_ptr(),
+                offsets.as_ptr(),
+            )
+        };
+
+        self
+    }
 }
@@ -1,12 +1,17 @@
 use crate::{
     command_buffer::sys::RecordingCommandBuffer,
     device::{DeviceOwned, QueueFlags},
+    image::SampleCount,
     pipeline::graphics::{
-        color_blend::LogicOp,
+        color_blend::{AttachmentBlend, ColorComponents, LogicOp},
         depth_stencil::{CompareOp, StencilFaces, StencilOp},
         fragment_shading_rate::{FragmentShadingRateCombinerOp, FragmentShadingRateState},
         input_assembly::PrimitiveTopology,
-        rasterization::{ConservativeRasterizationMode, CullMode, FrontFace},
+        rasterization::{
+            ConservativeRasterizationMode, CullMode, FrontFace, LineRasterizationMode, PolygonMode,
+            ProvokingVertexMode,
+        },
+        tessellation::TessellationDomainOrigin,
         vertex_input::VertexInputState,
         viewport::{Scissor, Viewport},
     },
@@ -2460,6 +2465,1196 @@ impl RecordingCommandBuffer {
         self
     }
 
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        unsafe { self.try_set_tessellation_domain_origin(domain_origin) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_tessellation_domain_origin(domain_origin)?;
+
+        Ok(unsafe { self.set_tessellation_domain_origin_unchecked(domain_origin) })
+    }
+
+    pub(crate) fn validate_set_tessellation_domain_origin(
+        &self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_tessellation_domain_origin
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_tessellation_domain_origin",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetTessellationDomainOriginEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetTessellationDomainOriginEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        domain_origin
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("domain_origin")
+                    .set_vuids(&["VUID-vkCmdSetTessellationDomainOriginEXT-domainOrigin-parameter"])
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_tessellation_domain_origin_unchecked(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_tessellation_domain_origin_ext)(
+                self.handle(), domain_origin.into()
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_depth_clamp_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_depth_clamp_enable(enable) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_depth_clamp_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_depth_clamp_enable(enable)?;
+
+        Ok(unsafe { self.set_depth_clamp_enable_unchecked(enable) })
+    }
+
+    pub(crate) fn validate_set_depth_clamp_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clamp_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_depth_clamp_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetDepthClampEnableEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDepthClampEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if enable && !self.device().enabled_features().depth_clamp {
+            return Err(Box::new(ValidationError {
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "depth_clamp",
+                )])]),
+                vuids: &["VUID-vkCmdSetDepthClampEnableEXT-depthClamp-07449"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_depth_clamp_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        unsafe { self.try_set_polygon_mode(polygon_mode) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_polygon_mode(
+        &mut self,
+        polygon_mode: PolygonMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_polygon_mode(polygon_mode)?;
+
+        Ok(unsafe { self.set_polygon_mode_unchecked(polygon_mode) })
+    }
+
+    pub(crate) fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_polygon_mode
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_polygon_mode",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        polygon_mode.validate_device(self.device()).map_err(|err| {
+            err.add_context("polygon_mode")
+                .set_vuids(&["VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter"])
+        })?;
+
+        if matches!(polygon_mode, PolygonMode::Line | PolygonMode::Point)
+            && !self.device().enabled_features().fill_mode_non_solid
+        {
+            return Err(Box::new(ValidationError {
+                context: "polygon_mode".into(),
+                problem: "is `PolygonMode::Line` or `PolygonMode::Point`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "fill_mode_non_solid",
+                )])]),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+                self.handle(),
+                polygon_mode.into(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        unsafe { self.try_set_rasterization_samples(rasterization_samples) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterization_samples(rasterization_samples)?;
+
+        Ok(unsafe { self.set_rasterization_samples_unchecked(rasterization_samples) })
+    }
+
+    pub(crate) fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_samples
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_rasterization_samples",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetRasterizationSamplesEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetRasterizationSamplesEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        rasterization_samples
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("rasterization_samples").set_vuids(&[
+                    "VUID-vkCmdSetRasterizationSamplesEXT-rasterizationSamples-parameter",
+                ])
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_rasterization_samples_ext)(
+                self.handle(), rasterization_samples.into()
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_sample_mask(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        unsafe { self.try_set_sample_mask(samples, sample_mask) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_sample_mask(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_sample_mask(samples, sample_mask)?;
+
+        Ok(unsafe { self.set_sample_mask_unchecked(samples, sample_mask) })
+    }
+
+    pub(crate) fn validate_set_sample_mask(
+        &self,
+        samples: SampleCount,
+        _sample_mask: [u32; 2],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_sample_mask
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_sample_mask",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetSampleMaskEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetSampleMaskEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        samples.validate_device(self.device()).map_err(|err| {
+            err.add_context("samples")
+                .set_vuids(&["VUID-vkCmdSetSampleMaskEXT-samples-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_mask_unchecked(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_sample_mask_ext)(
+                self.handle(),
+                samples.into(),
+                sample_mask.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_alpha_to_coverage_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_alpha_to_coverage_enable(enable) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_alpha_to_coverage_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_alpha_to_coverage_enable(enable)?;
+
+        Ok(unsafe { self.set_alpha_to_coverage_enable_unchecked(enable) })
+    }
+
+    pub(crate) fn validate_set_alpha_to_coverage_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_coverage_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_alpha_to_coverage_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetAlphaToCoverageEnableEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetAlphaToCoverageEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_alpha_to_coverage_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_alpha_to_one_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_alpha_to_one_enable(enable) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_alpha_to_one_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_alpha_to_one_enable(enable)?;
+
+        Ok(unsafe { self.set_alpha_to_one_enable_unchecked(enable) })
+    }
+
+    pub(crate) fn validate_set_alpha_to_one_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_one_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_alpha_to_one_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetAlphaToOneEnableEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetAlphaToOneEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if enable && !self.device().enabled_features().alpha_to_one {
+            return Err(Box::new(ValidationError {
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "alpha_to_one",
+                )])]),
+                vuids: &["VUID-vkCmdSetAlphaToOneEnableEXT-alphaToOne-07607"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_alpha_to_one_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_logic_op_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_logic_op_enable(enable) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_logic_op_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_logic_op_enable(enable)?;
+
+        Ok(unsafe { self.set_logic_op_enable_unchecked(enable) })
+    }
+
+    pub(crate) fn validate_set_logic_op_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_logic_op_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_logic_op_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetLogicOpEnableEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLogicOpEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if enable && !self.device().enabled_features().logic_op {
+            return Err(Box::new(ValidationError {
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "logic_op",
+                )])]),
+                vuids: &["VUID-vkCmdSetLogicOpEnableEXT-logicOp-07366"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_logic_op_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_logic_op_enable_ext)(
+                self.handle(),
+                enable.into(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> &mut Self {
+        unsafe { self.try_set_color_blend_enable(first_attachment, enables) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_enable(first_attachment, enables)?;
+
+        Ok(unsafe { self.set_color_blend_enable_unchecked(first_attachment, enables) })
+    }
+
+    pub(crate) fn validate_set_color_blend_enable(
+        &self,
+        _first_attachment: u32,
+        _enables: &[bool],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_color_blend_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetColorBlendEnableEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetColorBlendEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> &mut Self {
+        if enables.is_empty() {
+            return self;
+        }
+
+        let enables_vk = enables
+            .iter()
+            .copied()
+            .map(|v| v as vk::Bool32)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_color_blend_enable_ext)(
+                self.handle(),
+                first_attachment,
+                enables_vk.len() as u32,
+                enables_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> &mut Self {
+        unsafe { self.try_set_color_blend_equation(first_attachment, equations) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_equation(first_attachment, equations)?;
+
+        Ok(unsafe { self.set_color_blend_equation_unchecked(first_attachment, equations) })
+    }
+
+    pub(crate) fn validate_set_color_blend_equation(
+        &self,
+        _first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_equation
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_color_blend_equation",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetColorBlendEquationEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetColorBlendEquationEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, equation) in equations.iter().enumerate() {
+            equation
+                .validate(self.device())
+                .map_err(|err| err.add_context(format!("equations[{}]", index)))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_equation_unchecked(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> &mut Self {
+        if equations.is_empty() {
+            return self;
+        }
+
+        let equations_vk = equations
+            .iter()
+            .map(AttachmentBlend::to_vk_color_blend_equation)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_color_blend_equation_ext)(
+                self.handle(),
+                first_attachment,
+                equations_vk.len() as u32,
+                equations_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> &mut Self {
+        unsafe { self.try_set_color_write_mask(first_attachment, masks) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_write_mask(first_attachment, masks)?;
+
+        Ok(unsafe { self.set_color_write_mask_unchecked(first_attachment, masks) })
+    }
+
+    pub(crate) fn validate_set_color_write_mask(
+        &self,
+        _first_attachment: u32,
+        _masks: &[ColorComponents],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_write_mask
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_color_write_mask",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetColorWriteMaskEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetColorWriteMaskEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> &mut Self {
+        if masks.is_empty() {
+            return self;
+        }
+
+        let masks_vk = masks
+            .iter()
+            .map(|&mask| vk::ColorComponentFlags::from(mask))
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3.cmd_set_color_write_mask_ext)(
+                self.handle(),
+                first_attachment,
+                masks_vk.len() as u32,
+                masks_vk.as_ptr(),
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_provoking_vertex_mode(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        unsafe { self.try_set_provoking_vertex_mode(provoking_vertex_mode) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_provoking_vertex_mode(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_provoking_vertex_mode(provoking_vertex_mode)?;
+
+        Ok(unsafe { self.set_provoking_vertex_mode_unchecked(provoking_vertex_mode) })
+    }
+
+    pub(crate) fn validate_set_provoking_vertex_mode(
+        &self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_provoking_vertex_mode
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_provoking_vertex_mode",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetProvokingVertexModeEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetProvokingVertexModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        provoking_vertex_mode
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("provoking_vertex_mode").set_vuids(&[
+                    "VUID-vkCmdSetProvokingVertexModeEXT-provokingVertexMode-parameter",
+                ])
+            })?;
+
+        if provoking_vertex_mode == ProvokingVertexMode::LastVertex
+            && !self.device().enabled_features().provoking_vertex_last
+        {
+            return Err(Box::new(ValidationError {
+                context: "provoking_vertex_mode".into(),
+                problem: "is `ProvokingVertexMode::LastVertex`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "provoking_vertex_last",
+                )])]),
+                vuids: &["VUID-vkCmdSetProvokingVertexModeEXT-provokingVertexMode-07447"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_provoking_vertex_mode_unchecked(
+        &mut self,
+        provoking_vertex_mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_provoking_vertex_mode_ext)(
+                self.handle(), provoking_vertex_mode.into()
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        unsafe { self.try_set_line_rasterization_mode(line_rasterization_mode) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_line_rasterization_mode(line_rasterization_mode)?;
+
+        Ok(unsafe { self.set_line_rasterization_mode_unchecked(line_rasterization_mode) })
+    }
+
+    pub(crate) fn validate_set_line_rasterization_mode(
+        &self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_line_rasterization_mode
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_line_rasterization_mode",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetLineRasterizationModeEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLineRasterizationModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        line_rasterization_mode
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("line_rasterization_mode").set_vuids(&[
+                    "VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-parameter",
+                ])
+            })?;
+
+        if line_rasterization_mode == LineRasterizationMode::Rectangular
+            && !self.device().enabled_features().rectangular_lines
+        {
+            return Err(Box::new(ValidationError {
+                context: "line_rasterization_mode".into(),
+                problem: "is `LineRasterizationMode::Rectangular`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "rectangular_lines",
+                )])]),
+                vuids: &["VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07418"],
+            }));
+        }
+
+        if line_rasterization_mode == LineRasterizationMode::Bresenham
+            && !self.device().enabled_features().bresenham_lines
+        {
+            return Err(Box::new(ValidationError {
+                context: "line_rasterization_mode".into(),
+                problem: "is `LineRasterizationMode::Bresenham`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "bresenham_lines",
+                )])]),
+                vuids: &["VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07419"],
+            }));
+        }
+
+        if line_rasterization_mode == LineRasterizationMode::RectangularSmooth
+            && !self.device().enabled_features().smooth_lines
+        {
+            return Err(Box::new(ValidationError {
+                context: "line_rasterization_mode".into(),
+                problem: "is `LineRasterizationMode::RectangularSmooth`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "smooth_lines",
+                )])]),
+                vuids: &["VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07420"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_rasterization_mode_unchecked(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_line_rasterization_mode_ext)(
+                self.handle(), line_rasterization_mode.into()
+            )
+        };
+
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    pub unsafe fn set_line_stipple_enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { self.try_set_line_stipple_enable(enable) }.unwrap()
+    }
+
+    #[inline]
+    pub unsafe fn try_set_line_stipple_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_line_stipple_enable(enable)?;
+
+        Ok(unsafe { self.set_line_stipple_enable_unchecked(enable) })
+    }
+
+    pub(crate) fn validate_set_line_stipple_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_line_stipple_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_line_stipple_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::DeviceFeature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetLineStippleEnableEXT-None-09423"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLineStippleEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_stipple_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        unsafe {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_line_stipple_enable_ext)(self.handle(), enable.into())
+        };
+
+        self
+    }
+
     pub(crate) fn validate_set_fragment_shading_rate(
         &self,
         fragment_size: [u32; 2],
@@ -0,0 +1,417 @@
+//! Descriptors that are stored directly in buffer memory.
+//!
+//! With the [`descriptor_buffer`] feature, descriptors don't need to be allocated from a
+//! descriptor pool. Instead, a descriptor set layout is created with
+//! [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`], and the descriptors of each set are
+//! written into a region of a host-visible buffer with a [`DescriptorBufferWriter`]. Writing a
+//! descriptor this way only copies the few bytes of the descriptor into memory, which is
+//! typically cheaper than updating a descriptor set.
+//!
+//! To use the descriptors, the buffers that hold them are bound with
+//! [`bind_descriptor_buffers`], and then each set number is pointed at a region of one of the
+//! bound buffers with [`set_descriptor_buffer_offsets`]. The pipelines that use the descriptors
+//! must be created with [`PipelineCreateFlags::DESCRIPTOR_BUFFER`].
+//!
+//! The contents of a descriptor buffer are not visible to vulkano, so it can't track the
+//! resources that the descriptors refer to. It is up to you to keep those resources alive and to
+//! synchronize accesses to them. For this reason, descriptor buffers can only be used with
+//! [`RecordingCommandBuffer`], and not with [`AutoCommandBufferBuilder`].
+//!
+//! [`descriptor_buffer`]: crate::device::DeviceFeatures::descriptor_buffer
+//! [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`]: super::layout::DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER
+//! [`bind_descriptor_buffers`]: RecordingCommandBuffer::bind_descriptor_buffers
+//! [`set_descriptor_buffer_offsets`]: RecordingCommandBuffer::set_descriptor_buffer_offsets
+//! [`PipelineCreateFlags::DESCRIPTOR_BUFFER`]: crate::pipeline::PipelineCreateFlags::DESCRIPTOR_BUFFER
+//! [`RecordingCommandBuffer`]: crate::command_buffer::RecordingCommandBuffer
+//! [`AutoCommandBufferBuilder`]: crate::command_buffer::AutoCommandBufferBuilder
+
+use super::{
+    layout::{DescriptorSetLayout, DescriptorType},
+    WriteDescriptorSet, WriteDescriptorSetElements,
+};
+#[cfg(doc)]
+use crate::command_buffer::RecordingCommandBuffer;
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
+    memory::{is_aligned, DeviceAlignment},
+    sync::HostAccessError,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanObject,
+};
+use ash::vk;
+use std::{ptr, sync::Arc};
+
+/// Writes descriptors into a region of a buffer, according to a descriptor set layout.
+///
+/// The region of the buffer holds the descriptors of a single descriptor set. It can be bound by
+/// passing the index of the buffer in [`bind_descriptor_buffers`] and the offset of the region,
+/// [`buffer().offset()`], to [`set_descriptor_buffer_offsets`].
+///
+/// [`bind_descriptor_buffers`]: RecordingCommandBuffer::bind_descriptor_buffers
+/// [`buffer().offset()`]: Subbuffer::offset
+/// [`set_descriptor_buffer_offsets`]: RecordingCommandBuffer::set_descriptor_buffer_offsets
+#[derive(Debug)]
+pub struct DescriptorBufferWriter {
+    buffer: Subbuffer<[u8]>,
+    layout: DeviceOwnedDebugWrapper<Arc<DescriptorSetLayout>>,
+}
+
+impl DescriptorBufferWriter {
+    /// Creates a new `DescriptorBufferWriter` that writes descriptors for `layout` into `buffer`.
+    ///
+    /// `buffer` must be at least [`layout.descriptor_buffer_size()`] bytes in size, and its
+    /// offset must be aligned to the [`descriptor_buffer_offset_alignment`] limit.
+    ///
+    /// [`layout.descriptor_buffer_size()`]: DescriptorSetLayout::descriptor_buffer_size
+    /// [`descriptor_buffer_offset_alignment`]: crate::device::DeviceProperties::descriptor_buffer_offset_alignment
+    pub fn new(
+        buffer: Subbuffer<[u8]>,
+        layout: Arc<DescriptorSetLayout>,
+    ) -> Result<Self, Box<ValidationError>> {
+        Self::validate_new(&buffer, &layout)?;
+
+        Ok(unsafe { Self::new_unchecked(buffer, layout) })
+    }
+
+    fn validate_new(
+        buffer: &Subbuffer<[u8]>,
+        layout: &DescriptorSetLayout,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = layout.device();
+
+        assert_eq!(device, buffer.device());
+
+        if !device.enabled_features().descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "descriptor_buffer",
+                )])]),
+                vuids: &["VUID-vkGetDescriptorEXT-None-08015"],
+                ..Default::default()
+            }));
+        }
+
+        let Some(descriptor_buffer_size) = layout.descriptor_buffer_size() else {
+            return Err(Box::new(ValidationError {
+                context: "layout.flags()".into(),
+                problem: "does not contain `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        };
+
+        if !buffer.buffer().usage().intersects(
+            BufferUsage::SAMPLER_DESCRIPTOR_BUFFER | BufferUsage::RESOURCE_DESCRIPTOR_BUFFER,
+        ) {
+            return Err(Box::new(ValidationError {
+                context: "buffer.buffer().usage()".into(),
+                problem: "does not contain `BufferUsage::SAMPLER_DESCRIPTOR_BUFFER` or \
+                    `BufferUsage::RESOURCE_DESCRIPTOR_BUFFER`"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if buffer.size() < descriptor_buffer_size {
+            return Err(Box::new(ValidationError {
+                problem: "`buffer.size()` is less than `layout.descriptor_buffer_size()`".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        let descriptor_buffer_offset_alignment = DeviceAlignment::new(
+            device
+                .physical_device()
+                .properties()
+                .descriptor_buffer_offset_alignment
+                .unwrap_or(1),
+        )
+        .unwrap();
+
+        if !is_aligned(buffer.offset(), descriptor_buffer_offset_alignment) {
+            return Err(Box::new(ValidationError {
+                context: "buffer.offset()".into(),
+                problem: "is not a multiple of the `descriptor_buffer_offset_alignment` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08061"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn new_unchecked(buffer: Subbuffer<[u8]>, layout: Arc<DescriptorSetLayout>) -> Self {
+        DescriptorBufferWriter {
+            buffer,
+            layout: DeviceOwnedDebugWrapper(layout),
+        }
+    }
+
+    /// Returns the buffer region that the descriptors are written to.
+    #[inline]
+    pub fn buffer(&self) -> &Subbuffer<[u8]> {
+        &self.buffer
+    }
+
+    /// Returns the descriptor set layout that the descriptors are written for.
+    #[inline]
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// Writes descriptors into the buffer.
+    ///
+    /// Buffers that are written to a descriptor must have been created with
+    /// [`BufferUsage::SHADER_DEVICE_ADDRESS`].
+    ///
+    /// # Safety
+    ///
+    /// - The region of the buffer must not be in use by the device while it is being written to.
+    /// - The resources that the descriptors refer to must be kept alive for as long as the
+    ///   descriptors are used by the device.
+    pub unsafe fn write(
+        &self,
+        descriptor_writes: &[WriteDescriptorSet<'_>],
+    ) -> Result<(), Validated<HostAccessError>> {
+        self.validate_write(descriptor_writes)?;
+
+        unsafe { self.write_unchecked(descriptor_writes) }.map_err(Validated::Error)
+    }
+
+    fn validate_write(
+        &self,
+        descriptor_writes: &[WriteDescriptorSet<'_>],
+    ) -> Result<(), Box<ValidationError>> {
+        let variable_descriptor_count = self.layout.variable_descriptor_count();
+
+        for (index, write) in descriptor_writes.iter().enumerate() {
+            write
+                .validate(&self.layout, variable_descriptor_count)
+                .map_err(|err| err.add_context(format!("descriptor_writes[{}]", index)))?;
+
+            match write.elements() {
+                WriteDescriptorSetElements::Buffer(elements) => {
+                    for (element_index, element) in elements.iter().enumerate() {
+                        if let Some(buffer) = element.buffer {
+                            buffer.try_device_address().map_err(|err| {
+                                err.add_context(format!(
+                                    "descriptor_writes[{}].elements[{}].buffer",
+                                    index, element_index,
+                                ))
+                            })?;
+                        }
+                    }
+                }
+                WriteDescriptorSetElements::BufferView(elements) => {
+                    for (element_index, element) in elements.iter().enumerate() {
+                        if let Some(buffer_view) = element {
+                            buffer_view.buffer().try_device_address().map_err(|err| {
+                                err.add_context(format!(
+                                    "descriptor_writes[{}].elements[{}].buffer()",
+                                    index, element_index,
+                                ))
+                            })?;
+                        }
+                    }
+                }
+                WriteDescriptorSetElements::Image(_)
+                | WriteDescriptorSetElements::InlineUniformBlock(_)
+                | WriteDescriptorSetElements::AccelerationStructure(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn write_unchecked(
+        &self,
+        descriptor_writes: &[WriteDescriptorSet<'_>],
+    ) -> Result<(), HostAccessError> {
+        let device = self.layout.device();
+        let fns = device.fns();
+        let mut data = self.buffer.write()?;
+
+        for write in descriptor_writes {
+            let layout_binding = self.layout.binding(write.dst_binding()).unwrap();
+            let descriptor_type = layout_binding.descriptor_type;
+            let binding_offset = self
+                .layout
+                .descriptor_buffer_binding_offset(write.dst_binding())
+                .unwrap() as usize;
+
+            if let WriteDescriptorSetElements::InlineUniformBlock(bytes) = write.elements() {
+                let start = binding_offset + write.dst_array_element() as usize;
+                data[start..start + bytes.len()].copy_from_slice(bytes);
+
+                continue;
+            }
+
+            let descriptor_size = descriptor_size(device, descriptor_type);
+            let default_image_layout = descriptor_type.default_image_layout();
+
+            for index in 0..write.elements().len() as usize {
+                let array_element = write.dst_array_element() as usize + index;
+                let start = binding_offset + array_element * descriptor_size;
+                let dst = &mut data[start..start + descriptor_size];
+
+                let sampler_vk;
+                let image_info_vk;
+                let address_info_vk;
+
+                let data_vk = match write.elements() {
+                    WriteDescriptorSetElements::Image(elements) => {
+                        let mut info_vk = elements[index].to_vk(default_image_layout);
+
+                        if let Some(sampler) = layout_binding.immutable_samplers.get(array_element)
+                        {
+                            info_vk.sampler = sampler.handle();
+                        }
+
+                        if descriptor_type == DescriptorType::Sampler {
+                            sampler_vk = info_vk.sampler;
+
+                            vk::DescriptorDataEXT {
+                                p_sampler: &sampler_vk,
+                            }
+                        } else {
+                            image_info_vk = info_vk;
+
+                            match descriptor_type {
+                                DescriptorType::CombinedImageSampler => vk::DescriptorDataEXT {
+                                    p_combined_image_sampler: &image_info_vk,
+                                },
+                                DescriptorType::SampledImage => vk::DescriptorDataEXT {
+                                    p_sampled_image: &image_info_vk,
+                                },
+                                DescriptorType::StorageImage => vk::DescriptorDataEXT {
+                                    p_storage_image: &image_info_vk,
+                                },
+                                DescriptorType::InputAttachment => vk::DescriptorDataEXT {
+                                    p_input_attachment_image: &image_info_vk,
+                                },
+                                _ => unreachable!(),
+                            }
+                        }
+                    }
+                    WriteDescriptorSetElements::Buffer(elements) => {
+                        let element = &elements[index];
+                        let address_info_ptr = match element.buffer {
+                            Some(buffer) => {
+                                let address = unsafe { buffer.device_address_unchecked() };
+                                address_info_vk = vk::DescriptorAddressInfoEXT::default()
+                                    .address(address.get() + element.offset)
+                                    .range(element.range.unwrap_or(buffer.size() - element.offset));
+
+                                ptr::from_ref(&address_info_vk)
+                            }
+                            None => ptr::null(),
+                        };
+
+                        match descriptor_type {
+                            DescriptorType::UniformBuffer => vk::DescriptorDataEXT {
+                                p_uniform_buffer: address_info_ptr,
+                            },
+                            DescriptorType::StorageBuffer => vk::DescriptorDataEXT {
+                                p_storage_buffer: address_info_ptr,
+                            },
+                            _ => unreachable!(),
+                        }
+                    }
+                    WriteDescriptorSetElements::BufferView(elements) => {
+                        let address_info_ptr = match elements[index] {
+                            Some(buffer_view) => {
+                                let buffer = buffer_view.buffer();
+                                let address = unsafe { buffer.device_address_unchecked() };
+                                let range = buffer_view.range().unwrap_or_else(|| {
+                                    let block_size = buffer_view.format().block_size();
+
+                                    (buffer.size() - buffer_view.offset()) / block_size * block_size
+                                });
+                                address_info_vk = vk::DescriptorAddressInfoEXT::default()
+                                    .address(address.get() + buffer_view.offset())
+                                    .range(range)
+                                    .format(buffer_view.format().into());
+
+                                ptr::from_ref(&address_info_vk)
+                            }
+                            None => ptr::null(),
+                        };
+
+                        match descriptor_type {
+                            DescriptorType::UniformTexelBuffer => vk::DescriptorDataEXT {
+                                p_uniform_texel_buffer: address_info_ptr,
+                            },
+                            DescriptorType::StorageTexelBuffer => vk::DescriptorDataEXT {
+                                p_storage_texel_buffer: address_info_ptr,
+                            },
+                            _ => unreachable!(),
+                        }
+                    }
+                    WriteDescriptorSetElements::AccelerationStructure(elements) => {
+                        vk::DescriptorDataEXT {
+                            acceleration_structure: elements[index].map_or(
+                                0,
+                                |acceleration_structure| {
+                                    acceleration_structure.device_address().get()
+                                },
+                            ),
+                        }
+                    }
+                    WriteDescriptorSetElements::InlineUniformBlock(_) => unreachable!(),
+                };
+
+                let info_vk = vk::DescriptorGetInfoEXT::default()
+                    .ty(descriptor_type.into())
+                    .data(data_vk);
+
+                unsafe {
+                    (fns.ext_descriptor_buffer.get_descriptor_ext)(
+                        device.handle(),
+                        &info_vk,
+                        dst.len(),
+                        dst.as_mut_ptr().cast(),
+                    )
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the size in bytes of a single descriptor of the given type in a descriptor buffer.
+fn descriptor_size(device: &Device, descriptor_type: DescriptorType) -> usize {
+    let properties = device.physical_device().properties();
+    let robust = device.enabled_features().robust_buffer_access;
+
+    match descriptor_type {
+        DescriptorType::Sampler => properties.sampler_descriptor_size,
+        DescriptorType::CombinedImageSampler => properties.combined_image_sampler_descriptor_size,
+        DescriptorType::SampledImage => properties.sampled_image_descriptor_size,
+        DescriptorType::StorageImage => properties.storage_image_descriptor_size,
+        DescriptorType::UniformTexelBuffer if robust => {
+            properties.robust_uniform_texel_buffer_descriptor_size
+        }
+        DescriptorType::UniformTexelBuffer => properties.uniform_texel_buffer_descriptor_size,
+        DescriptorType::StorageTexelBuffer if robust => {
+            properties.robust_storage_texel_buffer_descriptor_size
+        }
+        DescriptorType::StorageTexelBuffer => properties.storage_texel_buffer_descriptor_size,
+        DescriptorType::UniformBuffer if robust => properties.robust_uniform_buffer_descriptor_size,
+        DescriptorType::UniformBuffer => properties.uniform_buffer_descriptor_size,
+        DescriptorType::StorageBuffer if robust => properties.robust_storage_buffer_descriptor_size,
+        DescriptorType::StorageBuffer => properties.storage_buffer_descriptor_size,
+        DescriptorType::InputAttachment => properties.input_attachment_descriptor_size,
+        DescriptorType::AccelerationStructure => properties.acceleration_structure_descriptor_size,
+        DescriptorType::InlineUniformBlock => Some(1),
+        DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic => None,
+    }
+    .unwrap_or(0)
+}
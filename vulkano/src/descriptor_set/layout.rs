@@ -9,8 +9,8 @@ use crate::{
     macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
     self_referential::self_referential,
     shader::{DescriptorBindingRequirements, ShaderStages},
-    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
-    VulkanObject,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
+    VulkanError, VulkanObject,
 };
 use ash::vk;
 use smallvec::SmallVec;
@@ -27,6 +27,8 @@ pub struct DescriptorSetLayout {
     bindings: OwnedDescriptorSetLayoutBindings,
 
     descriptor_counts: Vec<(DescriptorType, u32)>,
+    descriptor_buffer_size: Option<DeviceSize>,
+    descriptor_buffer_binding_offsets: Vec<DeviceSize>,
 }
 
 self_referential! {
@@ -211,6 +213,42 @@ impl DescriptorSetLayout {
                 bindings_out
             });
 
+        let mut descriptor_buffer_size = None;
+        let mut descriptor_buffer_binding_offsets = Vec::new();
+
+        if flags.intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER) {
+            let fns = device.fns();
+
+            let mut size = 0;
+            unsafe {
+                (fns.ext_descriptor_buffer.get_descriptor_set_layout_size_ext)(
+                    device.handle(),
+                    handle,
+                    &mut size,
+                )
+            };
+            descriptor_buffer_size = Some(size);
+
+            descriptor_buffer_binding_offsets = bindings
+                .as_ref()
+                .iter()
+                .map(|binding| {
+                    let mut offset = 0;
+                    unsafe {
+                        (fns.ext_descriptor_buffer
+                            .get_descriptor_set_layout_binding_offset_ext)(
+                            device.handle(),
+                            handle,
+                            binding.binding,
+                            &mut offset,
+                        )
+                    };
+
+                    offset
+                })
+                .collect();
+        }
+
         Arc::new(DescriptorSetLayout {
             handle,
             device: InstanceOwnedDebugWrapper(device.clone()),
@@ -218,6 +256,8 @@ impl DescriptorSetLayout {
             flags,
             bindings,
             descriptor_counts,
+            descriptor_buffer_size,
+            descriptor_buffer_binding_offsets,
         })
     }
 
@@ -276,6 +316,34 @@ impl DescriptorSetLayout {
             .unwrap_or(0)
     }
 
+    /// If the descriptor set layout was created with
+    /// [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`], returns the number of bytes that
+    /// the descriptors of a set with this layout occupy in a descriptor buffer. Otherwise returns
+    /// `None`.
+    ///
+    /// The returned size assumes that a binding with a variable descriptor count uses its maximum
+    /// `descriptor_count`.
+    #[inline]
+    pub fn descriptor_buffer_size(&self) -> Option<DeviceSize> {
+        self.descriptor_buffer_size
+    }
+
+    /// If the descriptor set layout was created with
+    /// [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`], returns the byte offset of the
+    /// given binding, relative to the start of the set's descriptors in a descriptor buffer.
+    /// Returns `None` if the layout wasn't created with that flag, or if the binding doesn't
+    /// exist.
+    #[inline]
+    pub fn descriptor_buffer_binding_offset(&self, binding: u32) -> Option<DeviceSize> {
+        let index = self
+            .bindings
+            .as_ref()
+            .binary_search_by_key(&binding, |binding| binding.binding)
+            .ok()?;
+
+        self.descriptor_buffer_binding_offsets.get(index).copied()
+    }
+
     /// Returns whether `self` is compatible with `other`.
     ///
     /// "Compatible" in this sense is defined by the Vulkan specification under the section
@@ -398,6 +466,26 @@ impl<'a> DescriptorSetLayoutCreateInfo<'a> {
 
             total_descriptor_count += descriptor_count;
 
+            if flags.intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+                && matches!(
+                    descriptor_type,
+                    DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                )
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`flags` contains `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`, \
+                        and `bindings[{}].descriptor_type` is \
+                        `DescriptorType::UniformBufferDynamic` or \
+                        `DescriptorType::StorageBufferDynamic`",
+                        binding_index,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkDescriptorSetLayoutCreateInfo-flags-08000"],
+                    ..Default::default()
+                }));
+            }
+
             if flags.intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR) {
                 if matches!(
                     descriptor_type,
@@ -489,6 +577,47 @@ impl<'a> DescriptorSetLayoutCreateInfo<'a> {
             }
         }
 
+        if flags.intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER) {
+            if !device.enabled_features().descriptor_buffer {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "descriptor_buffer",
+                    )])]),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+
+            if flags.intersects(DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL) {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains both `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER` \
+                        and `DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL`"
+                        .into(),
+                    vuids: &["VUID-VkDescriptorSetLayoutCreateInfo-flags-08002"],
+                    ..Default::default()
+                }));
+            }
+
+            if flags.intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR)
+                && !device.enabled_features().descriptor_buffer_push_descriptors
+            {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains both `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER` \
+                        and `DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR`"
+                        .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "descriptor_buffer_push_descriptors",
+                    )])]),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+        }
+
         let max_push_descriptors = device
             .physical_device()
             .properties()
@@ -666,12 +795,26 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_push_descriptor)]),
     ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether the descriptor set layout should be created for use with descriptor buffers.
+    ///
+    /// If set, descriptor sets with this layout can't be allocated from a descriptor pool.
+    /// Instead, the descriptors are written directly into buffer memory with a
+    /// [`DescriptorBufferWriter`], and are bound with
+    /// [`set_descriptor_buffer_offsets`]. Pipelines that use this layout must be created with
+    /// [`PipelineCreateFlags::DESCRIPTOR_BUFFER`].
+    ///
+    /// If set, there must be no bindings with a type of
+    /// [`DescriptorType::UniformBufferDynamic`] or [`DescriptorType::StorageBufferDynamic`], and
+    /// the [`descriptor_buffer`] feature must be enabled on the device.
+    ///
+    /// [`DescriptorBufferWriter`]: crate::descriptor_set::descriptor_buffer::DescriptorBufferWriter
+    /// [`set_descriptor_buffer_offsets`]: crate::command_buffer::RecordingCommandBuffer::set_descriptor_buffer_offsets
+    /// [`PipelineCreateFlags::DESCRIPTOR_BUFFER`]: crate::pipeline::PipelineCreateFlags::DESCRIPTOR_BUFFER
+    /// [`descriptor_buffer`]: crate::device::DeviceFeatures::descriptor_buffer
     DESCRIPTOR_BUFFER = DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -1504,8 +1647,8 @@ pub(crate) struct DescriptorSetLayoutSupportExtensionsVk {
 mod tests {
     use crate::{
         descriptor_set::layout::{
-            DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
-            DescriptorType,
+            DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateFlags,
+            DescriptorSetLayoutCreateInfo, DescriptorType,
         },
         shader::ShaderStages,
     };
@@ -1537,4 +1680,23 @@ mod tests {
             &[(DescriptorType::UniformBuffer, 1)],
         );
     }
+
+    #[test]
+    fn descriptor_buffer_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = DescriptorSetLayout::new(
+            &device,
+            &DescriptorSetLayoutCreateInfo {
+                flags: DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER,
+                bindings: &[DescriptorSetLayoutBinding {
+                    stages: ShaderStages::all_graphics(),
+                    ..DescriptorSetLayoutBinding::new(DescriptorType::UniformBuffer)
+                }],
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_err());
+    }
 }
@@ -107,10 +107,12 @@ use std::{
 
 pub mod allocator;
 mod collection;
+pub mod descriptor_buffer;
 pub mod layout;
 pub mod pool;
 pub mod sys;
 mod update;
+pub mod update_template;
 
 /// An object that contains a collection of resources that will be accessible by shaders.
 ///
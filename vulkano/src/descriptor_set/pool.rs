@@ -808,6 +808,18 @@ impl<'a> DescriptorSetAllocateInfo<'a> {
             }));
         }
 
+        if layout
+            .flags()
+            .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "layout.flags()".into(),
+                problem: "contains `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`".into(),
+                vuids: &["VUID-VkDescriptorSetAllocateInfo-pSetLayouts-08009"],
+                ..Default::default()
+            }));
+        }
+
         if variable_descriptor_count > layout.variable_descriptor_count() {
             return Err(Box::new(ValidationError {
                 problem: "`variable_descriptor_count` is greater than
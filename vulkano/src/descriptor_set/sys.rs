@@ -6,9 +6,14 @@ use super::{
     CopyDescriptorSet,
 };
 use crate::{
-    descriptor_set::{layout::DescriptorSetLayout, update::WriteDescriptorSet},
+    buffer::BufferContents,
+    descriptor_set::{
+        layout::DescriptorSetLayout,
+        update::WriteDescriptorSet,
+        update_template::{DescriptorUpdateTemplate, DescriptorUpdateTemplateType},
+    },
     device::{Device, DeviceOwned},
-    Validated, ValidationError, VulkanError, VulkanObject,
+    Validated, ValidationError, Version, VulkanError, VulkanObject,
 };
 use ash::vk;
 use smallvec::SmallVec;
@@ -16,6 +21,7 @@ use std::{
     fmt::Debug,
     hash::{Hash, Hasher},
     mem::ManuallyDrop,
+    ptr,
     sync::Arc,
 };
 
@@ -281,6 +287,130 @@ impl RawDescriptorSet {
             )
         };
     }
+
+    /// Updates the descriptor set with new values from `data`, using a descriptor update
+    /// template, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_update_with_template().unwrap()`.
+    ///
+    /// # Safety
+    ///
+    /// - `data` must be laid out according to the entries of `descriptor_update_template`, and
+    ///   each descriptor in it must be valid for the descriptor set binding it's written to.
+    /// - The resources in `data` must be kept alive for as long as `self` is in use.
+    /// - The descriptor set must not be in use by the device, or be recorded to a command buffer
+    ///   as part of a bind command.
+    /// - Host access to the descriptor set must be externally synchronized.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_update_with_template`] returns a [`ValidationError`].
+    ///
+    /// [`try_update_with_template`]: Self::try_update_with_template
+    #[inline]
+    #[track_caller]
+    pub unsafe fn update_with_template(
+        &self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        data: &(impl BufferContents + ?Sized),
+    ) {
+        unsafe { self.try_update_with_template(descriptor_update_template, data) }.unwrap()
+    }
+
+    /// Updates the descriptor set with new values from `data`, using a descriptor update
+    /// template.
+    ///
+    /// There is no equivalent on [`DescriptorSet`], because vulkano can't know which resources
+    /// `data` refers to, and so couldn't keep them alive or track accesses to them.
+    ///
+    /// # Safety
+    ///
+    /// - `data` must be laid out according to the entries of `descriptor_update_template`, and
+    ///   each descriptor in it must be valid for the descriptor set binding it's written to.
+    /// - The resources in `data` must be kept alive for as long as `self` is in use.
+    /// - The descriptor set must not be in use by the device, or be recorded to a command buffer
+    ///   as part of a bind command.
+    /// - Host access to the descriptor set must be externally synchronized.
+    ///
+    /// [`DescriptorSet`]: super::DescriptorSet
+    #[inline]
+    pub unsafe fn try_update_with_template(
+        &self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        data: &(impl BufferContents + ?Sized),
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_update_with_template(descriptor_update_template, size_of_val(data))?;
+
+        unsafe { self.update_with_template_unchecked(descriptor_update_template, data) };
+
+        Ok(())
+    }
+
+    fn validate_update_with_template(
+        &self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        data_size: usize,
+    ) -> Result<(), Box<ValidationError>> {
+        // VUID-vkUpdateDescriptorSetWithTemplate-descriptorUpdateTemplate-parent
+        assert_eq!(self.device(), descriptor_update_template.device());
+
+        if descriptor_update_template.template_type() != DescriptorUpdateTemplateType::DescriptorSet
+        {
+            return Err(Box::new(ValidationError {
+                context: "descriptor_update_template.template_type()".into(),
+                problem: "is not `DescriptorUpdateTemplateType::DescriptorSet`".into(),
+                vuids: &["VUID-vkUpdateDescriptorSetWithTemplate-pData-01685"],
+                ..Default::default()
+            }));
+        }
+
+        if !descriptor_update_template
+            .descriptor_set_layout()
+            .is_compatible_with(self.layout())
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`descriptor_update_template.descriptor_set_layout()` is not compatible \
+                    with the layout of the descriptor set"
+                    .into(),
+                vuids: &["VUID-vkUpdateDescriptorSetWithTemplate-pData-01685"],
+                ..Default::default()
+            }));
+        }
+
+        descriptor_update_template.validate_data(data_size, self.variable_descriptor_count())?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn update_with_template_unchecked(
+        &self,
+        descriptor_update_template: &DescriptorUpdateTemplate,
+        data: &(impl BufferContents + ?Sized),
+    ) {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_1 {
+            unsafe {
+                (fns.v1_1.update_descriptor_set_with_template)(
+                    self.device().handle(),
+                    self.handle(),
+                    descriptor_update_template.handle(),
+                    ptr::from_ref(data).cast(),
+                )
+            };
+        } else {
+            unsafe {
+                (fns.khr_descriptor_update_template
+                    .update_descriptor_set_with_template_khr)(
+                    self.device().handle(),
+                    self.handle(),
+                    descriptor_update_template.handle(),
+                    ptr::from_ref(data).cast(),
+                )
+            };
+        }
+    }
 }
 
 impl Drop for RawDescriptorSet {
@@ -0,0 +1,951 @@
+//! Updating many descriptors at once from a block of host memory.
+//!
+//! A descriptor update template describes where in a block of memory the data for each
+//! descriptor of a set can be found. Once it's created, a descriptor set can be updated from a
+//! single value laid out according to the template, without building a list of
+//! [`WriteDescriptorSet`]s every time. This is useful when the same bindings are rewritten very
+//! often, for example once per draw.
+//!
+//! The data is usually a `#[repr(C)]` struct that derives [`BufferContents`], with one field for
+//! every entry of the template. The fields hold the raw descriptor data, which is represented by
+//! the following types:
+//!
+//! - [`DescriptorImageData`] for `Sampler`, `CombinedImageSampler`, `SampledImage`,
+//!   `StorageImage` and `InputAttachment` descriptors.
+//! - [`DescriptorBufferData`] for `UniformBuffer`, `StorageBuffer`, `UniformBufferDynamic` and
+//!   `StorageBufferDynamic` descriptors.
+//! - [`DescriptorBufferViewData`] for `UniformTexelBuffer` and `StorageTexelBuffer` descriptors.
+//! - [`DescriptorAccelerationStructureData`] for `AccelerationStructure` descriptors.
+//! - `InlineUniformBlock` descriptors are read directly as bytes.
+//!
+//! An arrayed binding can be written with an array of these types, using their size as the
+//! `stride` of the entry.
+//!
+//! ```
+//! use std::mem::offset_of;
+//! use vulkano::{
+//!     buffer::BufferContents,
+//!     descriptor_set::{
+//!         layout::DescriptorType,
+//!         update_template::{
+//!             DescriptorBufferData, DescriptorImageData, DescriptorUpdateTemplateEntry,
+//!         },
+//!     },
+//! };
+//!
+//! #[derive(BufferContents)]
+//! #[repr(C)]
+//! struct MaterialDescriptors {
+//!     uniforms: DescriptorBufferData,
+//!     textures: [DescriptorImageData; 4],
+//! }
+//!
+//! let entries = [
+//!     DescriptorUpdateTemplateEntry {
+//!         dst_binding: 0,
+//!         offset: offset_of!(MaterialDescriptors, uniforms),
+//!         ..DescriptorUpdateTemplateEntry::new(DescriptorType::UniformBuffer)
+//!     },
+//!     DescriptorUpdateTemplateEntry {
+//!         dst_binding: 1,
+//!         descriptor_count: 4,
+//!         offset: offset_of!(MaterialDescriptors, textures),
+//!         stride: size_of::<DescriptorImageData>(),
+//!         ..DescriptorUpdateTemplateEntry::new(DescriptorType::CombinedImageSampler)
+//!     },
+//! ];
+//! ```
+//!
+//! The data only holds the raw handles of the resources, so vulkano can't keep them alive or
+//! track them for synchronization. Updating with a template is therefore always unsafe.
+//!
+//! [`BufferContents`]: crate::buffer::BufferContents
+
+#[cfg(doc)]
+use super::WriteDescriptorSet;
+use super::{
+    layout::{DescriptorBindingFlags, DescriptorSetLayout, DescriptorSetLayoutCreateFlags},
+    DescriptorBufferInfo, DescriptorImageInfo,
+};
+use crate::{
+    acceleration_structure::AccelerationStructure,
+    buffer::view::BufferView,
+    descriptor_set::layout::DescriptorType,
+    device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
+    instance::InstanceOwnedDebugWrapper,
+    macros::{impl_id_counter, vulkan_enum},
+    pipeline::{layout::PipelineLayout, PipelineBindPoint},
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
+    VulkanObject,
+};
+use ash::vk;
+use bytemuck::{AnyBitPattern, Zeroable};
+use smallvec::SmallVec;
+use std::{mem::MaybeUninit, num::NonZero, ptr, sync::Arc};
+
+/// Describes how to update the descriptors of a descriptor set from a block of host memory.
+#[derive(Debug)]
+pub struct DescriptorUpdateTemplate {
+    handle: vk::DescriptorUpdateTemplate,
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    id: NonZero<u64>,
+
+    entries: Vec<DescriptorUpdateTemplateEntry>,
+    template_type: DescriptorUpdateTemplateType,
+    descriptor_set_layout: DeviceOwnedDebugWrapper<Arc<DescriptorSetLayout>>,
+    pipeline_bind_point: PipelineBindPoint,
+    pipeline_layout: Option<DeviceOwnedDebugWrapper<Arc<PipelineLayout>>>,
+    set: u32,
+
+    data_size: usize,
+}
+
+impl DescriptorUpdateTemplate {
+    /// Creates a new `DescriptorUpdateTemplate`, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_new().map_err(Validated::unwrap)`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_new`] returns a [`ValidationError`].
+    ///
+    /// [`try_new`]: Self::try_new
+    #[inline]
+    #[track_caller]
+    pub fn new(
+        device: &Arc<Device>,
+        create_info: &DescriptorUpdateTemplateCreateInfo<'_>,
+    ) -> Result<Arc<DescriptorUpdateTemplate>, VulkanError> {
+        match Self::try_new(device, create_info) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    /// Creates a new `DescriptorUpdateTemplate`.
+    #[inline]
+    pub fn try_new(
+        device: &Arc<Device>,
+        create_info: &DescriptorUpdateTemplateCreateInfo<'_>,
+    ) -> Result<Arc<DescriptorUpdateTemplate>, Validated<VulkanError>> {
+        Self::validate_new(device, create_info)?;
+
+        Ok(unsafe { Self::new_unchecked(device, create_info) }?)
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &DescriptorUpdateTemplateCreateInfo<'_>,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(device.api_version() >= Version::V1_1
+            || device.enabled_extensions().khr_descriptor_update_template)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_1)]),
+                    RequiresAllOf(&[Requires::DeviceExtension("khr_descriptor_update_template")]),
+                ]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCreateDescriptorUpdateTemplate-pCreateInfo-parameter
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: &Arc<Device>,
+        create_info: &DescriptorUpdateTemplateCreateInfo<'_>,
+    ) -> Result<Arc<DescriptorUpdateTemplate>, VulkanError> {
+        let create_info_fields1_vk = create_info.to_vk_fields1();
+        let create_info_vk = create_info.to_vk(&create_info_fields1_vk);
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+
+            if device.api_version() >= Version::V1_1 {
+                unsafe {
+                    (fns.v1_1.create_descriptor_update_template)(
+                        device.handle(),
+                        &create_info_vk,
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    )
+                }
+            } else {
+                unsafe {
+                    (fns.khr_descriptor_update_template
+                        .create_descriptor_update_template_khr)(
+                        device.handle(),
+                        &create_info_vk,
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    )
+                }
+            }
+            .result()
+            .map_err(VulkanError::from)?;
+
+            unsafe { output.assume_init() }
+        };
+
+        Ok(unsafe { Self::from_handle(device, handle, create_info) })
+    }
+
+    /// Creates a new `DescriptorUpdateTemplate` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: &Arc<Device>,
+        handle: vk::DescriptorUpdateTemplate,
+        create_info: &DescriptorUpdateTemplateCreateInfo<'_>,
+    ) -> Arc<DescriptorUpdateTemplate> {
+        let &DescriptorUpdateTemplateCreateInfo {
+            entries,
+            template_type,
+            descriptor_set_layout,
+            pipeline_bind_point,
+            pipeline_layout,
+            set,
+            _ne: _,
+        } = create_info;
+
+        let data_size = entries
+            .iter()
+            .map(DescriptorUpdateTemplateEntry::data_end)
+            .max()
+            .unwrap_or(0);
+
+        Arc::new(DescriptorUpdateTemplate {
+            handle,
+            device: InstanceOwnedDebugWrapper(device.clone()),
+            id: Self::next_id(),
+            entries: entries.to_vec(),
+            template_type,
+            descriptor_set_layout: DeviceOwnedDebugWrapper(descriptor_set_layout.clone()),
+            pipeline_bind_point,
+            pipeline_layout: pipeline_layout
+                .map(|pipeline_layout| DeviceOwnedDebugWrapper(pipeline_layout.clone())),
+            set,
+            data_size,
+        })
+    }
+
+    /// Returns the entries of the template.
+    #[inline]
+    pub fn entries(&self) -> &[DescriptorUpdateTemplateEntry] {
+        &self.entries
+    }
+
+    /// Returns the kind of update that the template is used for.
+    #[inline]
+    pub fn template_type(&self) -> DescriptorUpdateTemplateType {
+        self.template_type
+    }
+
+    /// Returns the descriptor set layout that the template was created for.
+    #[inline]
+    pub fn descriptor_set_layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.descriptor_set_layout
+    }
+
+    /// Returns the pipeline bind point that the template pushes descriptors to, if it's a
+    /// template for push descriptors.
+    #[inline]
+    pub fn pipeline_bind_point(&self) -> PipelineBindPoint {
+        self.pipeline_bind_point
+    }
+
+    /// Returns the pipeline layout that the template pushes descriptors with, if it's a template
+    /// for push descriptors.
+    #[inline]
+    pub fn pipeline_layout(&self) -> Option<&Arc<PipelineLayout>> {
+        self.pipeline_layout.as_deref()
+    }
+
+    /// Returns the set number that the template pushes descriptors to, if it's a template for
+    /// push descriptors.
+    #[inline]
+    pub fn set(&self) -> u32 {
+        self.set
+    }
+
+    /// Returns the minimum number of bytes that the data of an update must span, so that every
+    /// entry can be read from it.
+    #[inline]
+    pub fn data_size(&self) -> usize {
+        self.data_size
+    }
+
+    /// Validates that `data` can be used to update the descriptors of a set with the given
+    /// variable descriptor count, using this template.
+    pub(crate) fn validate_data(
+        &self,
+        data_size: usize,
+        variable_descriptor_count: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if data_size < self.data_size {
+            return Err(Box::new(ValidationError {
+                context: "data".into(),
+                problem: "is smaller than `descriptor_update_template.data_size()`".into(),
+                vuids: &["VUID-vkUpdateDescriptorSetWithTemplate-pData-01685"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let layout_binding = self
+                .descriptor_set_layout
+                .binding(entry.dst_binding)
+                .unwrap();
+
+            if layout_binding
+                .binding_flags
+                .intersects(DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT)
+                && entry.dst_array_element + entry.descriptor_count > variable_descriptor_count
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`descriptor_update_template.entries()[{}].dst_array_element` + \
+                        `descriptor_update_template.entries()[{0}].descriptor_count` is greater \
+                        than the variable descriptor count of the descriptor set",
+                        index,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkUpdateDescriptorSetWithTemplate-pData-01685"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for DescriptorUpdateTemplate {
+    #[inline]
+    fn drop(&mut self) {
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_1 {
+            unsafe {
+                (fns.v1_1.destroy_descriptor_update_template)(
+                    self.device.handle(),
+                    self.handle,
+                    ptr::null(),
+                )
+            };
+        } else {
+            unsafe {
+                (fns.khr_descriptor_update_template
+                    .destroy_descriptor_update_template_khr)(
+                    self.device.handle(),
+                    self.handle,
+                    ptr::null(),
+                )
+            };
+        }
+    }
+}
+
+unsafe impl VulkanObject for DescriptorUpdateTemplate {
+    type Handle = vk::DescriptorUpdateTemplate;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for DescriptorUpdateTemplate {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl_id_counter!(DescriptorUpdateTemplate);
+
+/// Parameters to create a new `DescriptorUpdateTemplate`.
+#[derive(Clone, Debug)]
+pub struct DescriptorUpdateTemplateCreateInfo<'a> {
+    /// The descriptors that are updated with the template, and where their data is located.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub entries: &'a [DescriptorUpdateTemplateEntry],
+
+    /// The kind of update that the template will be used for.
+    ///
+    /// The default value is [`DescriptorUpdateTemplateType::DescriptorSet`].
+    pub template_type: DescriptorUpdateTemplateType,
+
+    /// The descriptor set layout that the entries refer to.
+    ///
+    /// If `template_type` is [`DescriptorUpdateTemplateType::PushDescriptors`], this must be the
+    /// same as `pipeline_layout.set_layouts()[set]`.
+    ///
+    /// There is no default value.
+    pub descriptor_set_layout: &'a Arc<DescriptorSetLayout>,
+
+    /// The pipeline bind point that descriptors are pushed to.
+    ///
+    /// This is ignored if `template_type` is not [`DescriptorUpdateTemplateType::PushDescriptors`].
+    ///
+    /// The default value is [`PipelineBindPoint::Graphics`].
+    pub pipeline_bind_point: PipelineBindPoint,
+
+    /// The pipeline layout that descriptors are pushed with.
+    ///
+    /// This must be `Some` if `template_type` is [`DescriptorUpdateTemplateType::PushDescriptors`],
+    /// and is ignored otherwise.
+    ///
+    /// The default value is `None`.
+    pub pipeline_layout: Option<&'a Arc<PipelineLayout>>,
+
+    /// The set number that descriptors are pushed to.
+    ///
+    /// This is ignored if `template_type` is not [`DescriptorUpdateTemplateType::PushDescriptors`].
+    ///
+    /// The default value is `0`.
+    pub set: u32,
+
+    pub _ne: crate::NonExhaustive<'a>,
+}
+
+impl<'a> DescriptorUpdateTemplateCreateInfo<'a> {
+    /// Returns a default `DescriptorUpdateTemplateCreateInfo` with the provided
+    /// `descriptor_set_layout`.
+    #[inline]
+    pub const fn new(descriptor_set_layout: &'a Arc<DescriptorSetLayout>) -> Self {
+        Self {
+            entries: &[],
+            template_type: DescriptorUpdateTemplateType::DescriptorSet,
+            descriptor_set_layout,
+            pipeline_bind_point: PipelineBindPoint::Graphics,
+            pipeline_layout: None,
+            set: 0,
+            _ne: crate::NE,
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            entries,
+            template_type,
+            descriptor_set_layout,
+            pipeline_bind_point,
+            pipeline_layout,
+            set,
+            _ne: _,
+        } = self;
+
+        template_type.validate_device(device).map_err(|err| {
+            err.add_context("template_type")
+                .set_vuids(&["VUID-VkDescriptorUpdateTemplateCreateInfo-templateType-parameter"])
+        })?;
+
+        // VUID-VkDescriptorUpdateTemplateCreateInfo-commonparent
+        assert_eq!(device, descriptor_set_layout.device().as_ref());
+
+        if entries.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "entries".into(),
+                problem: "is empty".into(),
+                vuids: &[
+                    "VUID-VkDescriptorUpdateTemplateCreateInfo-descriptorUpdateEntryCount-arraylength",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        for (index, entry) in entries.iter().enumerate() {
+            entry
+                .validate(descriptor_set_layout)
+                .map_err(|err| err.add_context(format!("entries[{}]", index)))?;
+        }
+
+        match template_type {
+            DescriptorUpdateTemplateType::DescriptorSet => {
+                if descriptor_set_layout
+                    .flags()
+                    .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR)
+                {
+                    return Err(Box::new(ValidationError {
+                        problem:
+                            "`template_type` is `DescriptorUpdateTemplateType::DescriptorSet`, \
+                            but `descriptor_set_layout.flags()` contains \
+                            `DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR`"
+                                .into(),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+            }
+            DescriptorUpdateTemplateType::PushDescriptors => {
+                pipeline_bind_point.validate_device(device).map_err(|err| {
+                    err.add_context("pipeline_bind_point").set_vuids(&[
+                        "VUID-VkDescriptorUpdateTemplateCreateInfo-templateType-00351",
+                    ])
+                })?;
+
+                let Some(pipeline_layout) = pipeline_layout else {
+                    return Err(Box::new(ValidationError {
+                        problem: "`template_type` is \
+                            `DescriptorUpdateTemplateType::PushDescriptors`, but \
+                            `pipeline_layout` is `None`"
+                            .into(),
+                        vuids: &["VUID-VkDescriptorUpdateTemplateCreateInfo-templateType-00352"],
+                        ..Default::default()
+                    }));
+                };
+
+                // VUID-VkDescriptorUpdateTemplateCreateInfo-commonparent
+                assert_eq!(device, pipeline_layout.device().as_ref());
+
+                let Some(set_layout) = pipeline_layout.set_layouts().get(set as usize) else {
+                    return Err(Box::new(ValidationError {
+                        problem: "`set` is not less than `pipeline_layout.set_layouts().len()`"
+                            .into(),
+                        vuids: &["VUID-VkDescriptorUpdateTemplateCreateInfo-templateType-00353"],
+                        ..Default::default()
+                    }));
+                };
+
+                if !set_layout
+                    .flags()
+                    .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR)
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "`pipeline_layout.set_layouts()[set].flags()` does not contain \
+                            `DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR`"
+                            .into(),
+                        vuids: &["VUID-VkDescriptorUpdateTemplateCreateInfo-templateType-00353"],
+                        ..Default::default()
+                    }));
+                }
+
+                if !set_layout.is_compatible_with(descriptor_set_layout) {
+                    return Err(Box::new(ValidationError {
+                        problem: "`pipeline_layout.set_layouts()[set]` is not compatible with \
+                            `descriptor_set_layout`"
+                            .into(),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_vk<'b>(
+        &'b self,
+        fields1_vk: &'b DescriptorUpdateTemplateCreateInfoFields1Vk,
+    ) -> vk::DescriptorUpdateTemplateCreateInfo<'b> {
+        let &Self {
+            entries: _,
+            template_type,
+            descriptor_set_layout,
+            pipeline_bind_point,
+            pipeline_layout,
+            set,
+            _ne: _,
+        } = self;
+        let DescriptorUpdateTemplateCreateInfoFields1Vk { entries_vk } = fields1_vk;
+
+        vk::DescriptorUpdateTemplateCreateInfo::default()
+            .flags(vk::DescriptorUpdateTemplateCreateFlags::empty())
+            .descriptor_update_entries(entries_vk)
+            .template_type(template_type.into())
+            .descriptor_set_layout(descriptor_set_layout.handle())
+            .pipeline_bind_point(pipeline_bind_point.into())
+            .pipeline_layout(
+                pipeline_layout.map_or(vk::PipelineLayout::null(), VulkanObject::handle),
+            )
+            .set(set)
+    }
+
+    pub(crate) fn to_vk_fields1(&self) -> DescriptorUpdateTemplateCreateInfoFields1Vk {
+        let entries_vk = self
+            .entries
+            .iter()
+            .map(DescriptorUpdateTemplateEntry::to_vk)
+            .collect();
+
+        DescriptorUpdateTemplateCreateInfoFields1Vk { entries_vk }
+    }
+}
+
+pub(crate) struct DescriptorUpdateTemplateCreateInfoFields1Vk {
+    pub(crate) entries_vk: SmallVec<[vk::DescriptorUpdateTemplateEntry; 8]>,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The kind of update that a descriptor update template is used for.
+    DescriptorUpdateTemplateType = DescriptorUpdateTemplateType(i32);
+
+    /// The template is used to update descriptor sets, with
+    /// [`RawDescriptorSet::update_with_template`].
+    ///
+    /// [`RawDescriptorSet::update_with_template`]: crate::descriptor_set::sys::RawDescriptorSet::update_with_template
+    DescriptorSet = DESCRIPTOR_SET,
+
+    /// The template is used to push descriptors, with
+    /// [`RecordingCommandBuffer::push_descriptor_set_with_template`].
+    ///
+    /// [`RecordingCommandBuffer::push_descriptor_set_with_template`]: crate::command_buffer::RecordingCommandBuffer::push_descriptor_set_with_template
+    PushDescriptors = PUSH_DESCRIPTORS_KHR
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(khr_push_descriptor)]),
+    ]),
+}
+
+/// A single entry of a descriptor update template.
+///
+/// An entry describes a range of consecutive descriptors in one binding, and where the data for
+/// each of those descriptors is located.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DescriptorUpdateTemplateEntry {
+    /// The binding number to update.
+    ///
+    /// The default value is `0`.
+    pub dst_binding: u32,
+
+    /// The first array element in the binding to update.
+    ///
+    /// If the descriptor type is [`DescriptorType::InlineUniformBlock`], this is the byte offset
+    /// into the block instead.
+    ///
+    /// The default value is `0`.
+    pub dst_array_element: u32,
+
+    /// The number of descriptors to update.
+    ///
+    /// If the descriptor type is [`DescriptorType::InlineUniformBlock`], this is the number of
+    /// bytes to update instead.
+    ///
+    /// The default value is `1`.
+    pub descriptor_count: u32,
+
+    /// The type of the descriptors. This must match the type of the binding.
+    ///
+    /// There is no default value.
+    pub descriptor_type: DescriptorType,
+
+    /// The byte offset into the data, where the data of the first descriptor is located.
+    ///
+    /// The default value is `0`.
+    pub offset: usize,
+
+    /// The number of bytes between the data of consecutive descriptors.
+    ///
+    /// This is ignored if the descriptor type is [`DescriptorType::InlineUniformBlock`].
+    ///
+    /// The default value is `0`.
+    pub stride: usize,
+
+    pub _ne: crate::NonExhaustive<'static>,
+}
+
+impl DescriptorUpdateTemplateEntry {
+    /// Returns a default `DescriptorUpdateTemplateEntry` with the provided `descriptor_type`.
+    #[inline]
+    pub const fn new(descriptor_type: DescriptorType) -> Self {
+        Self {
+            dst_binding: 0,
+            dst_array_element: 0,
+            descriptor_count: 1,
+            descriptor_type,
+            offset: 0,
+            stride: 0,
+            _ne: crate::NE,
+        }
+    }
+
+    pub(crate) fn validate(
+        &self,
+        descriptor_set_layout: &DescriptorSetLayout,
+    ) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            dst_binding,
+            dst_array_element,
+            descriptor_count,
+            descriptor_type,
+            offset: _,
+            stride: _,
+            _ne: _,
+        } = self;
+
+        let device = descriptor_set_layout.device();
+
+        descriptor_type.validate_device(device).map_err(|err| {
+            err.add_context("descriptor_type")
+                .set_vuids(&["VUID-VkDescriptorUpdateTemplateEntry-descriptorType-parameter"])
+        })?;
+
+        let Some(layout_binding) = descriptor_set_layout.binding(dst_binding) else {
+            return Err(Box::new(ValidationError {
+                context: "dst_binding".into(),
+                problem: "does not exist in the descriptor set layout".into(),
+                vuids: &["VUID-VkDescriptorUpdateTemplateEntry-dstBinding-00354"],
+                ..Default::default()
+            }));
+        };
+
+        if descriptor_type != layout_binding.descriptor_type {
+            return Err(Box::new(ValidationError {
+                problem: "`descriptor_type` does not equal the descriptor type of the \
+                    descriptor set binding"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if descriptor_count == 0 {
+            return Err(Box::new(ValidationError {
+                context: "descriptor_count".into(),
+                problem: "is zero".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if descriptor_type == DescriptorType::InlineUniformBlock
+            && (dst_array_element % 4 != 0 || descriptor_count % 4 != 0)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`descriptor_type` is `DescriptorType::InlineUniformBlock`, but \
+                    `dst_array_element` or `descriptor_count` is not a multiple of 4"
+                    .into(),
+                vuids: &["VUID-VkDescriptorUpdateTemplateEntry-descriptor-02226"],
+                ..Default::default()
+            }));
+        }
+
+        if dst_array_element
+            .checked_add(descriptor_count)
+            .is_none_or(|end| end > layout_binding.descriptor_count)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`dst_array_element` + `descriptor_count` is greater than the number of \
+                    descriptors in the descriptor set binding"
+                    .into(),
+                vuids: &["VUID-VkDescriptorUpdateTemplateEntry-dstArrayElement-00355"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the byte offset in the data just past the last byte that this entry reads.
+    fn data_end(&self) -> usize {
+        let element_size = match self.descriptor_type {
+            DescriptorType::Sampler
+            | DescriptorType::CombinedImageSampler
+            | DescriptorType::SampledImage
+            | DescriptorType::StorageImage
+            | DescriptorType::InputAttachment => size_of::<DescriptorImageData>(),
+            DescriptorType::UniformBuffer
+            | DescriptorType::StorageBuffer
+            | DescriptorType::UniformBufferDynamic
+            | DescriptorType::StorageBufferDynamic => size_of::<DescriptorBufferData>(),
+            DescriptorType::UniformTexelBuffer | DescriptorType::StorageTexelBuffer => {
+                size_of::<DescriptorBufferViewData>()
+            }
+            DescriptorType::AccelerationStructure => {
+                size_of::<DescriptorAccelerationStructureData>()
+            }
+            DescriptorType::InlineUniformBlock => {
+                return self.offset + self.descriptor_count as usize;
+            }
+        };
+
+        self.offset + (self.descriptor_count as usize - 1) * self.stride + element_size
+    }
+
+    pub(crate) fn to_vk(&self) -> vk::DescriptorUpdateTemplateEntry {
+        let &Self {
+            dst_binding,
+            dst_array_element,
+            descriptor_count,
+            descriptor_type,
+            offset,
+            stride,
+            _ne: _,
+        } = self;
+
+        vk::DescriptorUpdateTemplateEntry {
+            dst_binding,
+            dst_array_element,
+            descriptor_count,
+            descriptor_type: descriptor_type.into(),
+            offset,
+            stride,
+        }
+    }
+}
+
+/// The data of an image or sampler descriptor, as read by a descriptor update template.
+///
+/// The default value is a null descriptor.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct DescriptorImageData(vk::DescriptorImageInfo);
+
+unsafe impl Zeroable for DescriptorImageData {}
+unsafe impl AnyBitPattern for DescriptorImageData {}
+
+impl DescriptorImageData {
+    /// Returns the data of an image or sampler descriptor of the given type.
+    ///
+    /// As with [`WriteDescriptorSet`], if `image_info.image_layout` is
+    /// [`ImageLayout::Undefined`], it is replaced with the default layout for `descriptor_type`.
+    ///
+    /// [`ImageLayout::Undefined`]: crate::image::ImageLayout::Undefined
+    #[inline]
+    pub fn new(image_info: &DescriptorImageInfo<'_>, descriptor_type: DescriptorType) -> Self {
+        Self(image_info.to_vk(descriptor_type.default_image_layout()))
+    }
+}
+
+/// The data of a buffer descriptor, as read by a descriptor update template.
+///
+/// The default value is a null descriptor.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct DescriptorBufferData(vk::DescriptorBufferInfo);
+
+unsafe impl Zeroable for DescriptorBufferData {}
+unsafe impl AnyBitPattern for DescriptorBufferData {}
+
+impl DescriptorBufferData {
+    /// Returns the data of a buffer descriptor.
+    #[inline]
+    pub fn new(buffer_info: &DescriptorBufferInfo<'_>) -> Self {
+        Self(buffer_info.to_vk())
+    }
+}
+
+/// The data of a texel buffer descriptor, as read by a descriptor update template.
+///
+/// The default value is a null descriptor.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct DescriptorBufferViewData(vk::BufferView);
+
+unsafe impl Zeroable for DescriptorBufferViewData {}
+unsafe impl AnyBitPattern for DescriptorBufferViewData {}
+
+impl DescriptorBufferViewData {
+    /// Returns the data of a texel buffer descriptor.
+    #[inline]
+    pub fn new(buffer_view: Option<&BufferView>) -> Self {
+        Self(buffer_view.map_or(vk::BufferView::null(), VulkanObject::handle))
+    }
+}
+
+/// The data of an acceleration structure descriptor, as read by a descriptor update template.
+///
+/// The default value is a null descriptor.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct DescriptorAccelerationStructureData(vk::AccelerationStructureKHR);
+
+unsafe impl Zeroable for DescriptorAccelerationStructureData {}
+unsafe impl AnyBitPattern for DescriptorAccelerationStructureData {}
+
+impl DescriptorAccelerationStructureData {
+    /// Returns the data of an acceleration structure descriptor.
+    #[inline]
+    pub fn new(acceleration_structure: Option<&AccelerationStructure>) -> Self {
+        Self(
+            acceleration_structure
+                .map_or(vk::AccelerationStructureKHR::null(), VulkanObject::handle),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DescriptorBufferData, DescriptorImageData, DescriptorUpdateTemplate,
+        DescriptorUpdateTemplateCreateInfo, DescriptorUpdateTemplateEntry,
+    };
+    use crate::{
+        descriptor_set::layout::{
+            DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+            DescriptorType,
+        },
+        shader::ShaderStages,
+    };
+
+    #[test]
+    fn data_size() {
+        let entry = DescriptorUpdateTemplateEntry {
+            descriptor_count: 3,
+            offset: 8,
+            stride: 32,
+            ..DescriptorUpdateTemplateEntry::new(DescriptorType::SampledImage)
+        };
+        assert_eq!(
+            entry.data_end(),
+            8 + 2 * 32 + size_of::<DescriptorImageData>()
+        );
+
+        let entry = DescriptorUpdateTemplateEntry {
+            descriptor_count: 16,
+            offset: 4,
+            stride: 1000,
+            ..DescriptorUpdateTemplateEntry::new(DescriptorType::InlineUniformBlock)
+        };
+        assert_eq!(entry.data_end(), 20);
+
+        let entry = DescriptorUpdateTemplateEntry::new(DescriptorType::UniformBuffer);
+        assert_eq!(entry.data_end(), size_of::<DescriptorBufferData>());
+    }
+
+    #[test]
+    fn wrong_binding() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            &device,
+            &DescriptorSetLayoutCreateInfo {
+                bindings: &[DescriptorSetLayoutBinding {
+                    stages: ShaderStages::all_graphics(),
+                    ..DescriptorSetLayoutBinding::new(DescriptorType::UniformBuffer)
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let result = DescriptorUpdateTemplate::try_new(
+            &device,
+            &DescriptorUpdateTemplateCreateInfo {
+                entries: &[DescriptorUpdateTemplateEntry {
+                    dst_binding: 1,
+                    ..DescriptorUpdateTemplateEntry::new(DescriptorType::UniformBuffer)
+                }],
+                ..DescriptorUpdateTemplateCreateInfo::new(&layout)
+            },
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -138,6 +138,7 @@ use crate::{
         ExternalMemoryHandleType, MemoryFdProperties, MemoryRequirements,
         MemoryWin32HandleProperties,
     },
+    query::TimeDomain,
     RawFd, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
     VulkanError, VulkanObject,
 };
@@ -1532,6 +1533,149 @@ impl Device {
         ))
     }
 
+    /// Retrieves timestamps from multiple time domains at the same moment, panicking on a
+    /// validation error.
+    ///
+    /// The [`khr_calibrated_timestamps`] or [`ext_calibrated_timestamps`] extension must be
+    /// enabled on the device.
+    ///
+    /// This is a shortcut for `try_calibrated_timestamps().map_err(Validated::unwrap)`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_calibrated_timestamps`] returns a [`ValidationError`].
+    ///
+    /// [`khr_calibrated_timestamps`]: DeviceExtensions::khr_calibrated_timestamps
+    /// [`ext_calibrated_timestamps`]: DeviceExtensions::ext_calibrated_timestamps
+    /// [`try_calibrated_timestamps`]: Self::try_calibrated_timestamps
+    #[inline]
+    #[track_caller]
+    pub fn calibrated_timestamps(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<(Vec<u64>, u64), VulkanError> {
+        match self.try_calibrated_timestamps(time_domains) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    /// Retrieves timestamps from multiple time domains at the same moment.
+    ///
+    /// Returns a timestamp for each element of `time_domains`, in the same order, and the maximum
+    /// deviation between the moments at which the timestamps were actually taken, in
+    /// nanoseconds.
+    ///
+    /// The [`khr_calibrated_timestamps`] or [`ext_calibrated_timestamps`] extension must be
+    /// enabled on the device.
+    ///
+    /// [`khr_calibrated_timestamps`]: DeviceExtensions::khr_calibrated_timestamps
+    /// [`ext_calibrated_timestamps`]: DeviceExtensions::ext_calibrated_timestamps
+    #[inline]
+    pub fn try_calibrated_timestamps(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<(Vec<u64>, u64), Validated<VulkanError>> {
+        self.validate_calibrated_timestamps(time_domains)?;
+
+        Ok(unsafe { self.calibrated_timestamps_unchecked(time_domains) }?)
+    }
+
+    pub(crate) fn validate_calibrated_timestamps(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.enabled_extensions().khr_calibrated_timestamps
+            || self.enabled_extensions().ext_calibrated_timestamps)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::DeviceExtension("khr_calibrated_timestamps")]),
+                    RequiresAllOf(&[Requires::DeviceExtension("ext_calibrated_timestamps")]),
+                ]),
+                ..Default::default()
+            }));
+        }
+
+        if time_domains.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "time_domains".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-vkGetCalibratedTimestampsKHR-timestampCount-arraylength"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, &time_domain) in time_domains.iter().enumerate() {
+            time_domain.validate_device(self).map_err(|err| {
+                err.add_context(format!("time_domains[{}]", index))
+                    .set_vuids(&["VUID-VkCalibratedTimestampInfoKHR-timeDomain-parameter"])
+            })?;
+
+            if !self
+                .physical_device()
+                .calibrateable_time_domains()
+                .contains(&time_domain)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("time_domains[{}]", index).into(),
+                    problem: "is not one of the calibrateable time domains of the physical device"
+                        .into(),
+                    vuids: &["VUID-VkCalibratedTimestampInfoKHR-timeDomain-02354"],
+                    ..Default::default()
+                }));
+            }
+
+            if time_domains[..index].contains(&time_domain) {
+                return Err(Box::new(ValidationError {
+                    context: "time_domains".into(),
+                    problem: format!("contains `TimeDomain::{:?}` more than once", time_domain)
+                        .into(),
+                    vuids: &["VUID-vkGetCalibratedTimestampsKHR-timeDomain-09246"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn calibrated_timestamps_unchecked(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<(Vec<u64>, u64), VulkanError> {
+        let timestamp_infos_vk: SmallVec<[_; 4]> = time_domains
+            .iter()
+            .map(|&time_domain| {
+                vk::CalibratedTimestampInfoKHR::default().time_domain(time_domain.into())
+            })
+            .collect();
+
+        let mut timestamps = vec![0; time_domains.len()];
+        let mut max_deviation = 0;
+
+        let fns = self.fns();
+        let get_calibrated_timestamps = if self.enabled_extensions().khr_calibrated_timestamps {
+            fns.khr_calibrated_timestamps.get_calibrated_timestamps_khr
+        } else {
+            fns.ext_calibrated_timestamps.get_calibrated_timestamps_ext
+        };
+        unsafe {
+            get_calibrated_timestamps(
+                self.handle,
+                timestamp_infos_vk.len() as u32,
+                timestamp_infos_vk.as_ptr(),
+                timestamps.as_mut_ptr(),
+                &mut max_deviation,
+            )
+        }
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok((timestamps, max_deviation))
+    }
+
     /// Assigns a human-readable name to `object` for debugging purposes, panicking on a validation
     /// error.
     ///
@@ -12,6 +12,7 @@ use crate::{
     instance::{Instance, InstanceOwned},
     macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
     memory::{ExternalMemoryHandleType, MemoryBudget, MemoryProperties},
+    query::TimeDomain,
     swapchain::{
         ColorSpace, FullScreenExclusive, PresentMode, Surface, SurfaceApi, SurfaceCapabilities,
         SurfaceInfo, SurfaceInfo2ExtensionsVk,
@@ -72,6 +73,7 @@ pub struct PhysicalDevice {
     extension_properties: Vec<ExtensionProperties>,
     memory_properties: MemoryProperties,
     queue_family_properties: Vec<QueueFamilyProperties>,
+    calibrateable_time_domains: Vec<TimeDomain>,
 
     // Data queried by the user at runtime, cached for faster lookups.
     display_properties: WeakArcOnceCache<vk::DisplayKHR, Display>,
@@ -133,6 +135,10 @@ impl PhysicalDevice {
                 unsafe { Self::get_queue_family_properties(handle, instance) };
         };
 
+        let calibrateable_time_domains = unsafe {
+            Self::get_calibrateable_time_domains(handle, instance, &supported_extensions)
+        }?;
+
         Ok(Arc::new(PhysicalDevice {
             handle,
             instance: DebugWrapper(instance.clone()),
@@ -145,6 +151,7 @@ impl PhysicalDevice {
             extension_properties,
             memory_properties,
             queue_family_properties,
+            calibrateable_time_domains,
 
             display_properties: WeakArcOnceCache::new(),
             display_plane_properties: RwLock::new(Vec::new()),
@@ -445,6 +452,50 @@ impl PhysicalDevice {
             .collect()
     }
 
+    unsafe fn get_calibrateable_time_domains(
+        handle: vk::PhysicalDevice,
+        instance: &Instance,
+        supported_extensions: &DeviceExtensions,
+    ) -> Result<Vec<TimeDomain>, VulkanError> {
+        if !(supported_extensions.khr_calibrated_timestamps
+            || supported_extensions.ext_calibrated_timestamps)
+        {
+            return Ok(Vec::new());
+        }
+
+        let fns = instance.fns();
+        let get_calibrateable_time_domains = if supported_extensions.khr_calibrated_timestamps {
+            fns.khr_calibrated_timestamps
+                .get_physical_device_calibrateable_time_domains_khr
+        } else {
+            fns.ext_calibrated_timestamps
+                .get_physical_device_calibrateable_time_domains_ext
+        };
+
+        loop {
+            let mut count = 0;
+            unsafe { get_calibrateable_time_domains(handle, &mut count, ptr::null_mut()) }
+                .result()
+                .map_err(VulkanError::from)?;
+
+            let mut output = Vec::with_capacity(count as usize);
+            let result =
+                unsafe { get_calibrateable_time_domains(handle, &mut count, output.as_mut_ptr()) };
+
+            match result {
+                vk::Result::SUCCESS => {
+                    unsafe { output.set_len(count as usize) };
+                    return Ok(output
+                        .into_iter()
+                        .filter_map(|time_domain_vk| time_domain_vk.try_into().ok())
+                        .collect());
+                }
+                vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+
     /// Returns the instance that owns the physical device.
     #[inline]
     pub fn instance(&self) -> &Arc<Instance> {
@@ -497,6 +548,19 @@ impl PhysicalDevice {
         &self.queue_family_properties
     }
 
+    /// Returns the time domains that can be used with [`Device::calibrated_timestamps`].
+    ///
+    /// This is empty if neither the [`khr_calibrated_timestamps`] nor the
+    /// [`ext_calibrated_timestamps`] extension is supported by the physical device.
+    ///
+    /// [`Device::calibrated_timestamps`]: crate::device::Device::calibrated_timestamps
+    /// [`khr_calibrated_timestamps`]: crate::device::DeviceExtensions::khr_calibrated_timestamps
+    /// [`ext_calibrated_timestamps`]: crate::device::DeviceExtensions::ext_calibrated_timestamps
+    #[inline]
+    pub fn calibrateable_time_domains(&self) -> &[TimeDomain] {
+        &self.calibrateable_time_domains
+    }
+
     /// Retrieves the current memory budget and usage of each memory heap of the physical device,
     /// panicking on a validation error.
     ///
@@ -3480,6 +3544,7 @@ impl Debug for PhysicalDevice {
             extension_properties,
             memory_properties,
             queue_family_properties,
+            calibrateable_time_domains,
 
             display_properties: _,
             display_plane_properties: _,
@@ -3502,6 +3567,7 @@ impl Debug for PhysicalDevice {
             .field("extension_properties", extension_properties)
             .field("memory_properties", memory_properties)
             .field("queue_family_properties", queue_family_properties)
+            .field("calibrateable_time_domains", calibrateable_time_domains)
             .finish_non_exhaustive()
     }
 }
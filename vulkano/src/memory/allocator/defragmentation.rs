@@ -0,0 +1,401 @@
+use super::{
+    aliasable_box::AliasableBox, DeviceLayout, DeviceMemoryBlock, GenericMemoryAllocator,
+    MemoryAlloc, Suballocator,
+};
+use crate::{
+    memory::{DeviceAlignment, ResourceMemory},
+    DeviceSize,
+};
+use std::{
+    num::NonZero,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+impl<S: Suballocator> GenericMemoryAllocator<S> {
+    /// Performs one pass of incremental defragmentation of the pools of [`DeviceMemory`] blocks.
+    ///
+    /// The allocator tries to move the `candidates` out of the least used blocks into the most
+    /// used ones, such that the least used blocks end up empty. For every allocation that should
+    /// be moved, a new allocation is made at the destination and returned as part of a
+    /// [`DefragmentationMove`]. A block is only evacuated if all of its allocations are among the
+    /// `candidates`, and new blocks are never allocated. Candidates whose memory wasn't
+    /// suballocated from one of the pools of `self` are ignored.
+    ///
+    /// For each returned move, you are expected to create a new resource and bind the new
+    /// allocation to it using [`ResourceMemory::from_allocation`], record a copy of the contents
+    /// of the old resource to the new one, and then replace the old resource with the new one once
+    /// the copy has completed execution on the device. Dropping the old resource deallocates the
+    /// old allocation. If you don't want to perform a move after all, you must deallocate the new
+    /// allocation instead. Once all moves of a pass have been carried out, you can call
+    /// [`free_empty_blocks`] to free the blocks that were evacuated, and then start another pass
+    /// with the remaining candidates. Defragmentation is complete once a pass returns no moves.
+    ///
+    /// How much work a single pass is allowed to do can be limited using `info`, so that the
+    /// defragmentation can be spread out over multiple frames.
+    ///
+    /// [`DeviceMemory`]: crate::memory::DeviceMemory
+    /// [`free_empty_blocks`]: Self::free_empty_blocks
+    pub fn defragmentation_pass(
+        &self,
+        candidates: &[DefragmentationCandidate<'_>],
+        info: &DefragmentationInfo<'_>,
+    ) -> Vec<DefragmentationMove> {
+        let &DefragmentationInfo {
+            memory_type_bits,
+            max_bytes_per_pass,
+            max_allocations_per_pass,
+            max_duration_per_pass,
+            _ne: _,
+        } = info;
+
+        let start = Instant::now();
+        let mut moves = Vec::new();
+        let mut bytes_moved = 0;
+
+        'pools: for (memory_type_index, pool) in self.pools.iter().enumerate() {
+            if memory_type_bits & (1 << memory_type_index) == 0 {
+                continue;
+            }
+
+            let blocks = &mut *pool.blocks.lock();
+            let vec = &mut blocks.vec;
+
+            if vec.len() < 2 {
+                continue;
+            }
+
+            // For each block, the indices and layouts of the candidates that were suballocated
+            // from it.
+            let mut block_candidates = vec![Vec::new(); vec.len()];
+
+            for (candidate_index, candidate) in candidates.iter().enumerate() {
+                let memory = candidate.memory;
+
+                if memory.suballocation_handle.is_none() {
+                    continue;
+                }
+
+                let Some(layout) = NonZero::new(memory.size).and_then(|size| {
+                    DeviceLayout::new(size, candidate.alignment.max(pool.atom_size))
+                }) else {
+                    continue;
+                };
+
+                let block_ptr = memory
+                    .allocation_handle
+                    .as_ptr()
+                    .cast::<DeviceMemoryBlock<S>>();
+
+                if let Some(block_index) =
+                    vec.iter().position(|block| &raw const **block == block_ptr)
+                {
+                    block_candidates[block_index].push((candidate_index, layout));
+                }
+            }
+
+            // Evacuate the least used blocks first, into the most used blocks first.
+            let mut block_indices = (0..vec.len()).collect::<Vec<_>>();
+            block_indices.sort_by_key(|&block_index| {
+                let block = &vec[block_index];
+
+                block.device_memory.allocation_size() - block.free_size()
+            });
+
+            for (position, &src_block_index) in block_indices.iter().enumerate() {
+                let src_candidates = &block_candidates[src_block_index];
+
+                // Allocations that aren't candidates, including the ones we made in this pass,
+                // would keep the block alive regardless.
+                if src_candidates.is_empty()
+                    || vec[src_block_index].allocation_count != src_candidates.len()
+                {
+                    continue;
+                }
+
+                let moves_len = moves.len();
+
+                for &(candidate_index, layout) in src_candidates {
+                    let size = layout.size();
+
+                    if (max_allocations_per_pass != 0 && moves.len() >= max_allocations_per_pass)
+                        || (max_bytes_per_pass != 0 && bytes_moved + size > max_bytes_per_pass)
+                        || max_duration_per_pass.is_some_and(|max| start.elapsed() >= max)
+                    {
+                        break 'pools;
+                    }
+
+                    let allocation_type = candidates[candidate_index].memory.allocation_type;
+                    let allocation =
+                        block_indices[position + 1..]
+                            .iter()
+                            .rev()
+                            .find_map(|&dst_block_index| {
+                                vec[dst_block_index]
+                                    .allocate(
+                                        layout,
+                                        allocation_type,
+                                        self.buffer_image_granularity,
+                                    )
+                                    .ok()
+                                    .map(|allocation| (dst_block_index, allocation))
+                            });
+
+                    if let Some((dst_block_index, allocation)) = allocation {
+                        moves.push((dst_block_index, candidate_index, allocation));
+                        bytes_moved += size;
+                        pool.allocation_bytes.fetch_add(size, Ordering::Relaxed);
+                    } else {
+                        // The block can't be evacuated completely, so there's no point in moving
+                        // any of its allocations.
+                        for (dst_block_index, _, allocation) in moves.drain(moves_len..) {
+                            let size = allocation.suballocation.unwrap().size;
+                            bytes_moved -= size;
+                            pool.allocation_bytes.fetch_sub(size, Ordering::Relaxed);
+
+                            // SAFETY: We allocated the suballocation from this block above.
+                            unsafe {
+                                vec[dst_block_index].deallocate(allocation.suballocation.unwrap())
+                            };
+                        }
+
+                        break;
+                    }
+                }
+            }
+        }
+
+        moves
+            .into_iter()
+            .map(|(_, candidate_index, allocation)| {
+                self.record_allocation(&allocation);
+
+                DefragmentationMove {
+                    candidate_index,
+                    allocation,
+                }
+            })
+            .collect()
+    }
+
+    /// Frees all [`DeviceMemory`] blocks that have no allocations left in them, and returns the
+    /// total size of the freed blocks.
+    ///
+    /// The allocator never frees blocks on its own. You would usually call this after carrying
+    /// out the moves of a [defragmentation pass], but it can also be used to trim the pools after
+    /// a large number of allocations were deallocated.
+    ///
+    /// [`DeviceMemory`]: crate::memory::DeviceMemory
+    /// [defragmentation pass]: Self::defragmentation_pass
+    pub fn free_empty_blocks(&self) -> DeviceSize {
+        let mut freed_size = 0;
+
+        for pool in self.pools.iter() {
+            let blocks = &mut *pool.blocks.lock();
+            let block_allocator = &blocks.block_allocator;
+
+            blocks.vec.retain_mut(|block| {
+                if block.allocation_count != 0 {
+                    return true;
+                }
+
+                let size = block.device_memory.allocation_size();
+                freed_size += size;
+                pool.block_bytes.fetch_sub(size, Ordering::Relaxed);
+
+                // SAFETY: The block has no allocations left, so there can't be any allocation
+                // handles pointing to it, and we remove it from the pool right after.
+                unsafe { AliasableBox::drop(block, block_allocator) };
+
+                false
+            });
+        }
+
+        freed_size
+    }
+}
+
+/// An allocation that may be moved by a [defragmentation pass].
+///
+/// [defragmentation pass]: GenericMemoryAllocator::defragmentation_pass
+#[derive(Clone, Copy, Debug)]
+pub struct DefragmentationCandidate<'a> {
+    /// The memory of the resource that may be moved.
+    pub memory: &'a ResourceMemory,
+
+    /// The alignment that the memory of the resource must have.
+    ///
+    /// This should be the alignment of the [memory requirements] of the resource.
+    ///
+    /// [memory requirements]: crate::memory::MemoryRequirements
+    pub alignment: DeviceAlignment,
+}
+
+/// Parameters to perform a [defragmentation pass].
+///
+/// [defragmentation pass]: GenericMemoryAllocator::defragmentation_pass
+#[derive(Clone, Debug)]
+pub struct DefragmentationInfo<'a> {
+    /// Only the pools of memory types whose index has a corresponding bit set are defragmented.
+    ///
+    /// The default value is [`u32::MAX`].
+    pub memory_type_bits: u32,
+
+    /// The maximum total number of bytes that a pass is allowed to move, or `0` for no limit.
+    ///
+    /// The default value is `0`.
+    pub max_bytes_per_pass: DeviceSize,
+
+    /// The maximum number of allocations that a pass is allowed to move, or `0` for no limit.
+    ///
+    /// The default value is `0`.
+    pub max_allocations_per_pass: usize,
+
+    /// The maximum amount of time that a pass is allowed to spend finding new allocations, or
+    /// [`None`] for no limit.
+    ///
+    /// The default value is [`None`].
+    pub max_duration_per_pass: Option<Duration>,
+
+    pub _ne: crate::NonExhaustive<'a>,
+}
+
+impl Default for DefragmentationInfo<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DefragmentationInfo<'_> {
+    /// Returns a default `DefragmentationInfo`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            memory_type_bits: u32::MAX,
+            max_bytes_per_pass: 0,
+            max_allocations_per_pass: 0,
+            max_duration_per_pass: None,
+            _ne: crate::NE,
+        }
+    }
+}
+
+/// A move of an allocation returned by a [defragmentation pass].
+///
+/// [defragmentation pass]: GenericMemoryAllocator::defragmentation_pass
+#[derive(Debug)]
+pub struct DefragmentationMove {
+    /// The index of the candidate that should be moved.
+    pub candidate_index: usize,
+
+    /// The new allocation that the contents of the candidate should be moved to.
+    pub allocation: MemoryAlloc,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        memory::allocator::{
+            AllocationType, GenericMemoryAllocatorCreateInfo, MemoryAllocator, MemoryTypeFilter,
+            StandardMemoryAllocator,
+        },
+        VulkanObject,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn defragmentation_pass() {
+        const BLOCK_SIZE: DeviceSize = 64 * 1024;
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        let memory_type_count = device
+            .physical_device()
+            .memory_properties()
+            .memory_types
+            .len();
+        let allocator = Arc::new(StandardMemoryAllocator::new(
+            &device,
+            &GenericMemoryAllocatorCreateInfo {
+                block_sizes: &vec![BLOCK_SIZE; memory_type_count],
+                ..Default::default()
+            },
+        ));
+        let memory_type_index = allocator
+            .find_memory_type_index(u32::MAX, MemoryTypeFilter::PREFER_DEVICE)
+            .unwrap();
+        let layout = DeviceLayout::from_size_alignment(BLOCK_SIZE / 4, 256).unwrap();
+
+        let mut memories = (0..5)
+            .map(|_| {
+                let allocation = allocator
+                    .try_allocate_from_type(
+                        memory_type_index,
+                        layout,
+                        AllocationType::Linear,
+                        false,
+                    )
+                    .unwrap();
+
+                Some(unsafe { ResourceMemory::from_allocation(&allocator, allocation) })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            allocator.pools()[memory_type_index as usize].blocks().len(),
+            2
+        );
+
+        // Leave the first block half full and the second one a quarter full.
+        memories[0] = None;
+        memories[1] = None;
+
+        let candidates = memories
+            .iter()
+            .flatten()
+            .map(|memory| DefragmentationCandidate {
+                memory,
+                alignment: layout.alignment(),
+            })
+            .collect::<Vec<_>>();
+
+        let moves = allocator.defragmentation_pass(
+            &candidates,
+            &DefragmentationInfo {
+                max_bytes_per_pass: BLOCK_SIZE,
+                ..Default::default()
+            },
+        );
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].candidate_index, 2);
+        assert_ne!(
+            moves[0].allocation.device_memory.handle(),
+            memories[4].as_ref().unwrap().device_memory().handle(),
+        );
+
+        drop(candidates);
+
+        let allocation = moves.into_iter().next().unwrap().allocation;
+        memories[4] = Some(unsafe { ResourceMemory::from_allocation(&allocator, allocation) });
+
+        assert_eq!(allocator.free_empty_blocks(), BLOCK_SIZE);
+        assert_eq!(
+            allocator.pools()[memory_type_index as usize].blocks().len(),
+            1
+        );
+
+        let candidates = memories
+            .iter()
+            .flatten()
+            .map(|memory| DefragmentationCandidate {
+                memory,
+                alignment: layout.alignment(),
+            })
+            .collect::<Vec<_>>();
+
+        assert!(allocator
+            .defragmentation_pass(&candidates, &DefragmentationInfo::default())
+            .is_empty());
+    }
+}
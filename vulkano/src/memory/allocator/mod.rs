@@ -206,18 +206,25 @@
 //! [`Rc`]: std::rc::Rc
 //! [region]: Suballocator#regions
 
-use self::{aliasable_box::AliasableBox, array_vec::ArrayVec, suballocator::Region};
+use self::{
+    aliasable_box::AliasableBox,
+    array_vec::ArrayVec,
+    statistics::AllocationRecordKey,
+    suballocator::Region,
+};
 pub use self::{
+    defragmentation::{DefragmentationCandidate, DefragmentationInfo, DefragmentationMove},
     layout::DeviceLayout,
+    statistics::{LiveAllocation, Statistics},
     suballocator::{
         AllocationType, BuddyAllocator, BumpAllocator, FreeListAllocator, Suballocation,
-        Suballocator, SuballocatorError,
+        Suballocator, SuballocatorError, TlsfAllocator,
     },
 };
 use super::{
     DedicatedAllocation, DeviceAlignment, DeviceMemory, ExternalMemoryHandleTypes,
-    MemoryAllocateFlags, MemoryAllocateInfo, MemoryMapInfo, MemoryProperties, MemoryPropertyFlags,
-    MemoryRequirements,
+    MemoryAllocateFlags, MemoryAllocateInfo, MemoryBudget, MemoryMapInfo, MemoryProperties,
+    MemoryPropertyFlags, MemoryRequirements,
 };
 use crate::{
     device::{Device, DeviceOwned},
@@ -225,18 +232,25 @@ use crate::{
     DeviceSize, Validated, Version, VulkanError,
 };
 use ash::vk::{self, MAX_MEMORY_TYPES};
+use foldhash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
 use slabbin::SlabAllocator;
 use std::{
+    cmp,
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
     iter::FusedIterator,
     ops::BitOr,
     ptr, slice,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
+mod defragmentation;
 mod layout;
+mod statistics;
 pub mod suballocator;
 
 /// General-purpose memory allocators which allocate from any memory type dynamically as needed.
@@ -802,6 +816,32 @@ pub struct AllocationCreateInfo<'a> {
     /// The default value is [`MemoryAllocatePreference::Unknown`].
     pub allocate_preference: MemoryAllocatePreference,
 
+    /// Whether the allocation should fail instead of exceeding the [budget] of the memory heap.
+    ///
+    /// If this is `true` and allocating new [`DeviceMemory`] would exceed the budget of the
+    /// memory heap that the memory type resides in, the allocation is only suballocated from
+    /// existing blocks, and [`MemoryAllocatorError::OutOfBudget`] is returned if that fails. This
+    /// is useful if you would rather evict some resources than have the operating system start
+    /// paging memory. This option has no effect when using
+    /// [`MemoryAllocatePreference::NeverAllocate`].
+    ///
+    /// The default value is `false`.
+    ///
+    /// [budget]: GenericMemoryAllocator::budget
+    pub within_budget: bool,
+
+    /// A label to attach to the allocation for debugging purposes.
+    ///
+    /// This is only used if [`debug_allocations`] is enabled on the allocator, in which case the
+    /// label is included in the [JSON dump] and in the report of allocations that are still alive
+    /// when the allocator is dropped.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`debug_allocations`]: GenericMemoryAllocatorCreateInfo::debug_allocations
+    /// [JSON dump]: GenericMemoryAllocator::json_dump
+    pub debug_label: Option<&'a str>,
+
     pub _ne: crate::NonExhaustive<'a>,
 }
 
@@ -820,6 +860,8 @@ impl AllocationCreateInfo<'_> {
             memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
             memory_type_bits: u32::MAX,
             allocate_preference: MemoryAllocatePreference::Unknown,
+            within_budget: false,
+            debug_label: None,
             _ne: crate::NE,
         }
     }
@@ -948,6 +990,12 @@ pub enum MemoryAllocatorError {
     /// This is returned when using [`MemoryAllocatePreference::NeverAllocate`] and the allocation
     /// size exceeded the block size for all heaps of suitable memory types.
     BlockSizeExceeded,
+
+    /// Allocating [`DeviceMemory`] would exceed the budget of the memory heap.
+    ///
+    /// This is returned when using [`AllocationCreateInfo::within_budget`] and there is not
+    /// enough free space in the existing blocks of any of the suitable memory types.
+    OutOfBudget,
 }
 
 impl Error for MemoryAllocatorError {
@@ -972,12 +1020,34 @@ impl Display for MemoryAllocatorError {
                 "the allocation size was greater than the block size for all heaps of suitable \
                 memory types and dedicated allocations were explicitly forbidden"
             }
+            Self::OutOfBudget => "allocating device memory would exceed the budget of the heap",
         };
 
         f.write_str(msg)
     }
 }
 
+/// The budget of a memory heap, combined with the memory that a [`GenericMemoryAllocator`] has
+/// allocated from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HeapBudget {
+    /// The total size of the [`DeviceMemory`] blocks and dedicated allocations that the allocator
+    /// has allocated from the heap.
+    pub block_bytes: DeviceSize,
+
+    /// The total size of the allocations that the allocator has made from the heap. This is at
+    /// most `block_bytes`, the difference being the free space in the blocks.
+    pub allocation_bytes: DeviceSize,
+
+    /// An estimate of how much memory of the heap the process is currently using.
+    pub usage: DeviceSize,
+
+    /// An estimate of how much memory of the heap the process can use before allocation is likely
+    /// to fail or cause performance degradation.
+    pub budget: DeviceSize,
+}
+
 /// Standard memory allocator intended as a global and general-purpose allocator.
 ///
 /// This type of allocator is what you should always use, unless you know, for a fact, that it is
@@ -1001,8 +1071,9 @@ pub type StandardMemoryAllocator = GenericMemoryAllocator<FreeListAllocator>;
 ///
 /// Every time a new `DeviceMemory` block is allocated, it is mapped in full automatically as long
 /// as it resides in host-visible memory. It remains mapped until it is dropped, which only happens
-/// if the allocator is dropped. In other words, all eligible blocks are persistently mapped, so
-/// you don't need to worry about whether or not your host-visible allocations are host-accessible.
+/// if the allocator is dropped or the block is [freed explicitly]. In other words, all eligible
+/// blocks are persistently mapped, so you don't need to worry about whether or not your
+/// host-visible allocations are host-accessible.
 ///
 /// # `DeviceMemory` allocation
 ///
@@ -1018,10 +1089,28 @@ pub type StandardMemoryAllocator = GenericMemoryAllocator<FreeListAllocator>;
 /// another block. No `DeviceMemory` is allocated when the allocator is created, the blocks are
 /// only allocated once they are needed.
 ///
+/// # Defragmentation
+///
+/// Over time, the blocks of a pool can end up sparsely populated. To counteract that, the
+/// allocations can be compacted into fewer blocks incrementally using [defragmentation passes],
+/// after which the blocks that were emptied can be [freed explicitly].
+///
+/// # Debugging
+///
+/// The allocator can report [statistics] about its pools and blocks, as well as write a [JSON
+/// dump] of every suballocation for offline visualization. If [`debug_allocations`] is enabled,
+/// the allocator additionally keeps a record of every allocation and reports the ones that are
+/// still alive when it is dropped.
+///
 /// [memory allocator]: MemoryAllocator
 /// [a pool of `DeviceMemory` blocks]: DeviceMemoryPool
 /// [suballocate]: Suballocator
 /// [the `MemoryAllocator` implementation]: Self#impl-MemoryAllocator-for-GenericMemoryAllocator<S>
+/// [freed explicitly]: Self::free_empty_blocks
+/// [defragmentation passes]: Self::defragmentation_pass
+/// [statistics]: Self::statistics
+/// [JSON dump]: Self::json_dump
+/// [`debug_allocations`]: GenericMemoryAllocatorCreateInfo::debug_allocations
 #[derive(Debug)]
 pub struct GenericMemoryAllocator<S> {
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
@@ -1035,6 +1124,8 @@ pub struct GenericMemoryAllocator<S> {
     flags: MemoryAllocateFlags,
     // How many `DeviceMemory` allocations should be allowed before restricting them.
     max_allocations: u32,
+    // The allocations that are currently alive, if debugging of allocations is enabled.
+    allocation_records: Option<Mutex<HashMap<AllocationRecordKey, LiveAllocation>>>,
 }
 
 impl<S> GenericMemoryAllocator<S> {
@@ -1043,8 +1134,11 @@ impl<S> GenericMemoryAllocator<S> {
     const EMPTY_POOL: DeviceMemoryPool<S> = DeviceMemoryPool {
         blocks: Mutex::new(DeviceMemoryBlockVec::new()),
         property_flags: MemoryPropertyFlags::empty(),
+        heap_index: 0,
         atom_size: DeviceAlignment::MIN,
         block_size: 0,
+        block_bytes: AtomicU64::new(0),
+        allocation_bytes: AtomicU64::new(0),
     };
 
     /// Creates a new `GenericMemoryAllocator<S>` using the provided suballocator `S` for
@@ -1063,6 +1157,7 @@ impl<S> GenericMemoryAllocator<S> {
             dedicated_allocation,
             export_handle_types,
             mut device_address,
+            debug_allocations,
             _ne: _,
         } = create_info;
 
@@ -1103,6 +1198,7 @@ impl<S> GenericMemoryAllocator<S> {
 
             let property_flags = memory_type.property_flags;
             pools[index].property_flags = property_flags;
+            pools[index].heap_index = memory_type.heap_index;
 
             if property_flags.intersects(MemoryPropertyFlags::HOST_VISIBLE)
                 && !property_flags.intersects(MemoryPropertyFlags::HOST_COHERENT)
@@ -1179,6 +1275,7 @@ impl<S> GenericMemoryAllocator<S> {
             flags,
             memory_type_bits,
             max_allocations,
+            allocation_records: debug_allocations.then(Default::default),
         }
     }
 
@@ -1189,6 +1286,104 @@ impl<S> GenericMemoryAllocator<S> {
         &self.pools
     }
 
+    /// Returns the current budget of each memory heap, combined with the memory that the
+    /// allocator has allocated from it. Each memory heap index has a corresponding element in the
+    /// returned `Vec`.
+    ///
+    /// If the [`ext_memory_budget`] extension is enabled on the device, the usage and budget are
+    /// the ones reported by the implementation. Otherwise, they are estimated: the usage is the
+    /// size of the [`DeviceMemory`] that this allocator has allocated from the heap, which doesn't
+    /// include memory allocated by other means, and the budget is 80% of the heap size.
+    ///
+    /// [`ext_memory_budget`]: crate::device::DeviceExtensions::ext_memory_budget
+    pub fn budget(&self) -> Vec<HeapBudget> {
+        let memory_budget = self.memory_budget();
+        let heap_count = self
+            .device
+            .physical_device()
+            .memory_properties()
+            .memory_heaps
+            .len();
+
+        (0..heap_count as u32)
+            .map(|heap_index| self.heap_budget(heap_index, memory_budget.as_ref()))
+            .collect()
+    }
+
+    fn memory_budget(&self) -> Option<MemoryBudget> {
+        if self.device.enabled_extensions().ext_memory_budget {
+            // SAFETY: We checked that the extension is enabled on the device, which means that
+            // it's supported by the physical device.
+            Some(unsafe { self.device.physical_device().memory_budget_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    fn heap_budget(&self, heap_index: u32, memory_budget: Option<&MemoryBudget>) -> HeapBudget {
+        let (block_bytes, allocation_bytes) = self
+            .pools
+            .iter()
+            .filter(|pool| pool.heap_index == heap_index)
+            .fold((0, 0), |(block_bytes, allocation_bytes), pool| {
+                (
+                    block_bytes + pool.block_bytes.load(Ordering::Relaxed),
+                    allocation_bytes + pool.allocation_bytes.load(Ordering::Relaxed),
+                )
+            });
+        let heap_size = self
+            .device
+            .physical_device()
+            .memory_properties()
+            .memory_heaps[heap_index as usize]
+            .size;
+
+        let (usage, budget) = if let Some(memory_budget) = memory_budget {
+            (
+                memory_budget.heap_usage[heap_index as usize],
+                cmp::min(memory_budget.heap_budget[heap_index as usize], heap_size),
+            )
+        } else {
+            (block_bytes, heap_size / 10 * 8)
+        };
+
+        HeapBudget {
+            block_bytes,
+            allocation_bytes,
+            usage,
+            budget,
+        }
+    }
+
+    /// Returns `true` if the `DeviceMemory` that might need to be allocated for an allocation of
+    /// the given size and memory type would exceed the budget of the heap that it resides in.
+    fn allocation_exceeds_budget(
+        &self,
+        memory_type_index: u32,
+        size: DeviceSize,
+        allocate_preference: MemoryAllocatePreference,
+        requires_dedicated_allocation: bool,
+    ) -> bool {
+        if allocate_preference == MemoryAllocatePreference::NeverAllocate {
+            return false;
+        }
+
+        let pool = &self.pools[memory_type_index as usize];
+
+        // If the allocation isn't a dedicated allocation, a new block might need to be allocated.
+        let allocation_size = if requires_dedicated_allocation
+            || allocate_preference == MemoryAllocatePreference::AlwaysAllocate
+        {
+            size
+        } else {
+            cmp::max(size, pool.block_size)
+        };
+
+        let budget = self.heap_budget(pool.heap_index, self.memory_budget().as_ref());
+
+        budget.usage.saturating_add(allocation_size) > budget.budget
+    }
+
     #[cold]
     fn try_allocate_device_memory(
         &self,
@@ -1220,6 +1415,10 @@ impl<S> GenericMemoryAllocator<S> {
             unsafe { memory.map_unchecked(&MemoryMapInfo::default()) }?;
         }
 
+        self.pools[memory_type_index as usize]
+            .block_bytes
+            .fetch_add(allocation_size, Ordering::Relaxed);
+
         Ok(Arc::new(memory))
     }
 
@@ -1258,6 +1457,10 @@ impl<S> GenericMemoryAllocator<S> {
             unsafe { memory.map_unchecked(&MemoryMapInfo::default()) }?;
         }
 
+        self.pools[memory_type_index as usize]
+            .block_bytes
+            .fetch_add(allocation_size, Ordering::Relaxed);
+
         Ok(Arc::new(memory))
     }
 }
@@ -1346,6 +1549,9 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             if let Ok(allocation) =
                 block.allocate(layout, allocation_type, self.buffer_image_granularity)
             {
+                pool.allocation_bytes.fetch_add(size, Ordering::Relaxed);
+                self.record_allocation(&allocation);
+
                 return Ok(allocation);
             }
         }
@@ -1398,7 +1604,12 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
         let block = vec.last_mut().unwrap();
 
         match block.allocate(layout, allocation_type, self.buffer_image_granularity) {
-            Ok(allocation) => Ok(allocation),
+            Ok(allocation) => {
+                pool.allocation_bytes.fetch_add(size, Ordering::Relaxed);
+                self.record_allocation(&allocation);
+
+                Ok(allocation)
+            }
             // This can't happen as we always allocate a block of sufficient size.
             Err(SuballocatorError::OutOfRegionMemory) => unreachable!(),
             // This can't happen as the block is fresher than Febreze and we're still holding an
@@ -1481,6 +1692,9 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             if let Ok(allocation) =
                 block.allocate(layout, allocation_type, self.buffer_image_granularity)
             {
+                pool.allocation_bytes.fetch_add(size, Ordering::Relaxed);
+                self.record_allocation(&allocation);
+
                 return Ok(allocation);
             }
         }
@@ -1531,7 +1745,12 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
         let block = vec.last_mut().unwrap();
 
         match block.allocate(layout, allocation_type, self.buffer_image_granularity) {
-            Ok(allocation) => Ok(allocation),
+            Ok(allocation) => {
+                pool.allocation_bytes.fetch_add(size, Ordering::Relaxed);
+                self.record_allocation(&allocation);
+
+                Ok(allocation)
+            }
             // This can't happen as we always allocate a block of sufficient size.
             Err(SuballocatorError::OutOfRegionMemory) => unreachable!(),
             // This can't happen as the block is fresher than Febreze and we're still holding an
@@ -1582,6 +1801,8 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
     /// - Returns [`BlockSizeExceeded`] if `create_info.allocate_preference` is
     ///   [`MemoryAllocatePreference::NeverAllocate`] and `create_info.requirements.size` is
     ///   greater than the block size for all heaps of suitable memory types.
+    /// - Returns [`OutOfBudget`] if `create_info.within_budget` is `true` and allocating new
+    ///   `DeviceMemory` would exceed the budget of the heaps of all suitable memory types.
     ///
     /// [`RawBuffer::memory_requirements`]: crate::buffer::sys::RawBuffer::memory_requirements
     /// [`RawImage::memory_requirements`]: crate::image::sys::RawImage::memory_requirements
@@ -1594,6 +1815,7 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
     /// [`OutOfPoolMemory`]: MemoryAllocatorError::OutOfPoolMemory
     /// [`DedicatedAllocationRequired`]: MemoryAllocatorError::DedicatedAllocationRequired
     /// [`BlockSizeExceeded`]: MemoryAllocatorError::BlockSizeExceeded
+    /// [`OutOfBudget`]: MemoryAllocatorError::OutOfBudget
     fn try_allocate(
         &self,
         requirements: &MemoryRequirements,
@@ -1615,6 +1837,8 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             memory_type_filter,
             memory_type_bits: _,
             allocate_preference,
+            within_budget,
+            debug_label,
             _ne: _,
         } = create_info;
 
@@ -1637,7 +1861,30 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
         loop {
             let pool = &self.pools[memory_type_index as usize];
 
+            let exceeds_budget = within_budget
+                && self.allocation_exceeds_budget(
+                    memory_type_index,
+                    size,
+                    allocate_preference,
+                    requires_dedicated_allocation,
+                );
+
             let res = match allocate_preference {
+                _ if exceeds_budget => {
+                    if requires_dedicated_allocation
+                        || allocate_preference == MemoryAllocatePreference::AlwaysAllocate
+                    {
+                        Err(Validated::Error(MemoryAllocatorError::OutOfBudget))
+                    } else {
+                        self.try_allocate_from_type(
+                            memory_type_index,
+                            layout,
+                            allocation_type,
+                            true,
+                        )
+                        .map_err(|_| Validated::Error(MemoryAllocatorError::OutOfBudget))
+                    }
+                }
                 MemoryAllocatePreference::Unknown => {
                     // VUID-vkBindBufferMemory-buffer-01444
                     // VUID-vkBindImageMemory-image-01445
@@ -1714,7 +1961,11 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             };
 
             match res {
-                Ok(allocation) => return Ok(allocation),
+                Ok(allocation) => {
+                    self.label_allocation(&allocation, debug_label);
+
+                    return Ok(allocation);
+                }
                 // Try a different memory type.
                 Err(err) => {
                     memory_type_bits &= !(1 << memory_type_index);
@@ -1785,6 +2036,8 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
     /// - Returns [`BlockSizeExceeded`] if `create_info.allocate_preference` is
     ///   [`MemoryAllocatePreference::NeverAllocate`] and `create_info.requirements.size` is
     ///   greater than the block size for all heaps of suitable memory types.
+    /// - Returns [`OutOfBudget`] if `create_info.within_budget` is `true` and allocating new
+    ///   `DeviceMemory` would exceed the budget of the heaps of all suitable memory types.
     ///
     /// [`RawBuffer::memory_requirements`]: crate::buffer::sys::RawBuffer::memory_requirements
     /// [`RawImage::memory_requirements`]: crate::image::sys::RawImage::memory_requirements
@@ -1798,6 +2051,7 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
     /// [`OutOfPoolMemory`]: MemoryAllocatorError::OutOfPoolMemory
     /// [`DedicatedAllocationRequired`]: MemoryAllocatorError::DedicatedAllocationRequired
     /// [`BlockSizeExceeded`]: MemoryAllocatorError::BlockSizeExceeded
+    /// [`OutOfBudget`]: MemoryAllocatorError::OutOfBudget
     unsafe fn allocate_unchecked(
         &self,
         requirements: &MemoryRequirements,
@@ -1819,6 +2073,8 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             memory_type_filter,
             memory_type_bits: _,
             allocate_preference,
+            within_budget,
+            debug_label,
             _ne: _,
         } = create_info;
 
@@ -1841,7 +2097,33 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
         loop {
             let pool = &self.pools[memory_type_index as usize];
 
+            let exceeds_budget = within_budget
+                && self.allocation_exceeds_budget(
+                    memory_type_index,
+                    size,
+                    allocate_preference,
+                    requires_dedicated_allocation,
+                );
+
             let res = match allocate_preference {
+                _ if exceeds_budget => {
+                    if requires_dedicated_allocation
+                        || allocate_preference == MemoryAllocatePreference::AlwaysAllocate
+                    {
+                        Err(MemoryAllocatorError::OutOfBudget)
+                    } else {
+                        // SAFETY: Enforced by the caller.
+                        unsafe {
+                            self.allocate_from_type_unchecked(
+                                memory_type_index,
+                                layout,
+                                allocation_type,
+                                true,
+                            )
+                        }
+                        .map_err(|_| MemoryAllocatorError::OutOfBudget)
+                    }
+                }
                 MemoryAllocatePreference::Unknown => {
                     // VUID-vkBindBufferMemory-buffer-01444
                     // VUID-vkBindImageMemory-image-01445
@@ -1940,7 +2222,11 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             };
 
             match res {
-                Ok(allocation) => return Ok(allocation),
+                Ok(allocation) => {
+                    self.label_allocation(&allocation, debug_label);
+
+                    return Ok(allocation);
+                }
                 // Try a different memory type.
                 Err(err) => {
                     memory_type_bits &= !(1 << memory_type_index);
@@ -1969,11 +2255,18 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             )
             .map_err(|err| err.map(MemoryAllocatorError::AllocateDeviceMemory))?;
 
-        Ok(MemoryAlloc {
+        self.pools[memory_type_index as usize]
+            .allocation_bytes
+            .fetch_add(allocation_size, Ordering::Relaxed);
+
+        let allocation = MemoryAlloc {
             device_memory,
             suballocation: None,
             allocation_handle: AllocationHandle::null(),
-        })
+        };
+        self.record_allocation(&allocation);
+
+        Ok(allocation)
     }
 
     #[cold]
@@ -1994,17 +2287,28 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
         }
         .map_err(MemoryAllocatorError::AllocateDeviceMemory)?;
 
-        Ok(MemoryAlloc {
+        self.pools[memory_type_index as usize]
+            .allocation_bytes
+            .fetch_add(allocation_size, Ordering::Relaxed);
+
+        let allocation = MemoryAlloc {
             device_memory,
             suballocation: None,
             allocation_handle: AllocationHandle::null(),
-        })
+        };
+        self.record_allocation(&allocation);
+
+        Ok(allocation)
     }
 
     unsafe fn deallocate(&self, allocation: MemoryAlloc) {
+        self.forget_allocation(&allocation);
+
+        let memory_type_index = allocation.device_memory.memory_type_index();
+        let pool = &self.pools[memory_type_index as usize];
+
         if let Some(suballocation) = allocation.suballocation {
-            let memory_type_index = allocation.device_memory.memory_type_index();
-            let blocks = self.pools[memory_type_index as usize].blocks.lock();
+            let blocks = pool.blocks.lock();
             let vec = &blocks.vec;
             let block_ptr = allocation
                 .allocation_handle
@@ -2020,14 +2324,23 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
             // SAFETY: The caller must guarantee that `allocation` refers to one allocated by
             // `self`, therefore `block_ptr` must be the same one we gave out on allocation. We
             // know that this pointer must be valid, because all blocks are boxed and pinned in
-            // memory and because a block isn't dropped until the allocator itself is dropped, at
-            // which point it would be impossible to call this method. We also know that it must be
-            // valid to create a reference to the block, because we locked the pool it belongs to.
+            // memory and because a block isn't dropped until either the allocator itself is dropped,
+            // at which point it would be impossible to call this method, or the block has no
+            // allocations left in it. We also know that it must be valid to create a reference to
+            // the block, because we locked the pool it belongs to.
             let block = unsafe { &mut *block_ptr };
 
             // SAFETY: The caller must guarantee that `allocation` refers to a currently allocated
             // allocation of `self`.
             unsafe { block.deallocate(suballocation) };
+
+            pool.allocation_bytes
+                .fetch_sub(suballocation.size, Ordering::Relaxed);
+        } else {
+            let size = allocation.device_memory.allocation_size();
+
+            pool.block_bytes.fetch_sub(size, Ordering::Relaxed);
+            pool.allocation_bytes.fetch_sub(size, Ordering::Relaxed);
         }
     }
 
@@ -2157,8 +2470,13 @@ pub struct DeviceMemoryPool<S> {
     blocks: Mutex<DeviceMemoryBlockVec<S>>,
     // This is cached here for faster access, so we don't need to hop through 3 pointers.
     property_flags: MemoryPropertyFlags,
+    heap_index: u32,
     atom_size: DeviceAlignment,
     block_size: DeviceSize,
+    // The total size of the blocks and dedicated allocations of this memory type.
+    block_bytes: AtomicU64,
+    // The total size of the suballocations and dedicated allocations of this memory type.
+    allocation_bytes: AtomicU64,
 }
 
 impl<S> DeviceMemoryPool<S> {
@@ -2471,6 +2789,22 @@ pub struct GenericMemoryAllocatorCreateInfo<'a> {
     /// [`khr_device_group`]: crate::device::DeviceExtensions::khr_device_group
     pub device_address: bool,
 
+    /// Whether the allocator should keep a record of every allocation for debugging purposes.
+    ///
+    /// When this is enabled, the allocator records a [backtrace] when an allocation is created,
+    /// along with the [`debug_label`] of the allocation. The labels are included in the
+    /// [JSON dump], and the allocations that are still alive can be retrieved using
+    /// [`live_allocations`]. This comes with a performance cost, so it should only be enabled
+    /// when debugging memory leaks.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [backtrace]: std::backtrace::Backtrace::capture
+    /// [`debug_label`]: AllocationCreateInfo::debug_label
+    /// [JSON dump]: GenericMemoryAllocator::json_dump
+    /// [`live_allocations`]: GenericMemoryAllocator::live_allocations
+    pub debug_allocations: bool,
+
     pub _ne: crate::NonExhaustive<'a>,
 }
 
@@ -2491,6 +2825,7 @@ impl GenericMemoryAllocatorCreateInfo<'_> {
             dedicated_allocation: true,
             export_handle_types: &[],
             device_address: true,
+            debug_allocations: false,
             _ne: crate::NE,
         }
     }
@@ -2628,3 +2963,117 @@ pub(crate) mod aliasable_box {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let allocator = StandardMemoryAllocator::new(&device, &Default::default());
+        let memory_type_index = allocator
+            .find_memory_type_index(u32::MAX, MemoryTypeFilter::PREFER_DEVICE)
+            .unwrap();
+        let pool = &allocator.pools()[memory_type_index as usize];
+        let heap_index = device.physical_device().memory_properties().memory_types
+            [memory_type_index as usize]
+            .heap_index as usize;
+
+        let budget = allocator.budget();
+        assert_eq!(
+            budget.len(),
+            device
+                .physical_device()
+                .memory_properties()
+                .memory_heaps
+                .len(),
+        );
+        assert_eq!(budget[heap_index].block_bytes, 0);
+        assert_eq!(budget[heap_index].allocation_bytes, 0);
+
+        let layout = DeviceLayout::from_size_alignment(1024, 1).unwrap();
+        let allocation = allocator
+            .try_allocate_from_type(memory_type_index, layout, AllocationType::Linear, false)
+            .unwrap();
+
+        let budget = allocator.budget();
+        assert_eq!(budget[heap_index].block_bytes, pool.block_size);
+        assert_eq!(budget[heap_index].allocation_bytes, 1024);
+
+        unsafe { allocator.deallocate(allocation) };
+
+        let budget = allocator.budget();
+        assert_eq!(budget[heap_index].block_bytes, pool.block_size);
+        assert_eq!(budget[heap_index].allocation_bytes, 0);
+
+        allocator.free_empty_blocks();
+
+        let budget = allocator.budget();
+        assert_eq!(budget[heap_index].block_bytes, 0);
+    }
+
+    #[test]
+    fn out_of_budget() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let allocator = StandardMemoryAllocator::new(&device, &Default::default());
+        let memory_type_index = allocator
+            .find_memory_type_index(u32::MAX, MemoryTypeFilter::PREFER_DEVICE)
+            .unwrap();
+        let heap_index = device.physical_device().memory_properties().memory_types
+            [memory_type_index as usize]
+            .heap_index as usize;
+        let budget = allocator.budget()[heap_index].budget;
+
+        let requirements = MemoryRequirements {
+            layout: DeviceLayout::from_size_alignment(budget + 1, 1).unwrap(),
+            memory_type_bits: 1 << memory_type_index,
+            prefers_dedicated_allocation: false,
+            requires_dedicated_allocation: false,
+        };
+
+        for allocate_preference in [
+            MemoryAllocatePreference::Unknown,
+            MemoryAllocatePreference::AlwaysAllocate,
+        ] {
+            let res = allocator.try_allocate(
+                &requirements,
+                AllocationType::Linear,
+                &AllocationCreateInfo {
+                    allocate_preference,
+                    within_budget: true,
+                    ..Default::default()
+                },
+                None,
+            );
+            assert!(matches!(
+                res,
+                Err(Validated::Error(MemoryAllocatorError::OutOfBudget)),
+            ));
+        }
+
+        // Nothing must have been allocated by the failed allocations.
+        assert_eq!(allocator.budget()[heap_index].block_bytes, 0);
+
+        // Allocations that fit within the budget must still succeed.
+        let allocation = allocator
+            .try_allocate(
+                &MemoryRequirements {
+                    layout: DeviceLayout::from_size_alignment(1024, 1).unwrap(),
+                    ..requirements
+                },
+                AllocationType::Linear,
+                &AllocationCreateInfo {
+                    within_budget: true,
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+        assert_eq!(allocator.budget()[heap_index].allocation_bytes, 1024);
+
+        unsafe { allocator.deallocate(allocation) };
+    }
+}
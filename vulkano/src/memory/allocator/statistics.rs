@@ -0,0 +1,637 @@
+use super::{
+    suballocator::SuballocationType, DeviceMemoryBlock, DeviceMemoryPool, GenericMemoryAllocator,
+    MemoryAlloc, Suballocator,
+};
+use crate::{DeviceSize, VulkanObject};
+use ash::vk;
+use std::{
+    backtrace::Backtrace,
+    cmp,
+    fmt::{Debug, Result as FmtResult, Write},
+    sync::Arc,
+};
+
+impl<S: Suballocator> GenericMemoryAllocator<S> {
+    /// Returns the combined statistics of all the [pools] of the allocator.
+    ///
+    /// Dedicated allocations are not taken into account. See [`budget`] for the total amount of
+    /// memory that the allocator has allocated, including dedicated allocations.
+    ///
+    /// # Locking behavior
+    ///
+    /// This locks each pool in turn, so it can't be called while holding a
+    /// [`DeviceMemoryBlocks`] iterator, otherwise a deadlock occurs.
+    ///
+    /// [pools]: DeviceMemoryPool
+    /// [`budget`]: Self::budget
+    /// [`DeviceMemoryBlocks`]: super::DeviceMemoryBlocks
+    pub fn statistics(&self) -> Statistics {
+        let mut statistics = Statistics::new();
+
+        for pool in self.pools.iter() {
+            statistics.merge(&pool.statistics());
+        }
+
+        statistics
+    }
+
+    /// Returns a dump of the state of the allocator in JSON format, which can be used to
+    /// visualize the memory usage offline.
+    ///
+    /// The format follows that of the Vulkan Memory Allocator library's `vmaBuildStatsString`
+    /// with detailed maps enabled, such that the same tools can be used to visualize it. For each
+    /// [`DeviceMemory`] block, every node of the [suballocator]'s [`suballocations`] is listed.
+    /// [`Linear`] suballocations are reported as buffers and [`NonLinear`] ones as optimal images.
+    ///
+    /// If [`debug_allocations`] is enabled, the labels of the allocations are included as well,
+    /// as are the dedicated allocations. Otherwise, dedicated allocations are not listed.
+    ///
+    /// # Locking behavior
+    ///
+    /// This locks each pool in turn, so it can't be called while holding a
+    /// [`DeviceMemoryBlocks`] iterator, otherwise a deadlock occurs.
+    ///
+    /// [`DeviceMemory`]: super::DeviceMemory
+    /// [suballocator]: Suballocator
+    /// [`suballocations`]: Suballocator::suballocations
+    /// [`Linear`]: SuballocationType::Linear
+    /// [`NonLinear`]: SuballocationType::NonLinear
+    /// [`debug_allocations`]: super::GenericMemoryAllocatorCreateInfo::debug_allocations
+    /// [`DeviceMemoryBlocks`]: super::DeviceMemoryBlocks
+    #[must_use]
+    pub fn json_dump(&self) -> String {
+        let mut json = String::new();
+
+        // Writing to a `String` can't fail.
+        self.write_json(&mut json).unwrap();
+
+        json
+    }
+
+    fn write_json(&self, json: &mut String) -> FmtResult {
+        let physical_device = self.device.physical_device();
+        let properties = physical_device.properties();
+        let memory_properties = physical_device.memory_properties();
+        let memory_budget = self.memory_budget();
+        let pool_statistics: Vec<_> = self.pools.iter().map(|pool| pool.statistics()).collect();
+
+        json.push('{');
+
+        json.push_str("\"General\":{\"API\":\"Vulkan\",\"apiVersion\":");
+        write_json_string(json, &properties.api_version.to_string())?;
+        json.push_str(",\"GPU\":");
+        write_json_string(json, &properties.device_name)?;
+        json.push_str(",\"deviceType\":");
+        write_json_string(json, &format!("{:?}", properties.device_type))?;
+        write!(
+            json,
+            ",\"maxMemoryAllocationCount\":{},\"bufferImageGranularity\":{},\
+            \"nonCoherentAtomSize\":{},\"memoryHeapCount\":{},\"memoryTypeCount\":{}}}",
+            properties.max_memory_allocation_count,
+            self.buffer_image_granularity.as_devicesize(),
+            properties.non_coherent_atom_size.as_devicesize(),
+            memory_properties.memory_heaps.len(),
+            memory_properties.memory_types.len(),
+        )?;
+
+        json.push_str(",\"Total\":");
+        let mut total = Statistics::new();
+
+        for statistics in &pool_statistics {
+            total.merge(statistics);
+        }
+
+        total.write_json(json)?;
+
+        json.push_str(",\"MemoryInfo\":{");
+
+        for (heap_index, memory_heap) in memory_properties.memory_heaps.iter().enumerate() {
+            if heap_index != 0 {
+                json.push(',');
+            }
+
+            let heap_budget = self.heap_budget(heap_index as u32, memory_budget.as_ref());
+
+            write!(json, "\"Heap {heap_index}\":{{\"Flags\":")?;
+            write_json_flags(json, memory_heap.flags)?;
+            write!(
+                json,
+                ",\"Size\":{},\"Budget\":{{\"BudgetBytes\":{},\"UsageBytes\":{}}},\"Stats\":",
+                memory_heap.size, heap_budget.budget, heap_budget.usage,
+            )?;
+
+            let mut heap_statistics = Statistics::new();
+
+            for (memory_type_index, memory_type) in
+                memory_properties.memory_types.iter().enumerate()
+            {
+                if memory_type.heap_index as usize == heap_index {
+                    heap_statistics.merge(&pool_statistics[memory_type_index]);
+                }
+            }
+
+            heap_statistics.write_json(json)?;
+            json.push_str(",\"MemoryPools\":{");
+            let mut first = true;
+
+            for (memory_type_index, memory_type) in
+                memory_properties.memory_types.iter().enumerate()
+            {
+                if memory_type.heap_index as usize != heap_index {
+                    continue;
+                }
+
+                if !first {
+                    json.push(',');
+                }
+
+                first = false;
+
+                write!(json, "\"Type {memory_type_index}\":{{\"Flags\":")?;
+                write_json_flags(json, memory_type.property_flags)?;
+                json.push_str(",\"Stats\":");
+                pool_statistics[memory_type_index].write_json(json)?;
+                json.push('}');
+            }
+
+            json.push_str("}}");
+        }
+
+        json.push_str("},\"DefaultPools\":{");
+
+        for (memory_type_index, pool) in self.pools.iter().enumerate() {
+            if memory_type_index != 0 {
+                json.push(',');
+            }
+
+            write!(
+                json,
+                "\"Type {memory_type_index}\":{{\"PreferredBlockSize\":{},\"Blocks\":{{",
+                pool.block_size,
+            )?;
+
+            let blocks = pool.blocks.lock();
+            // The records must be locked after the pool, since that's the order in which they are
+            // locked when allocating.
+            let allocation_records = self
+                .allocation_records
+                .as_ref()
+                .map(|records| records.lock());
+
+            for (block_index, block) in blocks.vec.iter().enumerate() {
+                if block_index != 0 {
+                    json.push(',');
+                }
+
+                let statistics = block.statistics();
+                let device_memory = block.device_memory.handle();
+
+                write!(
+                    json,
+                    "\"{block_index}\":{{\"MapRefCount\":0,\"TotalBytes\":{},\"UnusedBytes\":{},\
+                    \"Allocations\":{},\"UnusedRanges\":{},\"Suballocations\":[",
+                    statistics.block_bytes,
+                    statistics.free_bytes,
+                    statistics.allocation_count,
+                    statistics.free_range_count,
+                )?;
+
+                for (node_index, node) in block.suballocator.suballocations().enumerate() {
+                    if node_index != 0 {
+                        json.push(',');
+                    }
+
+                    write!(
+                        json,
+                        "{{\"Offset\":{},\"Type\":\"{}\",\"Size\":{}",
+                        node.offset,
+                        suballocation_type_name(node.allocation_type),
+                        node.size,
+                    )?;
+
+                    if let Some(label) = allocation_records
+                        .as_ref()
+                        .and_then(|records| records.get(&(device_memory, node.offset)))
+                        .and_then(|record| record.label.as_deref())
+                    {
+                        json.push_str(",\"Name\":");
+                        write_json_string(json, label)?;
+                    }
+
+                    json.push('}');
+                }
+
+                json.push_str("]}");
+            }
+
+            drop(allocation_records);
+            drop(blocks);
+
+            json.push_str("},\"DedicatedAllocations\":[");
+
+            if let Some(records) = &self.allocation_records {
+                let records = records.lock();
+                let mut first = true;
+
+                for record in records.values() {
+                    if !record.dedicated || record.memory_type_index as usize != memory_type_index {
+                        continue;
+                    }
+
+                    if !first {
+                        json.push(',');
+                    }
+
+                    first = false;
+
+                    write!(json, "{{\"Type\":\"UNKNOWN\",\"Size\":{}", record.size)?;
+
+                    if let Some(label) = &record.label {
+                        json.push_str(",\"Name\":");
+                        write_json_string(json, label)?;
+                    }
+
+                    json.push('}');
+                }
+            }
+
+            json.push_str("]}");
+        }
+
+        json.push_str("}}");
+
+        Ok(())
+    }
+}
+
+impl<S> GenericMemoryAllocator<S> {
+    /// Returns the allocations that are currently alive if [`debug_allocations`] is enabled, or
+    /// `None` otherwise.
+    ///
+    /// This can be used to find memory leaks, for example by checking that there are no live
+    /// allocations left once all resources have been dropped, and reporting the label and
+    /// backtrace of each one otherwise. The allocations are in no particular order.
+    ///
+    /// [`debug_allocations`]: super::GenericMemoryAllocatorCreateInfo::debug_allocations
+    pub fn live_allocations(&self) -> Option<Vec<LiveAllocation>> {
+        self.allocation_records
+            .as_ref()
+            .map(|records| records.lock().values().cloned().collect())
+    }
+
+    /// Records the creation of `allocation` if debugging of allocations is enabled.
+    pub(super) fn record_allocation(&self, allocation: &MemoryAlloc) {
+        let Some(records) = &self.allocation_records else {
+            return;
+        };
+
+        let (offset, size) = match allocation.suballocation {
+            Some(suballocation) => (suballocation.offset, suballocation.size),
+            None => (0, allocation.device_memory.allocation_size()),
+        };
+
+        records.lock().insert(
+            (allocation.device_memory.handle(), offset),
+            LiveAllocation {
+                memory_type_index: allocation.device_memory.memory_type_index(),
+                offset,
+                size,
+                dedicated: allocation.suballocation.is_none(),
+                label: None,
+                backtrace: Arc::new(Backtrace::capture()),
+            },
+        );
+    }
+
+    /// Attaches `label` to the record of `allocation` if debugging of allocations is enabled.
+    pub(super) fn label_allocation(&self, allocation: &MemoryAlloc, label: Option<&str>) {
+        let (Some(records), Some(label)) = (&self.allocation_records, label) else {
+            return;
+        };
+
+        let offset = allocation
+            .suballocation
+            .map_or(0, |suballocation| suballocation.offset);
+
+        if let Some(record) = records
+            .lock()
+            .get_mut(&(allocation.device_memory.handle(), offset))
+        {
+            record.label = Some(label.to_owned());
+        }
+    }
+
+    /// Removes the record of `allocation` if debugging of allocations is enabled.
+    pub(super) fn forget_allocation(&self, allocation: &MemoryAlloc) {
+        let Some(records) = &self.allocation_records else {
+            return;
+        };
+
+        let offset = allocation
+            .suballocation
+            .map_or(0, |suballocation| suballocation.offset);
+
+        records
+            .lock()
+            .remove(&(allocation.device_memory.handle(), offset));
+    }
+}
+
+impl<S: Suballocator> DeviceMemoryPool<S> {
+    /// Returns the combined statistics of all [`DeviceMemory`] blocks in the pool.
+    ///
+    /// # Locking behavior
+    ///
+    /// This locks the pool, so it can't be called while holding a [`DeviceMemoryBlocks`]
+    /// iterator of the same pool, otherwise a deadlock occurs.
+    ///
+    /// [`DeviceMemory`]: super::DeviceMemory
+    /// [`DeviceMemoryBlocks`]: super::DeviceMemoryBlocks
+    pub fn statistics(&self) -> Statistics {
+        let mut statistics = Statistics::new();
+
+        for block in &self.blocks.lock().vec {
+            statistics.merge(&block.statistics());
+        }
+
+        statistics
+    }
+}
+
+impl<S: Suballocator> DeviceMemoryBlock<S> {
+    /// Returns the statistics of the block.
+    pub fn statistics(&self) -> Statistics {
+        let block_bytes = self.device_memory.allocation_size();
+        let free_bytes = self.free_size();
+        let mut statistics = Statistics {
+            block_count: 1,
+            allocation_count: self.allocation_count,
+            block_bytes,
+            allocation_bytes: block_bytes - free_bytes,
+            free_bytes,
+            ..Statistics::new()
+        };
+
+        for node in self.suballocator.suballocations() {
+            if node.allocation_type == SuballocationType::Free {
+                statistics.free_range_count += 1;
+                statistics.largest_free_range = cmp::max(statistics.largest_free_range, node.size);
+            } else {
+                statistics.allocation_size_histogram
+                    [node.size.checked_ilog2().unwrap_or(0) as usize] += 1;
+            }
+        }
+
+        statistics
+    }
+}
+
+/// Statistics about the memory of a [`DeviceMemoryBlock`], a [`DeviceMemoryPool`] or a whole
+/// [`GenericMemoryAllocator`].
+///
+/// Dedicated allocations are not taken into account, only [`DeviceMemory`] blocks and their
+/// suballocations.
+///
+/// [`DeviceMemory`]: super::DeviceMemory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Statistics {
+    /// The number of `DeviceMemory` blocks.
+    pub block_count: usize,
+
+    /// The number of suballocations made from the blocks.
+    pub allocation_count: usize,
+
+    /// The total size of the blocks.
+    pub block_bytes: DeviceSize,
+
+    /// The total size of the suballocations.
+    pub allocation_bytes: DeviceSize,
+
+    /// The total size of the free space in the blocks. This is equal to `block_bytes` minus
+    /// `allocation_bytes`.
+    pub free_bytes: DeviceSize,
+
+    /// The number of contiguous ranges of free space in the blocks.
+    pub free_range_count: usize,
+
+    /// The size of the largest contiguous range of free space in any of the blocks. This is the
+    /// size of the largest allocation that is guaranteed to succeed without allocating a new
+    /// block, provided that it has no alignment or buffer-image granularity requirements.
+    pub largest_free_range: DeviceSize,
+
+    /// A histogram of the sizes of the suballocations. The element at index `i` is the number of
+    /// suballocations with a size in the range [2<sup>`i`</sup>, 2<sup>`i` + 1</sup>).
+    pub allocation_size_histogram: [usize; DeviceSize::BITS as usize],
+}
+
+impl Statistics {
+    const fn new() -> Self {
+        Statistics {
+            block_count: 0,
+            allocation_count: 0,
+            block_bytes: 0,
+            allocation_bytes: 0,
+            free_bytes: 0,
+            free_range_count: 0,
+            largest_free_range: 0,
+            allocation_size_histogram: [0; DeviceSize::BITS as usize],
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.block_count += other.block_count;
+        self.allocation_count += other.allocation_count;
+        self.block_bytes += other.block_bytes;
+        self.allocation_bytes += other.allocation_bytes;
+        self.free_bytes += other.free_bytes;
+        self.free_range_count += other.free_range_count;
+        self.largest_free_range = cmp::max(self.largest_free_range, other.largest_free_range);
+
+        for (count, &other_count) in self
+            .allocation_size_histogram
+            .iter_mut()
+            .zip(&other.allocation_size_histogram)
+        {
+            *count += other_count;
+        }
+    }
+
+    fn write_json(&self, json: &mut String) -> FmtResult {
+        write!(
+            json,
+            "{{\"BlockCount\":{},\"BlockBytes\":{},\"AllocationCount\":{},\"AllocationBytes\":{},\
+            \"UnusedRangeCount\":{},\"UnusedRangeSizeMax\":{}}}",
+            self.block_count,
+            self.block_bytes,
+            self.allocation_count,
+            self.allocation_bytes,
+            self.free_range_count,
+            self.largest_free_range,
+        )
+    }
+}
+
+impl Default for Statistics {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An allocation that is alive, as recorded by a [`GenericMemoryAllocator`] with
+/// [`debug_allocations`] enabled.
+///
+/// [`debug_allocations`]: super::GenericMemoryAllocatorCreateInfo::debug_allocations
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LiveAllocation {
+    /// The index of the memory type that the allocation was made from.
+    pub memory_type_index: u32,
+
+    /// The offset of the allocation within its [`DeviceMemory`] block.
+    ///
+    /// [`DeviceMemory`]: super::DeviceMemory
+    pub offset: DeviceSize,
+
+    /// The size of the allocation.
+    pub size: DeviceSize,
+
+    /// Whether the allocation is a dedicated allocation, as opposed to a suballocation of a
+    /// block.
+    pub dedicated: bool,
+
+    /// The [`debug_label`] that the allocation was created with, if any.
+    ///
+    /// [`debug_label`]: super::AllocationCreateInfo::debug_label
+    pub label: Option<String>,
+
+    /// The backtrace of the creation of the allocation. Whether it was actually captured depends
+    /// on the environment, as described in [`Backtrace::capture`].
+    pub backtrace: Arc<Backtrace>,
+}
+
+/// The key of an allocation record: the `DeviceMemory` and the offset within it.
+pub(super) type AllocationRecordKey = (vk::DeviceMemory, DeviceSize);
+
+fn suballocation_type_name(allocation_type: SuballocationType) -> &'static str {
+    match allocation_type {
+        SuballocationType::Unknown => "UNKNOWN",
+        SuballocationType::Linear => "BUFFER",
+        SuballocationType::NonLinear => "IMAGE_OPTIMAL",
+        SuballocationType::Free => "FREE",
+    }
+}
+
+fn write_json_flags(json: &mut String, flags: impl Debug) -> FmtResult {
+    let flags = format!("{flags:?}");
+
+    json.push('[');
+
+    for (index, flag) in flags
+        .split(" | ")
+        .filter(|&flag| flag != "empty()")
+        .enumerate()
+    {
+        if index != 0 {
+            json.push(',');
+        }
+
+        write_json_string(json, flag)?;
+    }
+
+    json.push(']');
+
+    Ok(())
+}
+
+fn write_json_string(json: &mut String, string: &str) -> FmtResult {
+    json.push('"');
+
+    for char in string.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if char.is_control() => write!(json, "\\u{:04x}", char as u32)?,
+            char => json.push(char),
+        }
+    }
+
+    json.push('"');
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::allocator::{
+        AllocationType, DeviceLayout, GenericMemoryAllocatorCreateInfo, MemoryAllocator,
+        MemoryTypeFilter, StandardMemoryAllocator,
+    };
+
+    #[test]
+    fn statistics() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let allocator = StandardMemoryAllocator::new(
+            &device,
+            &GenericMemoryAllocatorCreateInfo {
+                debug_allocations: true,
+                ..Default::default()
+            },
+        );
+        let memory_type_index = allocator
+            .find_memory_type_index(u32::MAX, MemoryTypeFilter::PREFER_DEVICE)
+            .unwrap();
+        let pool = &allocator.pools()[memory_type_index as usize];
+
+        assert_eq!(allocator.statistics(), Statistics::default());
+
+        let layout = DeviceLayout::from_size_alignment(1024, 1).unwrap();
+        let allocation1 = allocator
+            .try_allocate_from_type(memory_type_index, layout, AllocationType::Linear, false)
+            .unwrap();
+        let allocation2 = allocator
+            .try_allocate_from_type(memory_type_index, layout, AllocationType::Linear, false)
+            .unwrap();
+        allocator.label_allocation(&allocation2, Some("test \"allocation\""));
+
+        let statistics = pool.statistics();
+        assert_eq!(statistics.block_count, 1);
+        assert_eq!(statistics.allocation_count, 2);
+        assert_eq!(statistics.block_bytes, pool.block_size);
+        assert_eq!(statistics.allocation_bytes, 2048);
+        assert_eq!(statistics.free_bytes, pool.block_size - 2048);
+        assert_eq!(statistics.largest_free_range, pool.block_size - 2048);
+        assert_eq!(statistics.allocation_size_histogram[10], 2);
+        assert_eq!(allocator.statistics(), statistics);
+
+        let json = allocator.json_dump();
+        assert!(json.contains("\"Size\":1024"));
+        assert!(json.contains("\"Name\":\"test \\\"allocation\\\"\""));
+
+        unsafe { allocator.deallocate(allocation1) };
+
+        let statistics = pool.statistics();
+        assert_eq!(statistics.allocation_count, 1);
+        assert_eq!(statistics.free_range_count, 2);
+        assert_eq!(statistics.largest_free_range, pool.block_size - 2048);
+
+        let live_allocations = allocator.live_allocations().unwrap();
+        assert_eq!(live_allocations.len(), 1);
+        assert_eq!(live_allocations[0].size, 1024);
+        assert!(!live_allocations[0].dedicated);
+        assert_eq!(
+            live_allocations[0].label.as_deref(),
+            Some("test \"allocation\""),
+        );
+
+        unsafe { allocator.deallocate(allocation2) };
+
+        assert!(allocator.live_allocations().unwrap().is_empty());
+    }
+}
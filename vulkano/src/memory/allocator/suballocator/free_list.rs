@@ -19,8 +19,9 @@ use std::{cmp, iter::FusedIterator, marker::PhantomData, ptr::NonNull};
 /// are made. Therefore, this allocator is best suited for long-lived allocations. If you need
 /// to create allocations of various sizes, but can't afford this fragmentation, then the
 /// [`BuddyAllocator`] is your best buddy. If you need to create allocations which share a similar
-/// size, consider an allocation pool. Lastly, if you need to allocate very often, then
-/// [`BumpAllocator`] is best suited.
+/// size, consider an allocation pool. If you need to make a large number of dynamic allocations,
+/// then the [`TlsfAllocator`] allocates and frees in constant time. Lastly, if you need to
+/// allocate very often, then [`BumpAllocator`] is best suited.
 ///
 /// See also [the `Suballocator` implementation].
 ///
@@ -57,6 +58,7 @@ use std::{cmp, iter::FusedIterator, marker::PhantomData, ptr::NonNull};
 /// [external fragmentation]: super::super#external-fragmentation
 /// [`BuddyAllocator`]: super::BuddyAllocator
 /// [`BumpAllocator`]: super::BumpAllocator
+/// [`TlsfAllocator`]: super::TlsfAllocator
 /// [the `Suballocator` implementation]: Suballocator#impl-Suballocator-for-Arc<FreeListAllocator>
 /// [internal fragmentation]: super::super#internal-fragmentation
 /// [alignment requirements]: super::super#alignment
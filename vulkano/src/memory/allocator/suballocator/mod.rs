@@ -6,6 +6,7 @@
 
 pub use self::{
     buddy::BuddyAllocator, bump::BumpAllocator, free_list::FreeListAllocator, region::Region,
+    tlsf::TlsfAllocator,
 };
 use super::{align_down, AllocationHandle, DeviceAlignment, DeviceLayout};
 use crate::{image::ImageTiling, DeviceSize};
@@ -18,6 +19,7 @@ use std::{
 mod buddy;
 mod bump;
 mod free_list;
+mod tlsf;
 
 /// Suballocators are used to divide a *region* into smaller *suballocations*.
 ///
@@ -823,6 +825,146 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tlsf_allocator_capacity() {
+        const THREADS: DeviceSize = 12;
+        const ALLOCATIONS_PER_THREAD: DeviceSize = 100;
+        const ALLOCATION_STEP: DeviceSize = 117;
+        const REGION_SIZE: DeviceSize =
+            (ALLOCATION_STEP * (THREADS + 1) * THREADS / 2) * ALLOCATIONS_PER_THREAD;
+
+        let allocator = Mutex::new(TlsfAllocator::new(Region::new(0, REGION_SIZE).unwrap()));
+        let allocs = ArrayQueue::new((ALLOCATIONS_PER_THREAD * THREADS) as usize);
+
+        // Using threads to randomize allocation order.
+        thread::scope(|scope| {
+            for i in 1..=THREADS {
+                let (allocator, allocs) = (&allocator, &allocs);
+
+                scope.spawn(move || {
+                    let layout = DeviceLayout::from_size_alignment(i * ALLOCATION_STEP, 1).unwrap();
+
+                    for _ in 0..ALLOCATIONS_PER_THREAD {
+                        allocs
+                            .push(
+                                allocator
+                                    .lock()
+                                    .allocate(layout, AllocationType::Unknown, DeviceAlignment::MIN)
+                                    .unwrap(),
+                            )
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        let mut allocator = allocator.into_inner();
+
+        assert!(allocator
+            .allocate(DUMMY_LAYOUT, AllocationType::Unknown, DeviceAlignment::MIN)
+            .is_err());
+        assert_eq!(allocator.free_size(), 0);
+
+        for alloc in allocs {
+            unsafe { allocator.deallocate(alloc) };
+        }
+
+        assert_eq!(allocator.free_size(), REGION_SIZE);
+        let alloc = allocator
+            .allocate(
+                DeviceLayout::from_size_alignment(REGION_SIZE, 1).unwrap(),
+                AllocationType::Unknown,
+                DeviceAlignment::MIN,
+            )
+            .unwrap();
+        unsafe { allocator.deallocate(alloc) };
+    }
+
+    #[test]
+    fn tlsf_allocator_respects_alignment() {
+        const REGION_SIZE: DeviceSize = 10 * 256;
+        const LAYOUT: DeviceLayout = unwrap(DeviceLayout::from_size_alignment(1, 256));
+
+        let mut allocator = TlsfAllocator::new(Region::new(0, REGION_SIZE).unwrap());
+        let mut allocs = Vec::with_capacity(10);
+
+        for _ in 0..10 {
+            allocs.push(
+                allocator
+                    .allocate(LAYOUT, AllocationType::Unknown, DeviceAlignment::MIN)
+                    .unwrap(),
+            );
+        }
+
+        assert!(allocator
+            .allocate(LAYOUT, AllocationType::Unknown, DeviceAlignment::MIN)
+            .is_err());
+        assert_eq!(allocator.free_size(), REGION_SIZE - 10);
+
+        for alloc in allocs.drain(..) {
+            unsafe { allocator.deallocate(alloc) };
+        }
+    }
+
+    #[test]
+    fn tlsf_allocator_respects_granularity() {
+        const GRANULARITY: DeviceAlignment = unwrap(DeviceAlignment::new(16));
+        const REGION_SIZE: DeviceSize = 2 * GRANULARITY.as_devicesize();
+
+        let mut allocator = TlsfAllocator::new(Region::new(0, REGION_SIZE).unwrap());
+        let mut linear_allocs = Vec::with_capacity(REGION_SIZE as usize / 2);
+        let mut nonlinear_allocs = Vec::with_capacity(REGION_SIZE as usize / 2);
+
+        for i in 0..REGION_SIZE {
+            if i % 2 == 0 {
+                linear_allocs.push(
+                    allocator
+                        .allocate(DUMMY_LAYOUT, AllocationType::Linear, GRANULARITY)
+                        .unwrap(),
+                );
+            } else {
+                nonlinear_allocs.push(
+                    allocator
+                        .allocate(DUMMY_LAYOUT, AllocationType::NonLinear, GRANULARITY)
+                        .unwrap(),
+                );
+            }
+        }
+
+        assert!(allocator
+            .allocate(DUMMY_LAYOUT, AllocationType::Linear, GRANULARITY)
+            .is_err());
+        assert_eq!(allocator.free_size(), 0);
+
+        for alloc in linear_allocs.drain(..) {
+            unsafe { allocator.deallocate(alloc) };
+        }
+
+        let alloc = allocator
+            .allocate(
+                DeviceLayout::from_size_alignment(GRANULARITY.as_devicesize(), 1).unwrap(),
+                AllocationType::Unknown,
+                GRANULARITY,
+            )
+            .unwrap();
+        unsafe { allocator.deallocate(alloc) };
+
+        let alloc = allocator
+            .allocate(DUMMY_LAYOUT, AllocationType::Unknown, GRANULARITY)
+            .unwrap();
+        assert!(allocator
+            .allocate(DUMMY_LAYOUT, AllocationType::Unknown, GRANULARITY)
+            .is_err());
+        assert!(allocator
+            .allocate(DUMMY_LAYOUT, AllocationType::Linear, GRANULARITY)
+            .is_err());
+        unsafe { allocator.deallocate(alloc) };
+
+        for alloc in nonlinear_allocs.drain(..) {
+            unsafe { allocator.deallocate(alloc) };
+        }
+    }
+
     #[test]
     fn buddy_allocator_capacity() {
         const MAX_ORDER: usize = 10;
@@ -0,0 +1,780 @@
+use super::{
+    are_blocks_on_same_page, AllocationType, Region, Suballocation, SuballocationNode,
+    SuballocationType, Suballocator, SuballocatorError,
+};
+use crate::{
+    memory::{
+        allocator::{align_up, AllocationHandle, DeviceLayout},
+        is_aligned, DeviceAlignment,
+    },
+    DeviceSize,
+};
+use std::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
+
+/// A [suballocator] that uses the two-level segregated fit (TLSF) algorithm.
+///
+/// Like the [`FreeListAllocator`], this allocator can create and free allocations of any size in
+/// any order, and it doesn't cause any [internal fragmentation]. The difference is that both
+/// allocating and freeing take constant time in the common case, which makes it a better fit than
+/// the `FreeListAllocator` when you need to make a large number of allocations of varying sizes,
+/// for example every frame. The [external fragmentation] is comparable to that of the
+/// `FreeListAllocator`, because the allocator finds a good fit rather than the best fit, which is
+/// in turn very close to the best fit.
+///
+/// See also [the `Suballocator` implementation].
+///
+/// # Algorithm
+///
+/// The free suballocations are sorted into segregated free-lists based on their size. The
+/// *first level* divides the sizes into classes of powers of two, and the *second level*
+/// subdivides each such class linearly into 32 free-lists. For example, all free suballocations
+/// with a size in the range \[1024, 1056) land in the same free-list, and so do the ones in the
+/// range \[1056, 1088). Bitmaps are used to keep track of which free-lists are non-empty, so that
+/// finding a non-empty free-list of a given size class or larger only takes a few bit operations.
+///
+/// When an allocation request is made, the size is rounded up to the next size class, taking into
+/// account the worst-case padding required by the alignment and the [buffer-image granularity],
+/// such that the first suballocation in the first non-empty free-list of that class or larger is
+/// guaranteed to fit the request. The chosen suballocation is then trimmed at the ends and the ends
+/// are returned to the free-lists, just like with the `FreeListAllocator`. When an allocation is
+/// freed, the allocator checks if the adjacent suballocations are free, and if so it coalesces
+/// them into a bigger one before putting it in the free-list of its size.
+///
+/// # Efficiency
+///
+/// Allocating and freeing are both *O*(1) in the common case.
+///
+/// There is one edge-case: when there is no free suballocation that is large enough to
+/// accommodate the worst-case padding, the allocator falls back to searching the free-lists that
+/// might contain a suballocation that fits the request exactly, as well as the ones after the one
+/// that was chosen if the suballocations in it can't be used because of a buffer-image
+/// granularity conflict with the next suballocation. In such a case, allocation is *O*(*n*) in
+/// the worst case. This is only likely to happen when the region is almost full.
+///
+/// [suballocator]: Suballocator
+/// [`FreeListAllocator`]: super::FreeListAllocator
+/// [internal fragmentation]: super::super#internal-fragmentation
+/// [external fragmentation]: super::super#external-fragmentation
+/// [the `Suballocator` implementation]: Suballocator#impl-Suballocator-for-TlsfAllocator
+/// [buffer-image granularity]: super::super#buffer-image-granularity
+#[derive(Debug)]
+pub struct TlsfAllocator {
+    region: Region,
+    // Total memory remaining in the region.
+    free_size: DeviceSize,
+    suballocations: SuballocationList,
+}
+
+unsafe impl Send for TlsfAllocator {}
+unsafe impl Sync for TlsfAllocator {}
+
+unsafe impl Suballocator for TlsfAllocator {
+    type Suballocations<'a> = Suballocations<'a>;
+
+    /// Creates a new `TlsfAllocator` for the given [region].
+    ///
+    /// [region]: Suballocator#regions
+    fn new(region: Region) -> Self {
+        let node_allocator = slabbin::SlabAllocator::<SuballocationListNode>::new(32);
+        let root_ptr = node_allocator.allocate();
+        let root = SuballocationListNode {
+            prev: None,
+            next: None,
+            prev_free: None,
+            next_free: None,
+            offset: region.offset(),
+            size: region.size(),
+            allocation_type: SuballocationType::Free,
+        };
+        unsafe { root_ptr.as_ptr().write(root) };
+
+        let mut suballocations = SuballocationList {
+            head: root_ptr,
+            tail: root_ptr,
+            len: 1,
+            fl_bitmap: 0,
+            sl_bitmaps: [0; FL_COUNT],
+            free_lists: vec![None; FL_COUNT * SL_COUNT].into_boxed_slice(),
+            node_allocator,
+        };
+
+        // SAFETY: We just created the suballocation, and it's not in any free-list yet.
+        unsafe { suballocations.insert_free(root_ptr) };
+
+        TlsfAllocator {
+            region,
+            free_size: region.size(),
+            suballocations,
+        }
+    }
+
+    #[inline]
+    fn allocate(
+        &mut self,
+        layout: DeviceLayout,
+        allocation_type: AllocationType,
+        buffer_image_granularity: DeviceAlignment,
+    ) -> Result<Suballocation, SuballocatorError> {
+        let size = layout.size();
+        let alignment = layout.alignment();
+
+        if size > self.free_size {
+            return Err(SuballocatorError::OutOfRegionMemory);
+        }
+
+        if buffer_image_granularity != DeviceAlignment::MIN {
+            debug_assert!(is_aligned(self.region.offset(), buffer_image_granularity));
+        }
+
+        // The size that a free suballocation must have in order to be guaranteed to fit the
+        // request regardless of its offset and the type of the previous suballocation.
+        let worst_case_size = size
+            .checked_add(alignment.as_devicesize() - 1)
+            .and_then(|size| size.checked_add(buffer_image_granularity.as_devicesize() - 1));
+        let good_fit_index = worst_case_size
+            .and_then(list_index_round_up)
+            .unwrap_or((FL_COUNT, 0));
+
+        // SAFETY: The free-lists only contain free suballocations of `self`.
+        let fit = unsafe {
+            self.suballocations.find_fit(
+                good_fit_index,
+                (FL_COUNT, 0),
+                size,
+                alignment,
+                allocation_type,
+                buffer_image_granularity,
+            )
+        }
+        .or_else(|| {
+            // SAFETY: Same as above.
+            unsafe {
+                self.suballocations.find_fit(
+                    list_index(size),
+                    good_fit_index,
+                    size,
+                    alignment,
+                    allocation_type,
+                    buffer_image_granularity,
+                )
+            }
+        });
+
+        if let Some((node_ptr, offset)) = fit {
+            // SAFETY: `find_fit` only returns free suballocations.
+            unsafe { self.suballocations.remove_free(node_ptr) };
+
+            // SAFETY:
+            // - `node_ptr` is free and was removed from the free-lists.
+            // - `find_fit` checked that `offset` and `size` fall within the suballocation.
+            unsafe { self.suballocations.split(node_ptr, offset, size) };
+
+            unsafe { (*node_ptr.as_ptr()).allocation_type = allocation_type.into() };
+
+            // This can't overflow because suballocation sizes in the free-lists are constrained by
+            // the remaining size of the region.
+            self.free_size -= size;
+
+            Ok(Suballocation {
+                offset,
+                size,
+                allocation_type,
+                handle: AllocationHandle::from_ptr(node_ptr.as_ptr().cast()),
+            })
+        } else if self.suballocations.fl_bitmap != 0 {
+            // There would be enough space if the region wasn't so fragmented. :(
+            Err(SuballocatorError::FragmentedRegion)
+        } else {
+            // There is no space at all.
+            Err(SuballocatorError::OutOfRegionMemory)
+        }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&mut self, suballocation: Suballocation) {
+        let node_ptr = suballocation
+            .handle
+            .as_ptr()
+            .cast::<SuballocationListNode>();
+
+        // SAFETY: The caller must guarantee that `suballocation` refers to a currently allocated
+        // allocation of `self`, which means that `node_ptr` is the same one we gave out on
+        // allocation, making it a valid pointer.
+        let node_ptr = unsafe { NonNull::new_unchecked(node_ptr) };
+
+        debug_assert!(self.suballocations.node_allocator.contains(node_ptr));
+
+        let node = unsafe { *node_ptr.as_ptr() };
+
+        debug_assert_ne!(node.allocation_type, SuballocationType::Free);
+
+        // Suballocation sizes are constrained by the size of the region, so they can't possibly
+        // overflow when added up.
+        self.free_size += node.size;
+
+        unsafe { (*node_ptr.as_ptr()).allocation_type = SuballocationType::Free };
+
+        // SAFETY: We marked the suballocation as free above, and it's not in any free-list
+        // because it was allocated.
+        unsafe { self.suballocations.coalesce(node_ptr) };
+
+        // SAFETY: Same as above.
+        unsafe { self.suballocations.insert_free(node_ptr) };
+    }
+
+    fn reset(&mut self) {
+        self.free_size = self.region.size();
+        self.suballocations.fl_bitmap = 0;
+        self.suballocations.sl_bitmaps = [0; FL_COUNT];
+        self.suballocations.free_lists.fill(None);
+        unsafe { self.suballocations.node_allocator.reset() };
+
+        let root_ptr = self.suballocations.node_allocator.allocate();
+        let root = SuballocationListNode {
+            prev: None,
+            next: None,
+            prev_free: None,
+            next_free: None,
+            offset: self.region.offset(),
+            size: self.region.size(),
+            allocation_type: SuballocationType::Free,
+        };
+        unsafe { root_ptr.as_ptr().write(root) };
+
+        self.suballocations.head = root_ptr;
+        self.suballocations.tail = root_ptr;
+        self.suballocations.len = 1;
+
+        // SAFETY: We just created the suballocation, and the free-lists are empty.
+        unsafe { self.suballocations.insert_free(root_ptr) };
+    }
+
+    #[inline]
+    fn free_size(&self) -> DeviceSize {
+        self.free_size
+    }
+
+    #[inline]
+    fn suballocations(&self) -> Self::Suballocations<'_> {
+        self.suballocations.iter()
+    }
+}
+
+/// The base-2 logarithm of the number of second-level free-lists per first-level size class.
+const SL_COUNT_LOG2: u32 = 5;
+
+/// The number of second-level free-lists per first-level size class.
+const SL_COUNT: usize = 1 << SL_COUNT_LOG2;
+
+/// Sizes smaller than this all fall into the first first-level size class, where each
+/// second-level free-list holds suballocations of exactly one size.
+const SMALL_SIZE: DeviceSize = 1 << SL_COUNT_LOG2;
+
+/// The number of first-level size classes, which is enough to cover any `DeviceSize`.
+const FL_COUNT: usize = (DeviceSize::BITS - SL_COUNT_LOG2 + 1) as usize;
+
+/// Returns the indices of the free-list that a free suballocation of the given size belongs in.
+fn list_index(size: DeviceSize) -> (usize, usize) {
+    if size < SMALL_SIZE {
+        (0, size as usize)
+    } else {
+        let log2 = DeviceSize::BITS - 1 - size.leading_zeros();
+        let fl = log2 - SL_COUNT_LOG2 + 1;
+        let sl = (size >> (log2 - SL_COUNT_LOG2)) as usize ^ SL_COUNT;
+
+        (fl as usize, sl)
+    }
+}
+
+/// Returns the indices of the first free-list in which all free suballocations are at least as
+/// large as the given size, or [`None`] if there is no such free-list.
+fn list_index_round_up(size: DeviceSize) -> Option<(usize, usize)> {
+    let size = if size < SMALL_SIZE {
+        size
+    } else {
+        let log2 = DeviceSize::BITS - 1 - size.leading_zeros();
+
+        size.checked_add((1 << (log2 - SL_COUNT_LOG2)) - 1)?
+    };
+
+    Some(list_index(size))
+}
+
+fn has_granularity_conflict(ty: SuballocationType, allocation_type: AllocationType) -> bool {
+    if ty == SuballocationType::Free {
+        false
+    } else if ty == SuballocationType::Unknown {
+        true
+    } else {
+        ty != allocation_type.into()
+    }
+}
+
+#[derive(Debug)]
+struct SuballocationList {
+    head: NonNull<SuballocationListNode>,
+    tail: NonNull<SuballocationListNode>,
+    len: usize,
+    // Each bit indicates whether the first-level size class of the same index has any non-empty
+    // free-lists.
+    fl_bitmap: u64,
+    // Each bit indicates whether the second-level free-list of the same index is non-empty.
+    sl_bitmaps: [u32; FL_COUNT],
+    // The heads of the free-lists, indexed by `fl * SL_COUNT + sl`.
+    free_lists: Box<[Option<NonNull<SuballocationListNode>>]>,
+    node_allocator: slabbin::SlabAllocator<SuballocationListNode>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SuballocationListNode {
+    prev: Option<NonNull<Self>>,
+    next: Option<NonNull<Self>>,
+    // These are only meaningful for free suballocations.
+    prev_free: Option<NonNull<Self>>,
+    next_free: Option<NonNull<Self>>,
+    offset: DeviceSize,
+    size: DeviceSize,
+    allocation_type: SuballocationType,
+}
+
+impl SuballocationList {
+    /// Returns the indices of the first non-empty free-list starting at `(fl, sl)`.
+    fn find_free_list(&self, fl: usize, sl: usize) -> Option<(usize, usize)> {
+        debug_assert!(fl < FL_COUNT && sl < SL_COUNT);
+
+        let sl_map = self.sl_bitmaps[fl] & (u32::MAX << sl);
+
+        if sl_map != 0 {
+            return Some((fl, sl_map.trailing_zeros() as usize));
+        }
+
+        let fl_map = self.fl_bitmap & (u64::MAX << (fl + 1));
+
+        if fl_map != 0 {
+            let fl = fl_map.trailing_zeros() as usize;
+
+            Some((fl, self.sl_bitmaps[fl].trailing_zeros() as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Searches the free-lists starting at `start` and ending before `end` for a suballocation
+    /// that can fit the request. Returns the suballocation together with the offset of the
+    /// allocation within it.
+    ///
+    /// # Safety
+    ///
+    /// - The free-lists must only contain free suballocations of `self`.
+    unsafe fn find_fit(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+        size: DeviceSize,
+        alignment: DeviceAlignment,
+        allocation_type: AllocationType,
+        buffer_image_granularity: DeviceAlignment,
+    ) -> Option<(NonNull<SuballocationListNode>, DeviceSize)> {
+        let (mut fl, mut sl) = start;
+
+        while fl < FL_COUNT {
+            let index = self.find_free_list(fl, sl)?;
+
+            if index >= end {
+                break;
+            }
+
+            let mut next_ptr = self.free_lists[index.0 * SL_COUNT + index.1];
+
+            while let Some(node_ptr) = next_ptr {
+                let node = unsafe { *node_ptr.as_ptr() };
+
+                if let Some(offset) =
+                    unsafe { node.fit(size, alignment, allocation_type, buffer_image_granularity) }
+                {
+                    return Some((node_ptr, offset));
+                }
+
+                next_ptr = node.next_free;
+            }
+
+            (fl, sl) = if index.1 + 1 == SL_COUNT {
+                (index.0 + 1, 0)
+            } else {
+                (index.0, index.1 + 1)
+            };
+        }
+
+        None
+    }
+
+    /// Inserts the target suballocation into the free-list of its size.
+    ///
+    /// # Safety
+    ///
+    /// - `node_ptr` must refer to a currently free suballocation of `self`.
+    /// - The suballocation must not be in any free-list.
+    unsafe fn insert_free(&mut self, node_ptr: NonNull<SuballocationListNode>) {
+        let node = unsafe { *node_ptr.as_ptr() };
+
+        debug_assert_eq!(node.allocation_type, SuballocationType::Free);
+
+        let (fl, sl) = list_index(node.size);
+        let head = &mut self.free_lists[fl * SL_COUNT + sl];
+
+        unsafe { (*node_ptr.as_ptr()).prev_free = None };
+        unsafe { (*node_ptr.as_ptr()).next_free = *head };
+
+        if let Some(head_ptr) = *head {
+            unsafe { (*head_ptr.as_ptr()).prev_free = Some(node_ptr) };
+        }
+
+        *head = Some(node_ptr);
+        self.fl_bitmap |= 1 << fl;
+        self.sl_bitmaps[fl] |= 1 << sl;
+    }
+
+    /// Removes the target suballocation from the free-list of its size.
+    ///
+    /// # Safety
+    ///
+    /// - `node_ptr` must refer to a currently free suballocation of `self`.
+    /// - The suballocation must be in the free-list of its size.
+    unsafe fn remove_free(&mut self, node_ptr: NonNull<SuballocationListNode>) {
+        let node = unsafe { *node_ptr.as_ptr() };
+
+        debug_assert_eq!(node.allocation_type, SuballocationType::Free);
+
+        let (fl, sl) = list_index(node.size);
+
+        if let Some(prev_ptr) = node.prev_free {
+            unsafe { (*prev_ptr.as_ptr()).next_free = node.next_free };
+        } else {
+            debug_assert_eq!(self.free_lists[fl * SL_COUNT + sl], Some(node_ptr));
+
+            self.free_lists[fl * SL_COUNT + sl] = node.next_free;
+
+            if node.next_free.is_none() {
+                self.sl_bitmaps[fl] &= !(1 << sl);
+
+                if self.sl_bitmaps[fl] == 0 {
+                    self.fl_bitmap &= !(1 << fl);
+                }
+            }
+        }
+
+        if let Some(next_ptr) = node.next_free {
+            unsafe { (*next_ptr.as_ptr()).prev_free = node.prev_free };
+        }
+    }
+
+    /// Fits a suballocation inside the target one, splitting the target at the ends if required.
+    ///
+    /// # Safety
+    ///
+    /// - `node_ptr` must refer to a currently free suballocation of `self`.
+    /// - The suballocation must not be in any free-list.
+    /// - `offset` and `size` must refer to a subregion of the given suballocation.
+    unsafe fn split(
+        &mut self,
+        node_ptr: NonNull<SuballocationListNode>,
+        offset: DeviceSize,
+        size: DeviceSize,
+    ) {
+        let node = unsafe { *node_ptr.as_ptr() };
+
+        debug_assert_eq!(node.allocation_type, SuballocationType::Free);
+        debug_assert!(offset >= node.offset);
+        debug_assert!(offset + size <= node.offset + node.size);
+
+        // These are guaranteed to not overflow because the caller must uphold that the given
+        // region is contained within that of `node`.
+        let padding_front = offset - node.offset;
+        let padding_back = node.offset + node.size - offset - size;
+
+        if padding_front > 0 {
+            let padding_ptr = self.node_allocator.allocate();
+            let padding = SuballocationListNode {
+                prev: node.prev,
+                next: Some(node_ptr),
+                prev_free: None,
+                next_free: None,
+                offset: node.offset,
+                size: padding_front,
+                allocation_type: SuballocationType::Free,
+            };
+            unsafe { padding_ptr.as_ptr().write(padding) };
+
+            if let Some(prev_ptr) = padding.prev {
+                unsafe { (*prev_ptr.as_ptr()).next = Some(padding_ptr) };
+            }
+
+            unsafe { (*node_ptr.as_ptr()).prev = Some(padding_ptr) };
+            unsafe { (*node_ptr.as_ptr()).offset = offset };
+            // The caller must uphold that the given region is contained within that of `node`, and
+            // it follows that if there is padding, the size of the node must be larger than that
+            // of the padding, so this can't overflow.
+            unsafe { (*node_ptr.as_ptr()).size -= padding.size };
+
+            if node_ptr == self.head {
+                self.head = padding_ptr;
+            }
+
+            self.len += 1;
+
+            // SAFETY: We just created this suballocation, so it can't be in any free-list yet.
+            unsafe { self.insert_free(padding_ptr) };
+        }
+
+        if padding_back > 0 {
+            let padding_ptr = self.node_allocator.allocate();
+            let padding = SuballocationListNode {
+                prev: Some(node_ptr),
+                next: node.next,
+                prev_free: None,
+                next_free: None,
+                offset: offset + size,
+                size: padding_back,
+                allocation_type: SuballocationType::Free,
+            };
+            unsafe { padding_ptr.as_ptr().write(padding) };
+
+            if let Some(next_ptr) = padding.next {
+                unsafe { (*next_ptr.as_ptr()).prev = Some(padding_ptr) };
+            }
+
+            unsafe { (*node_ptr.as_ptr()).next = Some(padding_ptr) };
+            // This is overflow-safe for the same reason as above.
+            unsafe { (*node_ptr.as_ptr()).size -= padding.size };
+
+            if node_ptr == self.tail {
+                self.tail = padding_ptr;
+            }
+
+            self.len += 1;
+
+            // SAFETY: Same as above.
+            unsafe { self.insert_free(padding_ptr) };
+        }
+    }
+
+    /// Coalesces the target (free) suballocation with adjacent ones that are also free.
+    ///
+    /// # Safety
+    ///
+    /// - `node_ptr` must refer to a currently free suballocation `self`.
+    /// - The suballocation must not be in any free-list.
+    unsafe fn coalesce(&mut self, node_ptr: NonNull<SuballocationListNode>) {
+        let node = unsafe { *node_ptr.as_ptr() };
+
+        debug_assert_eq!(node.allocation_type, SuballocationType::Free);
+
+        if let Some(prev_ptr) = node.prev {
+            let prev = unsafe { *prev_ptr.as_ptr() };
+
+            if prev.allocation_type == SuballocationType::Free {
+                // SAFETY: We checked that the suballocation is free, and all free suballocations
+                // other than the target are in the free-lists.
+                unsafe { self.remove_free(prev_ptr) };
+
+                unsafe { (*node_ptr.as_ptr()).prev = prev.prev };
+                unsafe { (*node_ptr.as_ptr()).offset = prev.offset };
+                // The sizes of suballocations are constrained by that of the parent allocation, so
+                // they can't possibly overflow when added up.
+                unsafe { (*node_ptr.as_ptr()).size += prev.size };
+
+                if let Some(prev_ptr) = prev.prev {
+                    unsafe { (*prev_ptr.as_ptr()).next = Some(node_ptr) };
+                }
+
+                if prev_ptr == self.head {
+                    self.head = node_ptr;
+                }
+
+                self.len -= 1;
+
+                // SAFETY:
+                // - The suballocation is free.
+                // - The suballocation was removed from the free-lists.
+                // - The next suballocation and possibly a previous suballocation have been updated
+                //   such that they no longer reference the suballocation.
+                // - The head no longer points to the suballocation if it used to.
+                // All of these conditions combined guarantee that `prev_ptr` cannot be used again.
+                unsafe { self.node_allocator.deallocate(prev_ptr) };
+            }
+        }
+
+        if let Some(next_ptr) = node.next {
+            let next = unsafe { *next_ptr.as_ptr() };
+
+            if next.allocation_type == SuballocationType::Free {
+                // SAFETY: Same as above.
+                unsafe { self.remove_free(next_ptr) };
+
+                unsafe { (*node_ptr.as_ptr()).next = next.next };
+                // This is overflow-safe for the same reason as above.
+                unsafe { (*node_ptr.as_ptr()).size += next.size };
+
+                if let Some(next_ptr) = next.next {
+                    unsafe { (*next_ptr.as_ptr()).prev = Some(node_ptr) };
+                }
+
+                if next_ptr == self.tail {
+                    self.tail = node_ptr;
+                }
+
+                self.len -= 1;
+
+                // SAFETY: Same as above.
+                unsafe { self.node_allocator.deallocate(next_ptr) };
+            }
+        }
+    }
+
+    fn iter(&self) -> Suballocations<'_> {
+        Suballocations {
+            head: Some(self.head),
+            tail: Some(self.tail),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl SuballocationListNode {
+    /// Returns the offset at which an allocation with the given parameters would be placed within
+    /// this free suballocation, or [`None`] if it doesn't fit.
+    ///
+    /// # Safety
+    ///
+    /// - `self.prev` and `self.next` must be valid pointers.
+    unsafe fn fit(
+        &self,
+        size: DeviceSize,
+        alignment: DeviceAlignment,
+        allocation_type: AllocationType,
+        buffer_image_granularity: DeviceAlignment,
+    ) -> Option<DeviceSize> {
+        // This can't overflow because suballocation offsets are bounded by the region, whose end
+        // can itself not exceed `DeviceLayout::MAX_SIZE`.
+        let mut offset = align_up(self.offset, alignment);
+
+        if buffer_image_granularity != DeviceAlignment::MIN {
+            if let Some(prev_ptr) = self.prev {
+                let prev = unsafe { *prev_ptr.as_ptr() };
+
+                if are_blocks_on_same_page(prev.offset, prev.size, offset, buffer_image_granularity)
+                    && has_granularity_conflict(prev.allocation_type, allocation_type)
+                {
+                    // This is overflow-safe for the same reason as above.
+                    offset = align_up(offset, buffer_image_granularity);
+                }
+            }
+        }
+
+        // `offset`, no matter the alignment, can't end up as more than `DeviceAlignment::MAX` for
+        // the same reason as above. `DeviceLayout` guarantees that `size` doesn't exceed
+        // `DeviceLayout::MAX_SIZE`. `DeviceAlignment::MAX.as_devicesize() +
+        // DeviceLayout::MAX_SIZE` is equal to `DeviceSize::MAX`. Therefore, `offset + size` can't
+        // overflow.
+        //
+        // `self.offset + self.size` can't overflow for the same reason as above.
+        if offset + size > self.offset + self.size {
+            return None;
+        }
+
+        if buffer_image_granularity != DeviceAlignment::MIN {
+            if let Some(next_ptr) = self.next {
+                let next = unsafe { *next_ptr.as_ptr() };
+
+                // The end of the allocation can't be moved, so the suballocation can't be used if
+                // the allocation would share a page with a conflicting next suballocation.
+                if are_blocks_on_same_page(offset, size, next.offset, buffer_image_granularity)
+                    && has_granularity_conflict(next.allocation_type, allocation_type)
+                {
+                    return None;
+                }
+            }
+        }
+
+        Some(offset)
+    }
+}
+
+#[derive(Clone)]
+pub struct Suballocations<'a> {
+    head: Option<NonNull<SuballocationListNode>>,
+    tail: Option<NonNull<SuballocationListNode>>,
+    len: usize,
+    marker: PhantomData<&'a SuballocationList>,
+}
+
+unsafe impl Send for Suballocations<'_> {}
+unsafe impl Sync for Suballocations<'_> {}
+
+impl Iterator for Suballocations<'_> {
+    type Item = SuballocationNode;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len != 0 {
+            if let Some(head) = self.head {
+                let head = unsafe { *head.as_ptr() };
+                self.head = head.next;
+                self.len -= 1;
+
+                Some(SuballocationNode {
+                    offset: head.offset,
+                    size: head.size,
+                    allocation_type: head.allocation_type,
+                })
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for Suballocations<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len != 0 {
+            if let Some(tail) = self.tail {
+                let tail = unsafe { *tail.as_ptr() };
+                self.tail = tail.prev;
+                self.len -= 1;
+
+                Some(SuballocationNode {
+                    offset: tail.offset,
+                    size: tail.size,
+                    allocation_type: tail.allocation_type,
+                })
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Suballocations<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl FusedIterator for Suballocations<'_> {}
@@ -961,6 +961,27 @@ impl AttachmentBlend {
             color_write_mask: vk::ColorComponentFlags::empty(),
         }
     }
+
+    #[doc(hidden)]
+    pub fn to_vk_color_blend_equation(&self) -> vk::ColorBlendEquationEXT {
+        let &Self {
+            src_color_blend_factor,
+            dst_color_blend_factor,
+            color_blend_op,
+            src_alpha_blend_factor,
+            dst_alpha_blend_factor,
+            alpha_blend_op,
+        } = self;
+
+        vk::ColorBlendEquationEXT {
+            src_color_blend_factor: src_color_blend_factor.into(),
+            dst_color_blend_factor: dst_color_blend_factor.into(),
+            color_blend_op: color_blend_op.into(),
+            src_alpha_blend_factor: src_alpha_blend_factor.into(),
+            dst_alpha_blend_factor: dst_alpha_blend_factor.into(),
+            alpha_blend_op: alpha_blend_op.into(),
+        }
+    }
 }
 
 vulkan_enum! {
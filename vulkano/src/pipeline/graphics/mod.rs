@@ -391,7 +391,10 @@ impl GraphicsPipeline {
         }
 
         if tessellation_state.is_some() {
-            fixed_state.extend(&[DynamicState::PatchControlPoints]);
+            fixed_state.extend(&[
+                DynamicState::PatchControlPoints,
+                DynamicState::TessellationDomainOrigin,
+            ]);
         }
 
         if viewport_state.is_some() {
@@ -414,6 +417,20 @@ impl GraphicsPipeline {
                 DynamicState::LineStipple,
                 DynamicState::ConservativeRasterizationMode,
                 DynamicState::ExtraPrimitiveOverestimationSize,
+                DynamicState::DepthClampEnable,
+                DynamicState::PolygonMode,
+                DynamicState::ProvokingVertexMode,
+                DynamicState::LineRasterizationMode,
+                DynamicState::LineStippleEnable,
+            ]);
+        }
+
+        if multisample_state.is_some() {
+            fixed_state.extend(&[
+                DynamicState::RasterizationSamples,
+                DynamicState::SampleMask,
+                DynamicState::AlphaToCoverageEnable,
+                DynamicState::AlphaToOneEnable,
             ]);
         }
 
@@ -437,6 +454,10 @@ impl GraphicsPipeline {
                 DynamicState::LogicOp,
                 DynamicState::BlendConstants,
                 DynamicState::ColorWriteEnable,
+                DynamicState::LogicOpEnable,
+                DynamicState::ColorBlendEnable,
+                DynamicState::ColorBlendEquation,
+                DynamicState::ColorWriteMask,
             ]);
         }
 
@@ -1595,6 +1616,131 @@ impl<'a> GraphicsPipelineCreateInfo<'a> {
                     .set_vuids(&["VUID-VkPipelineDynamicStateCreateInfo-pDynamicStates-parameter"])
             })?;
 
+            let (supported, requires_one_of, vuids): (_, _, &[_]) = match state {
+                DynamicState::TessellationDomainOrigin => (
+                    device.enabled_features().extended_dynamic_state3_tessellation_domain_origin,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_tessellation_domain_origin",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3TessellationDomainOrigin-07370"],
+                ),
+                DynamicState::DepthClampEnable => (
+                    device.enabled_features().extended_dynamic_state3_depth_clamp_enable,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_depth_clamp_enable",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3DepthClampEnable-07371"],
+                ),
+                DynamicState::PolygonMode => (
+                    device.enabled_features().extended_dynamic_state3_polygon_mode,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_polygon_mode",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3PolygonMode-07372"],
+                ),
+                DynamicState::RasterizationSamples => (
+                    device.enabled_features().extended_dynamic_state3_rasterization_samples,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_rasterization_samples",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3RasterizationSamples-07373"],
+                ),
+                DynamicState::SampleMask => (
+                    device.enabled_features().extended_dynamic_state3_sample_mask,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_sample_mask",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3SampleMask-07374"],
+                ),
+                DynamicState::AlphaToCoverageEnable => (
+                    device.enabled_features().extended_dynamic_state3_alpha_to_coverage_enable,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_alpha_to_coverage_enable",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3AlphaToCoverageEnable-07375"],
+                ),
+                DynamicState::AlphaToOneEnable => (
+                    device.enabled_features().extended_dynamic_state3_alpha_to_one_enable,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_alpha_to_one_enable",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3AlphaToOneEnable-07376"],
+                ),
+                DynamicState::LogicOpEnable => (
+                    device.enabled_features().extended_dynamic_state3_logic_op_enable,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_logic_op_enable",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3LogicOpEnable-07377"],
+                ),
+                DynamicState::ColorBlendEnable => (
+                    device.enabled_features().extended_dynamic_state3_color_blend_enable,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_color_blend_enable",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorBlendEnable-07378"],
+                ),
+                DynamicState::ColorBlendEquation => (
+                    device.enabled_features().extended_dynamic_state3_color_blend_equation,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_color_blend_equation",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorBlendEquation-07379"],
+                ),
+                DynamicState::ColorWriteMask => (
+                    device.enabled_features().extended_dynamic_state3_color_write_mask,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_color_write_mask",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorWriteMask-07380"],
+                ),
+                DynamicState::ConservativeRasterizationMode => (
+                    device.enabled_features().extended_dynamic_state3_conservative_rasterization_mode,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_conservative_rasterization_mode",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ConservativeRasterizationMode-07382"],
+                ),
+                DynamicState::ExtraPrimitiveOverestimationSize => (
+                    device.enabled_features().extended_dynamic_state3_extra_primitive_overestimation_size,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_extra_primitive_overestimation_size",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ExtraPrimitiveOverestimationSize-07383"],
+                ),
+                DynamicState::ProvokingVertexMode => (
+                    device.enabled_features().extended_dynamic_state3_provoking_vertex_mode,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_provoking_vertex_mode",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ProvokingVertexMode-07387"],
+                ),
+                DynamicState::LineRasterizationMode => (
+                    device.enabled_features().extended_dynamic_state3_line_rasterization_mode,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_line_rasterization_mode",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3LineRasterizationMode-07388"],
+                ),
+                DynamicState::LineStippleEnable => (
+                    device.enabled_features().extended_dynamic_state3_line_stipple_enable,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                        "extended_dynamic_state3_line_stipple_enable",
+                    )])]),
+                    &["VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3LineStippleEnable-07389"],
+                ),
+                _ => (true, RequiresOneOf::default(), &[]),
+            };
+
+            if !supported {
+                return Err(Box::new(ValidationError {
+                    context: format!("dynamic_state[{}]", state_index).into(),
+                    problem: format!("is `DynamicState::{:?}`", state).into(),
+                    requires_one_of,
+                    vuids,
+                }));
+            }
+
             for (other_state_index, &other_state) in
                 dynamic_state.iter().enumerate().skip(state_index + 1)
             {
@@ -2126,7 +2272,9 @@ impl<'a> GraphicsPipelineCreateInfo<'a> {
             match subpass {
                 PipelineSubpassType::BeginRenderPass(subpass) => {
                     if let Some(samples) = subpass.num_samples() {
-                        if multisample_state.rasterization_samples != samples {
+                        if !dynamic_state.contains(&DynamicState::RasterizationSamples)
+                            && multisample_state.rasterization_samples != samples
+                        {
                             return Err(Box::new(ValidationError {
                                 problem: "`multisample_state.rasterization_samples` does not \
                                     equal the number of samples in the color and depth/stencil \
@@ -771,3 +771,26 @@ impl Default for ConservativeRasterizationMode {
         ConservativeRasterizationMode::Disabled
     }
 }
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// Which vertex of a primitive is used as the source of flat-shaded values.
+    ProvokingVertexMode = ProvokingVertexModeEXT(i32);
+
+    /// The first vertex of the primitive is the provoking vertex.
+    FirstVertex = FIRST_VERTEX,
+
+    /// The last vertex of the primitive is the provoking vertex.
+    ///
+    /// The [`provoking_vertex_last`](crate::device::DeviceFeatures::provoking_vertex_last)
+    /// feature must be enabled on the device.
+    LastVertex = LAST_VERTEX,
+}
+
+impl Default for ProvokingVertexMode {
+    #[inline]
+    fn default() -> Self {
+        Self::FirstVertex
+    }
+}
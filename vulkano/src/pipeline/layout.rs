@@ -843,10 +843,21 @@ impl<'a> PipelineLayoutCreateInfo<'a> {
         let mut total_descriptors_all = [0; TOTAL_DESCRIPTOR_LIMITS.len()];
         let mut total_descriptors_not_uab = [0; TOTAL_DESCRIPTOR_LIMITS.len()];
         let mut has_push_descriptor_set = false;
+        let mut has_descriptor_buffer_set = false;
+        let mut has_non_descriptor_buffer_set = false;
 
         for set_layout in set_layouts {
             assert_eq!(device, set_layout.device().as_ref());
 
+            if set_layout
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+            {
+                has_descriptor_buffer_set = true;
+            } else {
+                has_non_descriptor_buffer_set = true;
+            }
+
             if set_layout
                 .flags()
                 .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR)
@@ -912,6 +923,18 @@ impl<'a> PipelineLayoutCreateInfo<'a> {
             }
         }
 
+        if has_descriptor_buffer_set && has_non_descriptor_buffer_set {
+            return Err(Box::new(ValidationError {
+                context: "set_layouts".into(),
+                problem: "contains both descriptor set layouts whose flags include \
+                    `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`, and descriptor set \
+                    layouts whose flags don't include it"
+                    .into(),
+                vuids: &["VUID-VkPipelineLayoutCreateInfo-pSetLayouts-08008"],
+                ..Default::default()
+            }));
+        }
+
         for ((limit, count_all), count_not_uab) in PER_STAGE_DESCRIPTOR_LIMITS
             .iter()
             .zip(per_stage_descriptors_all)
@@ -219,12 +219,15 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_pipeline_library)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline will access its descriptors through descriptor buffers, rather than through
+    /// descriptor sets. All descriptor set layouts in the pipeline layout must have been created
+    /// with [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`].
+    ///
+    /// [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`]: crate::descriptor_set::layout::DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER
     DESCRIPTOR_BUFFER = DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -650,82 +653,119 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_color_write_enable)]),
     ]),
 
-    /* TODO: enable
-    // TODO: document
-    TessellationDomainOrigin = TESSELLATION_DOMAIN_ORIGIN_EXT
+    /// The value of
+    /// [`TessellationState::domain_origin`](graphics::tessellation::TessellationState::domain_origin).
+    ///
+    /// Set with
+    /// [`set_tessellation_domain_origin`](crate::command_buffer::AutoCommandBufferBuilder::set_tessellation_domain_origin).
+TessellationDomainOrigin = TESSELLATION_DOMAIN_ORIGIN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    DepthClampEnable = DEPTH_CLAMP_ENABLE_EXT
+    /// The value of
+    /// [`RasterizationState::depth_clamp_enable`](graphics::rasterization::RasterizationState::depth_clamp_enable).
+    ///
+    /// Set with
+    /// [`set_depth_clamp_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_depth_clamp_enable).
+DepthClampEnable = DEPTH_CLAMP_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    PolygonMode = POLYGON_MODE_EXT
+    /// The value of
+    /// [`RasterizationState::polygon_mode`](graphics::rasterization::RasterizationState::polygon_mode).
+    ///
+    /// Set with
+    /// [`set_polygon_mode`](crate::command_buffer::AutoCommandBufferBuilder::set_polygon_mode).
+PolygonMode = POLYGON_MODE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    RasterizationSamples = RASTERIZATION_SAMPLES_EXT
+    /// The value of
+    /// [`MultisampleState::rasterization_samples`](graphics::multisample::MultisampleState::rasterization_samples).
+    ///
+    /// Set with
+    /// [`set_rasterization_samples`](crate::command_buffer::AutoCommandBufferBuilder::set_rasterization_samples).
+RasterizationSamples = RASTERIZATION_SAMPLES_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    SampleMask = SAMPLE_MASK_EXT
+    /// The value of
+    /// [`MultisampleState::sample_mask`](graphics::multisample::MultisampleState::sample_mask).
+    ///
+    /// Set with
+    /// [`set_sample_mask`](crate::command_buffer::AutoCommandBufferBuilder::set_sample_mask).
+SampleMask = SAMPLE_MASK_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    AlphaToCoverageEnable = ALPHA_TO_COVERAGE_ENABLE_EXT
+    /// The value of
+    /// [`MultisampleState::alpha_to_coverage_enable`](graphics::multisample::MultisampleState::alpha_to_coverage_enable).
+    ///
+    /// Set with
+    /// [`set_alpha_to_coverage_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_alpha_to_coverage_enable).
+AlphaToCoverageEnable = ALPHA_TO_COVERAGE_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    AlphaToOneEnable = ALPHA_TO_ONE_ENABLE_EXT
+    /// The value of
+    /// [`MultisampleState::alpha_to_one_enable`](graphics::multisample::MultisampleState::alpha_to_one_enable).
+    ///
+    /// Set with
+    /// [`set_alpha_to_one_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_alpha_to_one_enable).
+AlphaToOneEnable = ALPHA_TO_ONE_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    LogicOpEnable = LOGIC_OP_ENABLE_EXT
+    /// Whether
+    /// [`ColorBlendState::logic_op`](graphics::color_blend::ColorBlendState::logic_op)
+    /// is `Some`.
+    ///
+    /// Set with
+    /// [`set_logic_op_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_logic_op_enable).
+LogicOpEnable = LOGIC_OP_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    ColorBlendEnable = COLOR_BLEND_ENABLE_EXT
+    /// Whether
+    /// [`ColorBlendAttachmentState::blend`](graphics::color_blend::ColorBlendAttachmentState::blend)
+    /// is `Some`, for every attachment.
+    ///
+    /// Set with
+    /// [`set_color_blend_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_color_blend_enable).
+ColorBlendEnable = COLOR_BLEND_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    ColorBlendEquation = COLOR_BLEND_EQUATION_EXT
+    /// The contents of
+    /// [`ColorBlendAttachmentState::blend`](graphics::color_blend::ColorBlendAttachmentState::blend)
+    /// for every attachment.
+    ///
+    /// Set with
+    /// [`set_color_blend_equation`](crate::command_buffer::AutoCommandBufferBuilder::set_color_blend_equation).
+ColorBlendEquation = COLOR_BLEND_EQUATION_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    ColorWriteMask = COLOR_WRITE_MASK_EXT
+    /// The value of
+    /// [`ColorBlendAttachmentState::color_write_mask`](graphics::color_blend::ColorBlendAttachmentState::color_write_mask)
+    /// for every attachment.
+    ///
+    /// Set with
+    /// [`set_color_write_mask`](crate::command_buffer::AutoCommandBufferBuilder::set_color_write_mask).
+ColorWriteMask = COLOR_WRITE_MASK_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -775,26 +815,36 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
     ]), */
 
-    /* TODO: enable
-    // TODO: document
-    ProvokingVertexMode = PROVOKING_VERTEX_MODE_EXT
+    /// The provoking vertex mode. If this state is not dynamic, the mode is
+    /// [`ProvokingVertexMode::FirstVertex`](graphics::rasterization::ProvokingVertexMode::FirstVertex).
+    ///
+    /// Set with
+    /// [`set_provoking_vertex_mode`](crate::command_buffer::AutoCommandBufferBuilder::set_provoking_vertex_mode).
+ProvokingVertexMode = PROVOKING_VERTEX_MODE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    LineRasterizationMode = LINE_RASTERIZATION_MODE_EXT
+    /// The value of
+    /// [`RasterizationState::line_rasterization_mode`](graphics::rasterization::RasterizationState::line_rasterization_mode).
+    ///
+    /// Set with
+    /// [`set_line_rasterization_mode`](crate::command_buffer::AutoCommandBufferBuilder::set_line_rasterization_mode).
+LineRasterizationMode = LINE_RASTERIZATION_MODE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    LineStippleEnable = LINE_STIPPLE_ENABLE_EXT
+    /// Whether
+    /// [`RasterizationState::line_stipple`](graphics::rasterization::RasterizationState::line_stipple)
+    /// is `Some`.
+    ///
+    /// Set with
+    /// [`set_line_stipple_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_line_stipple_enable).
+LineStippleEnable = LINE_STIPPLE_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -18,6 +18,7 @@ use std::{
     num::NonZero,
     ptr,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 /// A collection of one or more queries of a particular type.
@@ -870,13 +871,297 @@ vulkan_bitflags! {
     ]),*/
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// A time domain that timestamps can be taken from with [`Device::calibrated_timestamps`].
+    TimeDomain = TimeDomainKHR(i32);
+
+    /// The device's timestamp counter. This is the same counter that is written to a query pool
+    /// by the [`write_timestamp`] command, so its values are in units of the
+    /// [`timestamp_period`] device property.
+    ///
+    /// [`write_timestamp`]: crate::command_buffer::AutoCommandBufferBuilder::write_timestamp
+    /// [`timestamp_period`]: crate::device::DeviceProperties::timestamp_period
+    Device = DEVICE,
+
+    /// The `CLOCK_MONOTONIC` clock, as returned by `clock_gettime`. Its values are in
+    /// nanoseconds.
+    ClockMonotonic = CLOCK_MONOTONIC,
+
+    /// The `CLOCK_MONOTONIC_RAW` clock, as returned by `clock_gettime`. Its values are in
+    /// nanoseconds.
+    ClockMonotonicRaw = CLOCK_MONOTONIC_RAW,
+
+    /// The performance counter, as returned by `QueryPerformanceCounter` on Windows. Its values
+    /// are in units of `QueryPerformanceFrequency`.
+    QueryPerformanceCounter = QUERY_PERFORMANCE_COUNTER,
+}
+
+/// A correlation between the device's timestamp counter and the host's [`Instant`] clock.
+///
+/// This can be used to convert timestamps that were written to a query pool into host
+/// [`Instant`]s, so that they can be compared with timings measured on the host, for example to
+/// place GPU and CPU work on the same profiler timeline.
+///
+/// The device timestamp counter and the host clock can drift apart over time, so a new
+/// calibration should be taken periodically.
+#[derive(Clone, Copy, Debug)]
+pub struct TimestampCalibration {
+    device_timestamp: u64,
+    instant: Instant,
+    uncertainty: Duration,
+    timestamp_period: f32,
+    timestamp_mask: u64,
+}
+
+impl TimestampCalibration {
+    /// The host time domain that device timestamps are calibrated against, if the current
+    /// platform has one.
+    ///
+    /// This must be the clock that [`Instant`] is based on, which is
+    /// [`TimeDomain::QueryPerformanceCounter`] on Windows, and [`TimeDomain::ClockMonotonic`] on
+    /// Linux, Android and the BSDs. On other platforms, such as Apple ones where `Instant` is
+    /// based on `CLOCK_UPTIME_RAW`, there is no such time domain and this is `None`.
+    pub const HOST_TIME_DOMAIN: Option<TimeDomain> = HOST_TIME_DOMAIN;
+
+    /// Takes a new calibration of the device's timestamp counter, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_new().map_err(Validated::unwrap)`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_new`] returns a [`ValidationError`].
+    ///
+    /// [`try_new`]: Self::try_new
+    #[inline]
+    #[track_caller]
+    pub fn new(device: &Device, queue_family_index: u32) -> Result<Self, VulkanError> {
+        Self::try_new(device, queue_family_index).map_err(Validated::unwrap)
+    }
+
+    /// Takes a new calibration of the device's timestamp counter.
+    ///
+    /// `queue_family_index` is the queue family whose timestamps will be converted. Its
+    /// [`timestamp_valid_bits`] determine which bits of a timestamp are used.
+    ///
+    /// [`HOST_TIME_DOMAIN`] must be `Some`, and both it and [`TimeDomain::Device`] must be among
+    /// the [`calibrateable_time_domains`] of the physical device.
+    ///
+    /// [`timestamp_valid_bits`]: crate::device::QueueFamilyProperties::timestamp_valid_bits
+    /// [`HOST_TIME_DOMAIN`]: Self::HOST_TIME_DOMAIN
+    /// [`calibrateable_time_domains`]: crate::device::physical::PhysicalDevice::calibrateable_time_domains
+    #[inline]
+    pub fn try_new(
+        device: &Device,
+        queue_family_index: u32,
+    ) -> Result<Self, Validated<VulkanError>> {
+        Self::validate_new(device, queue_family_index)?;
+
+        Ok(unsafe { Self::new_unchecked(device, queue_family_index) }?)
+    }
+
+    fn validate_new(device: &Device, queue_family_index: u32) -> Result<(), Box<ValidationError>> {
+        let Some(queue_family_properties) = device
+            .physical_device()
+            .queue_family_properties()
+            .get(queue_family_index as usize)
+        else {
+            return Err(Box::new(ValidationError {
+                context: "queue_family_index".into(),
+                problem: "is not less than the number of queue families in the physical device"
+                    .into(),
+                ..Default::default()
+            }));
+        };
+
+        if queue_family_properties.timestamp_valid_bits.is_none() {
+            return Err(Box::new(ValidationError {
+                context: "queue_family_index".into(),
+                problem: "the `timestamp_valid_bits` value of the queue family properties is \
+                    `None`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        let Some(host_time_domain) = Self::HOST_TIME_DOMAIN else {
+            return Err(Box::new(ValidationError {
+                problem: "the current platform has no time domain that `Instant` is based on"
+                    .into(),
+                ..Default::default()
+            }));
+        };
+
+        device.validate_calibrated_timestamps(&[TimeDomain::Device, host_time_domain])?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: &Device,
+        queue_family_index: u32,
+    ) -> Result<Self, VulkanError> {
+        let host_time_domain = Self::HOST_TIME_DOMAIN.unwrap();
+        let (timestamps, max_deviation) = unsafe {
+            device.calibrated_timestamps_unchecked(&[TimeDomain::Device, host_time_domain])
+        }?;
+        let (device_timestamp, host_timestamp) = (timestamps[0], timestamps[1]);
+
+        // The raw value of the host clock can't be turned into an `Instant` directly, so read
+        // the host clock right before and after `Instant::now`, and take the middle.
+        let host_before =
+            unsafe { device.calibrated_timestamps_unchecked(&[host_time_domain]) }?.0[0];
+        let now = Instant::now();
+        let host_after =
+            unsafe { device.calibrated_timestamps_unchecked(&[host_time_domain]) }?.0[0];
+
+        let host_now = host_before + (host_after - host_before) / 2;
+        let since_calibration = host_ticks_to_duration(host_now.saturating_sub(host_timestamp));
+        let mut uncertainty = Duration::from_nanos(max_deviation)
+            + host_ticks_to_duration(host_after - host_before) / 2;
+
+        // This can only fail if the host clock started less than a few microseconds ago. Keep
+        // the later instant then, and account for the difference in the uncertainty.
+        let instant = now.checked_sub(since_calibration).unwrap_or_else(|| {
+            uncertainty += since_calibration;
+            now
+        });
+
+        let timestamp_valid_bits = device.physical_device().queue_family_properties()
+            [queue_family_index as usize]
+            .timestamp_valid_bits
+            .unwrap();
+
+        Ok(TimestampCalibration {
+            device_timestamp,
+            instant,
+            uncertainty,
+            timestamp_period: device.physical_device().properties().timestamp_period,
+            timestamp_mask: u64::MAX >> (64 - timestamp_valid_bits.min(64)),
+        })
+    }
+
+    /// Returns the value of the device's timestamp counter at the moment of calibration.
+    #[inline]
+    pub fn device_timestamp(&self) -> u64 {
+        self.device_timestamp
+    }
+
+    /// Returns the host [`Instant`] that corresponds to [`device_timestamp`].
+    ///
+    /// [`device_timestamp`]: Self::device_timestamp
+    #[inline]
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Returns the maximum amount by which [`instant`] may differ from the actual moment at which
+    /// [`device_timestamp`] was taken.
+    ///
+    /// [`instant`]: Self::instant
+    /// [`device_timestamp`]: Self::device_timestamp
+    #[inline]
+    pub fn uncertainty(&self) -> Duration {
+        self.uncertainty
+    }
+
+    /// Returns the number of nanoseconds per increment of the device's timestamp counter.
+    #[inline]
+    pub fn timestamp_period(&self) -> f32 {
+        self.timestamp_period
+    }
+
+    /// Converts a number of device timestamp ticks into a [`Duration`].
+    ///
+    /// Returns `None` if the result can't be represented by a `Duration`, which happens if it's
+    /// too large or if [`timestamp_period`] is not finite.
+    ///
+    /// [`timestamp_period`]: Self::timestamp_period
+    #[inline]
+    pub fn ticks_to_duration(&self, ticks: u64) -> Option<Duration> {
+        Duration::try_from_secs_f64(ticks as f64 * f64::from(self.timestamp_period) / 1e9).ok()
+    }
+
+    /// Converts a timestamp that was written by the device into the corresponding host
+    /// [`Instant`].
+    ///
+    /// Only the valid bits of `timestamp` are used. If the counter wraps around within those
+    /// bits, then `timestamp` is taken to be whichever of the earlier or later values is closest
+    /// to the calibration, so a new calibration must be taken at least twice per wrap-around
+    /// period.
+    ///
+    /// Returns `None` if the result can't be represented by an `Instant`.
+    #[inline]
+    pub fn timestamp_to_instant(&self, timestamp: u64) -> Option<Instant> {
+        let forward = timestamp.wrapping_sub(self.device_timestamp) & self.timestamp_mask;
+        let backward = self.device_timestamp.wrapping_sub(timestamp) & self.timestamp_mask;
+
+        if forward <= backward {
+            let duration = self.ticks_to_duration(forward)?;
+            self.instant.checked_add(duration)
+        } else {
+            let duration = self.ticks_to_duration(backward)?;
+            self.instant.checked_sub(duration)
+        }
+    }
+}
+
+#[cfg(windows)]
+const HOST_TIME_DOMAIN: Option<TimeDomain> = Some(TimeDomain::QueryPerformanceCounter);
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+const HOST_TIME_DOMAIN: Option<TimeDomain> = Some(TimeDomain::ClockMonotonic);
+
+#[cfg(not(any(
+    windows,
+    target_os = "linux",
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+const HOST_TIME_DOMAIN: Option<TimeDomain> = None;
+
+/// Converts a number of ticks of [`TimestampCalibration::HOST_TIME_DOMAIN`] into a [`Duration`].
+fn host_ticks_to_duration(ticks: u64) -> Duration {
+    #[cfg(windows)]
+    {
+        unsafe extern "system" {
+            fn QueryPerformanceFrequency(frequency: *mut i64) -> i32;
+        }
+
+        let mut frequency = 0;
+        unsafe { QueryPerformanceFrequency(&mut frequency) };
+        let frequency = frequency as u64;
+
+        Duration::from_secs(ticks / frequency)
+            + Duration::from_nanos((ticks % frequency) * 1_000_000_000 / frequency)
+    }
+
+    #[cfg(not(windows))]
+    {
+        Duration::from_nanos(ticks)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::QueryPoolCreateInfo;
     use crate::{
-        query::{QueryPool, QueryType},
+        query::{QueryPool, QueryType, TimestampCalibration},
         Validated,
     };
+    use std::time::{Duration, Instant};
 
     #[test]
     fn pipeline_statistics_feature() {
@@ -892,4 +1177,101 @@ mod tests {
             Err(Validated::ValidationError(_)),
         ));
     }
+
+    #[test]
+    fn timestamp_calibration_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+        assert!(matches!(
+            TimestampCalibration::try_new(&device, queue.queue_family_index()),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    fn calibration(device_timestamp: u64, timestamp_valid_bits: u32) -> TimestampCalibration {
+        TimestampCalibration {
+            device_timestamp,
+            instant: Instant::now(),
+            uncertainty: Duration::ZERO,
+            timestamp_period: 2.0,
+            timestamp_mask: u64::MAX >> (64 - timestamp_valid_bits),
+        }
+    }
+
+    #[test]
+    fn timestamp_to_instant() {
+        let calibration = calibration(1000, 64);
+
+        assert_eq!(
+            calibration.timestamp_to_instant(1000),
+            Some(calibration.instant()),
+        );
+        assert_eq!(
+            calibration.timestamp_to_instant(1500),
+            Some(calibration.instant() + Duration::from_nanos(1000)),
+        );
+        assert_eq!(
+            calibration.timestamp_to_instant(500),
+            Some(calibration.instant() - Duration::from_nanos(1000)),
+        );
+    }
+
+    #[test]
+    fn timestamp_to_instant_valid_bits() {
+        let calibration = calibration(0xffff_fff0, 32);
+
+        // The counter wrapped around after the calibration.
+        assert_eq!(
+            calibration.timestamp_to_instant(0x10),
+            Some(calibration.instant() + Duration::from_nanos(0x40)),
+        );
+
+        // Bits above the valid bits are ignored.
+        assert_eq!(
+            calibration.timestamp_to_instant(0xabcd_0000_ffff_ffe0),
+            Some(calibration.instant() - Duration::from_nanos(0x20)),
+        );
+    }
+
+    #[test]
+    fn ticks_to_duration() {
+        let calibration = calibration(0, 64);
+        assert_eq!(
+            calibration.ticks_to_duration(500),
+            Some(Duration::from_nanos(1000)),
+        );
+        assert_eq!(
+            TimestampCalibration {
+                timestamp_period: 1e12,
+                ..calibration
+            }
+            .ticks_to_duration(u64::MAX),
+            None,
+        );
+        assert_eq!(
+            TimestampCalibration {
+                timestamp_period: f32::NAN,
+                ..calibration
+            }
+            .ticks_to_duration(1),
+            None,
+        );
+    }
+
+    #[test]
+    fn timestamp_to_instant_out_of_range() {
+        // About 1.75e19 seconds before the calibration, which is further back than an `Instant`
+        // can go.
+        let calibration = TimestampCalibration {
+            timestamp_period: 1.9e9,
+            ..calibration((1 << 63) - 1, 64)
+        };
+        assert_eq!(calibration.timestamp_to_instant(0), None);
+
+        // Too long to be represented by a `Duration`.
+        let calibration = TimestampCalibration {
+            timestamp_period: 1e12,
+            ..calibration
+        };
+        assert_eq!(calibration.timestamp_to_instant(0), None);
+    }
 }
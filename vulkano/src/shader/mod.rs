@@ -441,6 +441,7 @@ use std::{
     sync::Arc,
 };
 
+pub mod object;
 pub mod reflect;
 pub mod spirv;
 
@@ -453,6 +454,7 @@ pub struct ShaderModule {
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     id: NonZero<u64>,
 
+    code: Option<Vec<u32>>,
     spirv: Spirv,
     specialization_constants: Vec<(u32, SpecializationConstant)>,
 }
@@ -576,7 +578,7 @@ impl ShaderModule {
         create_info: &ShaderModuleCreateInfo<'_>,
         spirv: Spirv,
     ) -> Arc<ShaderModule> {
-        let &ShaderModuleCreateInfo { code: _, _ne: _ } = create_info;
+        let &ShaderModuleCreateInfo { code, _ne: _ } = create_info;
         let specialization_constants = reflect::specialization_constants(&spirv);
 
         Arc::new(ShaderModule {
@@ -584,6 +586,12 @@ impl ShaderModule {
             device: InstanceOwnedDebugWrapper(device.clone()),
             id: Self::next_id(),
 
+            // Shader objects are created from the code directly, rather than from the module.
+            // Only keep a copy if they can be used, to not waste memory otherwise.
+            code: device
+                .enabled_features()
+                .shader_object
+                .then(|| code.to_owned()),
             spirv,
             specialization_constants,
         })
@@ -624,6 +632,16 @@ impl ShaderModule {
         unsafe { Self::try_new(&device, &ShaderModuleCreateInfo::new(&words)) }
     }
 
+    /// Returns the SPIR-V code that the module was created from.
+    ///
+    /// This is only kept if the [`shader_object`] feature is enabled on the device.
+    ///
+    /// [`shader_object`]: crate::device::DeviceFeatures::shader_object
+    #[inline]
+    pub(crate) fn code(&self) -> Option<&[u32]> {
+        self.code.as_deref()
+    }
+
     /// Returns the specialization constants that are defined in the module,
     /// along with their default values.
     ///
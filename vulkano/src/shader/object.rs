@@ -0,0 +1,1020 @@
+//! Shaders that are bound to a command buffer individually, without a pipeline.
+//!
+//! A shader object holds the compiled code of a single shader stage, along with the pipeline
+//! layout that describes the descriptor sets and push constants it uses. Unlike a pipeline, a
+//! shader object contains none of the fixed-function state. When drawing with shader objects,
+//! all of the state that would otherwise be baked into a [`GraphicsPipeline`], such as the
+//! viewport, rasterization, depth/stencil and vertex input state, must be set with the dynamic
+//! state commands of the command buffer. In return, shader objects are much cheaper to create
+//! than a graphics pipeline, and any vertex shader can be combined with any fragment shader at
+//! record time, as long as their interfaces match.
+//!
+//! Shader objects can be created in two ways:
+//!
+//! - *Unlinked*, with [`ShaderObject::new`]. Each shader can be freely combined with other
+//!   shaders when binding them.
+//! - *Linked*, with [`ShaderObject::new_linked`]. The shaders are created together, which lets the
+//!   implementation optimize across stages, in the same way as it would for a pipeline. Linked
+//!   shaders must always be bound together.
+//!
+//! Shader objects require the [`shader_object`] feature to be enabled on the device. When it is,
+//! every [`ShaderModule`] created on the device keeps a copy of its SPIR-V code, which is what
+//! shader objects are created from.
+//!
+//! [`GraphicsPipeline`]: crate::pipeline::GraphicsPipeline
+//! [`shader_object`]: crate::device::DeviceFeatures::shader_object
+//! [`ShaderModule`]: super::ShaderModule
+
+use super::{DescriptorBindingRequirements, EntryPoint, ShaderStage, ShaderStages};
+use crate::{
+    device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
+    instance::InstanceOwnedDebugWrapper,
+    macros::{impl_id_counter, vulkan_bitflags},
+    pipeline::{
+        layout::PipelineLayout,
+        shader::{
+            inout_interface::{shader_interface_location_info, ShaderInterfaceLocationInfo},
+            PipelineShaderStageCreateInfo, PipelineShaderStageCreateInfoExtensionsVk,
+            PipelineShaderStageCreateInfoFields1Vk, PipelineShaderStageCreateInfoFields2Vk,
+        },
+        Pipeline, PipelineBindPoint,
+    },
+    shader::spirv::StorageClass,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
+};
+use ash::vk;
+use foldhash::HashMap;
+use smallvec::SmallVec;
+use std::{num::NonZero, ptr, slice, sync::Arc};
+
+/// A single shader stage that can be bound to a command buffer without a pipeline.
+#[derive(Debug)]
+pub struct ShaderObject {
+    handle: vk::ShaderEXT,
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    id: NonZero<u64>,
+
+    flags: ShaderCreateFlags,
+    stage: ShaderStage,
+    next_stage: ShaderStages,
+    layout: DeviceOwnedDebugWrapper<Arc<PipelineLayout>>,
+
+    descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+    num_used_descriptor_sets: u32,
+    required_vertex_inputs: Option<HashMap<u32, ShaderInterfaceLocationInfo>>,
+}
+
+impl ShaderObject {
+    /// Creates a new unlinked `ShaderObject`, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_new().map_err(Validated::unwrap)`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_new`] returns a [`ValidationError`].
+    ///
+    /// [`try_new`]: Self::try_new
+    #[inline]
+    #[track_caller]
+    pub fn new(
+        device: &Arc<Device>,
+        create_info: &ShaderCreateInfo<'_>,
+    ) -> Result<Arc<ShaderObject>, VulkanError> {
+        match Self::try_new(device, create_info) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    /// Creates a new unlinked `ShaderObject`.
+    #[inline]
+    pub fn try_new(
+        device: &Arc<Device>,
+        create_info: &ShaderCreateInfo<'_>,
+    ) -> Result<Arc<ShaderObject>, Validated<VulkanError>> {
+        Self::validate_new(device, create_info)?;
+
+        Ok(unsafe { Self::new_unchecked(device, create_info) }?)
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &ShaderCreateInfo<'_>,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_features().shader_object {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "shader_object",
+                )])]),
+                vuids: &["VUID-vkCreateShadersEXT-None-08400"],
+                ..Default::default()
+            }));
+        }
+
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: &Arc<Device>,
+        create_info: &ShaderCreateInfo<'_>,
+    ) -> Result<Arc<ShaderObject>, VulkanError> {
+        let mut shaders = unsafe { Self::create(device, slice::from_ref(create_info)) }?;
+
+        Ok(shaders.pop().unwrap())
+    }
+
+    /// Creates new `ShaderObject`s that are linked together, panicking on a validation error.
+    ///
+    /// This is a shortcut for `try_new_linked().map_err(Validated::unwrap)`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if [`try_new_linked`] returns a [`ValidationError`].
+    ///
+    /// [`try_new_linked`]: Self::try_new_linked
+    #[inline]
+    #[track_caller]
+    pub fn new_linked(
+        device: &Arc<Device>,
+        create_infos: &[ShaderCreateInfo<'_>],
+    ) -> Result<Vec<Arc<ShaderObject>>, VulkanError> {
+        match Self::try_new_linked(device, create_infos) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.unwrap()),
+        }
+    }
+
+    /// Creates new `ShaderObject`s that are linked together.
+    ///
+    /// [`ShaderCreateFlags::LINK_STAGE`] is added to the flags of every element of
+    /// `create_infos`. The returned shaders are in the same order as `create_infos`, and must
+    /// always be bound together.
+    #[inline]
+    pub fn try_new_linked(
+        device: &Arc<Device>,
+        create_infos: &[ShaderCreateInfo<'_>],
+    ) -> Result<Vec<Arc<ShaderObject>>, Validated<VulkanError>> {
+        Self::validate_new_linked(device, create_infos)?;
+
+        Ok(unsafe { Self::new_linked_unchecked(device, create_infos) }?)
+    }
+
+    fn validate_new_linked(
+        device: &Device,
+        create_infos: &[ShaderCreateInfo<'_>],
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_features().shader_object {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceFeature(
+                    "shader_object",
+                )])]),
+                vuids: &["VUID-vkCreateShadersEXT-None-08400"],
+                ..Default::default()
+            }));
+        }
+
+        if create_infos.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "create_infos".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-vkCreateShadersEXT-createInfoCount-arraylength"],
+                ..Default::default()
+            }));
+        }
+
+        let mut linked_stages = ShaderStages::empty();
+
+        for (index, create_info) in create_infos.iter().enumerate() {
+            create_info
+                .validate(device)
+                .map_err(|err| err.add_context(format!("create_infos[{}]", index)))?;
+
+            let stage = ShaderStage::from(create_info.entry_point.info().execution_model);
+
+            if stage == ShaderStage::Compute {
+                return Err(Box::new(ValidationError {
+                    context: format!("create_infos[{}].entry_point", index).into(),
+                    problem: "is a `ShaderStage::Compute` entry point, which cannot be linked"
+                        .into(),
+                    vuids: &["VUID-VkShaderCreateInfoEXT-flags-08412"],
+                    ..Default::default()
+                }));
+            }
+
+            if linked_stages.contains_enum(stage) {
+                return Err(Box::new(ValidationError {
+                    context: "create_infos".into(),
+                    problem: format!(
+                        "contains more than one element whose entry point is a \
+                        `ShaderStage::{:?}` entry point",
+                        stage,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCreateShadersEXT-pCreateInfos-08409"],
+                    ..Default::default()
+                }));
+            }
+
+            linked_stages |= stage.into();
+        }
+
+        if linked_stages.intersects(ShaderStages::TASK | ShaderStages::MESH)
+            && linked_stages.intersects(
+                ShaderStages::VERTEX
+                    | ShaderStages::TESSELLATION_CONTROL
+                    | ShaderStages::TESSELLATION_EVALUATION
+                    | ShaderStages::GEOMETRY,
+            )
+        {
+            return Err(Box::new(ValidationError {
+                context: "create_infos".into(),
+                problem: "contains both task or mesh shaders, and vertex, tessellation or \
+                    geometry shaders"
+                    .into(),
+                vuids: &["VUID-vkCreateShadersEXT-pCreateInfos-08404"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_linked_unchecked(
+        device: &Arc<Device>,
+        create_infos: &[ShaderCreateInfo<'_>],
+    ) -> Result<Vec<Arc<ShaderObject>>, VulkanError> {
+        let create_infos: SmallVec<[_; 4]> = create_infos
+            .iter()
+            .map(|create_info| ShaderCreateInfo {
+                flags: create_info.flags | ShaderCreateFlags::LINK_STAGE,
+                ..create_info.clone()
+            })
+            .collect();
+
+        unsafe { Self::create(device, &create_infos) }
+    }
+
+    unsafe fn create(
+        device: &Arc<Device>,
+        create_infos: &[ShaderCreateInfo<'_>],
+    ) -> Result<Vec<Arc<ShaderObject>>, VulkanError> {
+        let create_infos_fields2_vk: SmallVec<[_; 4]> = create_infos
+            .iter()
+            .map(ShaderCreateInfo::to_vk_fields2)
+            .collect();
+        let create_infos_fields1_vk: SmallVec<[_; 4]> = create_infos
+            .iter()
+            .zip(&create_infos_fields2_vk)
+            .map(|(create_info, fields2_vk)| create_info.to_vk_fields1(fields2_vk))
+            .collect();
+        let mut create_infos_extensions_vk: SmallVec<[_; 4]> = create_infos
+            .iter()
+            .map(ShaderCreateInfo::to_vk_extensions)
+            .collect();
+        let create_infos_vk: SmallVec<[_; 4]> = create_infos
+            .iter()
+            .zip(&create_infos_fields1_vk)
+            .zip(&mut create_infos_extensions_vk)
+            .map(|((create_info, fields1_vk), extensions_vk)| {
+                create_info.to_vk(fields1_vk, extensions_vk)
+            })
+            .collect();
+
+        let handles = {
+            let fns = device.fns();
+            let mut output = Vec::with_capacity(create_infos_vk.len());
+            unsafe {
+                (fns.ext_shader_object.create_shaders_ext)(
+                    device.handle(),
+                    create_infos_vk.len() as u32,
+                    create_infos_vk.as_ptr(),
+                    ptr::null(),
+                    output.as_mut_ptr(),
+                )
+            }
+            .result()
+            .map_err(VulkanError::from)?;
+            unsafe { output.set_len(create_infos_vk.len()) };
+            output
+        };
+
+        Ok(handles
+            .into_iter()
+            .zip(create_infos)
+            .map(|(handle, create_info)| unsafe { Self::from_handle(device, handle, create_info) })
+            .collect())
+    }
+
+    /// Creates a new `ShaderObject` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: &Arc<Device>,
+        handle: vk::ShaderEXT,
+        create_info: &ShaderCreateInfo<'_>,
+    ) -> Arc<ShaderObject> {
+        let &ShaderCreateInfo {
+            flags,
+            entry_point,
+            next_stage,
+            layout,
+            required_subgroup_size: _,
+            _ne: _,
+        } = create_info;
+
+        let entry_point_info = entry_point.info();
+        let stage = ShaderStage::from(entry_point_info.execution_model);
+
+        let descriptor_binding_requirements: HashMap<_, _> = entry_point_info
+            .descriptor_binding_requirements
+            .iter()
+            .map(|(&loc, reqs)| (loc, reqs.clone()))
+            .collect();
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+        let required_vertex_inputs = (stage == ShaderStage::Vertex).then(|| {
+            shader_interface_location_info(
+                entry_point.module().spirv(),
+                entry_point.id(),
+                StorageClass::Input,
+            )
+        });
+
+        Arc::new(ShaderObject {
+            handle,
+            device: InstanceOwnedDebugWrapper(device.clone()),
+            id: Self::next_id(),
+
+            flags,
+            stage,
+            next_stage,
+            layout: DeviceOwnedDebugWrapper(layout.clone()),
+
+            descriptor_binding_requirements,
+            num_used_descriptor_sets,
+            required_vertex_inputs,
+        })
+    }
+
+    /// Returns the flags that the shader was created with.
+    #[inline]
+    pub fn flags(&self) -> ShaderCreateFlags {
+        self.flags
+    }
+
+    /// Returns the shader stage of the shader.
+    #[inline]
+    pub fn stage(&self) -> ShaderStage {
+        self.stage
+    }
+
+    /// Returns the stages that may follow the shader.
+    #[inline]
+    pub fn next_stage(&self) -> ShaderStages {
+        self.next_stage
+    }
+
+    /// Returns whether the shader was created linked to other shaders.
+    #[inline]
+    pub fn is_linked(&self) -> bool {
+        self.flags.intersects(ShaderCreateFlags::LINK_STAGE)
+    }
+
+    /// Returns the vertex inputs that the shader requires, if it is a vertex shader.
+    #[inline]
+    pub(crate) fn required_vertex_inputs(
+        &self,
+    ) -> Option<&HashMap<u32, ShaderInterfaceLocationInfo>> {
+        self.required_vertex_inputs.as_ref()
+    }
+}
+
+impl Pipeline for ShaderObject {
+    #[inline]
+    fn bind_point(&self) -> PipelineBindPoint {
+        match self.stage {
+            ShaderStage::Compute => PipelineBindPoint::Compute,
+            _ => PipelineBindPoint::Graphics,
+        }
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    #[inline]
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+
+    #[inline]
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.descriptor_binding_requirements
+    }
+}
+
+impl Drop for ShaderObject {
+    #[inline]
+    fn drop(&mut self) {
+        let fns = self.device.fns();
+        unsafe {
+            (fns.ext_shader_object.destroy_shader_ext)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            )
+        };
+    }
+}
+
+unsafe impl VulkanObject for ShaderObject {
+    type Handle = vk::ShaderEXT;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for ShaderObject {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl_id_counter!(ShaderObject);
+
+/// Parameters to create a new `ShaderObject`.
+#[derive(Clone, Debug)]
+pub struct ShaderCreateInfo<'a> {
+    /// Additional properties of the shader.
+    ///
+    /// The default value is empty.
+    pub flags: ShaderCreateFlags,
+
+    /// The shader entry point, which includes any specialization constants.
+    ///
+    /// There is no default value.
+    pub entry_point: &'a EntryPoint,
+
+    /// The shader stages that may be bound after this shader when drawing.
+    ///
+    /// For example, a vertex shader that is used both with and without a geometry shader would
+    /// specify `ShaderStages::GEOMETRY | ShaderStages::FRAGMENT`.
+    ///
+    /// The default value is empty.
+    pub next_stage: ShaderStages,
+
+    /// The pipeline layout that describes the descriptor sets and push constants used by the
+    /// shader.
+    ///
+    /// There is no default value.
+    pub layout: &'a Arc<PipelineLayout>,
+
+    /// The required subgroup size.
+    ///
+    /// This has the same requirements as
+    /// [`PipelineShaderStageCreateInfo::required_subgroup_size`].
+    ///
+    /// The default value is None.
+    pub required_subgroup_size: Option<u32>,
+
+    pub _ne: crate::NonExhaustive<'a>,
+}
+
+impl<'a> ShaderCreateInfo<'a> {
+    /// Returns a default `ShaderCreateInfo` with the provided `entry_point` and `layout`.
+    #[inline]
+    pub const fn new(entry_point: &'a EntryPoint, layout: &'a Arc<PipelineLayout>) -> Self {
+        Self {
+            flags: ShaderCreateFlags::empty(),
+            entry_point,
+            next_stage: ShaderStages::empty(),
+            layout,
+            required_subgroup_size: None,
+            _ne: crate::NE,
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            flags,
+            entry_point,
+            next_stage,
+            layout,
+            required_subgroup_size: _,
+            _ne: _,
+        } = self;
+
+        // VUID-VkShaderCreateInfoEXT-pSetLayouts-parameter
+        assert_eq!(device, layout.device().as_ref());
+        assert_eq!(device, entry_point.module().base_module().device().as_ref());
+
+        flags.validate_device(device).map_err(|err| {
+            err.add_context("flags")
+                .set_vuids(&["VUID-VkShaderCreateInfoEXT-flags-parameter"])
+        })?;
+
+        next_stage.validate_device(device).map_err(|err| {
+            err.add_context("next_stage")
+                .set_vuids(&["VUID-VkShaderCreateInfoEXT-nextStage-parameter"])
+        })?;
+
+        self.stage().validate(device)?;
+
+        let entry_point_info = entry_point.info();
+        let stage = ShaderStage::from(entry_point_info.execution_model);
+
+        let (allowed_next_stages, vuids) = match stage {
+            ShaderStage::Vertex => (
+                ShaderStages::TESSELLATION_CONTROL
+                    | ShaderStages::GEOMETRY
+                    | ShaderStages::FRAGMENT,
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08428"],
+            ),
+            ShaderStage::TessellationControl => (
+                ShaderStages::TESSELLATION_EVALUATION,
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08429"],
+            ),
+            ShaderStage::TessellationEvaluation => (
+                ShaderStages::GEOMETRY | ShaderStages::FRAGMENT,
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08430"],
+            ),
+            ShaderStage::Geometry => (
+                ShaderStages::FRAGMENT,
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08431"],
+            ),
+            ShaderStage::Fragment | ShaderStage::Compute => (
+                ShaderStages::empty(),
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08432"],
+            ),
+            ShaderStage::Task => (
+                ShaderStages::MESH,
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08433"],
+            ),
+            ShaderStage::Mesh => (
+                ShaderStages::FRAGMENT,
+                &["VUID-VkShaderCreateInfoEXT-nextStage-08434"],
+            ),
+            ShaderStage::SubpassShading => {
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: "is a `ShaderStage::SubpassShading` entry point".into(),
+                    vuids: &["VUID-VkShaderCreateInfoEXT-stage-08425"],
+                    ..Default::default()
+                }));
+            }
+            _ => {
+                // Ray tracing shaders can only be used in a ray tracing pipeline, so a shader
+                // object created from one could never be bound.
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: format!(
+                        "is a `ShaderStage::{:?}` entry point, which is a ray tracing shader \
+                        stage",
+                        stage,
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+        };
+
+        if !allowed_next_stages.contains(next_stage) {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "`entry_point` is a `ShaderStage::{:?}` entry point, but `next_stage` \
+                    contains stages that cannot follow it",
+                    stage,
+                )
+                .into(),
+                vuids,
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(ShaderCreateFlags::LINK_STAGE) && stage == ShaderStage::Compute {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` contains `ShaderCreateFlags::LINK_STAGE`, but `entry_point` \
+                    is a `ShaderStage::Compute` entry point"
+                    .into(),
+                vuids: &["VUID-VkShaderCreateInfoEXT-flags-08412"],
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(ShaderCreateFlags::REQUIRE_FULL_SUBGROUPS)
+            && !matches!(
+                stage,
+                ShaderStage::Compute | ShaderStage::Mesh | ShaderStage::Task
+            )
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` contains `ShaderCreateFlags::REQUIRE_FULL_SUBGROUPS`, but \
+                    `entry_point` is not a `ShaderStage::Compute`, `ShaderStage::Mesh` or \
+                    `ShaderStage::Task` entry point"
+                    .into(),
+                vuids: &["VUID-VkShaderCreateInfoEXT-flags-08992"],
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(ShaderCreateFlags::NO_TASK_SHADER) && stage != ShaderStage::Mesh {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` contains `ShaderCreateFlags::NO_TASK_SHADER`, but \
+                    `entry_point` is not a `ShaderStage::Mesh` entry point"
+                    .into(),
+                vuids: &["VUID-VkShaderCreateInfoEXT-flags-08414"],
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(ShaderCreateFlags::DISPATCH_BASE) && stage != ShaderStage::Compute {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` contains `ShaderCreateFlags::DISPATCH_BASE`, but \
+                    `entry_point` is not a `ShaderStage::Compute` entry point"
+                    .into(),
+                vuids: &["VUID-VkShaderCreateInfoEXT-flags-08485"],
+                ..Default::default()
+            }));
+        }
+
+        layout
+            .ensure_compatible_with_shader(
+                entry_point_info
+                    .descriptor_binding_requirements
+                    .iter()
+                    .map(|(k, v)| (*k, v)),
+                entry_point_info.push_constant_requirements.as_ref(),
+            )
+            .map_err(|err| {
+                Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    ..ValidationError::from_error(err)
+                })
+            })?;
+
+        // VUID-VkShaderCreateInfoEXT-pCode-08737
+        // VUID-VkShaderCreateInfoEXT-codeType-08872
+        // Ensured by the safety requirements of `ShaderModule::new`.
+
+        Ok(())
+    }
+
+    /// Returns the equivalent pipeline shader stage, so that its validation and conversion can be
+    /// shared.
+    fn stage(&self) -> PipelineShaderStageCreateInfo<'a> {
+        PipelineShaderStageCreateInfo {
+            required_subgroup_size: self.required_subgroup_size,
+            ..PipelineShaderStageCreateInfo::new(self.entry_point)
+        }
+    }
+
+    pub(crate) fn to_vk(
+        &self,
+        fields1_vk: &'a ShaderCreateInfoFields1Vk<'_>,
+        extensions_vk: &'a mut ShaderCreateInfoExtensionsVk,
+    ) -> vk::ShaderCreateInfoEXT<'a> {
+        let &Self {
+            flags,
+            entry_point,
+            next_stage,
+            layout: _,
+            required_subgroup_size: _,
+            _ne: _,
+        } = self;
+        let ShaderCreateInfoFields1Vk {
+            stage_fields1_vk,
+            set_layouts_vk,
+            push_constant_ranges_vk,
+        } = fields1_vk;
+        let PipelineShaderStageCreateInfoFields1Vk {
+            name_vk,
+            specialization_info_vk,
+        } = stage_fields1_vk;
+
+        let stage = ShaderStage::from(entry_point.info().execution_model);
+        // The code is kept by every module of a device with the `shader_object` feature enabled.
+        let code = entry_point.module().base_module().code().unwrap();
+
+        let mut val_vk = vk::ShaderCreateInfoEXT::default()
+            .flags(flags.into())
+            .stage(stage.into())
+            .next_stage(next_stage.into())
+            .code_type(vk::ShaderCodeTypeEXT::SPIRV)
+            .code(bytemuck::cast_slice(code))
+            .name(name_vk)
+            .set_layouts(set_layouts_vk)
+            .push_constant_ranges(push_constant_ranges_vk);
+
+        if specialization_info_vk.data_size != 0 {
+            val_vk = val_vk.specialization_info(specialization_info_vk);
+        }
+
+        let ShaderCreateInfoExtensionsVk {
+            stage_extensions_vk,
+        } = extensions_vk;
+        let PipelineShaderStageCreateInfoExtensionsVk {
+            required_subgroup_size_vk,
+        } = stage_extensions_vk;
+
+        if let Some(next) = required_subgroup_size_vk {
+            val_vk = val_vk.push_next(next);
+        }
+
+        val_vk
+    }
+
+    pub(crate) fn to_vk_extensions(&self) -> ShaderCreateInfoExtensionsVk {
+        let stage_extensions_vk = self.stage().to_vk_extensions();
+
+        ShaderCreateInfoExtensionsVk {
+            stage_extensions_vk,
+        }
+    }
+
+    pub(crate) fn to_vk_fields1(
+        &self,
+        fields2_vk: &'a ShaderCreateInfoFields2Vk,
+    ) -> ShaderCreateInfoFields1Vk<'a> {
+        let ShaderCreateInfoFields2Vk { stage_fields2_vk } = fields2_vk;
+
+        let stage_fields1_vk = self.stage().to_vk_fields1(stage_fields2_vk);
+        let set_layouts_vk = self
+            .layout
+            .set_layouts()
+            .iter()
+            .map(|set_layout| set_layout.handle())
+            .collect();
+        let push_constant_ranges_vk = self
+            .layout
+            .push_constant_ranges()
+            .iter()
+            .map(|range| range.to_vk())
+            .collect();
+
+        ShaderCreateInfoFields1Vk {
+            stage_fields1_vk,
+            set_layouts_vk,
+            push_constant_ranges_vk,
+        }
+    }
+
+    pub(crate) fn to_vk_fields2(&self) -> ShaderCreateInfoFields2Vk {
+        let stage_fields2_vk = self.stage().to_vk_fields2();
+
+        ShaderCreateInfoFields2Vk { stage_fields2_vk }
+    }
+}
+
+pub(crate) struct ShaderCreateInfoExtensionsVk {
+    pub(crate) stage_extensions_vk: PipelineShaderStageCreateInfoExtensionsVk,
+}
+
+pub(crate) struct ShaderCreateInfoFields1Vk<'a> {
+    pub(crate) stage_fields1_vk: PipelineShaderStageCreateInfoFields1Vk<'a>,
+    pub(crate) set_layouts_vk: SmallVec<[vk::DescriptorSetLayout; 4]>,
+    pub(crate) push_constant_ranges_vk: SmallVec<[vk::PushConstantRange; 4]>,
+}
+
+pub(crate) struct ShaderCreateInfoFields2Vk {
+    pub(crate) stage_fields2_vk: PipelineShaderStageCreateInfoFields2Vk,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags specifying additional properties of a shader object.
+    ShaderCreateFlags = ShaderCreateFlagsEXT(u32);
+
+    /// The shader is linked to the other shaders that it was created with.
+    ///
+    /// This flag is added automatically by [`ShaderObject::new_linked`].
+    LINK_STAGE = LINK_STAGE,
+
+    /// The shader may use a varying subgroup size.
+    ALLOW_VARYING_SUBGROUP_SIZE = ALLOW_VARYING_SUBGROUP_SIZE
+    RequiresOneOf([
+        RequiresAllOf([DeviceFeature(subgroup_size_control)]),
+    ]),
+
+    /// The subgroups of the shader must be fully populated.
+    REQUIRE_FULL_SUBGROUPS = REQUIRE_FULL_SUBGROUPS
+    RequiresOneOf([
+        RequiresAllOf([DeviceFeature(compute_full_subgroups)]),
+    ]),
+
+    /// The mesh shader is used without a task shader.
+    NO_TASK_SHADER = NO_TASK_SHADER,
+
+    /// The compute shader can be dispatched with a nonzero base workgroup.
+    DISPATCH_BASE = DISPATCH_BASE,
+
+    /* TODO: enable
+    // TODO: document
+    FRAGMENT_SHADING_RATE_ATTACHMENT = FRAGMENT_SHADING_RATE_ATTACHMENT
+    RequiresOneOf([
+        RequiresAllOf([DeviceFeature(attachment_fragment_shading_rate)]),
+    ]),*/
+
+    /* TODO: enable
+    // TODO: document
+    FRAGMENT_DENSITY_MAP_ATTACHMENT = FRAGMENT_DENSITY_MAP_ATTACHMENT
+    RequiresOneOf([
+        RequiresAllOf([DeviceFeature(fragment_density_map)]),
+    ]),*/
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShaderCreateInfo, ShaderObject};
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        device::Device,
+        pipeline::{layout::PipelineLayout, PipelineShaderStageCreateInfo},
+        shader::{EntryPoint, ShaderModule, ShaderModuleCreateInfo, ShaderStage, ShaderStages},
+        Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
+    };
+    use std::{slice, sync::Arc};
+
+    /*
+    #version 450
+
+    layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+    layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+    layout(set = 0, binding = 0) buffer Output {
+        int write;
+    } write;
+
+    void main() {
+        write.write = VALUE;
+    }
+    */
+    const COMPUTE_MODULE: [u32; 120] = [
+        119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262, 808793134,
+        0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1, 196611, 2, 450,
+        262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7, 0, 1953067639, 101,
+        262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752, 7, 0, 35, 0, 196679, 7, 3,
+        262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83, 131091, 2, 196641, 3, 2, 262165, 6,
+        32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203, 8, 9, 2, 262187, 6, 10, 0, 262194, 6, 11,
+        3735928559, 262176, 12, 2, 6, 327734, 2, 4, 0, 3, 131320, 5, 327745, 12, 13, 9, 10, 196670,
+        13, 11, 65789, 65592,
+    ];
+
+    fn compute_entry_point(device: &Arc<Device>) -> (EntryPoint, Arc<PipelineLayout>) {
+        let module =
+            unsafe { ShaderModule::new(device, &ShaderModuleCreateInfo::new(&COMPUTE_MODULE)) }
+                .unwrap();
+        let cs = module.entry_point("main").unwrap();
+        let layout = PipelineLayout::from_stages(
+            device,
+            slice::from_ref(&PipelineShaderStageCreateInfo::new(&cs)),
+        )
+        .unwrap();
+
+        (cs, layout)
+    }
+
+    #[test]
+    fn basic_creation() {
+        let (device, _) = gfx_dev_and_queue!(shader_object; ext_shader_object);
+        let (cs, layout) = compute_entry_point(&device);
+
+        let shader = ShaderObject::new(&device, &ShaderCreateInfo::new(&cs, &layout)).unwrap();
+
+        assert_eq!(shader.stage(), ShaderStage::Compute);
+        assert_eq!(shader.next_stage(), ShaderStages::empty());
+        assert!(!shader.is_linked());
+    }
+
+    #[test]
+    fn missing_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+        let (cs, layout) = compute_entry_point(&device);
+
+        match ShaderObject::try_new(&device, &ShaderCreateInfo::new(&cs, &layout)) {
+            Err(Validated::ValidationError(err))
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([Requires::DeviceFeature(
+                            "shader_object"
+                        )])]),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_next_stage() {
+        let (device, _) = gfx_dev_and_queue!(shader_object; ext_shader_object);
+        let (cs, layout) = compute_entry_point(&device);
+
+        let res = ShaderObject::try_new(
+            &device,
+            &ShaderCreateInfo {
+                next_stage: ShaderStages::FRAGMENT,
+                ..ShaderCreateInfo::new(&cs, &layout)
+            },
+        );
+
+        match res {
+            Err(Validated::ValidationError(err))
+                if err.vuids == ["VUID-VkShaderCreateInfoEXT-nextStage-08432"] => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn linked_compute() {
+        let (device, _) = gfx_dev_and_queue!(shader_object; ext_shader_object);
+        let (cs, layout) = compute_entry_point(&device);
+
+        let res = ShaderObject::try_new_linked(&device, &[ShaderCreateInfo::new(&cs, &layout)]);
+
+        match res {
+            Err(Validated::ValidationError(err))
+                if err.vuids == ["VUID-VkShaderCreateInfoEXT-flags-08412"] => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn bind_shaders() {
+        let (device, queue) = gfx_dev_and_queue!(shader_object; ext_shader_object);
+        let (cs, layout) = compute_entry_point(&device);
+        let shader = ShaderObject::new(&device, &ShaderCreateInfo::new(&cs, &layout)).unwrap();
+
+        let cb_allocator = Arc::new(StandardCommandBufferAllocator::new(
+            &device,
+            &Default::default(),
+        ));
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        cbb.bind_shaders(&[ShaderStage::Compute], &[Some(shader.clone())])
+            .unwrap();
+
+        match cbb.bind_shaders(&[ShaderStage::Vertex], &[Some(shader.clone())]) {
+            Err(err) if err.vuids == ["VUID-vkCmdBindShadersEXT-pShaders-08469"] => {}
+            _ => panic!(),
+        }
+
+        match cbb.bind_shaders(
+            &[ShaderStage::Compute, ShaderStage::Compute],
+            &[Some(shader), None],
+        ) {
+            Err(err) if err.vuids == ["VUID-vkCmdBindShadersEXT-pStages-08463"] => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn bind_shaders_missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = Arc::new(StandardCommandBufferAllocator::new(
+            &device,
+            &Default::default(),
+        ));
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match cbb.bind_shaders(&[ShaderStage::Compute], &[None]) {
+            Err(err) if err.vuids == ["VUID-vkCmdBindShadersEXT-None-08462"] => {}
+            _ => panic!(),
+        }
+    }
+}